@@ -0,0 +1,270 @@
+//! `byte doctor`: check the environment Byte runs in
+//!
+//! Each check prints one line and, when something is wrong, the command or
+//! setting that fixes it. Failures make the command exit non-zero; warnings
+//! don't.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::config::GlobalConfig;
+use crate::exec::CommandBuilder;
+use crate::state::toolchain;
+
+/// Below this many inotify watches, watching a large workspace fails
+const MIN_INOTIFY_WATCHES: u64 = 65536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+    /// Not applicable here (a toolchain nobody needs, a non-Linux system)
+    Skip,
+}
+
+#[derive(Debug)]
+struct Check {
+    status: Status,
+    label: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn new(status: Status, label: impl Into<String>) -> Self {
+        Self {
+            status,
+            label: label.into(),
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+pub fn run() -> Result<()> {
+    let mut sections: Vec<(&str, Vec<Check>)> = vec![("Git", check_git())];
+
+    // An invalid config.toml is reported rather than aborting the other checks
+    let (config_checks, global) = check_config();
+    sections.push(("Config", config_checks));
+    sections.push(("Workspaces", check_workspaces(&global)));
+    sections.push(("File watcher", vec![check_inotify()]));
+    sections.push(("Toolchains", check_toolchains(&global)));
+
+    println!("Byte doctor\n");
+    let (mut failures, mut warnings) = (0, 0);
+    for (title, checks) in &sections {
+        println!("{}", title);
+        for check in checks {
+            let icon = match check.status {
+                Status::Ok => "✓",
+                Status::Warn => "⚠",
+                Status::Fail => "✗",
+                Status::Skip => "-",
+            };
+            println!("  {} {}", icon, check.label);
+            if let Some(fix) = &check.fix {
+                println!("      → {}", fix);
+            }
+            match check.status {
+                Status::Fail => failures += 1,
+                Status::Warn => warnings += 1,
+                _ => {}
+            }
+        }
+        println!();
+    }
+
+    match (failures, warnings) {
+        (0, 0) => println!("Everything looks good."),
+        (0, w) => println!("{} warning{}", w, plural(w)),
+        (f, w) => anyhow::bail!("{} problem{}, {} warning{}", f, plural(f), w, plural(w)),
+    }
+    Ok(())
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 { "" } else { "s" }
+}
+
+/// git on PATH, and an identity to commit with
+fn check_git() -> Vec<Check> {
+    let version = CommandBuilder::new("git")
+        .arg("--version")
+        .execute()
+        .ok()
+        .filter(|r| r.success);
+    let Some(version) = version else {
+        return vec![
+            Check::new(Status::Fail, "git not found on PATH")
+                .fix("install git from https://git-scm.com/downloads"),
+        ];
+    };
+
+    let mut checks = vec![Check::new(Status::Ok, version.stdout.trim().to_string())];
+    for key in ["user.name", "user.email"] {
+        let value = CommandBuilder::git("config")
+            .arg("--get")
+            .arg(key)
+            .execute()
+            .ok()
+            .filter(|r| r.success)
+            .map(|r| r.stdout.trim().to_string())
+            .filter(|v| !v.is_empty());
+        checks.push(match value {
+            Some(value) => Check::new(Status::Ok, format!("{} = {}", key, value)),
+            None => Check::new(
+                Status::Warn,
+                format!("{} not set; commits from Byte will fail", key),
+            )
+            .fix(format!("git config --global {} \"...\"", key)),
+        });
+    }
+    checks
+}
+
+/// config.toml parses and its theme exists; falls back to defaults otherwise
+fn check_config() -> (Vec<Check>, GlobalConfig) {
+    let mut checks = Vec::new();
+    let global = match crate::config::Config::load() {
+        Ok(config) => {
+            checks.push(Check::new(Status::Ok, "global config is valid"));
+            config.global
+        }
+        Err(e) => {
+            checks.push(
+                Check::new(Status::Fail, format!("global config: {}", e))
+                    .fix("fix the file, or move it away to start from the defaults (~/.config/byte/config.toml)"),
+            );
+            GlobalConfig::default()
+        }
+    };
+
+    checks.push(match crate::tui::theme::Theme::resolve(&global.tui.theme) {
+        Ok(_) => Check::new(Status::Ok, format!("theme \"{}\"", global.tui.theme)),
+        Err(e) => Check::new(
+            Status::Warn,
+            format!("theme \"{}\": {}", global.tui.theme, e),
+        )
+        .fix(format!(
+            "set [tui] theme to one of {} or a theme file",
+            crate::tui::theme::BUILTIN_THEMES.join(", ")
+        )),
+    });
+    (checks, global)
+}
+
+/// The primary and registered workspaces exist and are writable
+fn check_workspaces(global: &GlobalConfig) -> Vec<Check> {
+    std::iter::once(&global.workspace.path)
+        .chain(&global.workspace.registered)
+        .map(|workspace| {
+            let result = crate::path::SafePath::from_user_input(workspace)
+                .and_then(|path| path.validate_writable());
+            match result {
+                Ok(()) => Check::new(Status::Ok, format!("{} is writable", workspace)),
+                Err(e) if !Path::new(&shellexpand::tilde(workspace).to_string()).exists() => {
+                    Check::new(Status::Fail, format!("{}: {}", workspace, e))
+                        .fix(format!("mkdir -p {}", workspace))
+                }
+                Err(e) => Check::new(Status::Fail, format!("{}: {}", workspace, e))
+                    .fix(format!("chmod u+rwx {}", workspace)),
+            }
+        })
+        .collect()
+}
+
+/// inotify watch limit (Linux); the TUI watches every project's byte.toml and .git
+fn check_inotify() -> Check {
+    let limit = std::fs::read_to_string("/proc/sys/fs/inotify/max_user_watches")
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    match limit {
+        None => Check::new(Status::Skip, "inotify limits don't apply on this system"),
+        Some(limit) if limit < MIN_INOTIFY_WATCHES => {
+            Check::new(Status::Warn, format!("fs.inotify.max_user_watches = {} (low)", limit)).fix(
+                "echo fs.inotify.max_user_watches=524288 | sudo tee /etc/sysctl.d/60-byte.conf && sudo sysctl --system",
+            )
+        }
+        Some(limit) => Check::new(Status::Ok, format!("fs.inotify.max_user_watches = {}", limit)),
+    }
+}
+
+/// Each ecosystem's toolchain, then every project whose required version
+/// isn't installed
+fn check_toolchains(global: &GlobalConfig) -> Vec<Check> {
+    let projects = crate::projects::discover_projects(global).unwrap_or_default();
+    let used: Vec<&str> = projects
+        .iter()
+        .flat_map(|p| match &p.config.drivers {
+            Some(drivers) => drivers.uses.iter().map(String::as_str).collect(),
+            None => vec![p.config.project.ecosystem.as_str()],
+        })
+        .collect();
+
+    let mut checks: Vec<Check> = toolchain::ecosystems()
+        .filter_map(toolchain::toolchain_for)
+        .map(|tc| match &tc.version {
+            Some(version) => Check::new(Status::Ok, format!("{} {}", tc.name, version)),
+            // Only a problem when a project needs it
+            None if used.contains(&tc.ecosystem) => Check::new(
+                Status::Fail,
+                format!("{} not found on PATH, but projects use it", tc.name),
+            )
+            .fix(install_hint(tc.ecosystem)),
+            None => Check::new(Status::Skip, format!("{} not installed", tc.name)),
+        })
+        .collect();
+
+    for project in &projects {
+        let path = project.path.to_string_lossy();
+        let venv = crate::state::python::detect_python_env(&path)
+            .and_then(|env| env.installed_version(&path));
+        let mut toolchains = toolchain::detect_toolchains(&path, venv.as_deref());
+        for capability in crate::projects::drivers::capabilities(&project.config) {
+            toolchains.extend(toolchain::toolchain_in(
+                &project.path.join(capability.dir()),
+                &capability.driver,
+            ));
+        }
+
+        for tc in toolchains.iter().filter(|tc| tc.mismatch()) {
+            let Some(required) = &tc.required else {
+                continue;
+            };
+            let installed = tc.version.as_deref().unwrap_or("none");
+            let mut check = Check::new(
+                Status::Warn,
+                format!(
+                    "{}: {} {} required by {}, {} installed",
+                    project.config.project.name, tc.name, required.spec, required.source, installed
+                ),
+            );
+            if let Some(fix) = tc.fix() {
+                check = check.fix(fix);
+            }
+            checks.push(check);
+        }
+    }
+    checks
+}
+
+/// Where to get a toolchain that isn't installed at all
+fn install_hint(ecosystem: &str) -> &'static str {
+    match ecosystem {
+        "rust" => "install rustup from https://rustup.rs",
+        "python" => {
+            "install Python 3 from https://www.python.org/downloads/ or your package manager"
+        }
+        "go" => "install Go from https://go.dev/dl/",
+        "bun" => "curl -fsSL https://bun.sh/install | bash",
+        "node" => "nvm install --lts (https://github.com/nvm-sh/nvm)",
+        "deno" => "curl -fsSL https://deno.land/install.sh | sh",
+        "zig" => "install Zig from https://ziglang.org/download/",
+        _ => "install it and make sure it is on PATH",
+    }
+}
@@ -0,0 +1,541 @@
+//! `byte logs`: list, show, follow, search, diff and prune command logs
+//!
+//! Listings come from each project's log index via
+//! [`ProjectFileSystem::recent_logs_all`], so nothing here walks the log
+//! directories directly.
+
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::GlobalConfig;
+use crate::fs::log_diff::LogDiff;
+use crate::fs::log_format::{self, LogMeta};
+use crate::fs::{LogFile, ProjectFileSystem, PruneReport};
+
+#[derive(Subcommand, Debug)]
+pub enum LogsCommand {
+    /// Print a log
+    Show {
+        /// Log id (as shown by `byte logs`) or "latest"
+        #[arg(default_value = "latest")]
+        id: String,
+
+        /// Project name or path
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+
+    /// Print the end of a log, optionally following a running command
+    Tail {
+        /// Log id (as shown by `byte logs`) or "latest"
+        #[arg(default_value = "latest")]
+        id: String,
+
+        /// Keep printing new output until the command finishes
+        #[arg(long, short)]
+        follow: bool,
+
+        /// Number of lines to print before following
+        #[arg(long, short = 'n', default_value_t = 20)]
+        lines: usize,
+
+        /// Stop following after this many seconds without new output
+        #[arg(long, default_value_t = 300)]
+        timeout: u64,
+
+        /// Project name or path
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+
+    /// Search log output, printing file:line matches
+    Grep {
+        /// Text to search for
+        pattern: String,
+
+        /// Search every discovered project
+        #[arg(long)]
+        all_projects: bool,
+
+        /// Case-insensitive match
+        #[arg(long, short)]
+        ignore_case: bool,
+
+        /// Only search logs of this category
+        #[arg(long, short)]
+        category: Option<String>,
+
+        /// Project name or path
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+
+    /// Compare two runs, ignoring timestamps, durations and temp paths
+    Diff {
+        /// Older log id
+        old: String,
+
+        /// Newer log id (defaults to the latest log)
+        #[arg(default_value = "latest")]
+        new: String,
+
+        /// Lines of context around each change
+        #[arg(long, short = 'U', default_value_t = 3)]
+        context: usize,
+
+        /// Project name or path
+        #[arg(long, short)]
+        project: Option<String>,
+    },
+
+    /// Apply log retention across all projects and report what was pruned
+    Gc,
+}
+
+/// A project whose logs are being inspected
+struct LogProject {
+    name: String,
+    fs: ProjectFileSystem,
+}
+
+pub fn run(global: &GlobalConfig, command: LogsCommand) -> Result<()> {
+    match command {
+        LogsCommand::Show { id, project } => show(global, project.as_deref(), &id),
+        LogsCommand::Tail { id, follow, lines, timeout, project } => {
+            let timeout = follow.then(|| Duration::from_secs(timeout));
+            tail(global, project.as_deref(), &id, lines, timeout)
+        }
+        LogsCommand::Grep { pattern, all_projects, ignore_case, category, project } => {
+            let projects = if all_projects {
+                all_log_projects(global)?
+            } else {
+                resolve_projects(global, project.as_deref())?
+            };
+            grep(&projects, &pattern, ignore_case, category.as_deref())
+        }
+        LogsCommand::Diff { old, new, context, project } => diff(global, project.as_deref(), &old, &new, context),
+        LogsCommand::Gc => gc(global),
+    }
+}
+
+/// `byte logs [project]`: list recent logs
+pub fn list(global: &GlobalConfig, project: Option<&str>, category: Option<&str>, last: usize) -> Result<()> {
+    let projects = resolve_projects(global, project)?;
+    let mut printed = 0;
+
+    for project in &projects {
+        let logs = match category {
+            Some(category) => project.fs.recent_logs(category, last)?,
+            None => project.fs.recent_logs_all(last)?,
+        };
+
+        if logs.is_empty() {
+            continue;
+        }
+
+        if printed > 0 {
+            println!();
+        }
+        println!("{}", project.name);
+        for log in &logs {
+            println!("  {}", format_log_row(log));
+        }
+        printed += logs.len();
+    }
+
+    if printed == 0 {
+        println!("No logs found.");
+    }
+
+    Ok(())
+}
+
+/// `byte logs show <id|latest>`
+fn show(global: &GlobalConfig, project: Option<&str>, id: &str) -> Result<()> {
+    let projects = resolve_projects(global, project)?;
+    let log = find_log(&projects, id)?;
+    let parsed = log_format::parse_log(&crate::fs::read_log(&log.path)?);
+
+    let mut out = std::io::stdout().lock();
+    if let Some(meta) = &parsed.meta {
+        for line in log_format::describe_meta(meta) {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out)?;
+    }
+    for line in &parsed.lines {
+        writeln!(out, "{}", display_text(&line.text))?;
+    }
+
+    Ok(())
+}
+
+/// `byte logs tail [-f] [id]`
+fn tail(global: &GlobalConfig, project: Option<&str>, id: &str, lines: usize, follow: Option<Duration>) -> Result<()> {
+    let projects = resolve_projects(global, project)?;
+    let log = find_log(&projects, id)?;
+
+    let parsed = log_format::parse_log(&crate::fs::read_log(&log.path)?);
+    let mut printed = parsed.lines.len();
+    for line in &parsed.lines[printed.saturating_sub(lines)..] {
+        println!("{}", display_text(&line.text));
+    }
+
+    let mut meta = parsed.meta;
+    let Some(timeout) = follow else {
+        return Ok(());
+    };
+
+    // Poll the file: a live log grows line by line, then is rewritten once with
+    // the final header when the command exits. A log whose writer died is
+    // never rewritten, so give up when it goes quiet for too long.
+    let mut last_growth = Instant::now();
+    while let Some(running) = meta.as_ref().filter(|m| m.running) {
+        if let Some(pid) = running.pid
+            && !process_alive(pid)
+        {
+            eprintln!("— stopped following: the process writing the log (pid {}) has exited", pid);
+            return Ok(());
+        }
+        if last_growth.elapsed() >= timeout {
+            eprintln!("— stopped following: no new output for {}s", timeout.as_secs());
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(250));
+
+        let Ok(content) = crate::fs::read_log(&log.path) else {
+            break;
+        };
+        let parsed = log_format::parse_log(&content);
+        for line in parsed.lines.iter().skip(printed) {
+            println!("{}", display_text(&line.text));
+        }
+        if parsed.lines.len() > printed {
+            last_growth = Instant::now();
+        }
+        printed = printed.max(parsed.lines.len());
+        meta = parsed.meta;
+    }
+
+    if let Some(meta) = &meta {
+        eprintln!("{}", finished_summary(meta));
+    }
+
+    Ok(())
+}
+
+/// Whether a process is still running; unknown (assumed running) where
+/// there's no `/proc` to look in
+fn process_alive(pid: u32) -> bool {
+    let proc = Path::new("/proc");
+    !proc.exists() || proc.join(pid.to_string()).exists()
+}
+
+/// `byte logs grep <pattern>`
+fn grep(projects: &[LogProject], pattern: &str, ignore_case: bool, category: Option<&str>) -> Result<()> {
+    let needle = if ignore_case { pattern.to_lowercase() } else { pattern.to_string() };
+    let mut matches = 0;
+
+    for project in projects {
+        for log in project.fs.recent_logs_all(usize::MAX)? {
+            if category.is_some_and(|c| log.category != c) {
+                continue;
+            }
+            let Ok(content) = crate::fs::read_log(&log.path) else {
+                continue;
+            };
+            let structured = content.starts_with(log_format::LOG_MAGIC);
+
+            for (i, raw) in content.lines().enumerate() {
+                // Skip the JSON metadata header of structured logs
+                if structured && i == 0 {
+                    continue;
+                }
+                let text = match structured {
+                    true => log_format::split_line(raw).map(|(_, _, text)| text).unwrap_or(raw),
+                    false => raw,
+                };
+                let plain = plain_text(text);
+                let haystack = if ignore_case { plain.to_lowercase() } else { plain.clone() };
+
+                if haystack.contains(&needle) {
+                    println!("{}:{}: {}", display_path(&log.path), i + 1, plain);
+                    matches += 1;
+                }
+            }
+        }
+    }
+
+    if matches == 0 {
+        bail!("No matches for '{}'", pattern);
+    }
+
+    Ok(())
+}
+
+/// `byte logs diff <old> [new]`
+fn diff(global: &GlobalConfig, project: Option<&str>, old: &str, new: &str, context: usize) -> Result<()> {
+    let projects = resolve_projects(global, project)?;
+    let old_log = find_log(&projects, old)?;
+    let new_log = find_log(&projects, new)?;
+
+    let old_task = old_log.meta.as_ref().map(|m| m.task.clone().unwrap_or_else(|| m.command.clone()));
+    let new_task = new_log.meta.as_ref().map(|m| m.task.clone().unwrap_or_else(|| m.command.clone()));
+    if old_task != new_task {
+        eprintln!("warning: comparing different tasks ({} vs {})",
+            old_task.unwrap_or_default(),
+            new_task.unwrap_or_default()
+        );
+    }
+
+    let diff = LogDiff::between(&crate::fs::read_log(&old_log.path)?, &crate::fs::read_log(&new_log.path)?);
+    let color = atty::is(atty::Stream::Stdout);
+    let paint = |code: &str, text: &str| if color { format!("\x1b[{}m{}\x1b[0m", code, text) } else { text.to_string() };
+
+    println!("{}", paint("31", &format!("--- {} ({})", old_log.id(), run_summary(old_log.meta.as_ref()))));
+    println!("{}", paint("32", &format!("+++ {} ({})", new_log.id(), run_summary(new_log.meta.as_ref()))));
+
+    if diff.is_identical() {
+        println!("No differences (after normalizing timestamps, durations and temp paths)");
+        return Ok(());
+    }
+
+    for line in diff.unified(context) {
+        let code = match line.chars().next() {
+            Some('@') => "36",
+            Some('-') => "31",
+            Some('+') => "32",
+            _ => "",
+        };
+        if code.is_empty() {
+            println!("{}", line);
+        } else {
+            println!("{}", paint(code, &line));
+        }
+    }
+
+    Ok(())
+}
+
+/// `byte logs gc`: compress/prune logs in every discovered project
+fn gc(global: &GlobalConfig) -> Result<()> {
+    let projects = all_log_projects(global)?;
+    let mut total = PruneReport::default();
+
+    for project in &projects {
+        match project.fs.gc_logs(&global.logs) {
+            Ok(report) => {
+                if !report.is_empty() {
+                    println!("  • {}: {} compressed, {} deleted, {} freed",
+                        project.name,
+                        report.compressed,
+                        report.deleted,
+                        format_bytes(report.freed_bytes)
+                    );
+                }
+                total.merge(&report);
+            }
+            Err(e) => eprintln!("  ✗ {}: {}", project.name, e),
+        }
+    }
+
+    if total.is_empty() {
+        println!("✓ Nothing to prune across {} project{}",
+            projects.len(),
+            if projects.len() == 1 { "" } else { "s" }
+        );
+    } else {
+        println!("\n✓ {} compressed, {} deleted, {} freed across {} project{}",
+            total.compressed,
+            total.deleted,
+            format_bytes(total.freed_bytes),
+            projects.len(),
+            if projects.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Project and log resolution
+// ============================================================================
+
+/// Projects named on the command line, the current project, or all projects
+fn resolve_projects(global: &GlobalConfig, spec: Option<&str>) -> Result<Vec<LogProject>> {
+    match spec {
+        Some(spec) => {
+            let path = Path::new(spec);
+            if path.is_dir() {
+                return Ok(vec![log_project(path.to_path_buf(), None)?]);
+            }
+
+            let projects = crate::projects::discover_projects(global)?;
+            let found = projects.into_iter().find(|p| {
+                p.config.project.name == spec
+                    || p.path.file_name().is_some_and(|n| n.to_string_lossy() == spec)
+            });
+            match found {
+                Some(p) => Ok(vec![log_project(p.path, Some(p.config.project.name))?]),
+                None => bail!("No project named '{}'", spec),
+            }
+        }
+        None => match current_project_root() {
+            Some(root) => Ok(vec![log_project(root, None)?]),
+            None => all_log_projects(global),
+        },
+    }
+}
+
+fn all_log_projects(global: &GlobalConfig) -> Result<Vec<LogProject>> {
+    crate::projects::discover_projects(global)?
+        .into_iter()
+        .map(|p| log_project(p.path, Some(p.config.project.name)))
+        .collect()
+}
+
+fn log_project(path: PathBuf, name: Option<String>) -> Result<LogProject> {
+    let name = name.unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string())
+    });
+    Ok(LogProject { name, fs: ProjectFileSystem::new(path)? })
+}
+
+/// Nearest ancestor of the current directory that is a Byte project
+fn current_project_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .find(|dir| dir.join("byte.toml").exists() || dir.join(".byte").is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Find a log by id (exact, then prefix) or the newest log for "latest"
+fn find_log(projects: &[LogProject], id: &str) -> Result<LogFile> {
+    let mut logs = Vec::new();
+    for project in projects {
+        logs.extend(project.fs.recent_logs_all(usize::MAX)?);
+    }
+    logs.sort_by_key(|log| std::cmp::Reverse(log.timestamp));
+
+    let found = if id == "latest" {
+        logs.into_iter().next()
+    } else {
+        match logs.iter().position(|log| log.id() == id) {
+            Some(i) => Some(logs.swap_remove(i)),
+            None => logs.into_iter().find(|log| log.id().starts_with(id)),
+        }
+    };
+
+    match found {
+        Some(log) => Ok(log),
+        None if id == "latest" => bail!("No logs found"),
+        None => bail!("No log with id '{}' (run `byte logs` to list ids)", id),
+    }
+}
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+fn format_log_row(log: &LogFile) -> String {
+    let (status, duration, command) = match &log.meta {
+        Some(meta) => (status_label(meta), format_duration(meta.duration_ms), meta.command.clone()),
+        None => ("?".to_string(), String::new(), String::new()),
+    };
+
+    format!("{:<32} {:<6} {:<8} {:>7} {:>8}  {}",
+        log.id(),
+        log.category,
+        status,
+        duration,
+        format_age(log.timestamp),
+        command
+    )
+}
+
+fn status_label(meta: &LogMeta) -> String {
+    if meta.running {
+        "running".to_string()
+    } else if meta.success() {
+        "ok".to_string()
+    } else {
+        format!("exit {}", meta.exit_code)
+    }
+}
+
+fn run_summary(meta: Option<&LogMeta>) -> String {
+    match meta {
+        Some(meta) => format!("{}, {}", status_label(meta), format_duration(meta.duration_ms)),
+        None => "no metadata".to_string(),
+    }
+}
+
+fn finished_summary(meta: &LogMeta) -> String {
+    if meta.running {
+        "— log ended while the command was still running".to_string()
+    } else if meta.success() {
+        format!("✓ finished in {}", format_duration(meta.duration_ms))
+    } else {
+        format!("✗ exit code {} after {}", meta.exit_code, format_duration(meta.duration_ms))
+    }
+}
+
+fn format_duration(ms: u64) -> String {
+    if ms == 0 {
+        String::new()
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m{:02}s", ms / 60_000, (ms / 1000) % 60)
+    }
+}
+
+fn format_age(timestamp: SystemTime) -> String {
+    let secs = SystemTime::now().duration_since(timestamp).unwrap_or_default().as_secs();
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86400)
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= MB {
+        format!("{:.1} MB", bytes as f64 / MB)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Log paths relative to the current directory when possible
+fn display_path(path: &Path) -> String {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn plain_text(text: &str) -> String {
+    crate::tui::ansi::plain_text(&crate::tui::ansi::parse_line(text, false))
+}
+
+/// Keep ANSI colors on a terminal, strip them when piped
+fn display_text(text: &str) -> String {
+    if atty::is(atty::Stream::Stdout) {
+        text.to_string()
+    } else {
+        plain_text(text)
+    }
+}
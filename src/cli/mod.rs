@@ -1,6 +1,11 @@
+mod doctor;
+mod logs;
+
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
+pub use logs::LogsCommand;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
@@ -11,35 +16,116 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Initialize a new project
+    #[command(after_help = "Examples:\n  byte init rust cli my-tool\n  byte init go+bun web shop\n  byte init --template service billing --set docker=true")]
     Init {
-        /// Ecosystem (e.g., go, bun, rust)
-        ecosystem: String,
+        /// ECOSYSTEM TYPE NAME (e.g. go cli my-tool, go+bun web shop), or just NAME with --template
+        #[arg(value_name = "ARGS", required = true, num_args = 1..=3)]
+        args: Vec<String>,
 
-        /// Project type (e.g., cli, desktop, web)
-        project_type: String,
+        /// Template name, directory or git URL
+        #[arg(long, short)]
+        template: Option<String>,
 
-        /// Project name
-        name: String,
+        /// Set a template variable (repeatable); unset variables are prompted for
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
     },
 
     /// Discover and list all projects
     Discover,
 
+    /// Write a byte.toml for an existing project, inferred from its manifest
+    Adopt {
+        /// Project directory (defaults to the current directory)
+        path: Option<String>,
+    },
+
     /// Launch TUI
     Tui,
+
+    /// Tag a release: bump the manifest version, commit, tag and optionally push
+    Release {
+        /// Project name or path
+        project: String,
+
+        /// Version to release (e.g. 1.2.0 or v1.2.0); the tag is "v<version>"
+        version: String,
+
+        /// Tag message
+        #[arg(long, short)]
+        message: Option<String>,
+
+        /// Create a lightweight tag instead of an annotated one
+        #[arg(long)]
+        lightweight: bool,
+
+        /// Don't touch the manifest version; only tag
+        #[arg(long)]
+        no_bump: bool,
+
+        /// Push the release commit and tag to this remote
+        #[arg(long)]
+        push: Option<String>,
+    },
+
+    /// Fetch all (or the named) projects in parallel and report ahead/behind
+    Sync {
+        /// Project names or paths (defaults to all discovered projects)
+        projects: Vec<String>,
+
+        /// Also fast-forward branches that are behind (clean working trees only)
+        #[arg(long)]
+        pull: bool,
+
+        /// Also push branches that are ahead (never diverged ones)
+        #[arg(long)]
+        push: bool,
+    },
+
+    /// Check git, config, workspaces, file watcher limits and toolchains
+    Doctor,
+
+    /// List, show, follow and search command logs
+    #[command(args_conflicts_with_subcommands = true)]
+    Logs {
+        /// Project name or path (defaults to the project in the current directory)
+        project: Option<String>,
+
+        /// Only show logs of this category (build, lint, git, test, other)
+        #[arg(long, short)]
+        category: Option<String>,
+
+        /// Number of logs to list
+        #[arg(long, short = 'n', default_value_t = 20)]
+        last: usize,
+
+        #[command(subcommand)]
+        command: Option<LogsCommand>,
+    },
 }
 
 pub fn run() -> Result<()> {
-    let config = crate::config::Config::load()?;
-
     let cli = Cli::parse();
 
+    // Doctor reports a broken config.toml instead of failing on it
+    if let Some(Commands::Doctor) = cli.command {
+        return doctor::run();
+    }
+
+    let config = crate::config::Config::load()?;
+    crate::policy::install(&config.global);
+
     match cli.command {
-        Some(Commands::Init {
-            ecosystem,
-            project_type,
-            name,
-        }) => {
+        Some(Commands::Init { args, template: Some(spec), set }) => {
+            let [name] = args.as_slice() else {
+                anyhow::bail!("With --template, pass only the project name");
+            };
+            init_from_template(&config.global, &spec, name, &set)
+        }
+        Some(Commands::Init { args, template: None, .. }) => {
+            let [ecosystem, project_type, name] = args.as_slice() else {
+                anyhow::bail!("Usage: byte init <ECOSYSTEM> <TYPE> <NAME> (or byte init --template <TEMPLATE> <NAME>)");
+            };
             let workspace_path = &config.global.workspace.path;
 
             println!(
@@ -47,8 +133,9 @@ pub fn run() -> Result<()> {
                 ecosystem, project_type, name
             );
 
-            match crate::projects::init_project(workspace_path, &ecosystem, &project_type, &name) {
+            match crate::projects::init_project(workspace_path, ecosystem, project_type, name) {
                 Ok(path) => {
+                    crate::policy::trust_created(&path);
                     println!("✓ Project created at: {}", path.display());
                     println!("\nNext steps:");
                     println!("  cd {}", path.display());
@@ -82,11 +169,12 @@ pub fn run() -> Result<()> {
                         for project in &projects {
                             let ecosystem = &project.config.project.ecosystem;
                             let project_type = &project.config.project.project_type;
-                            println!("  • {} ({}/{}) at {}",
+                            println!("  • {} ({}/{}) at {}{}",
                                 project.config.project.name,
                                 ecosystem,
                                 project_type,
-                                project.path.display()
+                                project.path.display(),
+                                if project.implicit { "  [implicit]" } else { "" }
                             );
                         }
                     }
@@ -98,6 +186,99 @@ pub fn run() -> Result<()> {
                 }
             }
         }
+        Some(Commands::Adopt { path }) => {
+            let path = match path {
+                Some(path) => {
+                    let safe_path = crate::path::SafePath::from_user_input(&path)?;
+                    safe_path.canonical().unwrap_or(safe_path.expanded()).to_path_buf()
+                }
+                None => std::env::current_dir()?,
+            };
+
+            match crate::projects::adopt_project(&path) {
+                Ok(config_path) => {
+                    println!("✓ Wrote {}", config_path.display());
+                    println!("  Review the generated [build] tasks, then run `byte tui`");
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("✗ Failed to adopt project: {}", e);
+                    Err(e)
+                }
+            }
+        }
+        Some(Commands::Release { project, version, message, lightweight, no_bump, push }) => {
+            let path = find_project(&config.global, &project)?;
+            let tag = if version.starts_with('v') { version } else { format!("v{}", version) };
+            let options = crate::release::ReleaseOptions {
+                tag: tag.clone(),
+                message: message.unwrap_or_default(),
+                annotated: !lightweight,
+                bump: !no_bump,
+                remote: push,
+                prompts: true,
+            };
+
+            match crate::release::release(&path, &options) {
+                Ok(outcome) => {
+                    if let Some(commit) = &outcome.commit {
+                        println!("✓ Committed version bump ({})", commit);
+                    } else if options.bump {
+                        println!("  No manifest version to bump");
+                    }
+                    println!("✓ {}", outcome.summary(&tag));
+                    Ok(())
+                }
+                Err(e) => {
+                    eprintln!("✗ Release failed: {:#}", e);
+                    Err(e)
+                }
+            }
+        }
+        Some(Commands::Sync { projects, pull, push }) => {
+            use crate::state::git_sync::{self, SyncMode, SyncOutcome};
+
+            let targets: Vec<(String, String)> = if projects.is_empty() {
+                crate::projects::discover_projects(&config.global)?
+                    .into_iter()
+                    .map(|p| (p.config.project.name, p.path.to_string_lossy().to_string()))
+                    .collect()
+            } else {
+                projects
+                    .iter()
+                    .map(|spec| {
+                        let path = find_project(&config.global, spec)?;
+                        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| spec.clone());
+                        Ok((name, path.to_string_lossy().to_string()))
+                    })
+                    .collect::<Result<_>>()?
+            };
+
+            println!("{} {} projects...\n", if pull || push { "Syncing" } else { "Fetching" }, targets.len());
+            let results = git_sync::sync_all(&targets, SyncMode { pull, push });
+            let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+            for result in &results {
+                let icon = match &result.outcome {
+                    SyncOutcome::FastForwarded(_) => "↓",
+                    SyncOutcome::Pushed(_) => "↑",
+                    outcome if outcome.is_problem() => "✗",
+                    SyncOutcome::NotARepo | SyncOutcome::NoUpstream => "-",
+                    SyncOutcome::Behind(_) | SyncOutcome::Ahead(_) => "•",
+                    _ => "✓",
+                };
+                println!("  {} {:width$}  {}", icon, result.name, result.outcome.label(), width = width);
+            }
+            println!("\n{}", git_sync::summarize(&results));
+            if push {
+                println!("Push: {}", git_sync::push_report(&results));
+            }
+            Ok(())
+        }
+        Some(Commands::Logs { project, category, last, command }) => match command {
+            Some(command) => logs::run(&config.global, command),
+            None => logs::list(&config.global, project.as_deref(), category.as_deref(), last),
+        },
+        Some(Commands::Doctor) => unreachable!("handled before loading the config"),
         Some(Commands::Tui) => {
             println!("Launching TUI...");
             crate::tui::run()?;
@@ -111,3 +292,126 @@ pub fn run() -> Result<()> {
         }
     }
 }
+
+/// `byte init --template`: resolve the template, collect its variables from
+/// `--set` and (on a terminal) prompts, then create the project
+fn init_from_template(global: &crate::config::GlobalConfig, spec: &str, name: &str, set: &[String]) -> Result<()> {
+    let mut provided = crate::templates::Values::new();
+    for pair in set {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("--set expects KEY=VALUE, got '{}'", pair))?;
+        provided.insert(key.trim().to_string(), value.to_string());
+    }
+
+    let template = crate::templates::resolve_template(global, spec)?;
+    let result = (|| {
+        if let Some(unknown) = provided.keys().find(|key| !template.manifest.variables.iter().any(|v| &v.name == *key)) {
+            anyhow::bail!("Template '{}' has no variable '{}'", template.name, unknown);
+        }
+        if atty::is(atty::Stream::Stdin) {
+            prompt_variables(&template, name, &mut provided)?;
+        }
+        let values = template.resolve_values(name, &provided)?;
+
+        let run_hooks = approve_hooks(&template, &values)?;
+
+        println!("Creating '{}' from template '{}'...", name, template.name);
+        crate::projects::init_from_template(&global.workspace.path, &template, name, &values, run_hooks)
+    })();
+    template.cleanup();
+
+    match result {
+        Ok(project) => {
+            for (hook, error) in &project.failed_hooks {
+                eprintln!("✗ Hook failed: {}\n  {}", hook, error);
+            }
+            if !project.skipped_hooks.is_empty() {
+                println!("Skipped post-create hooks (run them yourself once you've reviewed them):");
+                for hook in &project.skipped_hooks {
+                    println!("  {}", hook);
+                }
+            }
+            println!("✓ Project created at: {}", project.path.display());
+            println!("\nNext steps:");
+            println!("  cd {}", project.path.display());
+            println!("  byte tui  # to view in the project browser");
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("✗ Failed to create project: {}", e);
+            Err(e)
+        }
+    }
+}
+
+/// Show a template's post-create hooks and ask whether to run them; without
+/// a terminal to ask on they're skipped
+fn approve_hooks(template: &crate::templates::Template, values: &crate::templates::Values) -> Result<bool> {
+    use std::io::Write;
+
+    let hooks = &template.manifest.hooks.post_create;
+    if hooks.is_empty() {
+        return Ok(true);
+    }
+    if !atty::is(atty::Stream::Stdin) {
+        return Ok(false);
+    }
+
+    println!("Template '{}' runs these commands after creating the project:", template.name);
+    for hook in hooks {
+        println!("  {}", crate::templates::hook_command(hook, values));
+    }
+    print!("Run them? [y/N]: ");
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Ask for every variable not given with `--set`; an empty answer keeps the default
+fn prompt_variables(template: &crate::templates::Template, name: &str, provided: &mut crate::templates::Values) -> Result<()> {
+    use std::io::Write;
+
+    let mut values = template.default_values(name);
+    for variable in &template.manifest.variables {
+        if let Some(value) = provided.get(&variable.name) {
+            values.insert(variable.name.clone(), value.clone());
+            continue;
+        }
+        let default = crate::templates::substitute(&variable.default, &values);
+        let hint = match variable.kind {
+            crate::templates::VariableKind::Choice => format!(" ({})", variable.choices.join("/")),
+            crate::templates::VariableKind::Bool => " (y/n)".to_string(),
+            crate::templates::VariableKind::Text => String::new(),
+        };
+        print!("{}{} [{}]: ", variable.prompt(), hint, default);
+        std::io::stdout().flush()?;
+
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        let answer = answer.trim();
+        let value = if answer.is_empty() { default } else { answer.to_string() };
+        values.insert(variable.name.clone(), value.clone());
+        provided.insert(variable.name.clone(), value);
+    }
+    Ok(())
+}
+
+/// A project by path, or by name among discovered projects
+fn find_project(global: &crate::config::GlobalConfig, spec: &str) -> Result<std::path::PathBuf> {
+    let path = std::path::Path::new(spec);
+    if path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+
+    crate::projects::discover_projects(global)?
+        .into_iter()
+        .find(|p| {
+            p.config.project.name == spec
+                || p.path.file_name().is_some_and(|n| n.to_string_lossy() == spec)
+        })
+        .map(|p| p.path)
+        .ok_or_else(|| anyhow::anyhow!("No project named '{}'", spec))
+}
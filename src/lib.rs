@@ -1,12 +1,18 @@
 pub mod cli;
 pub mod config;
+pub mod diagnostics;
 pub mod exec;
 pub mod forms;
 pub mod fs;
 pub mod log;
 pub mod path;
+pub mod policy;
 pub mod projects;
+pub mod release;
 pub mod state;
+pub mod tasks;
+pub mod templates;
+pub mod testing;
 pub mod tui;
 
 // Re-export commonly used types
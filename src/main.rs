@@ -1,12 +1,18 @@
 mod cli;
 mod config;
+mod diagnostics;
 mod exec;
 mod forms;
 mod fs;
 mod log;
 mod projects;
+mod release;
 mod state;
+mod tasks;
+mod templates;
 mod path;
+mod policy;
+mod testing;
 mod tui;
 
 fn main() {
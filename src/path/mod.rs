@@ -163,7 +163,6 @@ impl SafePath {
     /// - Read, write, and execute permissions (Unix)
     /// - Not read-only (cross-platform)
     /// - Can actually create files (test write)
-    #[allow(dead_code)]
     pub fn validate_writable(&self) -> Result<()> {
         // Must be a directory first
         self.validate_directory()?;
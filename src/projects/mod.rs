@@ -0,0 +1,683 @@
+pub mod detect;
+pub mod drivers;
+pub mod scaffold;
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::types::ProjectMeta;
+use crate::config::{GlobalConfig, ProjectConfig};
+
+/// Discovered project with path and config
+#[derive(Debug, Clone)]
+pub struct DiscoveredProject {
+    pub path: PathBuf,
+    pub config: ProjectConfig,
+    /// No byte.toml yet; the config was inferred from the project's manifest
+    pub implicit: bool,
+}
+
+/// Validate project name for safety and filesystem compatibility
+///
+/// Prevents:
+/// - Path traversal attacks (../, sub/dir)
+/// - Reserved names (.git, target, etc.)
+/// - Invalid characters
+/// - Length violations
+/// - Shell injection via special characters
+pub fn validate_project_name(name: &str) -> Result<()> {
+    // 1. Empty/whitespace check
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("Project name cannot be empty");
+    }
+
+    // 2. Path separator check (prevents traversal)
+    if name.contains('/') || name.contains('\\') || name.contains('\0') {
+        anyhow::bail!(
+            "Project name cannot contain path separators (/, \\, or null bytes)"
+        );
+    }
+
+    // 3. Reserved name check (case-insensitive for cross-platform safety)
+    const RESERVED: &[&str] = &[
+        ".", "..", ".git", ".byte",
+        "target", "node_modules", "dist", "build", "out",
+        ".vscode", ".idea", ".vs", ".fleet",
+        // Windows reserved names (uppercase for comparison)
+        "con", "prn", "aux", "nul",
+        "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9",
+        "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+    ];
+
+    let name_lower = name.to_lowercase();
+    if RESERVED.contains(&name) || RESERVED.contains(&name_lower.as_str()) {
+        anyhow::bail!("Project name '{}' is reserved and cannot be used", name);
+    }
+
+    // 4. Length check (filesystem limit is typically 255 bytes)
+    if name.len() > 255 {
+        anyhow::bail!(
+            "Project name too long ({} characters). Maximum is 255.",
+            name.len()
+        );
+    }
+
+    // 5. Character restrictions (conservative: alphanumeric + dash + underscore + dot)
+    let valid_chars = name.chars().all(|c| {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'
+    });
+
+    if !valid_chars {
+        anyhow::bail!(
+            "Project name can only contain ASCII letters, numbers, hyphens (-), underscores (_), and dots (.). Got: '{}'",
+            name
+        );
+    }
+
+    // 6. Cannot start with dot or hyphen (hidden files, command flags)
+    if name.starts_with('.') {
+        anyhow::bail!("Project name cannot start with '.' (would create hidden directory)");
+    }
+    if name.starts_with('-') {
+        anyhow::bail!("Project name cannot start with '-' (conflicts with command flags)");
+    }
+
+    // 7. Cannot end with dot (Windows filesystem issue)
+    if name.ends_with('.') {
+        anyhow::bail!("Project name cannot end with '.'");
+    }
+
+    Ok(())
+}
+
+/// Scan workspace and registered paths for projects
+pub fn discover_projects(global_config: &GlobalConfig) -> Result<Vec<DiscoveredProject>> {
+    let mut projects = Vec::new();
+
+    // Scan workspace if auto_scan is enabled
+    if global_config.workspace.auto_scan {
+        match crate::path::SafePath::from_user_input(&global_config.workspace.path) {
+            Ok(workspace_path) => {
+                crate::log::debug("DISCOVERY", &format!("Scanning primary workspace: {}", workspace_path));
+
+                match scan_directory(workspace_path.expanded(), global_config.workspace.detect_implicit) {
+                    Ok(workspace_projects) => {
+                        crate::log::debug("DISCOVERY", &format!("Found {} projects in primary workspace", workspace_projects.len()));
+                        projects.extend(workspace_projects);
+                    }
+                    Err(e) => {
+                        crate::log::error("DISCOVERY", &format!("Failed to scan primary workspace: {}", e));
+                    }
+                }
+            }
+            Err(e) => {
+                crate::log::error("DISCOVERY", &format!("Invalid workspace path '{}': {}", global_config.workspace.path, e));
+            }
+        }
+    }
+
+    // Scan manually registered directories for projects
+    for registered_path in &global_config.workspace.registered {
+        match crate::path::SafePath::from_user_input(registered_path) {
+            Ok(safe_path) => {
+                crate::log::debug("DISCOVERY", &format!("Scanning registered path: {}", safe_path));
+
+                match scan_directory(safe_path.expanded(), global_config.workspace.detect_implicit) {
+                    Ok(registered_projects) => {
+                        crate::log::debug("DISCOVERY", &format!("Found {} projects in {}", registered_projects.len(), safe_path));
+                        for proj in &registered_projects {
+                            crate::log::debug("DISCOVERY", &format!("  - {} at {}", proj.config.project.name, proj.path.display()));
+                        }
+                        projects.extend(registered_projects);
+                    }
+                    Err(e) => {
+                        crate::log::error("DISCOVERY", &format!("Failed to scan {}: {}", safe_path, e));
+                    }
+                }
+            }
+            Err(e) => {
+                crate::log::error("DISCOVERY", &format!("Invalid registered path '{}': {}", registered_path, e));
+            }
+        }
+    }
+
+                crate::log::debug("DISCOVERY", &format!("Total projects discovered: {}", projects.len()));
+    Ok(projects)
+}
+
+/// Scan a directory for byte.toml files, and optionally for implicit projects
+fn scan_directory(path: &Path, detect_implicit: bool) -> Result<Vec<DiscoveredProject>> {
+    let mut projects = Vec::new();
+
+    crate::log::debug("SCAN", &format!("Scanning directory: {}", path.display()));
+
+    if !path.exists() {
+        crate::log::debug("SCAN", &format!("Path does not exist: {}", path.display()));
+        return Ok(projects);
+    }
+
+    // Walk directory looking for byte.toml files
+    let mut entry_count = 0;
+    for entry in WalkDir::new(path)
+        .max_depth(3) // Don't go too deep
+        .follow_links(false)
+    {
+        match entry {
+            Ok(entry) => {
+                entry_count += 1;
+                let file_name = entry.file_name().to_string_lossy();
+                if file_name == "byte.toml" {
+                    crate::log::debug("SCAN", &format!("Found byte.toml at: {}", entry.path().display()));
+
+                    if let Some(project_dir) = entry.path().parent() {
+                        match load_project(project_dir.to_str().unwrap_or("")) {
+                            Ok(project) => {
+                                crate::log::debug("SCAN", &format!("Successfully loaded project: {}", project.config.project.name));
+                                projects.push(project);
+                            }
+                            Err(e) => {
+                                crate::log::error("SCAN", &format!("Failed to load project from {}: {}", project_dir.display(), e));
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                crate::log::error("SCAN", &format!("Error reading entry: {}", e));
+            }
+        }
+    }
+
+    if detect_implicit {
+        let implicit = scan_implicit(path, &projects);
+        crate::log::debug("SCAN", &format!("Found {} implicit projects", implicit.len()));
+        projects.extend(implicit);
+    }
+
+    crate::log::debug("SCAN", &format!("Scanned {} entries, found {} projects", entry_count, projects.len()));
+
+    Ok(projects)
+}
+
+/// Directories with a known manifest but no byte.toml
+///
+/// The scanned directory itself is a container, not a project. Nothing inside
+/// a project is reported, so crates of a Cargo workspace or packages of a
+/// monorepo stay part of their root.
+fn scan_implicit(path: &Path, explicit: &[DiscoveredProject]) -> Vec<DiscoveredProject> {
+    let mut projects = Vec::new();
+    let mut walker = WalkDir::new(path)
+        .min_depth(1)
+        .max_depth(3)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            e.file_type().is_dir() && !detect::is_ignored_dir(&e.file_name().to_string_lossy())
+        });
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        let dir = entry.path();
+
+        if explicit.iter().any(|p| p.path == dir) {
+            walker.skip_current_dir();
+            continue;
+        }
+        if let Some(config) = detect::infer_config(dir) {
+            crate::log::debug("SCAN", &format!("Found implicit {} project at: {}", config.project.ecosystem, dir.display()));
+            projects.push(DiscoveredProject {
+                path: dir.to_path_buf(),
+                config,
+                implicit: true,
+            });
+            walker.skip_current_dir();
+        }
+    }
+
+    projects
+}
+
+/// Load a project from a directory containing byte.toml
+fn load_project(path: &str) -> Result<DiscoveredProject> {
+    let project_path = PathBuf::from(path);
+    let config_path = project_path.join("byte.toml");
+
+    let config_content = fs::read_to_string(&config_path)?;
+    let config: ProjectConfig = toml::from_str(&config_content)?;
+
+    Ok(DiscoveredProject {
+        path: project_path,
+        config,
+        implicit: false,
+    })
+}
+
+/// byte.toml with a `[project]` section and `[build]` tasks in the given order
+pub fn byte_toml(meta: &ProjectMeta, tasks: &[(String, String)]) -> String {
+    let quote = |s: &str| toml::Value::String(s.to_string()).to_string();
+
+    let mut out = String::from("[project]\n");
+    out.push_str(&format!("name = {}\n", quote(&meta.name)));
+    out.push_str(&format!("type = {}\n", quote(&meta.project_type)));
+    out.push_str(&format!("ecosystem = {}\n", quote(&meta.ecosystem)));
+    if let Some(description) = &meta.description {
+        out.push_str(&format!("description = {}\n", quote(description)));
+    }
+    if !tasks.is_empty() {
+        out.push_str("\n[build]\n");
+        for (name, command) in tasks {
+            // Namespaced tasks (`backend:build`) need a quoted key
+            let bare = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            let key = if bare { name.clone() } else { quote(name) };
+            out.push_str(&format!("{} = {}\n", key, quote(command)));
+        }
+    }
+    out
+}
+
+/// Turn an implicit project into a Byte project
+///
+/// Writes a byte.toml inferred from the project's manifest, creates `.byte/`
+/// and adds it to `.gitignore`. Returns the path of the new byte.toml.
+pub fn adopt_project(path: &Path) -> Result<PathBuf> {
+    let config_path = path.join("byte.toml");
+    if config_path.exists() {
+        anyhow::bail!("{} already exists", config_path.display());
+    }
+
+    let inferred = detect::infer(path).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No project manifest found in {} (looked for Cargo.toml, go.mod, package.json, deno.json, pyproject.toml, build.zig, Makefile)",
+            path.display()
+        )
+    })?;
+    fs::write(&config_path, inferred.to_toml())?;
+
+    let fs_api = crate::fs::ProjectFileSystem::new(path)?;
+    fs_api.init_byte_structure()?;
+    fs_api.create_gitignore()?;
+
+    Ok(config_path)
+}
+
+/// Initialize a new project using the FS and Exec APIs
+pub fn init_project(
+    workspace_path: &str,
+    ecosystem: &str,
+    project_type: &str,
+    name: &str,
+) -> Result<PathBuf> {
+    // Validate project name for security and filesystem compatibility
+    validate_project_name(name)?;
+
+    // Expand and validate workspace path
+    let safe_workspace = crate::path::SafePath::from_user_input(workspace_path)?;
+    let workspace_path = safe_workspace.expanded();
+
+    // Create workspace if it doesn't exist
+    if !workspace_path.exists() {
+        fs::create_dir_all(workspace_path)?;
+    }
+
+    // Create project directory
+    let project_path = workspace_path.join(name);
+    if project_path.exists() {
+        anyhow::bail!(
+            "Project directory already exists: {}",
+            project_path.display()
+        );
+    }
+
+    // `go+bun`: one subdirectory per driver
+    let capabilities = drivers::parse(ecosystem).map(|list| drivers::assign_capabilities(&list)).transpose()?;
+
+    fs::create_dir_all(&project_path)?;
+
+    // Use FS API to initialize project structure
+    let fs_api = crate::fs::ProjectFileSystem::new(&project_path)?;
+    match &capabilities {
+        Some(capabilities) => {
+            fs_api.init_byte_structure()?;
+            for capability in capabilities {
+                let driver_fs = crate::fs::ProjectFileSystem::new(project_path.join(capability.dir()))?;
+                let driver_type = drivers::driver_type(&capability.driver, &capability.name);
+                driver_fs.init_ecosystem_structure(&capability.driver, driver_type, &scaffold::driver_name(name, capability))?;
+            }
+            fs_api.create_gitignore()?;
+            scaffold::scaffold_polyglot(&project_path, capabilities, project_type, name, true)?;
+        }
+        None => {
+            fs_api.init_project(ecosystem, project_type, name)?;
+
+            // Manifest, byte.toml and README
+            scaffold::scaffold(&project_path, ecosystem, project_type, name, true)?;
+        }
+    }
+
+    // Initialize git repository
+    init_git_repo(&project_path, name)?;
+
+    Ok(project_path)
+}
+
+/// A project created from a user template
+#[derive(Debug, Clone)]
+pub struct TemplateProject {
+    pub path: PathBuf,
+    /// Post-create hooks that failed, with their error output
+    pub failed_hooks: Vec<(String, String)>,
+    /// Post-create hooks that weren't run because the user didn't approve them
+    pub skipped_hooks: Vec<String>,
+}
+
+/// Create a project from a user template
+///
+/// Renders the template, writes a byte.toml if the template has none, sets up
+/// `.byte/`, runs the post-create hooks when `run_hooks` (the user approved
+/// them; templates can come from anywhere) and makes the initial commit
+/// (unless a hook already created a repository). A failing hook doesn't undo
+/// the project.
+pub fn init_from_template(
+    workspace_path: &str,
+    template: &crate::templates::Template,
+    name: &str,
+    values: &crate::templates::Values,
+    run_hooks: bool,
+) -> Result<TemplateProject> {
+    validate_project_name(name)?;
+
+    let safe_workspace = crate::path::SafePath::from_user_input(workspace_path)?;
+    let project_path = safe_workspace.expanded().join(name);
+    if project_path.exists() {
+        anyhow::bail!("Project directory already exists: {}", project_path.display());
+    }
+    fs::create_dir_all(&project_path)?;
+
+    if let Err(e) = crate::templates::render(template, &project_path, values) {
+        let _ = fs::remove_dir_all(&project_path);
+        return Err(e);
+    }
+
+    if !project_path.join("byte.toml").exists() {
+        let meta = &template.manifest.template;
+        let inferred = detect::infer(&project_path);
+        let project = ProjectMeta {
+            name: name.to_string(),
+            project_type: meta
+                .project_type
+                .clone()
+                .or_else(|| inferred.as_ref().map(|i| i.meta.project_type.clone()))
+                .unwrap_or_else(|| "app".to_string()),
+            ecosystem: meta
+                .ecosystem
+                .clone()
+                .or_else(|| inferred.as_ref().map(|i| i.meta.ecosystem.clone()))
+                .unwrap_or_else(|| "custom".to_string()),
+            description: meta.description.clone(),
+        };
+        let tasks = inferred.map(|i| i.tasks).unwrap_or_default();
+        let content = format!("# Created from the {} template\n\n{}", template.name, byte_toml(&project, &tasks));
+        fs::write(project_path.join("byte.toml"), content)?;
+    }
+
+    let fs_api = crate::fs::ProjectFileSystem::new(&project_path)?;
+    fs_api.init_byte_structure()?;
+    fs_api.create_gitignore()?;
+
+    let mut failed_hooks = Vec::new();
+    let mut skipped_hooks = Vec::new();
+    for hook in &template.manifest.hooks.post_create {
+        let command = crate::templates::hook_command(hook, values);
+        if !run_hooks {
+            skipped_hooks.push(command);
+            continue;
+        }
+        let result = crate::exec::CommandBuilder::shell(&command).working_dir(&project_path).execute();
+        match result {
+            Ok(result) if result.success => {}
+            Ok(result) => {
+                let error = match result.stderr.trim() {
+                    "" => format!("exited with code {}", result.exit_code),
+                    stderr => stderr.to_string(),
+                };
+                failed_hooks.push((command, error));
+            }
+            Err(e) => failed_hooks.push((command, e.to_string())),
+        }
+    }
+
+    if !project_path.join(".git").exists() {
+        init_git_repo(&project_path, name)?;
+    }
+
+    Ok(TemplateProject { path: project_path, failed_hooks, skipped_hooks })
+}
+
+/// Initialize git repository with initial commit using Exec API
+fn init_git_repo(project_path: &Path, name: &str) -> Result<()> {
+    use crate::exec::CommandBuilder;
+
+    // Initialize git repository
+    CommandBuilder::git("init")
+        .working_dir(project_path)
+        .execute()?;
+
+    // Stage all files
+    CommandBuilder::git("add")
+        .arg(".")
+        .working_dir(project_path)
+        .execute()?;
+
+    // Create initial commit
+    let commit_message = format!("Initial commit: {} project", name);
+    CommandBuilder::git("commit")
+        .arg("-m")
+        .arg(&commit_message)
+        .working_dir(project_path)
+        .execute()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_project_names() {
+        // Standard names
+        assert!(validate_project_name("my-project").is_ok());
+        assert!(validate_project_name("MyProject").is_ok());
+        assert!(validate_project_name("my_project").is_ok());
+        assert!(validate_project_name("project123").is_ok());
+
+        // With dots (versions, extensions)
+        assert!(validate_project_name("app.v2").is_ok());
+        assert!(validate_project_name("my-app.2024").is_ok());
+
+        // Mixed
+        assert!(validate_project_name("My_Cool-Project.v1").is_ok());
+    }
+
+    #[test]
+    fn test_path_traversal_rejected() {
+        assert!(validate_project_name("../../etc/passwd").is_err());
+        assert!(validate_project_name("../parent").is_err());
+        assert!(validate_project_name("sub/dir").is_err());
+        assert!(validate_project_name("..").is_err());
+        assert!(validate_project_name(".").is_err());
+        assert!(validate_project_name("path\\to\\project").is_err());
+    }
+
+    #[test]
+    fn test_reserved_names_rejected() {
+        // Build artifacts
+        assert!(validate_project_name("target").is_err());
+        assert!(validate_project_name("node_modules").is_err());
+        assert!(validate_project_name("dist").is_err());
+        assert!(validate_project_name("build").is_err());
+
+        // IDE configs
+        assert!(validate_project_name(".vscode").is_err());
+        assert!(validate_project_name(".idea").is_err());
+
+        // Git
+        assert!(validate_project_name(".git").is_err());
+        assert!(validate_project_name(".byte").is_err());
+
+        // Windows reserved (case insensitive)
+        assert!(validate_project_name("CON").is_err());
+        assert!(validate_project_name("con").is_err());
+        assert!(validate_project_name("PRN").is_err());
+        assert!(validate_project_name("AUX").is_err());
+        assert!(validate_project_name("NUL").is_err());
+        assert!(validate_project_name("COM1").is_err());
+        assert!(validate_project_name("LPT1").is_err());
+    }
+
+    #[test]
+    fn test_invalid_characters_rejected() {
+        assert!(validate_project_name("my project").is_err());  // space
+        assert!(validate_project_name("my@project").is_err());  // @
+        assert!(validate_project_name("my$project").is_err());  // $
+        assert!(validate_project_name("my#project").is_err());  // #
+        assert!(validate_project_name("my!project").is_err());  // !
+        assert!(validate_project_name("my*project").is_err());  // *
+        assert!(validate_project_name("my&project").is_err());  // &
+        assert!(validate_project_name("project<script>").is_err());  // < >
+    }
+
+    #[test]
+    fn test_starting_characters_rejected() {
+        assert!(validate_project_name(".hidden").is_err());     // starts with dot
+        assert!(validate_project_name("-dash").is_err());       // starts with dash
+        assert!(validate_project_name("..relative").is_err());  // starts with ..
+    }
+
+    #[test]
+    fn test_ending_characters_rejected() {
+        assert!(validate_project_name("project.").is_err());  // ends with dot
+    }
+
+    #[test]
+    fn test_empty_and_whitespace_rejected() {
+        assert!(validate_project_name("").is_err());
+        assert!(validate_project_name("   ").is_err());
+        assert!(validate_project_name("\t").is_err());
+        assert!(validate_project_name("\n").is_err());
+    }
+
+    #[test]
+    fn test_length_limits() {
+        // Valid length
+        let name_254 = "a".repeat(254);
+        assert!(validate_project_name(&name_254).is_ok());
+
+        let name_255 = "a".repeat(255);
+        assert!(validate_project_name(&name_255).is_ok());
+
+        // Too long
+        let name_256 = "a".repeat(256);
+        assert!(validate_project_name(&name_256).is_err());
+    }
+
+    #[test]
+    fn test_null_bytes_rejected() {
+        assert!(validate_project_name("project\0name").is_err());
+    }
+
+    #[test]
+    fn test_init_project_is_discoverable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let workspace = temp.path().to_str().unwrap();
+
+        for (ecosystem, project_type, name, manifest) in [
+            ("rust", "cli", "tool", "Cargo.toml"),
+            ("go", "api", "service", "go.mod"),
+            ("bun", "web", "site", "package.json"),
+            ("go+bun", "web", "shop", "frontend/package.json"),
+        ] {
+            let path = init_project(workspace, ecosystem, project_type, name).unwrap();
+            assert!(path.join(manifest).is_file(), "{} has no {}", name, manifest);
+            assert!(path.join("README.md").is_file());
+        }
+
+        let mut projects = scan_directory(temp.path(), false).unwrap();
+        projects.sort_by(|a, b| a.config.project.name.cmp(&b.config.project.name));
+        let found: Vec<(&str, &str, bool)> = projects
+            .iter()
+            .map(|p| (p.config.project.name.as_str(), p.config.project.ecosystem.as_str(), p.implicit))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("service", "go", false), ("shop", "go+bun", false), ("site", "bun", false), ("tool", "rust", false)]
+        );
+        assert!(projects.iter().all(|p| p.config.build.as_ref().is_some_and(|b| b.contains_key("test") || b.contains_key("backend:test"))));
+    }
+
+    #[test]
+    fn test_init_from_template_is_discoverable() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("template");
+        crate::templates::tests::write_template(&root);
+        let template = crate::templates::Template::load(&root, crate::templates::TemplateSource::Path).unwrap();
+        let values = template.resolve_values("billing", &crate::templates::Values::new()).unwrap();
+
+        let workspace = temp.path().join("ws");
+        let project = init_from_template(&workspace.to_string_lossy(), &template, "billing", &values, true).unwrap();
+        assert!(project.failed_hooks.is_empty(), "{:?}", project.failed_hooks);
+        assert_eq!(fs::read_to_string(project.path.join("hook.txt")).unwrap().trim(), "example.com/billing");
+        assert!(!project.path.join("Dockerfile").exists());
+        assert!(project.path.join(".git").is_dir());
+
+        let projects = scan_directory(&workspace, false).unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].config.project.name, "billing");
+        assert_eq!(projects[0].config.project.ecosystem, "go");
+        assert_eq!(projects[0].config.project.project_type, "api");
+        assert!(init_from_template(&workspace.to_string_lossy(), &template, "billing", &values, true).is_err());
+
+        // Hooks only run when approved
+        let project = init_from_template(&workspace.to_string_lossy(), &template, "invoices", &values, false).unwrap();
+        assert!(!project.path.join("hook.txt").exists());
+        assert_eq!(project.skipped_hooks.len(), template.manifest.hooks.post_create.len());
+    }
+
+    #[test]
+    fn test_scan_implicit_and_adopt() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("explicit/byte.toml", "[project]\nname = \"explicit\"\ntype = \"cli\"\necosystem = \"go\"\n");
+        write("explicit/go.mod", "module explicit\n");
+        write("tool/Cargo.toml", "[package]\nname = \"tool\"\n");
+        write("tool/crates/inner/Cargo.toml", "[package]\nname = \"inner\"\n");
+        write("web/package.json", "{\"name\": \"web\"}");
+        write("web/node_modules/dep/package.json", "{\"name\": \"dep\"}");
+        write("Makefile", "all:\n");
+
+        assert_eq!(scan_directory(root, false).unwrap().len(), 1);
+
+        let mut projects = scan_directory(root, true).unwrap();
+        projects.sort_by(|a, b| a.path.cmp(&b.path));
+        let found: Vec<(&str, bool)> = projects.iter().map(|p| (p.config.project.name.as_str(), p.implicit)).collect();
+        assert_eq!(found, vec![("explicit", false), ("tool", true), ("web", true)]);
+
+        fs::write(root.join("tool/.gitignore"), "/target\n").unwrap();
+        let config = adopt_project(&root.join("tool")).unwrap();
+        assert!(toml::from_str::<ProjectConfig>(&fs::read_to_string(&config).unwrap()).is_ok());
+        assert!(root.join("tool/.byte/state").is_dir());
+        assert_eq!(fs::read_to_string(root.join("tool/.gitignore")).unwrap(), "/target\n\n# Byte runtime data\n.byte/\n");
+        assert!(adopt_project(&root.join("tool")).is_err(), "already adopted");
+
+        let projects = scan_directory(root, true).unwrap();
+        assert!(projects.iter().all(|p| !p.implicit || p.config.project.name == "web"));
+    }
+}
@@ -0,0 +1,674 @@
+//! Manifests, byte.toml and README for `byte init`
+//!
+//! The manifest comes from the ecosystem's toolchain when it is installed
+//! (`cargo init`, `go mod init`), so it matches the local toolchain version;
+//! otherwise a built-in template is written. Bun's own `bun init` lays out
+//! files differently from Byte's `src/index.ts`, so Bun always uses the
+//! template, as do Node, Deno and Zig for the same reason, and Python, whose
+//! pyproject.toml has no single canonical generator. Python projects also get
+//! a `.venv` when `python3` is available.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::config::types::{Capability, ProjectMeta};
+use crate::exec::CommandBuilder;
+
+/// How a manifest was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestOrigin {
+    Toolchain,
+    Template,
+}
+
+/// Write the manifest, byte.toml and README of a freshly initialized project
+///
+/// Expects the ecosystem's source layout to exist already (see
+/// [`crate::fs::ProjectFileSystem::init_project`]).
+pub fn scaffold(
+    project_path: &Path,
+    ecosystem: &str,
+    project_type: &str,
+    name: &str,
+    use_toolchain: bool,
+) -> Result<ManifestOrigin> {
+    let origin = create_driver(project_path, ecosystem, project_type, name, use_toolchain)?;
+
+    let meta = ProjectMeta {
+        name: name.to_string(),
+        project_type: project_type.to_string(),
+        ecosystem: ecosystem.to_string(),
+        description: Some(format!(
+            "{} {} project",
+            display_name(ecosystem),
+            project_type
+        )),
+    };
+    let tasks = build_tasks(ecosystem, project_type, name);
+
+    write_config(project_path, &meta, &tasks, None)?;
+    Ok(origin)
+}
+
+/// Manifests of a polyglot project, one per capability directory, and the
+/// root byte.toml with `[drivers]` and namespaced tasks (`backend:build`)
+///
+/// Expects each capability's source layout to exist already.
+pub fn scaffold_polyglot(
+    project_path: &Path,
+    capabilities: &[Capability],
+    project_type: &str,
+    name: &str,
+    use_toolchain: bool,
+) -> Result<()> {
+    let mut tasks = Vec::new();
+    for capability in capabilities {
+        let driver_type = super::drivers::driver_type(&capability.driver, &capability.name);
+        let driver_name = driver_name(name, capability);
+        create_driver(
+            &project_path.join(capability.dir()),
+            &capability.driver,
+            driver_type,
+            &driver_name,
+            use_toolchain,
+        )?;
+        tasks.extend(
+            build_tasks(&capability.driver, driver_type, &driver_name)
+                .into_iter()
+                .map(|(task, command)| (format!("{}:{}", capability.name, task), command)),
+        );
+    }
+
+    let drivers: Vec<&str> = capabilities
+        .iter()
+        .map(|c| display_name(&c.driver))
+        .collect();
+    let meta = ProjectMeta {
+        name: name.to_string(),
+        project_type: project_type.to_string(),
+        ecosystem: capabilities
+            .iter()
+            .map(|c| c.driver.as_str())
+            .collect::<Vec<_>>()
+            .join(&super::drivers::SEPARATOR.to_string()),
+        description: Some(format!("{} {} project", drivers.join(" + "), project_type)),
+    };
+    write_config(
+        project_path,
+        &meta,
+        &tasks,
+        Some(&super::drivers::drivers_toml(capabilities)),
+    )
+}
+
+/// Package/module name of one driver of a polyglot project: `shop-backend`
+pub fn driver_name(name: &str, capability: &Capability) -> String {
+    format!("{}-{}", name, capability.name)
+}
+
+/// Manifest of one ecosystem, plus the virtualenv for Python
+fn create_driver(
+    dir: &Path,
+    ecosystem: &str,
+    project_type: &str,
+    name: &str,
+    use_toolchain: bool,
+) -> Result<ManifestOrigin> {
+    let origin = create_manifest(dir, ecosystem, project_type, name, use_toolchain)?;
+    if ecosystem == "python" && use_toolchain {
+        create_virtualenv(dir);
+    }
+    Ok(origin)
+}
+
+/// byte.toml, with any extra sections appended, and a README unless one exists
+fn write_config(
+    project_path: &Path,
+    meta: &ProjectMeta,
+    tasks: &[(String, String)],
+    extra: Option<&str>,
+) -> Result<()> {
+    let mut byte_toml = format!(
+        "# Byte project configuration\n\n{}",
+        super::byte_toml(meta, tasks)
+    );
+    if let Some(extra) = extra {
+        byte_toml.push('\n');
+        byte_toml.push_str(extra);
+    }
+    fs::write(project_path.join("byte.toml"), byte_toml).context("Failed to write byte.toml")?;
+
+    let readme = project_path.join("README.md");
+    if !readme.exists() {
+        fs::write(&readme, readme_content(meta, tasks)).context("Failed to write README.md")?;
+    }
+    Ok(())
+}
+
+/// Create the ecosystem's manifest, preferring the toolchain
+fn create_manifest(
+    project_path: &Path,
+    ecosystem: &str,
+    project_type: &str,
+    name: &str,
+    use_toolchain: bool,
+) -> Result<ManifestOrigin> {
+    let toolchain = match ecosystem {
+        "rust" => Some(
+            CommandBuilder::new("cargo")
+                .arg("init")
+                .arg("--vcs")
+                .arg("none")
+                .arg("--name")
+                .arg(crate_name(name))
+                .arg(if project_type == "lib" {
+                    "--lib"
+                } else {
+                    "--bin"
+                }),
+        ),
+        "go" => Some(CommandBuilder::new("go").arg("mod").arg("init").arg(name)),
+        _ => None,
+    };
+
+    if use_toolchain && let Some(builder) = toolchain {
+        // Not installed or failed: fall back to the template
+        let succeeded = builder
+            .working_dir(project_path)
+            .execute()
+            .is_ok_and(|result| result.success);
+        if succeeded {
+            return Ok(ManifestOrigin::Toolchain);
+        }
+    }
+
+    for (file, content) in manifest_templates(ecosystem, project_type, name)? {
+        fs::write(project_path.join(file), content)
+            .with_context(|| format!("Failed to write {}", file))?;
+    }
+    Ok(ManifestOrigin::Template)
+}
+
+/// `python3 -m venv .venv`; a missing interpreter leaves the `venv` task to
+/// the user
+fn create_virtualenv(project_path: &Path) {
+    let result = CommandBuilder::new("python3")
+        .arg("-m")
+        .arg("venv")
+        .arg(".venv")
+        .working_dir(project_path)
+        .execute();
+    if !result.is_ok_and(|result| result.success) {
+        crate::log::info(
+            "SCAFFOLD",
+            "python3 -m venv failed; skipping the virtualenv",
+        );
+    }
+}
+
+/// Built-in manifests, used when the toolchain isn't available
+fn manifest_templates(
+    ecosystem: &str,
+    project_type: &str,
+    name: &str,
+) -> Result<Vec<(&'static str, String)>> {
+    let files = match ecosystem {
+        "rust" => vec![(
+            "Cargo.toml",
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+                crate_name(name)
+            ),
+        )],
+        "go" => vec![("go.mod", format!("module {}\n\ngo 1.22\n", name))],
+        "bun" => {
+            let package = serde_json::json!({
+                "name": name.to_lowercase(),
+                "version": "0.1.0",
+                "module": "src/index.ts",
+                "type": "module",
+                "private": project_type != "lib",
+                "scripts": {
+                    "dev": "bun --watch src/index.ts",
+                    "build": "bun build src/index.ts --outdir dist --target bun",
+                    "test": "bun test",
+                },
+                "devDependencies": { "@types/bun": "latest" },
+                "peerDependencies": { "typescript": "^5" },
+            });
+            let tsconfig = serde_json::json!({
+                "compilerOptions": {
+                    "lib": ["ESNext"],
+                    "target": "ESNext",
+                    "module": "ESNext",
+                    "moduleResolution": "bundler",
+                    "types": ["bun-types"],
+                    "strict": true,
+                    "skipLibCheck": true,
+                    "noEmit": true,
+                },
+                "include": ["src"],
+            });
+            vec![
+                (
+                    "package.json",
+                    serde_json::to_string_pretty(&package)? + "\n",
+                ),
+                (
+                    "tsconfig.json",
+                    serde_json::to_string_pretty(&tsconfig)? + "\n",
+                ),
+            ]
+        }
+        "python" => vec![("pyproject.toml", pyproject(project_type, name))],
+        "node" => {
+            let mut package = serde_json::json!({
+                "name": name.to_lowercase(),
+                "version": "0.1.0",
+                "type": "module",
+                "private": project_type != "lib",
+                "engines": { "node": ">=20" },
+            });
+            package["scripts"] = match project_type {
+                "cli" => serde_json::json!({ "start": "node src/index.js", "test": "node --test" }),
+                "lib" => serde_json::json!({ "test": "node --test" }),
+                _ => serde_json::json!({
+                    "dev": "node --watch src/server.js",
+                    "start": "node src/server.js",
+                    "test": "node --test",
+                }),
+            };
+            match project_type {
+                "cli" => {
+                    package["bin"] = serde_json::json!({ name.to_lowercase(): "src/index.js" })
+                }
+                "lib" => package["exports"] = serde_json::json!("./src/index.js"),
+                _ => {}
+            }
+            vec![(
+                "package.json",
+                serde_json::to_string_pretty(&package)? + "\n",
+            )]
+        }
+        "deno" => {
+            let mut deno = serde_json::json!({
+                "imports": { "@std/assert": "jsr:@std/assert@1" },
+            });
+            match project_type {
+                // JSR package names are always scoped
+                "lib" => {
+                    deno["name"] = serde_json::json!(format!("@{0}/{0}", name.to_lowercase()));
+                    deno["version"] = serde_json::json!("0.1.0");
+                    deno["exports"] = serde_json::json!("./mod.ts");
+                }
+                "cli" => deno["tasks"] = serde_json::json!({ "start": "deno run main.ts" }),
+                _ => {
+                    deno["tasks"] =
+                        serde_json::json!({ "dev": "deno run --watch --allow-net main.ts" })
+                }
+            }
+            vec![("deno.json", serde_json::to_string_pretty(&deno)? + "\n")]
+        }
+        "zig" => vec![
+            ("build.zig", build_zig(project_type, name)),
+            (
+                "build.zig.zon",
+                format!(
+                    ".{{\n    .name = \"{}\",\n    .version = \"0.1.0\",\n    .minimum_zig_version = \"0.13.0\",\n    .paths = .{{ \"build.zig\", \"build.zig.zon\", \"src\" }},\n}}\n",
+                    name
+                ),
+            ),
+        ],
+        _ => anyhow::bail!("Unsupported ecosystem: {}", ecosystem),
+    };
+    Ok(files)
+}
+
+fn pyproject(project_type: &str, name: &str) -> String {
+    let package = python_package(name);
+    let dependencies = match project_type {
+        "api" => "[\"fastapi>=0.110\", \"uvicorn>=0.29\"]",
+        _ => "[]",
+    };
+    let mut out = format!(
+        "[project]\nname = \"{}\"\nversion = \"0.1.0\"\nreadme = \"README.md\"\nrequires-python = \">=3.10\"\ndependencies = {}\n\n\
+         [project.optional-dependencies]\ndev = [\"pytest>=8\", \"ruff>=0.4\", \"build>=1\"]\n\n",
+        name, dependencies
+    );
+    if project_type == "cli" {
+        out.push_str(&format!(
+            "[project.scripts]\n{} = \"{}.cli:main\"\n\n",
+            name, package
+        ));
+    }
+    out.push_str(&format!(
+        "[build-system]\nrequires = [\"hatchling\"]\nbuild-backend = \"hatchling.build\"\n\n\
+         [tool.hatch.build.targets.wheel]\npackages = [\"src/{}\"]\n\n\
+         [tool.pytest.ini_options]\ntestpaths = [\"tests\"]\n",
+        package
+    ));
+    out
+}
+
+/// build.zig for Zig 0.13: an executable with a `run` step, or a static
+/// library, plus a `test` step
+fn build_zig(project_type: &str, name: &str) -> String {
+    let (root, artifact) = if project_type == "lib" {
+        ("src/root.zig", "addStaticLibrary")
+    } else {
+        ("src/main.zig", "addExecutable")
+    };
+    let mut out = format!(
+        "const std = @import(\"std\");\n\n\
+         pub fn build(b: *std.Build) void {{\n    \
+         const target = b.standardTargetOptions(.{{}});\n    \
+         const optimize = b.standardOptimizeOption(.{{}});\n\n    \
+         const artifact = b.{artifact}(.{{\n        \
+         .name = \"{name}\",\n        \
+         .root_source_file = b.path(\"{root}\"),\n        \
+         .target = target,\n        \
+         .optimize = optimize,\n    }});\n    \
+         b.installArtifact(artifact);\n\n"
+    );
+    if project_type != "lib" {
+        out.push_str(
+            "    const run_cmd = b.addRunArtifact(artifact);\n    \
+             run_cmd.step.dependOn(b.getInstallStep());\n    \
+             if (b.args) |args| run_cmd.addArgs(args);\n    \
+             b.step(\"run\", \"Run the app\").dependOn(&run_cmd.step);\n\n",
+        );
+    }
+    out.push_str(&format!(
+        "    const tests = b.addTest(.{{\n        \
+         .root_source_file = b.path(\"{root}\"),\n        \
+         .target = target,\n        \
+         .optimize = optimize,\n    }});\n    \
+         b.step(\"test\", \"Run unit tests\").dependOn(&b.addRunArtifact(tests).step);\n}}\n"
+    ));
+    out
+}
+
+/// Default byte.toml `[build]` tasks for a new project
+fn build_tasks(ecosystem: &str, project_type: &str, name: &str) -> Vec<(String, String)> {
+    let tasks: Vec<(&str, String)> = match ecosystem {
+        "rust" => {
+            let mut tasks = vec![
+                ("dev", "cargo build".to_string()),
+                ("release", "cargo build --release".to_string()),
+                ("test", "cargo test".to_string()),
+                ("check", "cargo check".to_string()),
+                ("fmt", "cargo fmt".to_string()),
+                ("lint", "cargo clippy -- -D warnings".to_string()),
+            ];
+            if project_type != "lib" {
+                tasks.insert(2, ("run", "cargo run".to_string()));
+            }
+            tasks
+        }
+        "go" => {
+            let main = match project_type {
+                "api" | "web" => "./cmd/server".to_string(),
+                _ => format!("./cmd/{}", name),
+            };
+            vec![
+                ("build", format!("go build -o bin/ {}", main)),
+                ("run", format!("go run {}", main)),
+                ("test", "go test ./...".to_string()),
+                ("fmt", "go fmt ./...".to_string()),
+                ("lint", "go vet ./...".to_string()),
+            ]
+        }
+        "bun" => vec![
+            ("install", "bun install".to_string()),
+            ("dev", "bun run dev".to_string()),
+            ("build", "bun run build".to_string()),
+            ("test", "bun test".to_string()),
+        ],
+        // Everything runs from the project's .venv; no lockfile yet, so no uv/poetry
+        "python" => {
+            let mut tasks = vec![
+                ("venv", "python3 -m venv .venv".to_string()),
+                (
+                    "install",
+                    ".venv/bin/python -m pip install -e '.[dev]'".to_string(),
+                ),
+                ("test", ".venv/bin/python -m pytest".to_string()),
+                ("lint", ".venv/bin/python -m ruff check .".to_string()),
+                ("fmt", ".venv/bin/python -m ruff format .".to_string()),
+                ("build", ".venv/bin/python -m build".to_string()),
+            ];
+            match project_type {
+                "cli" => tasks.insert(
+                    2,
+                    (
+                        "run",
+                        format!(".venv/bin/python -m {}", python_package(name)),
+                    ),
+                ),
+                "api" => tasks.insert(
+                    2,
+                    (
+                        "dev",
+                        format!(
+                            ".venv/bin/python -m uvicorn {}.app:app --reload",
+                            python_package(name)
+                        ),
+                    ),
+                ),
+                _ => {}
+            }
+            tasks
+        }
+        // npm until the project has a lockfile from another package manager
+        "node" => {
+            let mut tasks = vec![
+                ("install", "npm install".to_string()),
+                ("test", "npm test".to_string()),
+            ];
+            match project_type {
+                "cli" => tasks.insert(1, ("run", "npm start".to_string())),
+                "web" | "api" => tasks.insert(1, ("dev", "npm run dev".to_string())),
+                _ => {}
+            }
+            tasks
+        }
+        "deno" => {
+            let entry = if project_type == "lib" {
+                "mod.ts"
+            } else {
+                "main.ts"
+            };
+            let mut tasks = vec![
+                ("check", format!("deno check {}", entry)),
+                ("test", "deno test".to_string()),
+                ("lint", "deno lint".to_string()),
+                ("fmt", "deno fmt".to_string()),
+            ];
+            match project_type {
+                "cli" => tasks.insert(0, ("run", "deno task start".to_string())),
+                "web" | "api" => tasks.insert(0, ("dev", "deno task dev".to_string())),
+                _ => {}
+            }
+            tasks
+        }
+        "zig" => {
+            let mut tasks = vec![
+                ("build", "zig build".to_string()),
+                ("release", "zig build -Doptimize=ReleaseSafe".to_string()),
+                ("test", "zig build test".to_string()),
+                ("fmt", "zig fmt .".to_string()),
+            ];
+            if project_type != "lib" {
+                tasks.insert(2, ("run", "zig build run".to_string()));
+            }
+            tasks
+        }
+        _ => Vec::new(),
+    };
+    tasks
+        .into_iter()
+        .map(|(name, command)| (name.to_string(), command))
+        .collect()
+}
+
+fn readme_content(meta: &ProjectMeta, tasks: &[(String, String)]) -> String {
+    let mut out = format!("# {}\n\n", meta.name);
+    if let Some(description) = &meta.description {
+        out.push_str(&format!("{}.\n\n", description));
+    }
+    out.push_str("## Tasks\n\nDefined in `byte.toml`; run them from the Commands view (`byte tui`) or directly:\n\n");
+    out.push_str("| Task | Command |\n|------|---------|\n");
+    for (name, command) in tasks {
+        out.push_str(&format!("| {} | `{}` |\n", name, command));
+    }
+    out
+}
+
+/// Importable package name for a project: `my-tool.v2` → `my_tool_v2`
+pub fn python_package(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Cargo package names can't contain dots
+fn crate_name(name: &str) -> String {
+    name.replace('.', "-")
+}
+
+fn display_name(ecosystem: &str) -> &str {
+    match ecosystem {
+        "rust" => "Rust",
+        "go" => "Go",
+        "bun" => "Bun",
+        "python" => "Python",
+        "node" => "Node",
+        "deno" => "Deno",
+        "zig" => "Zig",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_templates_are_valid_manifests() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+
+        assert_eq!(
+            scaffold(dir, "rust", "lib", "my.lib", false).unwrap(),
+            ManifestOrigin::Template
+        );
+        let cargo: toml::Table = std::fs::read_to_string(dir.join("Cargo.toml"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(cargo["package"]["name"].as_str(), Some("my-lib"));
+
+        scaffold(dir, "bun", "web", "Site", false).unwrap();
+        let package: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("package.json")).unwrap())
+                .unwrap();
+        assert_eq!(package["name"], "site");
+        assert!(std::fs::read_to_string(dir.join("tsconfig.json")).is_ok());
+
+        scaffold(dir, "go", "api", "service", false).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("go.mod")).unwrap(),
+            "module service\n\ngo 1.22\n"
+        );
+        let config: crate::config::ProjectConfig =
+            toml::from_str(&std::fs::read_to_string(dir.join("byte.toml")).unwrap()).unwrap();
+        assert_eq!(
+            config.build.unwrap()["build"].command(),
+            "go build -o bin/ ./cmd/server"
+        );
+
+        // The first README is kept
+        assert!(
+            std::fs::read_to_string(dir.join("README.md"))
+                .unwrap()
+                .starts_with("# my.lib")
+        );
+        assert!(scaffold(dir, "cobol", "cli", "x", false).is_err());
+
+        let py = temp.path().join("py");
+        std::fs::create_dir(&py).unwrap();
+        scaffold(&py, "python", "cli", "my-tool", false).unwrap();
+        let pyproject: toml::Table = std::fs::read_to_string(py.join("pyproject.toml"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            pyproject["project"]["scripts"]["my-tool"].as_str(),
+            Some("my_tool.cli:main")
+        );
+        let config: crate::config::ProjectConfig =
+            toml::from_str(&std::fs::read_to_string(py.join("byte.toml")).unwrap()).unwrap();
+        assert_eq!(
+            config.build.unwrap()["run"].command(),
+            ".venv/bin/python -m my_tool"
+        );
+
+        let node = temp.path().join("node");
+        std::fs::create_dir(&node).unwrap();
+        scaffold(&node, "node", "cli", "Greeter", false).unwrap();
+        let package: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(node.join("package.json")).unwrap())
+                .unwrap();
+        assert_eq!(package["bin"]["greeter"], "src/index.js");
+
+        let deno = temp.path().join("deno");
+        std::fs::create_dir(&deno).unwrap();
+        scaffold(&deno, "deno", "web", "site", false).unwrap();
+        let config: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(deno.join("deno.json")).unwrap())
+                .unwrap();
+        assert!(
+            config["tasks"]["dev"]
+                .as_str()
+                .unwrap()
+                .contains("--allow-net")
+        );
+
+        let zig = temp.path().join("zig");
+        std::fs::create_dir(&zig).unwrap();
+        scaffold(&zig, "zig", "lib", "mathz", false).unwrap();
+        let build = std::fs::read_to_string(zig.join("build.zig")).unwrap();
+        assert!(build.contains("addStaticLibrary") && !build.contains("\"run\""));
+        assert!(
+            std::fs::read_to_string(zig.join("build.zig.zon"))
+                .unwrap()
+                .contains(".name = \"mathz\"")
+        );
+    }
+
+    #[test]
+    fn test_polyglot_byte_toml() {
+        let temp = TempDir::new().unwrap();
+        let capabilities =
+            crate::projects::drivers::assign_capabilities(&["go".to_string(), "bun".to_string()])
+                .unwrap();
+        for capability in &capabilities {
+            std::fs::create_dir(temp.path().join(capability.dir())).unwrap();
+        }
+        scaffold_polyglot(temp.path(), &capabilities, "web", "shop", false).unwrap();
+
+        assert!(temp.path().join("backend/go.mod").exists());
+        assert!(temp.path().join("frontend/package.json").exists());
+        let config: crate::config::ProjectConfig =
+            toml::from_str(&std::fs::read_to_string(temp.path().join("byte.toml")).unwrap())
+                .unwrap();
+        assert_eq!(config.project.ecosystem, "go+bun");
+        assert_eq!(config.drivers.unwrap().capabilities, capabilities);
+        let build = config.build.unwrap();
+        assert_eq!(
+            build["backend:build"].command(),
+            "go build -o bin/ ./cmd/server"
+        );
+        assert_eq!(build["frontend:dev"].command(), "bun run dev");
+    }
+}
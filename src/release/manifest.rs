@@ -0,0 +1,248 @@
+//! Version fields in ecosystem manifests
+//!
+//! Versions are rewritten in place, line by line, so comments, key order and
+//! formatting of the manifest survive the bump.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Manifest formats that carry a project version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    /// Cargo.toml `[package] version`
+    Cargo,
+    /// package.json `"version"`
+    PackageJson,
+    /// deno.json `"version"`
+    Deno,
+    /// pyproject.toml `[project]` or `[tool.poetry]` version
+    Pyproject,
+    /// build.zig.zon `.version`
+    Zig,
+}
+
+impl ManifestKind {
+    const ALL: [(ManifestKind, &'static str); 6] = [
+        (ManifestKind::Cargo, "Cargo.toml"),
+        (ManifestKind::PackageJson, "package.json"),
+        (ManifestKind::Deno, "deno.json"),
+        (ManifestKind::Deno, "deno.jsonc"),
+        (ManifestKind::Pyproject, "pyproject.toml"),
+        (ManifestKind::Zig, "build.zig.zon"),
+    ];
+}
+
+/// A manifest file in a project root
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub path: PathBuf,
+    pub kind: ManifestKind,
+}
+
+/// Manifests in a project root, in the order they are checked
+pub fn find_manifests(project_path: &Path) -> Vec<Manifest> {
+    ManifestKind::ALL
+        .iter()
+        .map(|(kind, file)| Manifest { path: project_path.join(file), kind: *kind })
+        .filter(|m| m.path.is_file())
+        .collect()
+}
+
+impl Manifest {
+    /// The version declared in the manifest, if it has one
+    pub fn version(&self) -> Result<Option<String>> {
+        let content = self.read()?;
+        Ok(find_version(&content, self.kind).map(|(_, version)| version))
+    }
+
+    /// Write a new version; returns the previous one, or None if the manifest
+    /// declares no version of its own (e.g. `version.workspace = true`)
+    pub fn set_version(&self, version: &str) -> Result<Option<String>> {
+        let content = self.read()?;
+        let Some((index, old)) = find_version(&content, self.kind) else {
+            return Ok(None);
+        };
+
+        let updated = replace_line(&content, index, version);
+        std::fs::write(&self.path, updated)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+
+        if self.kind == ManifestKind::Cargo {
+            self.update_cargo_lock(&content, &old, version)?;
+        }
+        Ok(Some(old))
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    }
+
+    fn read(&self) -> Result<String> {
+        std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))
+    }
+
+    /// Keep Cargo.lock's entry for the package in step with Cargo.toml
+    fn update_cargo_lock(&self, manifest: &str, old: &str, version: &str) -> Result<()> {
+        let lock = self.path.with_file_name("Cargo.lock");
+        let (Ok(content), Some(name)) = (std::fs::read_to_string(&lock), package_name(manifest)) else {
+            return Ok(());
+        };
+
+        let lines: Vec<&str> = content.lines().collect();
+        let name_line = format!("name = \"{}\"", name);
+        let old_line = format!("version = \"{}\"", old);
+        let Some(index) = lines
+            .windows(2)
+            .position(|pair| pair[0] == name_line && pair[1] == old_line)
+        else {
+            return Ok(());
+        };
+
+        std::fs::write(&lock, replace_line(&content, index + 1, version))?;
+        Ok(())
+    }
+}
+
+/// Line index and value of the manifest's own version
+fn find_version(content: &str, kind: ManifestKind) -> Option<(usize, String)> {
+    match kind {
+        ManifestKind::Cargo => toml_version(content, &["package"]),
+        ManifestKind::Pyproject => toml_version(content, &["project", "tool.poetry"]),
+        ManifestKind::PackageJson | ManifestKind::Deno => json_version(content),
+        ManifestKind::Zig => content.lines().enumerate().find_map(|(i, line)| {
+            let value = line.trim().strip_prefix(".version")?.trim_start().strip_prefix('=')?;
+            Some((i, quoted(value)?))
+        }),
+    }
+}
+
+/// `version = "..."` directly inside one of `sections`
+fn toml_version(content: &str, sections: &[&str]) -> Option<(usize, String)> {
+    let mut in_section = false;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_prefix('[') {
+            in_section = sections.contains(&header.trim_end_matches(']').trim());
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        if let Some((key, value)) = trimmed.split_once('=')
+            && key.trim() == "version"
+        {
+            return Some((i, quoted(value)?));
+        }
+    }
+    None
+}
+
+/// Top-level `"version": "..."` of a JSON document
+fn json_version(content: &str) -> Option<(usize, String)> {
+    let mut depth = 0usize;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if depth == 1
+            && let Some(rest) = trimmed.strip_prefix("\"version\"")
+            && let Some(value) = rest.trim_start().strip_prefix(':')
+        {
+            return Some((i, quoted(value)?));
+        }
+        // Braces inside strings would throw this off; manifests don't have them
+        // before the version in practice
+        for c in trimmed.chars() {
+            match c {
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// `name = "..."` in Cargo.toml's [package]
+fn package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_package = trimmed == "[package]";
+        } else if in_package
+            && let Some((key, value)) = trimmed.split_once('=')
+            && key.trim() == "name"
+        {
+            return quoted(value);
+        }
+    }
+    None
+}
+
+/// First double-quoted string in `text`
+fn quoted(text: &str) -> Option<String> {
+    let start = text.find('"')? + 1;
+    let end = start + text[start..].find('"')?;
+    Some(text[start..end].to_string())
+}
+
+/// Replace the first quoted value on line `index`, keeping everything else
+fn replace_line(content: &str, index: usize, value: &str) -> String {
+    let mut out: Vec<String> = content.lines().map(String::from).collect();
+    if let Some(line) = out.get_mut(index)
+        && let Some(start) = line.find('"').map(|i| i + 1)
+        && let Some(len) = line[start..].find('"')
+    {
+        line.replace_range(start..start + len, value);
+    }
+
+    let mut joined = out.join("\n");
+    if content.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_version() {
+        let cargo = "[package]\nname = \"app\"\nversion = \"0.1.0\" # bumped by byte\n\n[dependencies]\nserde = { version = \"1\" }\n";
+        assert_eq!(find_version(cargo, ManifestKind::Cargo), Some((2, "0.1.0".to_string())));
+        assert_eq!(
+            find_version("[workspace]\n\n[package]\nversion.workspace = true\n", ManifestKind::Cargo),
+            None
+        );
+
+        let json = "{\n  \"name\": \"app\",\n  \"dependencies\": {\n    \"version\": \"9.9.9\"\n  },\n  \"version\": \"1.2.3\"\n}\n";
+        assert_eq!(find_version(json, ManifestKind::PackageJson), Some((5, "1.2.3".to_string())));
+
+        let poetry = "[tool.poetry]\nname = \"app\"\nversion = \"2.0.0\"\n";
+        assert_eq!(find_version(poetry, ManifestKind::Pyproject), Some((2, "2.0.0".to_string())));
+
+        let zon = ".{\n    .name = .app,\n    .version = \"0.3.0\",\n}\n";
+        assert_eq!(find_version(zon, ManifestKind::Zig), Some((2, "0.3.0".to_string())));
+    }
+
+    #[test]
+    fn test_set_version_updates_lock() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]\nname = \"app\"\nversion = \"0.1.0\" # keep me\n").unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.lock"),
+            "[[package]]\nname = \"anyhow\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let manifest = &find_manifests(temp.path())[0];
+        assert_eq!(manifest.set_version("0.2.0").unwrap().as_deref(), Some("0.1.0"));
+
+        let toml = std::fs::read_to_string(temp.path().join("Cargo.toml")).unwrap();
+        assert_eq!(toml, "[package]\nname = \"app\"\nversion = \"0.2.0\" # keep me\n");
+        let lock = std::fs::read_to_string(temp.path().join("Cargo.lock")).unwrap();
+        assert!(lock.contains("name = \"anyhow\"\nversion = \"0.1.0\""));
+        assert!(lock.contains("name = \"app\"\nversion = \"0.2.0\""));
+    }
+}
@@ -0,0 +1,261 @@
+//! Releases: bump the manifest version, commit, tag and push
+//!
+//! Shared by the TUI tag form and `byte release`.
+
+pub mod manifest;
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::exec::{CommandBuilder, CommandResult};
+use manifest::find_manifests;
+
+/// What to release
+#[derive(Debug, Clone)]
+pub struct ReleaseOptions {
+    /// Tag name, e.g. "v1.2.0"
+    pub tag: String,
+    /// Annotated tag message (defaults to "Release <tag>")
+    pub message: String,
+    /// Annotated (`git tag -a`) or lightweight tag
+    pub annotated: bool,
+    /// Write the version into the project's manifest and commit it first
+    pub bump: bool,
+    /// Remote to push the release commit and tag to
+    pub remote: Option<String>,
+    /// Let git ask for credentials when pushing; off in the TUI, where
+    /// nobody can answer
+    pub prompts: bool,
+}
+
+/// What a release did
+#[derive(Debug, Clone, Default)]
+pub struct ReleaseOutcome {
+    /// Manifests whose version changed, with the previous version
+    pub bumped: Vec<(PathBuf, String)>,
+    /// Short hash of the version bump commit
+    pub commit: Option<String>,
+    pub pushed_to: Option<String>,
+}
+
+impl ReleaseOutcome {
+    /// One-line summary for status bars and CLI output
+    pub fn summary(&self, tag: &str) -> String {
+        let mut parts = vec![format!("Tagged {}", tag)];
+        for (path, old) in &self.bumped {
+            let file = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            parts.push(format!("{} {} → {}", file, old, tag.trim_start_matches('v')));
+        }
+        if let Some(remote) = &self.pushed_to {
+            parts.push(format!("pushed to {}", remote));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Check a tag name; versions ("v1.2.3", "1.2.3-rc.1") must be valid semver
+///
+/// Returns the parsed version for version-like tags.
+pub fn validate_tag(tag: &str) -> Result<Option<semver::Version>> {
+    if tag.is_empty() {
+        bail!("Tag name is required");
+    }
+    let invalid_char = tag
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || "~^:?*[\\".contains(*c));
+    if let Some(c) = invalid_char {
+        bail!("Tag name can't contain {:?}", c);
+    }
+    if tag.contains("..") || tag.contains("@{") || tag.starts_with(['-', '/', '.']) || tag.ends_with(['/', '.']) || tag.ends_with(".lock") {
+        bail!("'{}' is not a valid git tag name", tag);
+    }
+
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+    semver::Version::parse(version)
+        .map(Some)
+        .with_context(|| format!("'{}' is not a valid semantic version (expected MAJOR.MINOR.PATCH)", tag))
+}
+
+/// Run a release in a project
+pub fn release(project_path: &Path, options: &ReleaseOptions) -> Result<ReleaseOutcome> {
+    let version = validate_tag(&options.tag)?;
+    let project = project_path.to_string_lossy().to_string();
+    let mut outcome = ReleaseOutcome::default();
+
+    let existing = git(&project, CommandBuilder::git("rev-parse").arg("-q").arg("--verify").arg(format!("refs/tags/{}", options.tag)));
+    if existing.is_ok() {
+        bail!("Tag {} already exists", options.tag);
+    }
+
+    if options.bump {
+        let Some(version) = &version else {
+            bail!("Can't bump the manifest version: '{}' is not a version", options.tag);
+        };
+        outcome.bumped = bump_manifests(project_path, version)?;
+
+        if !outcome.bumped.is_empty() {
+            let mut commit = CommandBuilder::git("commit").arg("-m").arg(format!("Release {}", options.tag)).arg("--");
+            for (path, _) in &outcome.bumped {
+                commit = commit.arg(path.to_string_lossy());
+            }
+            let lock = project_path.join("Cargo.lock");
+            if tracked(&project, &lock) {
+                commit = commit.arg(lock.to_string_lossy());
+            }
+            git(&project, commit)?;
+            let head = git(&project, CommandBuilder::git("rev-parse").arg("--short").arg("HEAD"))?;
+            outcome.commit = Some(head.stdout.trim().to_string());
+        }
+    }
+
+    let tag = if options.annotated {
+        let message = if options.message.trim().is_empty() {
+            format!("Release {}", options.tag)
+        } else {
+            options.message.trim().to_string()
+        };
+        CommandBuilder::git("tag").arg("-a").arg(&options.tag).arg("-m").arg(message)
+    } else {
+        CommandBuilder::git("tag").arg(&options.tag)
+    };
+    git(&project, tag)?;
+
+    if let Some(remote) = &options.remote {
+        let mut push = CommandBuilder::git("push").arg(remote);
+        if !options.prompts {
+            push = push.without_prompts();
+        }
+        if outcome.commit.is_some() {
+            push = push.arg("HEAD");
+        }
+        git(&project, push.arg(format!("refs/tags/{}", options.tag)))
+            .with_context(|| format!("Tagged {} but pushing to {} failed", options.tag, remote))?;
+        outcome.pushed_to = Some(remote.clone());
+    }
+
+    Ok(outcome)
+}
+
+/// Write `version` into every manifest that declares one
+fn bump_manifests(project_path: &Path, version: &semver::Version) -> Result<Vec<(PathBuf, String)>> {
+    let manifests: Vec<_> = find_manifests(project_path)
+        .into_iter()
+        .filter_map(|m| match m.version() {
+            Ok(Some(current)) => Some(Ok((m, current))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<Result<_>>()?;
+
+    // Check every manifest before touching any of them
+    for (manifest, current) in &manifests {
+        if let Ok(current) = semver::Version::parse(current)
+            && *version <= current
+        {
+            bail!("{} is already at {} (new version must be higher)", manifest.file_name(), current);
+        }
+    }
+
+    let mut bumped = Vec::new();
+    for (manifest, _) in manifests {
+        if let Some(old) = manifest.set_version(&version.to_string())? {
+            bumped.push((manifest.path, old));
+        }
+    }
+    Ok(bumped)
+}
+
+fn tracked(project: &str, path: &Path) -> bool {
+    path.exists() && git(project, CommandBuilder::git("ls-files").arg("--error-unmatch").arg(path.to_string_lossy())).is_ok()
+}
+
+/// Run git in the project, failing with git's message
+fn git(project: &str, builder: CommandBuilder) -> Result<CommandResult> {
+    let result = builder.working_dir(project).execute()?;
+    if !result.success {
+        bail!("{}", result.stderr.trim());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let mut builder = CommandBuilder::new("git");
+        for arg in args {
+            builder = builder.arg(*arg);
+        }
+        git(&dir.to_string_lossy(), builder).unwrap().stdout
+    }
+
+    #[test]
+    fn test_validate_tag() {
+        assert_eq!(validate_tag("v1.2.3").unwrap(), Some(semver::Version::new(1, 2, 3)));
+        assert!(validate_tag("2.0.0-rc.1").unwrap().is_some());
+        assert_eq!(validate_tag("nightly").unwrap(), None);
+        assert!(validate_tag("v1.2").is_err());
+        assert!(validate_tag("bad tag").is_err());
+        assert!(validate_tag("a..b").is_err());
+        assert!(validate_tag("").is_err());
+    }
+
+    #[test]
+    fn test_release_pushes_to_bare_remote() {
+        let temp = TempDir::new().unwrap();
+        let remote = temp.path().join("remote.git");
+        let repo = temp.path().join("app");
+        std::fs::create_dir(&repo).unwrap();
+        run(temp.path(), &["init", "-q", "--bare", "remote.git"]);
+        run(&repo, &["init", "-q", "-b", "main"]);
+        run(&repo, &["config", "user.name", "Byte"]);
+        run(&repo, &["config", "user.email", "byte@example.com"]);
+        run(&repo, &["remote", "add", "origin", &remote.to_string_lossy()]);
+
+        std::fs::write(repo.join("package.json"), "{\n  \"name\": \"app\",\n  \"version\": \"0.1.0\"\n}\n").unwrap();
+        run(&repo, &["add", "."]);
+        run(&repo, &["commit", "-q", "-m", "Initial"]);
+
+        let mut options = ReleaseOptions {
+            tag: "v0.1.0".to_string(),
+            message: String::new(),
+            annotated: true,
+            bump: true,
+            remote: Some("origin".to_string()),
+            prompts: false,
+        };
+        // Not higher than the current version
+        assert!(release(&repo, &options).is_err());
+
+        options.tag = "v0.2.0".to_string();
+        let outcome = release(&repo, &options).unwrap();
+        assert_eq!(outcome.bumped.len(), 1);
+        assert!(outcome.commit.is_some());
+        assert_eq!(outcome.summary("v0.2.0"), "Tagged v0.2.0, package.json 0.1.0 → 0.2.0, pushed to origin");
+
+        let remote_dir = remote.to_string_lossy().to_string();
+        assert_eq!(run(&remote, &["--git-dir", &remote_dir, "tag"]).trim(), "v0.2.0");
+        assert_eq!(run(&remote, &["--git-dir", &remote_dir, "cat-file", "-t", "v0.2.0"]).trim(), "tag");
+        assert_eq!(run(&remote, &["--git-dir", &remote_dir, "log", "-1", "--format=%s", "main"]).trim(), "Release v0.2.0");
+        assert!(std::fs::read_to_string(repo.join("package.json")).unwrap().contains("\"0.2.0\""));
+
+        // Lightweight tag, no bump, no push
+        let outcome = release(&repo, &ReleaseOptions {
+            tag: "nightly".to_string(),
+            message: String::new(),
+            annotated: false,
+            bump: false,
+            remote: None,
+            prompts: false,
+        })
+        .unwrap();
+        assert!(outcome.commit.is_none());
+        assert_eq!(run(&repo, &["cat-file", "-t", "nightly"]).trim(), "commit");
+        assert!(release(&repo, &options).is_err(), "duplicate tag");
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use super::build::BuildState;
+use super::toolchain::Toolchain;
+
+/// One driver of a polyglot project (`[drivers]` in byte.toml)
+#[derive(Debug, Clone)]
+pub struct DriverStatus {
+    pub capability: String,
+    pub driver: String,
+    /// Directory, relative to the project root
+    pub dir: String,
+    /// Manifest found in the directory (`go.mod`, `package.json`, ...)
+    pub manifest: Option<&'static str>,
+    pub toolchain: Option<Toolchain>,
+    /// Last build, when it was one of this capability's tasks
+    pub last_build: Option<BuildState>,
+}
+
+/// Status of each driver; empty for single-driver projects
+pub fn load_driver_statuses(project_path: &str, build: Option<&BuildState>) -> Vec<DriverStatus> {
+    let root = Path::new(project_path);
+    let Some(config) = std::fs::read_to_string(root.join("byte.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<crate::config::ProjectConfig>(&content).ok())
+    else {
+        return Vec::new();
+    };
+
+    crate::projects::drivers::capabilities(&config)
+        .into_iter()
+        .map(|capability| {
+            let prefix = format!("{}:", capability.name);
+            DriverStatus {
+                manifest: crate::projects::detect::detect_ecosystem(&root.join(capability.dir())).map(|(file, _)| file),
+                toolchain: super::toolchain::toolchain_in(&root.join(capability.dir()), &capability.driver),
+                last_build: build.filter(|b| b.task.starts_with(&prefix)).cloned(),
+                dir: capability.dir().to_string(),
+                capability: capability.name,
+                driver: capability.driver,
+            }
+        })
+        .collect()
+}
@@ -1,16 +1,123 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Git repository status
 #[derive(Debug, Clone)]
 pub struct GitStatus {
     pub is_repo: bool,
     pub branch: Option<String>,
+    /// Upstream tracking branch (e.g. "origin/main"); None if not set
+    pub upstream: Option<String>,
     pub is_clean: bool,
     pub modified: usize,
     pub staged: usize,
     pub untracked: usize,
+    /// Renamed or copied entries (also counted in staged/modified)
+    pub renamed: usize,
+    /// Unmerged paths (merge/rebase conflicts)
+    pub conflicted: usize,
     pub ahead: usize,
     pub behind: usize,
+    pub stash: usize,
+    pub operation: Option<GitOperation>,
+    pub last_commit: Option<CommitInfo>,
+    /// Tag pointing at HEAD
+    pub tag: Option<String>,
+    /// When the remote was last fetched (FETCH_HEAD mtime, Unix timestamp);
+    /// ahead/behind are only as fresh as this
+    pub last_fetch: Option<i64>,
+    /// Where the project sits: repository root, worktree, submodule
+    pub layout: Option<RepoLayout>,
+    /// Submodules of the repository (from `git submodule status`)
+    pub submodules: Vec<Submodule>,
+}
+
+/// How a project's directory relates to its repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepoLayout {
+    /// Root of the working tree
+    pub toplevel: PathBuf,
+    /// The project's directory relative to the root, if it isn't the root
+    pub subdir: Option<String>,
+    pub git_dir: PathBuf,
+    /// Git dir shared by all worktrees (refs, config, FETCH_HEAD); the same
+    /// as `git_dir` outside a linked worktree
+    pub common_dir: PathBuf,
+    /// The main working tree, when the project is a linked worktree
+    pub main_worktree: Option<PathBuf>,
+    /// The superproject's working tree, when the project is a submodule
+    pub superproject: Option<PathBuf>,
+}
+
+/// A submodule and how its checkout compares to the recorded commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submodule {
+    pub path: String,
+    pub commit: String,
+    pub state: SubmoduleState,
+    /// Has uncommitted or untracked changes inside
+    pub modified: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmoduleState {
+    /// Checked out at the commit the superproject records
+    Current,
+    /// Checked out at a different commit than recorded
+    OutOfDate,
+    Uninitialized,
+    Conflict,
+}
+
+/// A multi-step operation left in progress in the working tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl GitOperation {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GitOperation::Merge => "merge",
+            GitOperation::Rebase => "rebase",
+            GitOperation::CherryPick => "cherry-pick",
+            GitOperation::Revert => "revert",
+            GitOperation::Bisect => "bisect",
+        }
+    }
+}
+
+/// The commit at HEAD
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitInfo {
+    pub hash: String,
+    pub subject: String,
+    pub author: String,
+    pub timestamp: i64, // Unix timestamp
+}
+
+impl CommitInfo {
+    /// Relative age, e.g. "3 hours ago"
+    pub fn age(&self) -> String {
+        format_age(self.timestamp)
+    }
+}
+
+/// Relative age of a Unix timestamp, e.g. "3 hours ago"
+pub fn format_age(timestamp: i64) -> String {
+    let seconds = (chrono::Utc::now().timestamp() - timestamp).max(0);
+    let (value, unit) = match seconds {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86400 => (s / 3600, "hour"),
+        s if s < 86400 * 30 => (s / 86400, "day"),
+        s if s < 86400 * 365 => (s / (86400 * 30), "month"),
+        s => (s / (86400 * 365), "year"),
+    };
+    format!("{} {}{} ago", value, unit, if value == 1 { "" } else { "s" })
 }
 
 impl GitStatus {
@@ -18,28 +125,18 @@ impl GitStatus {
     pub fn not_a_repo() -> Self {
         Self {
             is_repo: false,
-            branch: None,
-            is_clean: true,
-            modified: 0,
-            staged: 0,
-            untracked: 0,
-            ahead: 0,
-            behind: 0,
+            ..Self::default()
         }
     }
 
     /// Create a status indicating an error occurred
     pub fn error() -> Self {
-        Self {
-            is_repo: true,
-            branch: None,
-            is_clean: true,
-            modified: 0,
-            staged: 0,
-            untracked: 0,
-            ahead: 0,
-            behind: 0,
-        }
+        Self::default()
+    }
+
+    /// On a branch that doesn't track a remote branch
+    pub fn has_no_upstream(&self) -> bool {
+        self.is_repo && self.branch.is_some() && self.upstream.is_none()
     }
 }
 
@@ -48,29 +145,64 @@ impl Default for GitStatus {
         Self {
             is_repo: true,
             branch: None,
+            upstream: None,
             is_clean: true,
             modified: 0,
             staged: 0,
             untracked: 0,
+            renamed: 0,
+            conflicted: 0,
             ahead: 0,
             behind: 0,
+            stash: 0,
+            operation: None,
+            last_commit: None,
+            tag: None,
+            last_fetch: None,
+            layout: None,
+            submodules: Vec::new(),
         }
     }
 }
 
 /// Get git status for a project directory
 pub fn get_git_status(project_path: &str) -> GitStatus {
-    // Fast exit: check if .git directory exists
-    let git_dir = PathBuf::from(project_path).join(".git");
-    if !git_dir.exists() {
+    // Fast exit: no `.git` (a directory, or a file for worktrees/submodules) here or above
+    if !Path::new(project_path).ancestors().any(|dir| dir.join(".git").exists()) {
         return GitStatus::not_a_repo();
     }
+    let Some(layout) = run_rev_parse_command(project_path) else {
+        return GitStatus::not_a_repo();
+    };
 
     // Run git status command
-    match run_git_status_command(project_path) {
-        Ok(output) => parse_git_status(&output),
-        Err(_) => GitStatus::error(),
+    let output = match run_git_status_command(project_path) {
+        Ok(output) => output,
+        Err(_) => return GitStatus::error(),
+    };
+    let mut status = parse_git_status(&output);
+
+    status.operation = detect_operation(&layout.git_dir);
+    status.last_fetch = std::fs::metadata(layout.common_dir.join("FETCH_HEAD"))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64);
+
+    if layout.toplevel.join(".gitmodules").exists() {
+        let dirty = dirty_submodules(&output);
+        status.submodules = run_submodule_status_command(&layout.toplevel);
+        for submodule in &mut status.submodules {
+            submodule.modified = dirty.contains(&submodule.path);
+        }
     }
+    status.layout = Some(layout);
+    if let Some((commit, tag)) = run_git_log_command(project_path) {
+        status.last_commit = Some(commit);
+        status.tag = tag;
+    }
+
+    status
 }
 
 /// Run git status command and capture output using exec API
@@ -78,8 +210,9 @@ fn run_git_status_command(project_path: &str) -> Result<String, std::io::Error>
     use crate::exec::CommandBuilder;
 
     let result = CommandBuilder::git("status")
-        .arg("--porcelain=v1")
+        .arg("--porcelain=v2")
         .arg("--branch")
+        .arg("--show-stash")
         .working_dir(project_path)
         .execute()
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
@@ -94,100 +227,315 @@ fn run_git_status_command(project_path: &str) -> Result<String, std::io::Error>
     }
 }
 
-/// Parse git status porcelain output
+/// Repository root, git dir, and worktree/submodule relationships
+fn run_rev_parse_command(project_path: &str) -> Option<RepoLayout> {
+    use crate::exec::CommandBuilder;
+
+    let result = CommandBuilder::git("rev-parse")
+        .arg("--path-format=absolute")
+        .arg("--show-toplevel")
+        .arg("--show-prefix")
+        .arg("--git-dir")
+        .arg("--git-common-dir")
+        .arg("--show-superproject-working-tree")
+        .working_dir(project_path)
+        .execute()
+        .ok()?;
+
+    if !result.success {
+        return None;
+    }
+    parse_rev_parse(&result.stdout)
+}
+
+/// Parse the rev-parse output: toplevel, prefix (empty at the root), git dir,
+/// common dir, and the superproject (only printed for submodules)
+fn parse_rev_parse(output: &str) -> Option<RepoLayout> {
+    let mut lines = output.lines().map(str::trim);
+    let toplevel = PathBuf::from(lines.next().filter(|l| !l.is_empty())?);
+    let subdir = lines.next().map(|l| l.trim_end_matches('/')).filter(|l| !l.is_empty()).map(String::from);
+    let git_dir = PathBuf::from(lines.next()?);
+    let common_dir = PathBuf::from(lines.next()?);
+    let superproject = lines.next().filter(|l| !l.is_empty()).map(PathBuf::from);
+
+    // A linked worktree has its own git dir under the main repository's `worktrees/`
+    let main_worktree = (git_dir != common_dir).then(|| {
+        if common_dir.file_name().is_some_and(|n| n == ".git") {
+            common_dir.parent().map(Path::to_path_buf).unwrap_or(common_dir.clone())
+        } else {
+            common_dir.clone()
+        }
+    });
+
+    Some(RepoLayout { toplevel, subdir, git_dir, common_dir, main_worktree, superproject })
+}
+
+/// Submodules of the repository at `toplevel`
+fn run_submodule_status_command(toplevel: &Path) -> Vec<Submodule> {
+    use crate::exec::CommandBuilder;
+
+    CommandBuilder::git("submodule")
+        .arg("status")
+        .working_dir(toplevel)
+        .execute()
+        .ok()
+        .filter(|result| result.success)
+        .map(|result| parse_submodule_status(&result.stdout))
+        .unwrap_or_default()
+}
+
+/// Parse `git submodule status`: `<state><sha> <path> (<describe>)`
+fn parse_submodule_status(output: &str) -> Vec<Submodule> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let state = match line.chars().next()? {
+                '+' => SubmoduleState::OutOfDate,
+                '-' => SubmoduleState::Uninitialized,
+                'U' => SubmoduleState::Conflict,
+                _ => SubmoduleState::Current,
+            };
+            let mut fields = line[1..].split_whitespace();
+            let commit = fields.next()?.chars().take(7).collect();
+            let path = fields.next()?.to_string();
+            Some(Submodule { path, commit, state, modified: false })
+        })
+        .collect()
+}
+
+/// Submodule paths with modified or untracked content, from porcelain v2
+/// entries whose submodule field is `S?M?` or `S??U`
+fn dirty_submodules(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // Fields before the path: ordinary entries 8, renames 9
+            let fields_before_path = match line.get(..2)? {
+                "1 " => 8,
+                "2 " => 9,
+                _ => return None,
+            };
+            let fields: Vec<&str> = line.splitn(fields_before_path + 1, ' ').collect();
+            let sub: Vec<char> = fields.get(2)?.chars().collect();
+            let dirty = sub.first() == Some(&'S') && (sub.get(2) == Some(&'M') || sub.get(3) == Some(&'U'));
+            let path = fields.get(fields_before_path)?.split('\t').next()?;
+            dirty.then(|| path.to_string())
+        })
+        .collect()
+}
+
+/// Last commit and tag at HEAD (None for a repository without commits)
+fn run_git_log_command(project_path: &str) -> Option<(CommitInfo, Option<String>)> {
+    use crate::exec::CommandBuilder;
+
+    let result = CommandBuilder::git("log")
+        .arg("-1")
+        .arg("--format=%h%x00%s%x00%an%x00%ct%x00%D")
+        .working_dir(project_path)
+        .execute()
+        .ok()?;
+
+    if !result.success {
+        return None;
+    }
+    parse_git_log(&result.stdout)
+}
+
+/// Parse `git log -1 --format=%h%x00%s%x00%an%x00%ct%x00%D`
+fn parse_git_log(output: &str) -> Option<(CommitInfo, Option<String>)> {
+    let mut fields = output.trim_end_matches('\n').split('\0');
+    let commit = CommitInfo {
+        hash: fields.next()?.to_string(),
+        subject: fields.next()?.to_string(),
+        author: fields.next()?.to_string(),
+        timestamp: fields.next()?.trim().parse().ok()?,
+    };
+
+    // %D: "HEAD -> main, tag: v1.2.0, origin/main"
+    let tag = fields
+        .next()
+        .unwrap_or_default()
+        .split(", ")
+        .find_map(|decoration| decoration.strip_prefix("tag: "))
+        .map(String::from);
+
+    Some((commit, tag))
+}
+
+/// Detect an interrupted merge, rebase, cherry-pick, revert or bisect
+fn detect_operation(git_dir: &Path) -> Option<GitOperation> {
+    if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+        Some(GitOperation::Rebase)
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        Some(GitOperation::Merge)
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        Some(GitOperation::CherryPick)
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        Some(GitOperation::Revert)
+    } else if git_dir.join("BISECT_LOG").exists() {
+        Some(GitOperation::Bisect)
+    } else {
+        None
+    }
+}
+
+/// Parse `git status --porcelain=v2 --branch --show-stash` output
+///
+/// ```text
+/// # branch.oid 4f2c1e0...
+/// # branch.head main
+/// # branch.upstream origin/main
+/// # branch.ab +1 -0
+/// # stash 2
+/// 1 .M N... 100644 100644 100644 3f2a... 3f2a... src/main.rs
+/// 2 R. N... 100644 100644 100644 9c1b... 9c1b... R100 src/new.rs<TAB>src/old.rs
+/// u UU N... 100644 100644 100644 100644 a1... b2... c3... src/conflict.rs
+/// ? notes.txt
+/// ```
 fn parse_git_status(output: &str) -> GitStatus {
     let mut status = GitStatus::default();
 
     for line in output.lines() {
-        if line.starts_with("##") {
-            // Parse branch line
-            parse_branch_line(line, &mut status);
-        } else if line.starts_with("??") {
-            // Untracked file
+        if let Some(header) = line.strip_prefix("# ") {
+            parse_header_line(header, &mut status);
+        } else if line.starts_with("? ") {
             status.untracked += 1;
-        } else if line.len() >= 2 {
-            // Check staged status (first character)
-            let first_char = line.chars().nth(0);
-            if first_char.is_some() && first_char != Some(' ') && first_char != Some('?') {
+        } else if line.starts_with("u ") {
+            status.conflicted += 1;
+        } else if let Some(entry) = line.strip_prefix("1 ").or_else(|| line.strip_prefix("2 ")) {
+            // XY: index status then worktree status, '.' meaning unchanged
+            let mut xy = entry.chars();
+            if xy.next().is_some_and(|x| x != '.') {
                 status.staged += 1;
             }
-
-            // Check unstaged status (second character)
-            let second_char = line.chars().nth(1);
-            if second_char.is_some() && second_char != Some(' ') && second_char != Some('?') {
+            if xy.next().is_some_and(|y| y != '.') {
                 status.modified += 1;
             }
+            if line.starts_with("2 ") {
+                status.renamed += 1;
+            }
         }
     }
 
     // Determine if repository is clean
-    status.is_clean = status.modified == 0 && status.staged == 0 && status.untracked == 0;
+    status.is_clean = status.modified == 0
+        && status.staged == 0
+        && status.untracked == 0
+        && status.conflicted == 0;
 
     status
 }
 
-/// Parse the branch line from git status output
-/// Format: ## branch...remote [ahead X, behind Y]
-fn parse_branch_line(line: &str, status: &mut GitStatus) {
-    // Remove "## " prefix
-    let line = line.trim_start_matches("## ");
-
-    // Handle initial commit case
-    if line.starts_with("No commits yet on ") {
-        let branch = line.trim_start_matches("No commits yet on ");
-        status.branch = Some(branch.to_string());
+/// Parse a `# branch.*` or `# stash` header line
+fn parse_header_line(header: &str, status: &mut GitStatus) {
+    let Some((key, value)) = header.split_once(' ') else {
         return;
+    };
+
+    match key {
+        // "(detached)" shows as "(detached HEAD)"
+        "branch.head" if value != "(detached)" => status.branch = Some(value.to_string()),
+        "branch.upstream" => status.upstream = Some(value.to_string()),
+        "branch.ab" => {
+            for part in value.split_whitespace() {
+                if let Some(ahead) = part.strip_prefix('+') {
+                    status.ahead = ahead.parse().unwrap_or(0);
+                } else if let Some(behind) = part.strip_prefix('-') {
+                    status.behind = behind.parse().unwrap_or(0);
+                }
+            }
+        }
+        "stash" => status.stash = value.parse().unwrap_or(0),
+        _ => {}
     }
+}
 
-    // Handle detached HEAD
-    if line.contains("HEAD (no branch)") || line.starts_with("HEAD detached") {
-        status.branch = None; // Will show as "(detached HEAD)"
-        return;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_porcelain_v2() {
+        let output = "\
+# branch.oid 4f2c1e0d9b8a7f6e5d4c3b2a1f0e9d8c7b6a5f4e
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+# stash 3
+1 .M N... 100644 100644 100644 3f2a 3f2a src/main.rs
+1 M. N... 100644 100644 100644 3f2a 4b3c src/lib.rs
+2 R. N... 100644 100644 100644 9c1b 9c1b R100 src/new.rs\tsrc/old.rs
+u UU N... 100644 100644 100644 100644 a1 b2 c3 src/conflict.rs
+? notes.txt
+";
+        let status = parse_git_status(output);
+
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert_eq!(status.stash, 3);
+        // The rename is staged only, not also modified
+        assert_eq!((status.staged, status.modified, status.renamed), (2, 1, 1));
+        assert_eq!(status.conflicted, 1);
+        assert_eq!(status.untracked, 1);
+        assert!(!status.is_clean);
+        assert!(!status.has_no_upstream());
     }
 
-    // Parse branch name and tracking info
-    if let Some(dots_pos) = line.find("...") {
-        // Branch with remote tracking
-        let branch = &line[..dots_pos];
-        status.branch = Some(branch.to_string());
-
-        // Parse ahead/behind from the rest of the line
-        let rest = &line[dots_pos + 3..];
-
-        // Look for [ahead X, behind Y] or [ahead X] or [behind Y]
-        if let Some(bracket_start) = rest.find('[') {
-            if let Some(bracket_end) = rest.find(']') {
-                let tracking_info = &rest[bracket_start + 1..bracket_end];
-
-                // Parse ahead
-                if let Some(ahead_pos) = tracking_info.find("ahead ") {
-                    let ahead_str = &tracking_info[ahead_pos + 6..];
-                    if let Some(comma_or_end) =
-                        ahead_str.find(',').or_else(|| Some(ahead_str.len()))
-                    {
-                        if let Ok(ahead) = ahead_str[..comma_or_end].trim().parse::<usize>() {
-                            status.ahead = ahead;
-                        }
-                    }
-                }
+    #[test]
+    fn test_parse_detached_and_no_upstream() {
+        let detached = parse_git_status("# branch.oid abc\n# branch.head (detached)\n");
+        assert_eq!(detached.branch, None);
+        assert!(detached.is_clean);
 
-                // Parse behind
-                if let Some(behind_pos) = tracking_info.find("behind ") {
-                    let behind_str = &tracking_info[behind_pos + 7..];
-                    if let Some(comma_or_end) =
-                        behind_str.find(',').or_else(|| Some(behind_str.len()))
-                    {
-                        if let Ok(behind) = behind_str[..comma_or_end].trim().parse::<usize>() {
-                            status.behind = behind;
-                        }
-                    }
-                }
-            }
-        }
-    } else {
-        // Branch without remote tracking (local branch)
-        // Remove any trailing indicators
-        let branch = line.split_whitespace().next().unwrap_or(line);
-        status.branch = Some(branch.to_string());
+        let local = parse_git_status("# branch.oid (initial)\n# branch.head feature\n");
+        assert!(local.has_no_upstream());
+    }
+
+    #[test]
+    fn test_parse_git_log() {
+        let (commit, tag) = parse_git_log("4f2c1e0\0Fix parser\0Ada\x001767225600\0HEAD -> main, tag: v1.2.0, origin/main\n").unwrap();
+        assert_eq!(commit.hash, "4f2c1e0");
+        assert_eq!(commit.subject, "Fix parser");
+        assert_eq!(commit.author, "Ada");
+        assert_eq!(tag.as_deref(), Some("v1.2.0"));
+
+        let (_, tag) = parse_git_log("4f2c1e0\0Fix parser\0Ada\x001767225600\0HEAD -> main\n").unwrap();
+        assert_eq!(tag, None);
+    }
+
+    #[test]
+    fn test_parse_layout_and_submodules() {
+        let worktree = parse_rev_parse("/src/app-feature\nweb/\n/src/app/.git/worktrees/app-feature\n/src/app/.git\n").unwrap();
+        assert_eq!(worktree.subdir.as_deref(), Some("web"));
+        assert_eq!(worktree.main_worktree, Some(PathBuf::from("/src/app")));
+        assert_eq!(worktree.common_dir, PathBuf::from("/src/app/.git"));
+        assert_eq!(worktree.superproject, None);
+
+        let submodule = parse_rev_parse("/src/app/vendor/lib\n\n/src/app/.git/modules/lib\n/src/app/.git/modules/lib\n/src/app\n").unwrap();
+        assert_eq!(submodule.subdir, None);
+        assert_eq!(submodule.main_worktree, None);
+        assert_eq!(submodule.superproject, Some(PathBuf::from("/src/app")));
+
+        let submodules = parse_submodule_status(" 1a2b3c4d5e6f vendor/lib (v1.0)\n+9f8e7d6c5b4a vendor/other (heads/main)\n-0000000000 vendor/new\n");
+        assert_eq!(submodules.len(), 3);
+        assert_eq!(submodules[0].commit, "1a2b3c4");
+        assert_eq!(submodules[1].state, SubmoduleState::OutOfDate);
+        assert_eq!(submodules[2].state, SubmoduleState::Uninitialized);
+
+        let status = "1 .M S.M. 160000 160000 160000 1a2b 1a2b vendor/lib\n1 .M SC.. 160000 160000 160000 1a2b 9f8e vendor/other\n1 .M N... 100644 100644 100644 3f2a 3f2a src/main.rs\n";
+        assert_eq!(dirty_submodules(status), vec!["vendor/lib"]);
+    }
+
+    #[test]
+    fn test_detect_operation() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(detect_operation(temp.path()), None);
+
+        std::fs::write(temp.path().join("MERGE_HEAD"), "abc").unwrap();
+        assert_eq!(detect_operation(temp.path()), Some(GitOperation::Merge));
+
+        std::fs::create_dir(temp.path().join("rebase-merge")).unwrap();
+        assert_eq!(detect_operation(temp.path()), Some(GitOperation::Rebase));
     }
 }
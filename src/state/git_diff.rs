@@ -0,0 +1,324 @@
+//! Per-file git changes and unified diffs
+
+use anyhow::Result;
+
+use crate::exec::CommandBuilder;
+
+/// One changed path from `git status --porcelain=v2`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    /// Original path of a rename or copy
+    pub orig_path: Option<String>,
+    /// Index (staged) status: 'M', 'A', 'D', 'R', 'C', 'U', '?' or '.' for unchanged
+    pub index: char,
+    /// Worktree (unstaged) status, same codes
+    pub worktree: char,
+}
+
+impl FileChange {
+    pub fn is_untracked(&self) -> bool {
+        self.index == '?'
+    }
+
+    pub fn is_conflicted(&self) -> bool {
+        self.index == 'U' || self.worktree == 'U' || (self.index == 'A' && self.worktree == 'A') || (self.index == 'D' && self.worktree == 'D')
+    }
+
+    pub fn has_staged(&self) -> bool {
+        !self.is_untracked() && self.index != '.'
+    }
+
+    pub fn has_unstaged(&self) -> bool {
+        self.is_untracked() || self.worktree != '.'
+    }
+
+    /// Two-letter status code as shown by `git status --short` ("M ", " M", "R ", "??")
+    pub fn code(&self) -> String {
+        let show = |c: char| if c == '.' { ' ' } else { c };
+        format!("{}{}", show(self.index), show(self.worktree))
+    }
+}
+
+/// List changed files of a repository
+pub fn list_changes(project_path: &str) -> Result<Vec<FileChange>> {
+    let result = CommandBuilder::git("status")
+        .arg("--porcelain=v2")
+        .arg("-z")
+        .arg("--untracked-files=all")
+        .working_dir(project_path)
+        .execute()?;
+    if !result.success {
+        anyhow::bail!("git status failed: {}", result.stderr.trim());
+    }
+    Ok(parse_changes(&result.stdout))
+}
+
+/// Root of the repository containing a project; status paths are relative to it
+///
+/// One `rev-parse`, since every stage, unstage and diff asks for it.
+pub fn repo_root(project_path: &str) -> String {
+    CommandBuilder::git("rev-parse")
+        .arg("--show-toplevel")
+        .working_dir(project_path)
+        .execute()
+        .ok()
+        .filter(|result| result.success)
+        .map(|result| result.stdout.trim().to_string())
+        .filter(|root| !root.is_empty())
+        .unwrap_or_else(|| project_path.to_string())
+}
+
+/// Parse the entries of `git status --porcelain=v2 -z`
+///
+/// Entries end in NUL and paths are verbatim (no quoting of tabs, quotes or
+/// non-ASCII names); a rename's original path is the entry after it.
+pub fn parse_changes(output: &str) -> Vec<FileChange> {
+    let mut changes = Vec::new();
+    let mut records = output.split('\0');
+
+    while let Some(record) = records.next() {
+        let (kind, rest) = match record.split_once(' ') {
+            Some(split) => split,
+            None => continue,
+        };
+        // Fields before the path: ordinary 7, rename/copy 8 (+ score), unmerged 9
+        let fields_before_path = match kind {
+            "1" => 7,
+            "2" => 8,
+            "u" => 9,
+            "?" => {
+                changes.push(FileChange {
+                    path: rest.to_string(),
+                    orig_path: None,
+                    index: '?',
+                    worktree: '?',
+                });
+                continue;
+            }
+            _ => continue,
+        };
+
+        let mut parts = rest.splitn(fields_before_path + 1, ' ');
+        let mut xy = parts.next().unwrap_or("..").chars();
+        let (index, worktree) = (xy.next().unwrap_or('.'), xy.next().unwrap_or('.'));
+        let orig_path = if kind == "2" { records.next().map(String::from) } else { None };
+        let Some(path) = parts.nth(fields_before_path - 1) else {
+            continue;
+        };
+        changes.push(FileChange { path: path.to_string(), orig_path, index, worktree });
+    }
+
+    changes
+}
+
+/// Unified diff of one file: staged (index vs HEAD) or unstaged (worktree vs index)
+pub fn file_diff(project_path: &str, change: &FileChange, staged: bool) -> Result<FileDiff> {
+    let builder = if change.is_untracked() {
+        // Show a new file as all additions; exits 1 when the files differ
+        CommandBuilder::git("diff")
+            .arg("--no-color")
+            .arg("--no-index")
+            .arg("--")
+            .arg("/dev/null")
+            .arg(&change.path)
+    } else {
+        let mut builder = CommandBuilder::git("diff").arg("--no-color");
+        if staged {
+            builder = builder.arg("--cached");
+        }
+        builder.arg("--").arg(&change.path)
+    };
+
+    let result = builder.working_dir(repo_root(project_path)).execute()?;
+    if result.exit_code > 1 {
+        anyhow::bail!("git diff failed: {}", result.stderr.trim());
+    }
+    Ok(FileDiff::parse(&result.stdout))
+}
+
+/// How a diff line should be displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// `diff --git`, `index`, `---`/`+++` lines
+    Header,
+    /// `@@ -a,b +c,d @@`
+    HunkHeader,
+    Added,
+    Removed,
+    Context,
+}
+
+/// A hunk of a unified diff
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk {
+    pub header: String,
+    /// First line of the hunk in the new file
+    pub new_start: u32,
+    pub lines: Vec<String>,
+}
+
+/// A parsed single-file unified diff
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileDiff {
+    pub header: Vec<String>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    pub fn parse(diff: &str) -> Self {
+        let mut parsed = FileDiff::default();
+
+        for line in diff.lines() {
+            if line.starts_with("@@") {
+                parsed.hunks.push(Hunk {
+                    header: line.to_string(),
+                    new_start: parse_new_start(line).unwrap_or(1),
+                    lines: Vec::new(),
+                });
+            } else if let Some(hunk) = parsed.hunks.last_mut() {
+                hunk.lines.push(line.to_string());
+            } else {
+                parsed.header.push(line.to_string());
+            }
+        }
+
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// Every display line with its kind, in order
+    pub fn lines(&self) -> Vec<(DiffLineKind, &str)> {
+        let mut lines: Vec<(DiffLineKind, &str)> =
+            self.header.iter().map(|l| (DiffLineKind::Header, l.as_str())).collect();
+
+        for hunk in &self.hunks {
+            lines.push((DiffLineKind::HunkHeader, hunk.header.as_str()));
+            for line in &hunk.lines {
+                let kind = match line.chars().next() {
+                    Some('+') => DiffLineKind::Added,
+                    Some('-') => DiffLineKind::Removed,
+                    _ => DiffLineKind::Context,
+                };
+                lines.push((kind, line.as_str()));
+            }
+        }
+
+        lines
+    }
+
+    /// Display row of each hunk header
+    pub fn hunk_rows(&self) -> Vec<usize> {
+        let mut row = self.header.len();
+        self.hunks
+            .iter()
+            .map(|hunk| {
+                let start = row;
+                row += 1 + hunk.lines.len();
+                start
+            })
+            .collect()
+    }
+
+    /// Hunk whose lines include a display row (the first hunk above the header)
+    pub fn hunk_at(&self, display_row: usize) -> Option<usize> {
+        let rows = self.hunk_rows();
+        if rows.is_empty() {
+            return None;
+        }
+        Some(rows.iter().rposition(|&start| start <= display_row).unwrap_or(0))
+    }
+
+    /// A patch containing only one hunk, for `git apply`
+    pub fn hunk_patch(&self, index: usize) -> Option<String> {
+        let hunk = self.hunks.get(index)?;
+        let mut patch = String::new();
+        for line in self.header.iter().chain(std::iter::once(&hunk.header)).chain(&hunk.lines) {
+            patch.push_str(line);
+            patch.push('\n');
+        }
+        Some(patch)
+    }
+
+    /// Line in the new file shown at a display row (for opening the editor there)
+    pub fn new_line_at(&self, display_row: usize) -> Option<u32> {
+        let rows = self.hunk_rows();
+        let index = rows.iter().rposition(|&start| start <= display_row)?;
+        let hunk = &self.hunks[index];
+        let offset = display_row - rows[index];
+
+        // Count lines present in the new file before the row
+        let before = hunk
+            .lines
+            .iter()
+            .take(offset.saturating_sub(1))
+            .filter(|l| !l.starts_with('-'))
+            .count() as u32;
+        Some(hunk.new_start + before)
+    }
+}
+
+/// `@@ -12,7 +14,8 @@ fn main()` → 14
+fn parse_new_start(header: &str) -> Option<u32> {
+    let new = header.split_whitespace().find(|part| part.starts_with('+'))?;
+    new.trim_start_matches('+').split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_changes() {
+        let output = "\
+1 .M N... 100644 100644 100644 3f2a 3f2a src/main.rs\0\
+2 R. N... 100644 100644 100644 9c1b 9c1b R100 src/new name.rs\0src/old.rs\0\
+u UU N... 100644 100644 100644 100644 a1 b2 c3 src/conflict.rs\0\
+? notes.txt\0\
+? tab\there \"quoted\" é.txt\0";
+        let changes = parse_changes(output);
+
+        assert_eq!(changes.len(), 5);
+        assert_eq!(changes[0].code(), " M");
+        assert!(changes[0].has_unstaged() && !changes[0].has_staged());
+        assert_eq!(changes[1].path, "src/new name.rs");
+        assert_eq!(changes[1].orig_path.as_deref(), Some("src/old.rs"));
+        assert!(changes[1].has_staged() && !changes[1].has_unstaged());
+        assert!(changes[2].is_conflicted());
+        assert_eq!(changes[3].code(), "??");
+        assert_eq!(changes[4].path, "tab\there \"quoted\" é.txt");
+    }
+
+    #[test]
+    fn test_file_diff_hunks() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() { todo!() }
+ fn c() {}
+@@ -10,2 +10,3 @@ impl X {
+ fn d() {}
++fn e() {}
+";
+        let parsed = FileDiff::parse(diff);
+
+        assert_eq!(parsed.header.len(), 4);
+        assert_eq!(parsed.hunks.len(), 2);
+        assert_eq!(parsed.hunk_rows(), vec![4, 9]);
+        assert_eq!(parsed.lines()[6].0, DiffLineKind::Removed);
+        // "+fn b()" is the second line of the new file
+        assert_eq!(parsed.new_line_at(7), Some(2));
+        assert_eq!(parsed.new_line_at(11), Some(11));
+        assert_eq!(parsed.hunk_at(2), Some(0));
+        assert_eq!(parsed.hunk_at(10), Some(1));
+        assert!(parsed.hunk_patch(1).unwrap().ends_with("@@ -10,2 +10,3 @@ impl X {\n fn d() {}\n+fn e() {}\n"));
+    }
+}
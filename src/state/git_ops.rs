@@ -0,0 +1,269 @@
+//! Git write operations: staging, commits, branches and stashes
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::exec::{CommandBuilder, CommandResult};
+use crate::state::git_diff::{repo_root, FileChange, FileDiff};
+
+/// Run a git command in a project, failing with git's own message
+fn run(project_path: &str, builder: CommandBuilder) -> Result<CommandResult> {
+    let result = builder.working_dir(project_path).execute()?;
+    if !result.success {
+        let message = if result.stderr.trim().is_empty() { &result.stdout } else { &result.stderr };
+        anyhow::bail!("{}", message.trim());
+    }
+    Ok(result)
+}
+
+/// Stage a whole file (including deletions and untracked files)
+pub fn stage_file(project_path: &str, change: &FileChange) -> Result<()> {
+    run(&repo_root(project_path), CommandBuilder::git("add").arg("--all").arg("--").arg(&change.path))?;
+    Ok(())
+}
+
+/// Unstage a whole file, keeping its worktree changes
+pub fn unstage_file(project_path: &str, change: &FileChange) -> Result<()> {
+    let mut builder = CommandBuilder::git("restore").arg("--staged").arg("--");
+    if let Some(orig) = &change.orig_path {
+        builder = builder.arg(orig);
+    }
+    run(&repo_root(project_path), builder.arg(&change.path))?;
+    Ok(())
+}
+
+/// Stage one hunk of an unstaged diff, or unstage one hunk of a staged diff
+pub fn apply_hunk(project_path: &str, diff: &FileDiff, hunk: usize, unstage: bool) -> Result<()> {
+    let patch = diff.hunk_patch(hunk).context("No such hunk")?;
+
+    // Created exclusively with a random name, and removed when dropped
+    let mut patch_file = tempfile::Builder::new().prefix("byte-hunk-").suffix(".patch").tempfile()?;
+    patch_file.write_all(patch.as_bytes())?;
+    patch_file.flush()?;
+
+    let mut builder = CommandBuilder::git("apply").arg("--cached");
+    if unstage {
+        builder = builder.arg("--reverse");
+    }
+    run(&repo_root(project_path), builder.arg(patch_file.path().to_string_lossy()))?;
+    Ok(())
+}
+
+/// Commit the index; an amend with an empty subject keeps the previous message
+pub fn commit(project_path: &str, subject: &str, body: &str, amend: bool) -> Result<String> {
+    let mut builder = CommandBuilder::git("commit");
+    if amend {
+        builder = builder.arg("--amend");
+    }
+    if subject.trim().is_empty() {
+        if !amend {
+            anyhow::bail!("Commit subject is required");
+        }
+        builder = builder.arg("--no-edit");
+    } else {
+        builder = builder.arg("-m").arg(subject.trim());
+        if !body.trim().is_empty() {
+            builder = builder.arg("-m").arg(body.trim());
+        }
+    }
+    let result = run(project_path, builder)?;
+    Ok(result.stdout.lines().next().unwrap_or_default().to_string())
+}
+
+/// A local branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    pub name: String,
+    pub current: bool,
+    pub upstream: Option<String>,
+}
+
+/// List local branches, current branch first
+pub fn list_branches(project_path: &str) -> Result<Vec<Branch>> {
+    let result = run(
+        project_path,
+        CommandBuilder::git("branch").arg("--format=%(HEAD)%00%(refname:short)%00%(upstream:short)"),
+    )?;
+    let mut branches = parse_branches(&result.stdout);
+    branches.sort_by_key(|b| !b.current);
+    Ok(branches)
+}
+
+/// Parse `git branch --format=%(HEAD)%00%(refname:short)%00%(upstream:short)`
+pub fn parse_branches(output: &str) -> Vec<Branch> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\0');
+            let head = fields.next()?;
+            let name = fields.next()?;
+            // Detached HEAD shows up as "(HEAD detached at ...)"
+            if name.is_empty() || name.starts_with('(') {
+                return None;
+            }
+            Some(Branch {
+                name: name.to_string(),
+                current: head == "*",
+                upstream: fields.next().filter(|u| !u.is_empty()).map(String::from),
+            })
+        })
+        .collect()
+}
+
+pub fn create_branch(project_path: &str, name: &str) -> Result<()> {
+    run(project_path, CommandBuilder::git("switch").arg("-c").arg(name))?;
+    Ok(())
+}
+
+pub fn switch_branch(project_path: &str, name: &str) -> Result<()> {
+    run(project_path, CommandBuilder::git("switch").arg(name))?;
+    Ok(())
+}
+
+/// Delete a branch; git refuses if it isn't merged
+pub fn delete_branch(project_path: &str, name: &str) -> Result<()> {
+    run(project_path, CommandBuilder::git("branch").arg("-d").arg(name))?;
+    Ok(())
+}
+
+/// Check out a branch (created if it doesn't exist) in a new worktree next to
+/// the repository; returns the project's path inside the new worktree
+pub fn add_worktree(project_path: &str, branch: &str, dir_name: &str) -> Result<PathBuf> {
+    let layout = crate::state::git::get_git_status(project_path)
+        .layout
+        .context("Not a git repository")?;
+    let toplevel = layout.toplevel.to_string_lossy().to_string();
+
+    let dir_name = if dir_name.trim().is_empty() {
+        let repo = layout.toplevel.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        format!("{}-{}", repo, branch.replace('/', "-"))
+    } else {
+        dir_name.trim().to_string()
+    };
+    let target = layout.toplevel.parent().context("Repository has no parent directory")?.join(&dir_name);
+    if target.exists() {
+        anyhow::bail!("{} already exists", target.display());
+    }
+
+    let branch_exists = run(&toplevel, CommandBuilder::git("rev-parse").arg("--verify").arg("--quiet").arg(format!("refs/heads/{}", branch))).is_ok();
+    let mut builder = CommandBuilder::git("worktree").arg("add");
+    if branch_exists {
+        builder = builder.arg(target.to_string_lossy()).arg(branch);
+    } else {
+        builder = builder.arg("-b").arg(branch).arg(target.to_string_lossy());
+    }
+    run(&toplevel, builder)?;
+
+    let project = match &layout.subdir {
+        Some(subdir) => target.join(subdir),
+        None => target,
+    };
+    // An untracked byte.toml isn't part of the checkout
+    let config = Path::new(project_path).join("byte.toml");
+    if config.exists() && project.is_dir() && !project.join("byte.toml").exists() {
+        std::fs::copy(&config, project.join("byte.toml"))?;
+    }
+    Ok(project)
+}
+
+/// Stash worktree and index changes, including untracked files
+pub fn stash_push(project_path: &str) -> Result<()> {
+    run(project_path, CommandBuilder::git("stash").arg("push").arg("--include-untracked"))?;
+    Ok(())
+}
+
+pub fn stash_pop(project_path: &str) -> Result<()> {
+    run(project_path, CommandBuilder::git("stash").arg("pop"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::git_diff::{file_diff, list_changes};
+    use tempfile::TempDir;
+
+    fn git(repo: &str, args: &[&str]) {
+        let mut builder = CommandBuilder::new("git");
+        for arg in args {
+            builder = builder.arg(*arg);
+        }
+        run(repo, builder).unwrap();
+    }
+
+    #[test]
+    fn test_stage_hunk_and_commit() {
+        // The repository is a subdirectory so worktrees next to it stay in the TempDir
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("app");
+        std::fs::create_dir(&root).unwrap();
+        let repo = root.to_str().unwrap();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["config", "user.name", "Byte"]);
+        git(repo, &["config", "user.email", "byte@example.com"]);
+
+        let lines: Vec<String> = (1..=20).map(|n| format!("line {}", n)).collect();
+        std::fs::write(root.join("a.txt"), lines.join("\n") + "\n").unwrap();
+        let change = &list_changes(repo).unwrap()[0];
+        stage_file(repo, change).unwrap();
+        commit(repo, "Initial", "", false).unwrap();
+
+        // Two far-apart edits give two hunks; stage only the first
+        let mut edited = lines.clone();
+        edited[0] = "first".to_string();
+        edited[19] = "last".to_string();
+        std::fs::write(root.join("a.txt"), edited.join("\n") + "\n").unwrap();
+        let change = list_changes(repo).unwrap().remove(0);
+        let diff = file_diff(repo, &change, false).unwrap();
+        assert_eq!(diff.hunks.len(), 2);
+        apply_hunk(repo, &diff, 0, false).unwrap();
+
+        let change = list_changes(repo).unwrap().remove(0);
+        assert_eq!(change.code(), "MM");
+        assert_eq!(file_diff(repo, &change, true).unwrap().hunks.len(), 1);
+
+        commit(repo, "Edit first line", "Body text", false).unwrap();
+        commit(repo, "", "", true).unwrap();
+        stash_push(repo).unwrap();
+        assert!(list_changes(repo).unwrap().is_empty());
+        stash_pop(repo).unwrap();
+
+        create_branch(repo, "feature").unwrap();
+        switch_branch(repo, "main").unwrap();
+        let branches = list_branches(repo).unwrap();
+        assert_eq!(branches[0].name, "main");
+        assert!(branches[0].current);
+        delete_branch(repo, "feature").unwrap();
+        assert_eq!(list_branches(repo).unwrap().len(), 1);
+
+        // A worktree lands next to the repository and reports its main worktree
+        std::fs::write(root.join("byte.toml"), "[project]\nname = \"app\"\n").unwrap();
+        let worktree = add_worktree(repo, "feature/x", "").unwrap();
+        assert_eq!(worktree, temp.path().join("app-feature-x"));
+        assert!(worktree.join("byte.toml").exists());
+        let status = crate::state::git::get_git_status(&worktree.to_string_lossy());
+        assert_eq!(status.layout.unwrap().main_worktree.unwrap().canonicalize().unwrap(), root.canonicalize().unwrap());
+
+        // Fetches from any worktree land in the shared git dir
+        assert_eq!(status.last_fetch, None);
+        std::fs::write(root.join(".git/FETCH_HEAD"), "").unwrap();
+        assert!(crate::state::git::get_git_status(&worktree.to_string_lossy()).last_fetch.is_some());
+    }
+
+    #[test]
+    fn test_commit_requires_subject() {
+        assert!(commit("/nonexistent", " ", "", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_branches() {
+        let output = "*\0main\0origin/main\n \0feature\0\n \0(HEAD detached at 1a2b3c)\0\n";
+        let branches = parse_branches(output);
+
+        assert_eq!(branches.len(), 2);
+        assert!(branches[0].current);
+        assert_eq!(branches[0].upstream.as_deref(), Some("origin/main"));
+        assert_eq!(branches[1].upstream, None);
+    }
+}
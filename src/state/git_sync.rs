@@ -0,0 +1,319 @@
+//! Fetch (and fast-forward or push) many repositories at once
+
+use crate::exec::CommandBuilder;
+use crate::state::git::get_git_status;
+
+/// Projects synced at the same time
+const MAX_PARALLEL: usize = 8;
+
+/// What a sync does besides fetching
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SyncMode {
+    /// Fast-forward branches that are strictly behind, in clean working trees
+    pub pull: bool,
+    /// Push branches that are strictly ahead
+    pub push: bool,
+}
+
+/// Result of syncing one repository
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    UpToDate,
+    /// Pulled this many commits (fast-forward only)
+    FastForwarded(usize),
+    /// Behind the upstream, not pulled
+    Behind(usize),
+    /// Local commits not pushed yet
+    Ahead(usize),
+    /// Pushed this many commits to the upstream
+    Pushed(usize),
+    /// Ahead, but the remote refused the push
+    PushRejected(String),
+    Diverged { ahead: usize, behind: usize },
+    /// Behind, but local changes prevented the pull
+    Dirty,
+    /// Current branch doesn't track a remote branch (or HEAD is detached)
+    NoUpstream,
+    NotARepo,
+    Error(String),
+}
+
+impl SyncOutcome {
+    pub fn label(&self) -> String {
+        match self {
+            SyncOutcome::UpToDate => "up to date".to_string(),
+            SyncOutcome::FastForwarded(n) => format!("fast-forwarded {} commit{}", n, plural(*n)),
+            SyncOutcome::Behind(n) => format!("behind {}", n),
+            SyncOutcome::Ahead(n) => format!("ahead {}", n),
+            SyncOutcome::Pushed(n) => format!("pushed {} commit{}", n, plural(*n)),
+            SyncOutcome::PushRejected(e) => format!("push rejected: {}", e),
+            SyncOutcome::Diverged { ahead, behind } => format!("diverged (↑{} ↓{})", ahead, behind),
+            SyncOutcome::Dirty => "dirty (skipped pull)".to_string(),
+            SyncOutcome::NoUpstream => "no upstream".to_string(),
+            SyncOutcome::NotARepo => "not a git repository".to_string(),
+            SyncOutcome::Error(e) => format!("error: {}", e),
+        }
+    }
+
+    /// Needs attention: diverged, dirty, rejected or failed
+    pub fn is_problem(&self) -> bool {
+        matches!(
+            self,
+            SyncOutcome::Diverged { .. } | SyncOutcome::Dirty | SyncOutcome::PushRejected(_) | SyncOutcome::Error(_)
+        )
+    }
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 { "" } else { "s" }
+}
+
+/// One project's sync result
+#[derive(Debug, Clone)]
+pub struct SyncResult {
+    pub name: String,
+    pub path: String,
+    pub outcome: SyncOutcome,
+}
+
+/// Fetch a repository; with `pull`, fast-forward the current branch when it is
+/// strictly behind and the working tree is clean, and with `push`, push it to
+/// its upstream when it is strictly ahead
+pub fn sync_project(project_path: &str, mode: SyncMode) -> SyncOutcome {
+    if !get_git_status(project_path).is_repo {
+        return SyncOutcome::NotARepo;
+    }
+
+    let fetch = CommandBuilder::git("fetch")
+        .arg("--prune")
+        .arg("--quiet")
+        .working_dir(project_path)
+        .without_prompts()
+        .execute();
+    match fetch {
+        Ok(result) if result.success => {}
+        Ok(result) => return SyncOutcome::Error(first_line(&result.stderr)),
+        Err(e) => return SyncOutcome::Error(e.to_string()),
+    }
+
+    // Fresh ahead/behind now that the remote-tracking refs are updated
+    let status = get_git_status(project_path);
+    if status.upstream.is_none() {
+        return SyncOutcome::NoUpstream;
+    }
+
+    match (status.ahead, status.behind) {
+        (0, 0) => SyncOutcome::UpToDate,
+        (ahead, 0) if !mode.push || status.operation.is_some() => SyncOutcome::Ahead(ahead),
+        (ahead, 0) => {
+            // `upstream` pushes to the tracked branch even when its name differs
+            let push = CommandBuilder::new("git")
+                .arg("-c")
+                .arg("push.default=upstream")
+                .arg("push")
+                .arg("--quiet")
+                .working_dir(project_path)
+                .without_prompts()
+                .execute();
+            match push {
+                Ok(result) if result.success => SyncOutcome::Pushed(ahead),
+                Ok(result) => SyncOutcome::PushRejected(first_line(&result.stderr)),
+                Err(e) => SyncOutcome::Error(e.to_string()),
+            }
+        }
+        (0, behind) if !mode.pull => SyncOutcome::Behind(behind),
+        (0, behind) => {
+            if status.modified + status.staged + status.conflicted > 0 || status.operation.is_some() {
+                return SyncOutcome::Dirty;
+            }
+            let merge = CommandBuilder::git("merge")
+                .arg("--ff-only")
+                .arg("--quiet")
+                .arg("@{upstream}")
+                .working_dir(project_path)
+                .without_prompts()
+                .execute();
+            match merge {
+                Ok(result) if result.success => SyncOutcome::FastForwarded(behind),
+                Ok(result) => SyncOutcome::Error(first_line(&result.stderr)),
+                Err(e) => SyncOutcome::Error(e.to_string()),
+            }
+        }
+        (ahead, behind) => SyncOutcome::Diverged { ahead, behind },
+    }
+}
+
+/// Sync projects in parallel, keeping the input order
+pub fn sync_all(projects: &[(String, String)], mode: SyncMode) -> Vec<SyncResult> {
+    let mut results = Vec::with_capacity(projects.len());
+
+    for chunk in projects.chunks(MAX_PARALLEL) {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|(name, path)| {
+                    scope.spawn(move || SyncResult {
+                        name: name.clone(),
+                        path: path.clone(),
+                        outcome: sync_project(path, mode),
+                    })
+                })
+                .collect();
+            for (handle, (name, path)) in handles.into_iter().zip(chunk) {
+                results.push(handle.join().unwrap_or_else(|_| SyncResult {
+                    name: name.clone(),
+                    path: path.clone(),
+                    outcome: SyncOutcome::Error("sync panicked".to_string()),
+                }));
+            }
+        });
+    }
+
+    results
+}
+
+/// "12 projects: 9 up to date, 1 fast-forwarded, 1 diverged, 1 error"
+pub fn summarize(results: &[SyncResult]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for result in results {
+        let key = match result.outcome {
+            SyncOutcome::UpToDate => "up to date",
+            SyncOutcome::FastForwarded(_) => "fast-forwarded",
+            SyncOutcome::Behind(_) => "behind",
+            SyncOutcome::Ahead(_) => "ahead",
+            SyncOutcome::Pushed(_) => "pushed",
+            SyncOutcome::PushRejected(_) => "push rejected",
+            SyncOutcome::Diverged { .. } => "diverged",
+            SyncOutcome::Dirty => "dirty",
+            SyncOutcome::NoUpstream => "no upstream",
+            SyncOutcome::NotARepo => "not a repo",
+            SyncOutcome::Error(_) => "error",
+        };
+        match counts.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((key, 1)),
+        }
+    }
+
+    let parts: Vec<String> = counts.iter().map(|(key, count)| format!("{} {}", count, key)).collect();
+    format!("{} project{}: {}", results.len(), plural(results.len()), parts.join(", "))
+}
+
+/// "2 pushed, 1 rejected, 1 skipped (diverged)": what became of the
+/// branches with commits to push
+pub fn push_report(results: &[SyncResult]) -> String {
+    let count = |matches: fn(&SyncOutcome) -> bool| results.iter().filter(|r| matches(&r.outcome)).count();
+    let pushed = count(|o| matches!(o, SyncOutcome::Pushed(_)));
+    let rejected = count(|o| matches!(o, SyncOutcome::PushRejected(_)));
+    let skipped = count(|o| matches!(o, SyncOutcome::Ahead(_) | SyncOutcome::Diverged { .. }));
+    format!("{} pushed, {} rejected, {} skipped (diverged or mid-operation)", pushed, rejected, skipped)
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("failed").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let mut builder = CommandBuilder::new("git");
+        for arg in args {
+            builder = builder.arg(*arg);
+        }
+        let result = builder.working_dir(dir).execute().unwrap();
+        assert!(result.success, "git {:?}: {}", args, result.stderr);
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        std::fs::write(dir.join(file), file).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["-c", "user.name=Byte", "-c", "user.email=byte@example.com", "commit", "-q", "-m", file]);
+    }
+
+    #[test]
+    fn test_sync_against_bare_remote() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "--bare", "-b", "main", "remote.git"]);
+        git(root, &["clone", "-q", "remote.git", "upstream"]);
+        commit(&root.join("upstream"), "a");
+        git(&root.join("upstream"), &["push", "-q", "-u", "origin", "HEAD:main"]);
+        git(root, &["clone", "-q", "remote.git", "clean"]);
+        git(root, &["clone", "-q", "remote.git", "dirty"]);
+        git(root, &["clone", "-q", "remote.git", "diverged"]);
+        std::fs::create_dir(root.join("plain")).unwrap();
+
+        commit(&root.join("upstream"), "b");
+        git(&root.join("upstream"), &["push", "-q", "origin", "HEAD:main"]);
+        std::fs::write(root.join("dirty/a"), "changed").unwrap();
+        commit(&root.join("diverged"), "c");
+
+        let projects: Vec<(String, String)> = ["clean", "dirty", "diverged", "plain", "upstream"]
+            .iter()
+            .map(|name| (name.to_string(), root.join(name).to_string_lossy().to_string()))
+            .collect();
+
+        let fetched = sync_all(&projects, SyncMode::default());
+        assert_eq!(fetched[0].outcome, SyncOutcome::Behind(1));
+
+        let results = sync_all(&projects, SyncMode { pull: true, push: false });
+        let outcomes: Vec<_> = results.iter().map(|r| r.outcome.clone()).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                SyncOutcome::FastForwarded(1),
+                SyncOutcome::Dirty,
+                SyncOutcome::Diverged { ahead: 1, behind: 1 },
+                SyncOutcome::NotARepo,
+                SyncOutcome::UpToDate,
+            ]
+        );
+        assert_eq!(get_git_status(&projects[0].1).behind, 0);
+        assert!(get_git_status(&projects[0].1).last_fetch.is_some());
+        assert_eq!(
+            summarize(&results),
+            "5 projects: 1 fast-forwarded, 1 dirty, 1 diverged, 1 not a repo, 1 up to date"
+        );
+    }
+
+    #[test]
+    fn test_push_branches_that_are_ahead() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for (remote, seed) in [("remote.git", "seed"), ("locked.git", "seed-locked")] {
+            git(root, &["init", "-q", "--bare", "-b", "main", remote]);
+            git(root, &["clone", "-q", remote, seed]);
+            commit(&root.join(seed), "a");
+            git(&root.join(seed), &["push", "-q", "-u", "origin", "HEAD:main"]);
+        }
+        git(root, &["clone", "-q", "remote.git", "diverged"]);
+        git(root, &["clone", "-q", "locked.git", "rejected"]);
+        let hook = root.join("locked.git/hooks/pre-receive");
+        std::fs::write(&hook, "#!/bin/sh\necho locked >&2\nexit 1\n").unwrap();
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        commit(&root.join("seed"), "b");
+        git(&root.join("seed"), &["push", "-q", "origin", "HEAD:main"]);
+        git(root, &["clone", "-q", "remote.git", "ahead"]);
+        for clone in ["ahead", "diverged", "rejected"] {
+            commit(&root.join(clone), "c");
+        }
+
+        let projects: Vec<(String, String)> = ["ahead", "diverged", "rejected"]
+            .iter()
+            .map(|name| (name.to_string(), root.join(name).to_string_lossy().to_string()))
+            .collect();
+        let results = sync_all(&projects, SyncMode { pull: false, push: true });
+        assert_eq!(results[0].outcome, SyncOutcome::Pushed(1));
+        assert_eq!(results[1].outcome, SyncOutcome::Diverged { ahead: 1, behind: 1 });
+        assert!(matches!(&results[2].outcome, SyncOutcome::PushRejected(e) if e.contains("locked")));
+        assert_eq!(get_git_status(&projects[0].1).ahead, 0);
+        assert_eq!(push_report(&results), "1 pushed, 1 rejected, 1 skipped (diverged or mid-operation)");
+    }
+}
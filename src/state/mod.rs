@@ -1,20 +1,51 @@
 pub mod build;
+pub mod drivers;
 pub mod git;
+pub mod git_diff;
+pub mod git_ops;
+pub mod git_sync;
+pub mod python;
+pub mod test_results;
+pub mod toolchain;
 
 pub use build::{BuildState, BuildStatus};
+pub use drivers::DriverStatus;
 pub use git::GitStatus;
+pub use python::PythonEnv;
+pub use test_results::TestState;
+pub use toolchain::Toolchain;
 
 /// Complete project state including git and build information
 #[derive(Debug, Clone)]
 pub struct ProjectState {
     pub git: GitStatus,
     pub build: Option<BuildState>,
+    /// (errors, warnings) from the latest run of each log category
+    pub problems: (usize, usize),
+    pub tests: Option<TestState>,
+    pub python: Option<PythonEnv>,
+    /// The manifest's toolchain and any other with a pinned version
+    pub toolchains: Vec<Toolchain>,
+    /// Per-driver status of a polyglot project
+    pub drivers: Vec<DriverStatus>,
 }
 
 /// Get the complete state for a project
 pub fn get_project_state(project_path: &str) -> ProjectState {
     let git = git::get_git_status(project_path);
     let build = build::load_build_state(project_path);
+    let tests = test_results::load_test_state(project_path);
+    let python = python::detect_python_env(project_path);
+    let venv = python.as_ref().and_then(|p| p.installed_version(project_path));
+    let toolchains = toolchain::detect_toolchains(project_path, venv.as_deref());
+    let drivers = drivers::load_driver_statuses(project_path, build.as_ref());
+    let problems = if std::path::Path::new(project_path).exists() {
+        crate::fs::ProjectFileSystem::new(project_path)
+            .map(|fs| fs.problem_counts())
+            .unwrap_or_default()
+    } else {
+        (0, 0)
+    };
 
-    ProjectState { git, build }
+    ProjectState { git, build, problems, tests, python, toolchains, drivers }
 }
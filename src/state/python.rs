@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Python environment of a project, read from disk without running the interpreter
+#[derive(Debug, Clone, PartialEq)]
+pub struct PythonEnv {
+    /// "uv", "poetry" or "pip"
+    pub manager: &'static str,
+    /// Virtualenv directory, relative to the project when it lives inside it
+    pub venv: Option<PathBuf>,
+    /// Interpreter version of the virtualenv, or the `.python-version` pin
+    pub version: Option<String>,
+}
+
+impl PythonEnv {
+    /// Version of the virtualenv's interpreter alone, without the
+    /// `.python-version` fallback: what is installed, for comparing with the pin
+    pub fn installed_version(&self, project_path: &str) -> Option<String> {
+        venv_version(&Path::new(project_path).join(self.venv.as_ref()?))
+    }
+}
+
+/// Python environment for projects with a pyproject.toml or requirements.txt
+///
+/// `$VIRTUAL_ENV` wins when it points inside the project; otherwise `.venv`
+/// or `venv` is used.
+pub fn detect_python_env(project_path: &str) -> Option<PythonEnv> {
+    let root = Path::new(project_path);
+    if !root.join("pyproject.toml").exists() && !root.join("requirements.txt").exists() {
+        return None;
+    }
+
+    let active = std::env::var_os("VIRTUAL_ENV")
+        .map(PathBuf::from)
+        .filter(|venv| venv.starts_with(root));
+    let venv = active.or_else(|| {
+        [".venv", "venv"]
+            .iter()
+            .map(|name| root.join(name))
+            .find(|dir| dir.join("pyvenv.cfg").exists())
+    });
+
+    let version = venv
+        .as_deref()
+        .and_then(venv_version)
+        .or_else(|| {
+            fs::read_to_string(root.join(".python-version"))
+                .ok()
+                .and_then(|pin| pin.lines().next().map(|line| line.trim().to_string()))
+                .filter(|pin| !pin.is_empty())
+        });
+
+    Some(PythonEnv {
+        manager: crate::tasks::providers::python_manager(root),
+        venv: venv.map(|dir| dir.strip_prefix(root).map(Path::to_path_buf).unwrap_or(dir)),
+        version,
+    })
+}
+
+/// `version` (venv) or `version_info` (virtualenv, uv) from pyvenv.cfg
+fn venv_version(venv: &Path) -> Option<String> {
+    let cfg = fs::read_to_string(venv.join("pyvenv.cfg")).ok()?;
+    cfg.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        matches!(key.trim(), "version" | "version_info").then(|| value.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_python_env() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+        assert_eq!(detect_python_env(path), None);
+
+        std::fs::write(temp.path().join("pyproject.toml"), "[project]\nname = \"x\"\n").unwrap();
+        std::fs::write(temp.path().join(".python-version"), "3.11\n").unwrap();
+        let env = detect_python_env(path).unwrap();
+        assert_eq!((env.manager, env.venv, env.version.as_deref()), ("pip", None, Some("3.11")));
+
+        std::fs::create_dir(temp.path().join(".venv")).unwrap();
+        std::fs::write(
+            temp.path().join(".venv/pyvenv.cfg"),
+            "home = /usr/bin\ninclude-system-site-packages = false\nversion_info = 3.12.4\n",
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("uv.lock"), "").unwrap();
+        let env = detect_python_env(path).unwrap();
+        assert_eq!(env.manager, "uv");
+        assert_eq!(env.venv, Some(PathBuf::from(".venv")));
+        assert_eq!(env.version.as_deref(), Some("3.12.4"));
+    }
+
+    #[test]
+    fn test_pin_is_not_installed() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+        std::fs::write(temp.path().join("requirements.txt"), "").unwrap();
+        std::fs::write(temp.path().join(".python-version"), "3.11\n").unwrap();
+        assert_eq!(detect_python_env(path).unwrap().installed_version(path), None);
+
+        std::fs::create_dir(temp.path().join("venv")).unwrap();
+        std::fs::write(temp.path().join("venv/pyvenv.cfg"), "version = 3.12.1\n").unwrap();
+        assert_eq!(detect_python_env(path).unwrap().installed_version(path).as_deref(), Some("3.12.1"));
+    }
+}
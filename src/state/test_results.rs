@@ -0,0 +1,168 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::testing::{TestCase, TestOutcome};
+
+/// Outcomes remembered per test for flaky detection
+const HISTORY_LEN: usize = 10;
+
+/// A test is flaky once it has flipped between pass and fail this often
+/// within the remembered history
+const FLAKY_FLIPS: usize = 2;
+
+/// The most recent test run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestRun {
+    pub timestamp: i64, // Unix timestamp
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub tests: Vec<TestCase>,
+}
+
+impl TestRun {
+    pub fn new(command: &str, task: Option<&str>, tests: Vec<TestCase>) -> Self {
+        let (passed, failed, ignored) = crate::testing::counts(&tests);
+        Self {
+            timestamp: chrono::Utc::now().timestamp(),
+            command: command.to_string(),
+            task: task.map(String::from),
+            passed,
+            failed,
+            ignored,
+            tests,
+        }
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &TestCase> {
+        self.tests.iter().filter(|t| t.outcome == TestOutcome::Failed)
+    }
+}
+
+/// Test state tracking (.byte/state/tests.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestState {
+    pub last_run: TestRun,
+    /// Recent outcomes per test id, oldest first
+    #[serde(default)]
+    pub history: BTreeMap<String, Vec<TestOutcome>>,
+}
+
+impl TestState {
+    /// Number of pass/fail flips in a test's remembered history (ignored runs don't count)
+    pub fn flips(&self, id: &str) -> usize {
+        let outcomes: Vec<&TestOutcome> = self
+            .history
+            .get(id)
+            .map(|h| h.iter().filter(|o| **o != TestOutcome::Ignored).collect())
+            .unwrap_or_default();
+        outcomes.windows(2).filter(|pair| pair[0] != pair[1]).count()
+    }
+
+    /// Tests that keep flipping between passing and failing, most flips first
+    pub fn flaky_tests(&self) -> Vec<(&str, usize)> {
+        let mut flaky: Vec<(&str, usize)> = self
+            .history
+            .keys()
+            .map(|id| (id.as_str(), self.flips(id)))
+            .filter(|(_, flips)| *flips >= FLAKY_FLIPS)
+            .collect();
+        flaky.sort_by_key(|(_, flips)| std::cmp::Reverse(*flips));
+        flaky
+    }
+
+    /// Tests whose outcome differs from their previous (non-ignored) run
+    pub fn flipped_in_last_run(&self) -> Vec<String> {
+        self.last_run
+            .tests
+            .iter()
+            .filter(|test| test.outcome != TestOutcome::Ignored)
+            .map(|test| test.id())
+            .filter(|id| {
+                let relevant: Vec<_> = self.history.get(id).into_iter().flatten()
+                    .filter(|o| **o != TestOutcome::Ignored)
+                    .collect();
+                relevant.len() >= 2 && relevant[relevant.len() - 1] != relevant[relevant.len() - 2]
+            })
+            .collect()
+    }
+}
+
+fn state_file(project_path: &str) -> PathBuf {
+    PathBuf::from(project_path).join(".byte/state/tests.json")
+}
+
+/// Load test state from .byte/state/tests.json
+pub fn load_test_state(project_path: &str) -> Option<TestState> {
+    let content = fs::read_to_string(state_file(project_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Save test state to .byte/state/tests.json
+pub fn save_test_state(project_path: &str, state: &TestState) -> Result<()> {
+    let path = state_file(project_path);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// Record a run: replace the last run and extend each test's history
+pub fn record_test_run(project_path: &str, run: TestRun) -> Result<TestState> {
+    let mut history = load_test_state(project_path)
+        .map(|state| state.history)
+        .unwrap_or_default();
+
+    for test in &run.tests {
+        let outcomes = history.entry(test.id()).or_default();
+        outcomes.push(test.outcome);
+        if outcomes.len() > HISTORY_LEN {
+            outcomes.remove(0);
+        }
+    }
+
+    let state = TestState { last_run: run, history };
+    save_test_state(project_path, &state)?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(outcome: TestOutcome) -> TestRun {
+        TestRun::new(
+            "cargo test",
+            Some("test"),
+            vec![
+                TestCase::new("stable", None, TestOutcome::Passed),
+                TestCase::new("racy", None, outcome),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_flaky_detection() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().to_str().unwrap();
+
+        record_test_run(project, run(TestOutcome::Passed)).unwrap();
+        let state = record_test_run(project, run(TestOutcome::Failed)).unwrap();
+        assert_eq!(state.last_run.failed, 1);
+        assert_eq!(state.flipped_in_last_run(), vec!["racy"]);
+        assert!(state.flaky_tests().is_empty());
+
+        record_test_run(project, run(TestOutcome::Ignored)).unwrap();
+        let state = record_test_run(project, run(TestOutcome::Passed)).unwrap();
+        assert_eq!(state.flaky_tests(), vec![("racy", 2)]);
+        assert_eq!(load_test_state(project).unwrap().history["stable"].len(), 4);
+    }
+}
@@ -0,0 +1,385 @@
+//! Installed and required toolchain versions
+//!
+//! Requirements come from the files each ecosystem's version managers read:
+//! rust-toolchain(.toml), the `go`/`toolchain` lines of go.mod, `.nvmrc`,
+//! `.node-version` or package.json `engines`, `.python-version`, and asdf/mise
+//! `.tool-versions`. A dedicated file wins over `.tool-versions`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::exec::CommandBuilder;
+
+/// A toolchain a project uses, with what's installed and what it asks for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toolchain {
+    /// Ecosystem id ("node", "zig", ...)
+    pub ecosystem: &'static str,
+    /// Display name ("Node", "Zig", ...)
+    pub name: &'static str,
+    /// None when the tool isn't on PATH
+    pub version: Option<String>,
+    pub required: Option<Requirement>,
+}
+
+/// A version a project asks for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Requirement {
+    pub spec: String,
+    /// File the requirement was read from
+    pub source: &'static str,
+    pub kind: RequirementKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequirementKind {
+    /// `20` matches any 20.x, `3.12.4` only itself
+    Pin,
+    /// At least this version (go.mod `go` directive)
+    Minimum,
+    /// A semver range (package.json `engines`)
+    Range,
+}
+
+impl Toolchain {
+    /// Whether the installed version meets the requirement; None when there is
+    /// nothing to compare (no requirement, not installed, or a channel name
+    /// like `stable` or `lts/*`)
+    pub fn satisfied(&self) -> Option<bool> {
+        let required = self.required.as_ref()?;
+        required.matches(self.version.as_deref()?)
+    }
+
+    /// Requirement set but not met: not installed, or the wrong version
+    pub fn mismatch(&self) -> bool {
+        self.required.is_some() && (self.version.is_none() || self.satisfied() == Some(false))
+    }
+
+    /// Command that installs the required version
+    pub fn fix(&self) -> Option<String> {
+        let required = self.required.as_ref()?;
+        // A range or minimum installs its lowest version: `>=20 <22` → `20`
+        let spec = match required.kind {
+            RequirementKind::Pin => required.spec.clone(),
+            _ => required
+                .spec
+                .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .find(|word| !word.is_empty())?
+                .to_string(),
+        };
+        let fix = match self.ecosystem {
+            "rust" => format!("rustup toolchain install {}", spec),
+            "go" if required.kind == RequirementKind::Minimum => format!("install Go {} or newer from https://go.dev/dl/", spec),
+            "go" => format!("go install golang.org/dl/go{0}@latest && go{0} download", spec),
+            "node" => format!("nvm install {}", spec),
+            "python" => format!("pyenv install {}", spec),
+            "deno" => format!("deno upgrade --version {}", spec),
+            "bun" => format!("bun upgrade --version {}", spec),
+            "zig" => format!("zigup {}", spec),
+            _ => return None,
+        };
+        Some(fix)
+    }
+}
+
+impl Requirement {
+    fn new(spec: &str, source: &'static str, kind: RequirementKind) -> Option<Requirement> {
+        let spec = spec.trim().trim_matches('"');
+        (!spec.is_empty()).then(|| Requirement {
+            spec: spec.to_string(),
+            source,
+            kind,
+        })
+    }
+
+    /// None when the spec isn't a version (channels, aliases, bad ranges)
+    pub fn matches(&self, installed: &str) -> Option<bool> {
+        let installed_parts = numeric_parts(installed)?;
+        match self.kind {
+            RequirementKind::Pin => {
+                let wanted = numeric_parts(self.spec.trim_start_matches('v'))?;
+                Some(installed_parts.iter().take(wanted.len()).eq(wanted.iter()))
+            }
+            RequirementKind::Minimum => {
+                let wanted = numeric_parts(&self.spec)?;
+                Some(pad(&installed_parts) >= pad(&wanted))
+            }
+            RequirementKind::Range => {
+                let [major, minor, patch] = pad(&installed_parts);
+                let version = semver::Version::new(major, minor, patch);
+                let ranges: Option<Vec<semver::VersionReq>> = self.spec.split("||").map(npm_range).collect();
+                Some(ranges?.iter().any(|range| range.matches(&version)))
+            }
+        }
+    }
+}
+
+/// One `||` alternative of an npm range (`>=18 <21`, `>= 18`) as a semver
+/// requirement, whose comparators are comma-separated
+fn npm_range(alternative: &str) -> Option<semver::VersionReq> {
+    let mut comparators: Vec<String> = Vec::new();
+    for token in alternative.split_whitespace() {
+        match comparators.last_mut() {
+            // `>= 18`: an operator on its own belongs to the next token
+            Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(token),
+            _ => comparators.push(token.to_string()),
+        }
+    }
+    semver::VersionReq::parse(&comparators.join(", ")).ok()
+}
+
+/// `1.22.3` → [1, 22, 3]; a pre-release suffix (`1.82.0-nightly`) is ignored
+fn numeric_parts(version: &str) -> Option<Vec<u64>> {
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+fn pad(parts: &[u64]) -> [u64; 3] {
+    [0, 1, 2].map(|i| parts.get(i).copied().unwrap_or(0))
+}
+
+/// Ecosystem, display name, tool and version argument
+const VERSION_COMMANDS: &[(&str, &str, &str, &str)] = &[
+    ("rust", "Rust", "rustc", "--version"),
+    ("python", "Python", "python3", "--version"),
+    ("go", "Go", "go", "version"),
+    ("bun", "Bun", "bun", "--version"),
+    ("node", "Node", "node", "--version"),
+    ("deno", "Deno", "deno", "--version"),
+    ("zig", "Zig", "zig", "version"),
+];
+
+/// Every ecosystem whose toolchain Byte can check
+pub fn ecosystems() -> impl Iterator<Item = &'static str> {
+    VERSION_COMMANDS.iter().map(|(ecosystem, ..)| *ecosystem)
+}
+
+/// The toolchain of the project's manifest ecosystem, then any other the
+/// project pins a version of
+///
+/// `venv_python` is the version of the project's virtualenv, which is the
+/// Python that matters when there is one.
+pub fn detect_toolchains(project_path: &str, venv_python: Option<&str>) -> Vec<Toolchain> {
+    let root = Path::new(project_path);
+    let requirements = read_requirements(root);
+
+    let mut ecosystems: Vec<&str> = crate::projects::detect::detect_ecosystem(root).map(|(_, e)| e).into_iter().collect();
+    for (ecosystem, _) in &requirements {
+        if !ecosystems.contains(ecosystem) {
+            ecosystems.push(ecosystem);
+        }
+    }
+
+    ecosystems
+        .into_iter()
+        .filter_map(|ecosystem| {
+            let mut toolchain = toolchain_for(ecosystem)?;
+            if ecosystem == "python" && venv_python.is_some() {
+                toolchain.version = venv_python.map(String::from);
+            }
+            toolchain.required = requirements.iter().find(|(e, _)| *e == ecosystem).map(|(_, r)| r.clone());
+            Some(toolchain)
+        })
+        .collect()
+}
+
+/// Toolchain of one ecosystem in a directory, with its requirement
+pub fn toolchain_in(dir: &Path, ecosystem: &str) -> Option<Toolchain> {
+    let mut toolchain = toolchain_for(ecosystem)?;
+    toolchain.required = read_requirements(dir).into_iter().find(|(e, _)| *e == ecosystem).map(|(_, r)| r);
+    Some(toolchain)
+}
+
+/// Installed toolchain of an ecosystem, if Byte knows how to ask for its version
+pub fn toolchain_for(ecosystem: &str) -> Option<Toolchain> {
+    let (ecosystem, name, tool, arg) = VERSION_COMMANDS.iter().find(|(eco, ..)| *eco == ecosystem)?;
+    Some(Toolchain {
+        ecosystem,
+        name,
+        version: installed_version(tool, arg),
+        required: None,
+    })
+}
+
+/// Version requirements found in a directory, at most one per ecosystem
+pub fn read_requirements(dir: &Path) -> Vec<(&'static str, Requirement)> {
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+    let first_line = |content: String| content.lines().map(str::trim).find(|l| !l.is_empty() && !l.starts_with('#')).map(String::from);
+    let mut found: Vec<(&'static str, Requirement)> = Vec::new();
+
+    // rust-toolchain.toml `[toolchain] channel`, or the legacy plain file
+    let rust = read("rust-toolchain.toml")
+        .and_then(|c| c.parse::<toml::Table>().ok())
+        .and_then(|t| t.get("toolchain")?.get("channel")?.as_str().map(String::from))
+        .and_then(|channel| Requirement::new(&channel, "rust-toolchain.toml", RequirementKind::Pin))
+        .or_else(|| {
+            read("rust-toolchain")
+                .and_then(first_line)
+                .and_then(|channel| Requirement::new(&channel, "rust-toolchain", RequirementKind::Pin))
+        });
+    found.extend(rust.map(|r| ("rust", r)));
+
+    // `toolchain go1.22.3` pins exactly; `go 1.22` is a minimum
+    if let Some(go_mod) = read("go.mod") {
+        let directive = |name: &str| go_mod.lines().find_map(|l| l.trim().strip_prefix(name).map(str::trim).map(String::from));
+        let go = directive("toolchain ")
+            .and_then(|t| Requirement::new(t.trim_start_matches("go"), "go.mod toolchain", RequirementKind::Pin))
+            .or_else(|| directive("go ").and_then(|v| Requirement::new(&v, "go.mod", RequirementKind::Minimum)));
+        found.extend(go.map(|r| ("go", r)));
+    }
+
+    let node = read(".nvmrc")
+        .and_then(first_line)
+        .and_then(|v| Requirement::new(&v, ".nvmrc", RequirementKind::Pin))
+        .or_else(|| read(".node-version").and_then(first_line).and_then(|v| Requirement::new(&v, ".node-version", RequirementKind::Pin)))
+        .or_else(|| {
+            read("package.json")
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                .and_then(|v| v.get("engines")?.get("node")?.as_str().map(String::from))
+                .and_then(|range| Requirement::new(&range, "package.json engines", RequirementKind::Range))
+        });
+    found.extend(node.map(|r| ("node", r)));
+
+    let python = read(".python-version").and_then(first_line).and_then(|v| Requirement::new(&v, ".python-version", RequirementKind::Pin));
+    found.extend(python.map(|r| ("python", r)));
+
+    // asdf/mise: `nodejs 20.11.0`, one tool per line
+    for line in read(".tool-versions").unwrap_or_default().lines() {
+        let mut words = line.split_whitespace();
+        let (Some(tool), Some(version)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let ecosystem = match tool {
+            "nodejs" | "node" => "node",
+            "golang" | "go" => "go",
+            "python" => "python",
+            "rust" => "rust",
+            "deno" => "deno",
+            "bun" => "bun",
+            "zig" => "zig",
+            _ => continue,
+        };
+        if !found.iter().any(|(e, _)| *e == ecosystem)
+            && let Some(requirement) = Requirement::new(version, ".tool-versions", RequirementKind::Pin)
+        {
+            found.push((ecosystem, requirement));
+        }
+    }
+
+    found
+}
+
+/// `<tool> <arg>` output, parsed once per process; states are refreshed for
+/// every project, and the installed version doesn't change under a running TUI
+fn installed_version(tool: &'static str, arg: &str) -> Option<String> {
+    static CACHE: OnceLock<Mutex<HashMap<&'static str, Option<String>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(version) = cache.lock().ok()?.get(tool) {
+        return version.clone();
+    }
+
+    let version = CommandBuilder::new(tool)
+        .arg(arg)
+        .execute()
+        .ok()
+        .filter(|result| result.success)
+        .and_then(|result| parse_version(&result.stdout));
+    cache.lock().ok()?.insert(tool, version.clone());
+    version
+}
+
+/// Real path of the interpreter `python3` runs, asked of the interpreter so
+/// version manager shims resolve too; once per process, like versions
+pub fn python_interpreter() -> Option<PathBuf> {
+    static INTERPRETER: OnceLock<Option<PathBuf>> = OnceLock::new();
+    INTERPRETER
+        .get_or_init(|| {
+            CommandBuilder::new("python3")
+                .arg("-c")
+                .arg("import os, sys; print(os.path.realpath(sys.executable))")
+                .execute()
+                .ok()
+                .filter(|result| result.success)
+                .map(|result| PathBuf::from(result.stdout.trim()))
+                .filter(|path| path.is_absolute())
+        })
+        .clone()
+}
+
+/// First version-looking word of the first line: `rustc 1.80.0 (…)`,
+/// `go version go1.22.1 linux/amd64`, `v20.11.0`, `deno 1.44.0 (…)`
+fn parse_version(output: &str) -> Option<String> {
+    output.lines().next()?.split_whitespace().find_map(|word| {
+        let version = word.strip_prefix("go").unwrap_or(word).trim_start_matches('v');
+        version.starts_with(|c: char| c.is_ascii_digit()).then(|| version.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("rustc 1.80.0 (051478957 2024-07-21)\n").as_deref(), Some("1.80.0"));
+        assert_eq!(parse_version("go version go1.22.1 linux/amd64\n").as_deref(), Some("1.22.1"));
+        assert_eq!(parse_version("v20.11.0\n").as_deref(), Some("20.11.0"));
+        assert_eq!(parse_version("deno 1.44.0 (release, x86_64-unknown-linux-gnu)\nv8 12.6\n").as_deref(), Some("1.44.0"));
+        assert_eq!(parse_version("0.13.0\n").as_deref(), Some("0.13.0"));
+        assert_eq!(parse_version(""), None);
+    }
+
+    #[test]
+    fn test_requirement_matching() {
+        let pin = |spec: &str| Requirement::new(spec, "test", RequirementKind::Pin).unwrap();
+        assert_eq!(pin("20").matches("20.11.0"), Some(true));
+        assert_eq!(pin("v18.19.0").matches("20.11.0"), Some(false));
+        assert_eq!(pin("1.80.0").matches("1.82.0-nightly"), Some(false));
+        assert_eq!(pin("stable").matches("1.80.0"), None);
+        assert_eq!(pin("lts/*").matches("20.11.0"), None);
+
+        let minimum = Requirement::new("1.22", "go.mod", RequirementKind::Minimum).unwrap();
+        assert_eq!(minimum.matches("1.22.1"), Some(true));
+        assert_eq!(minimum.matches("1.21.9"), Some(false));
+
+        let range = Requirement::new(">=18 <21", "package.json engines", RequirementKind::Range).unwrap();
+        assert_eq!(range.matches("20.11.0"), Some(true));
+        assert_eq!(range.matches("22.1.0"), Some(false));
+        let either = Requirement::new("^18 || >= 20", "package.json engines", RequirementKind::Range).unwrap();
+        assert_eq!(either.matches("19.0.0"), Some(false));
+        assert_eq!(either.matches("22.1.0"), Some(true));
+    }
+
+    #[test]
+    fn test_read_requirements() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("rust-toolchain.toml"), "[toolchain]\nchannel = \"1.79.0\"\n").unwrap();
+        std::fs::write(dir.join("go.mod"), "module x\n\ngo 1.22\n\ntoolchain go1.22.3\n").unwrap();
+        std::fs::write(dir.join("package.json"), r#"{"engines": {"node": ">=20"}}"#).unwrap();
+        std::fs::write(dir.join(".tool-versions"), "nodejs 18.19.0\npython 3.12.1\nterraform 1.7.0\n").unwrap();
+
+        let found = read_requirements(dir);
+        let specs: Vec<(&str, &str, &str)> = found.iter().map(|(e, r)| (*e, r.spec.as_str(), r.source)).collect();
+        assert_eq!(
+            specs,
+            vec![
+                ("rust", "1.79.0", "rust-toolchain.toml"),
+                ("go", "1.22.3", "go.mod toolchain"),
+                ("node", ">=20", "package.json engines"),
+                ("python", "3.12.1", ".tool-versions"),
+            ]
+        );
+
+        let node = Toolchain {
+            ecosystem: "node",
+            name: "Node",
+            version: Some("18.19.0".to_string()),
+            required: Some(found[2].1.clone()),
+        };
+        assert!(node.mismatch());
+        assert_eq!(node.fix().as_deref(), Some("nvm install 20"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Tasks imported from the project's own tooling
+//!
+//! Providers read `package.json` scripts, `.cargo/config.toml` aliases,
+//! Makefile targets, justfile recipes and `Taskfile.yml` tasks so they show up
+//! next to byte.toml tasks without being mirrored by hand. byte.toml wins: a
+//! `[build]` or `[commands]` entry with the same name replaces an imported
+//! task, and `[tasks] hide` removes imported tasks from the list.
+
+pub mod providers;
+
+use std::path::Path;
+
+use crate::config::types::ProjectConfig;
+
+/// Where an imported task comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSource {
+    /// package.json `scripts`, run with the project's package manager
+    Package(&'static str),
+    /// `.cargo/config.toml` `[alias]`
+    CargoAlias,
+    Make,
+    Just,
+    /// Taskfile.yml (go-task)
+    Taskfile,
+}
+
+impl TaskSource {
+    /// Provenance badge shown next to the task, also used in `[tasks] hide`
+    pub fn badge(&self) -> &'static str {
+        match self {
+            TaskSource::Package(runner) => runner,
+            TaskSource::CargoAlias => "cargo",
+            TaskSource::Make => "make",
+            TaskSource::Just => "just",
+            TaskSource::Taskfile => "task",
+        }
+    }
+}
+
+/// A task found in one of the project's tool configs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTask {
+    pub name: String,
+    /// Shell command that runs the task
+    pub command: String,
+    /// Doc comment, or what the task expands to
+    pub description: Option<String>,
+    pub source: TaskSource,
+}
+
+/// Every file a provider reads tasks from, relative to the project root
+pub fn source_files() -> impl Iterator<Item = &'static str> {
+    [
+        providers::PACKAGE_FILES,
+        providers::CARGO_CONFIG_FILES,
+        providers::MAKEFILES,
+        providers::JUSTFILES,
+        providers::TASKFILES,
+    ]
+    .into_iter()
+    .flatten()
+    .copied()
+}
+
+/// Every imported task of a project, minus the ones byte.toml overrides or hides
+pub fn imported_tasks(project_path: &Path, config: Option<&ProjectConfig>) -> Vec<ImportedTask> {
+    let mut tasks = providers::package_scripts(project_path);
+    tasks.extend(providers::cargo_aliases(project_path));
+    tasks.extend(providers::make_targets(project_path));
+    tasks.extend(providers::just_recipes(project_path));
+    tasks.extend(providers::taskfile_tasks(project_path));
+
+    let Some(config) = config else {
+        return tasks;
+    };
+    let defined = |name: &str| {
+        [&config.build, &config.commands]
+            .iter()
+            .any(|section| section.as_ref().is_some_and(|tasks| tasks.contains_key(name)))
+    };
+    let hide = config.tasks.as_ref().map(|t| t.hide.as_slice()).unwrap_or_default();
+
+    tasks.retain(|task| !defined(&task.name) && !hide.iter().any(|pattern| is_hidden(task, pattern)));
+    tasks
+}
+
+/// `[tasks] hide` entries: "name" (any source), "make:name" or "make:*"
+fn is_hidden(task: &ImportedTask, pattern: &str) -> bool {
+    match pattern.split_once(':') {
+        Some((source, name)) => source == task.source.badge() && (name == "*" || name == task.name),
+        None => pattern == task.name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_byte_toml_overrides_and_hides() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"scripts": {"build": "vite build", "dev": "vite", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("Makefile"), "deploy: ## Ship it\n\t./deploy.sh\nclean:\n\trm -rf dist\n").unwrap();
+
+        let names = |config: Option<&ProjectConfig>| -> Vec<String> {
+            imported_tasks(temp.path(), config)
+                .iter()
+                .map(|t| format!("{}:{}", t.source.badge(), t.name))
+                .collect()
+        };
+        assert_eq!(names(None), vec!["npm:build", "npm:dev", "npm:lint", "make:deploy", "make:clean"]);
+
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"web\"\ntype = \"web\"\necosystem = \"node\"\n\n\
+             [build]\nbuild = \"bun build src/index.ts\"\n\n\
+             [tasks]\nhide = [\"lint\", \"make:clean\"]\n",
+        )
+        .unwrap();
+        assert_eq!(names(Some(&config)), vec!["npm:dev", "make:deploy"]);
+
+        let config: ProjectConfig = toml::from_str(
+            "[project]\nname = \"web\"\ntype = \"web\"\necosystem = \"node\"\n\n[tasks]\nhide = [\"npm:*\"]\n",
+        )
+        .unwrap();
+        assert_eq!(names(Some(&config)), vec!["make:deploy", "make:clean"]);
+    }
+}
@@ -0,0 +1,342 @@
+//! One parser per tool config
+//!
+//! Each provider returns an empty list when its file is missing or can't be
+//! read; a broken Makefile shouldn't hide the project's other tasks.
+
+use std::path::Path;
+
+use super::{ImportedTask, TaskSource};
+
+pub const PACKAGE_FILES: &[&str] = &["package.json"];
+pub const CARGO_CONFIG_FILES: &[&str] = &[".cargo/config.toml", ".cargo/config"];
+pub const MAKEFILES: &[&str] = &["Makefile", "makefile", "GNUmakefile"];
+pub const JUSTFILES: &[&str] = &["justfile", "Justfile", ".justfile"];
+pub const TASKFILES: &[&str] = &["Taskfile.yml", "Taskfile.yaml", "taskfile.yml", "taskfile.yaml"];
+
+fn read(project_path: &Path, files: &[&str]) -> Option<String> {
+    files.iter().find_map(|file| std::fs::read_to_string(project_path.join(file)).ok())
+}
+
+fn task(name: &str, command: String, description: Option<String>, source: TaskSource) -> ImportedTask {
+    ImportedTask {
+        name: name.to_string(),
+        command,
+        description: description.filter(|d| !d.is_empty()),
+        source,
+    }
+}
+
+/// Package manager a JavaScript project uses, from its lockfile
+pub fn package_runner(project_path: &Path) -> &'static str {
+    const LOCKFILES: &[(&str, &str)] = &[
+        ("bun.lockb", "bun"),
+        ("bun.lock", "bun"),
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+    ];
+    LOCKFILES
+        .iter()
+        .find(|(file, _)| project_path.join(file).exists())
+        .map(|(_, runner)| *runner)
+        .unwrap_or("npm")
+}
+
+/// Python package manager a project uses, from its lockfile: "uv", "poetry"
+/// or "pip" (a plain virtualenv)
+pub fn python_manager(project_path: &Path) -> &'static str {
+    if project_path.join("uv.lock").exists() {
+        "uv"
+    } else if project_path.join("poetry.lock").exists() {
+        "poetry"
+    } else {
+        "pip"
+    }
+}
+
+/// package.json `scripts`, described by the script body
+pub fn package_scripts(project_path: &Path) -> Vec<ImportedTask> {
+    let Some(value) = read(project_path, PACKAGE_FILES)
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(scripts) = value.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+
+    let runner = package_runner(project_path);
+    scripts
+        .iter()
+        .map(|(name, body)| {
+            task(
+                name,
+                format!("{} run {}", runner, name),
+                body.as_str().map(String::from),
+                TaskSource::Package(runner),
+            )
+        })
+        .collect()
+}
+
+/// `.cargo/config.toml` `[alias]` entries, described by their expansion
+pub fn cargo_aliases(project_path: &Path) -> Vec<ImportedTask> {
+    let Some(table) = read(project_path, CARGO_CONFIG_FILES)
+        .and_then(|content| content.parse::<toml::Table>().ok())
+    else {
+        return Vec::new();
+    };
+    let Some(aliases) = table.get("alias").and_then(|a| a.as_table()) else {
+        return Vec::new();
+    };
+
+    aliases
+        .iter()
+        .map(|(name, expansion)| {
+            let expansion = match expansion {
+                toml::Value::Array(args) => args.iter().filter_map(|a| a.as_str()).collect::<Vec<_>>().join(" "),
+                other => other.as_str().unwrap_or_default().to_string(),
+            };
+            task(name, format!("cargo {}", name), Some(format!("cargo {}", expansion)), TaskSource::CargoAlias)
+        })
+        .collect()
+}
+
+/// Makefile targets, documented with `target: ## Help text` or a `## Help
+/// text` line right above the target
+///
+/// Special (`.PHONY`), pattern (`%.o`) and variable-named targets are skipped.
+pub fn make_targets(project_path: &Path) -> Vec<ImportedTask> {
+    let Some(content) = read(project_path, MAKEFILES) else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<ImportedTask> = Vec::new();
+    let mut doc: Option<String> = None;
+    for line in content.lines() {
+        if let Some(comment) = line.strip_prefix("##") {
+            doc = Some(comment.trim().to_string());
+            continue;
+        }
+        let line_doc = doc.take();
+        if line.starts_with([' ', '\t', '#']) {
+            continue;
+        }
+
+        let (rule, comment) = match line.split_once("##") {
+            Some((rule, comment)) => (rule, Some(comment.trim().to_string())),
+            None => (line, line_doc),
+        };
+        // `a b: deps` defines two targets; `X := y` and `X ::= y` are variables
+        let Some((targets, rest)) = rule.split_once(':') else { continue };
+        if rest.starts_with('=') || rest.starts_with(":=") || targets.contains('=') {
+            continue;
+        }
+        for target in targets.split_whitespace() {
+            let skip = target.starts_with('.') || target.contains(['%', '$', '/']) || tasks.iter().any(|t| t.name == target);
+            if !skip {
+                tasks.push(task(target, format!("make {}", target), comment.clone(), TaskSource::Make));
+            }
+        }
+    }
+    tasks
+}
+
+/// justfile recipes, documented by the `# comment` line above them
+///
+/// Private recipes (`_name` or `[private]`) are skipped.
+pub fn just_recipes(project_path: &Path) -> Vec<ImportedTask> {
+    let Some(content) = read(project_path, JUSTFILES) else {
+        return Vec::new();
+    };
+
+    let mut tasks = Vec::new();
+    let mut doc: Option<String> = None;
+    let mut private = false;
+    for line in content.lines() {
+        if let Some(comment) = line.strip_prefix('#') {
+            doc = Some(comment.trim().to_string());
+            continue;
+        }
+        if line.starts_with('[') {
+            private |= line.contains("private");
+            continue;
+        }
+        let (line_doc, line_private) = (doc.take(), std::mem::take(&mut private));
+        if line.starts_with([' ', '\t']) || line.trim().is_empty() {
+            continue;
+        }
+
+        // `name arg='x': deps`, but not `x := y`, `set ...`, `alias b := build`
+        let Some((head, _)) = line.split_once(':') else { continue };
+        if line.contains(":=") {
+            continue;
+        }
+        let name = head.split_whitespace().next().unwrap_or_default().trim_start_matches('@');
+        let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if valid && !name.starts_with('_') && !line_private {
+            tasks.push(task(name, format!("just {}", name), line_doc, TaskSource::Just));
+        }
+    }
+    tasks
+}
+
+/// Taskfile.yml `tasks`, described by their `desc`
+///
+/// A small line-based reader: task names are the keys one level below
+/// `tasks:`, and `desc:` is picked up from the lines under each task.
+pub fn taskfile_tasks(project_path: &Path) -> Vec<ImportedTask> {
+    let Some(content) = read(project_path, TASKFILES) else {
+        return Vec::new();
+    };
+
+    let mut tasks: Vec<ImportedTask> = Vec::new();
+    let mut in_tasks = false;
+    let mut task_indent: Option<usize> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if indent == 0 {
+            in_tasks = trimmed == "tasks:";
+            task_indent = None;
+            continue;
+        }
+        if !in_tasks {
+            continue;
+        }
+
+        let indent_of_tasks = *task_indent.get_or_insert(indent);
+        if indent == indent_of_tasks {
+            if let Some(name) = trimmed.strip_suffix(':').or_else(|| trimmed.split_once(": ").map(|(k, _)| k)) {
+                let name = name.trim_matches(['"', '\'']);
+                tasks.push(task(name, format!("task {}", name), None, TaskSource::Taskfile));
+            }
+        } else if indent > indent_of_tasks
+            && let Some(desc) = trimmed.strip_prefix("desc:")
+            && let Some(last) = tasks.last_mut()
+            && last.description.is_none()
+        {
+            let desc = desc.trim().trim_matches(['"', '\'']);
+            last.description = (!desc.is_empty()).then(|| desc.to_string());
+        }
+    }
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn summary(tasks: &[ImportedTask]) -> Vec<(&str, &str, Option<&str>)> {
+        tasks
+            .iter()
+            .map(|t| (t.name.as_str(), t.command.as_str(), t.description.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_package_scripts_use_lockfile_runner() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("package.json"), r#"{"scripts": {"test": "vitest run"}}"#).unwrap();
+        assert_eq!(summary(&package_scripts(temp.path())), vec![("test", "npm run test", Some("vitest run"))]);
+
+        std::fs::write(temp.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(package_scripts(temp.path())[0].command, "pnpm run test");
+        assert_eq!(package_scripts(temp.path())[0].source.badge(), "pnpm");
+    }
+
+    #[test]
+    fn test_python_manager_from_lockfile() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(python_manager(temp.path()), "pip");
+        std::fs::write(temp.path().join("poetry.lock"), "").unwrap();
+        assert_eq!(python_manager(temp.path()), "poetry");
+        std::fs::write(temp.path().join("uv.lock"), "").unwrap();
+        assert_eq!(python_manager(temp.path()), "uv");
+    }
+
+    #[test]
+    fn test_cargo_aliases() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp.path().join(".cargo/config.toml"),
+            "[alias]\nxtask = \"run --package xtask --\"\nci = [\"clippy\", \"--all-targets\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&cargo_aliases(temp.path())),
+            vec![
+                ("ci", "cargo ci", Some("cargo clippy --all-targets")),
+                ("xtask", "cargo xtask", Some("cargo run --package xtask --")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_make_targets() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("Makefile"),
+            "CC := gcc\nVERSION ?= 1.0\n.PHONY: build test\n\n\
+             build: main.o ## Compile the binary\n\t$(CC) -o app main.o\n\n\
+             ## Run the test suite\ntest: build\n\t./app --test\n\n\
+             %.o: %.c\n\t$(CC) -c $<\n\n\
+             fmt lint:\n\techo $@\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&make_targets(temp.path())),
+            vec![
+                ("build", "make build", Some("Compile the binary")),
+                ("test", "make test", Some("Run the test suite")),
+                ("fmt", "make fmt", None),
+                ("lint", "make lint", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_just_recipes() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("justfile"),
+            "set shell := [\"bash\", \"-c\"]\nalias b := build\nversion := \"1.0\"\n\n\
+             # Build everything\nbuild target='debug':\n    cargo build\n\n\
+             @serve port: build\n    ./serve {{port}}\n\n\
+             _helper:\n    echo hidden\n\n\
+             [private]\nsecret:\n    echo hidden\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&just_recipes(temp.path())),
+            vec![("build", "just build", Some("Build everything")), ("serve", "just serve", None)]
+        );
+    }
+
+    #[test]
+    fn test_taskfile_tasks() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("Taskfile.yml"),
+            "version: '3'\n\nvars:\n  NAME: app\n\ntasks:\n  build:\n    desc: Build the app\n    cmds:\n      - go build\n\n  \"test\":\n    cmds:\n      - go test ./...\n  lint: golangci-lint run\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&taskfile_tasks(temp.path())),
+            vec![
+                ("build", "task build", Some("Build the app")),
+                ("test", "task test", None),
+                ("lint", "task lint", None),
+            ]
+        );
+    }
+}
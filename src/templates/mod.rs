@@ -0,0 +1,415 @@
+//! User-defined project templates
+//!
+//! A template is a directory with a `template.toml` manifest next to the files
+//! it creates:
+//!
+//! ```toml
+//! [template]
+//! description = "Internal Go service skeleton"
+//! ecosystem = "go"          # for the generated byte.toml, if the template has none
+//! type = "api"
+//!
+//! [[variables]]
+//! name = "module"
+//! prompt = "Go module path"
+//! default = "github.com/acme/{{name}}"
+//!
+//! [[variables]]
+//! name = "docker"
+//! prompt = "Include a Dockerfile?"
+//! kind = "bool"
+//! default = "true"
+//!
+//! [[files]]
+//! path = "Dockerfile"       # a file or a whole directory
+//! when = "docker"           # "var", "!var", "var == value" or "var != value"
+//!
+//! [hooks]
+//! post_create = ["go mod tidy"]
+//! ```
+//!
+//! `{{variable}}` is replaced in file names, file contents and hooks; `name`
+//! (the project name) and `year` are always defined. Templates are found in
+//! `~/.config/byte/templates/<name>/` and `<workspace>/.byte/templates/<name>/`
+//! (workspace templates shadow global ones), or given by path or git URL.
+
+pub mod render;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::GlobalConfig;
+use crate::exec::CommandBuilder;
+
+pub use render::{hook_command, render, substitute};
+
+/// Variable values by name
+pub type Values = HashMap<String, String>;
+
+/// Manifest file at the root of a template directory
+pub const MANIFEST: &str = "template.toml";
+
+/// Parsed `template.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateManifest {
+    #[serde(default)]
+    pub template: TemplateMeta,
+    #[serde(default)]
+    pub variables: Vec<Variable>,
+    #[serde(default)]
+    pub files: Vec<ConditionalFile>,
+    #[serde(default)]
+    pub hooks: Hooks,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateMeta {
+    /// Defaults to the directory name
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub ecosystem: Option<String>,
+    #[serde(rename = "type")]
+    pub project_type: Option<String>,
+}
+
+/// A value asked for when the template is used
+#[derive(Debug, Clone, Deserialize)]
+pub struct Variable {
+    pub name: String,
+    /// Question shown to the user; defaults to the variable name
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub kind: VariableKind,
+    /// Default value; may reference earlier variables
+    #[serde(default)]
+    pub default: String,
+    /// Allowed values for `kind = "choice"`
+    #[serde(default)]
+    pub choices: Vec<String>,
+    /// Refuse an empty value
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl Variable {
+    pub fn prompt(&self) -> &str {
+        self.prompt.as_deref().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableKind {
+    #[default]
+    Text,
+    /// "true" or "false"
+    Bool,
+    /// One of `choices`
+    Choice,
+}
+
+/// A file or directory that is only created when its condition holds
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConditionalFile {
+    /// Path relative to the template root, before substitution
+    pub path: String,
+    pub when: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Hooks {
+    /// Shell commands run in the new project, in order
+    #[serde(default)]
+    pub post_create: Vec<String>,
+}
+
+/// Where a template was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    Global,
+    Workspace(PathBuf),
+    Path,
+    /// Cloned into a temporary directory, removed by [`Template::cleanup`]
+    Git(String),
+}
+
+/// A template directory and its manifest
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub name: String,
+    pub root: PathBuf,
+    pub manifest: TemplateManifest,
+    pub source: TemplateSource,
+}
+
+impl Template {
+    /// Load the template in `root`
+    pub fn load(root: &Path, source: TemplateSource) -> Result<Template> {
+        let manifest_path = root.join(MANIFEST);
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("{} has no {}", root.display(), MANIFEST))?;
+        let manifest: TemplateManifest = toml::from_str(&content)
+            .with_context(|| format!("Invalid {}", manifest_path.display()))?;
+
+        for variable in &manifest.variables {
+            if variable.kind == VariableKind::Choice && variable.choices.is_empty() {
+                bail!("Template variable '{}' is a choice without choices", variable.name);
+            }
+        }
+
+        let name = manifest.template.name.clone().unwrap_or_else(|| {
+            root.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "template".to_string())
+        });
+        Ok(Template { name, root: root.to_path_buf(), manifest, source })
+    }
+
+    pub fn description(&self) -> &str {
+        self.manifest.template.description.as_deref().unwrap_or("")
+    }
+
+    /// Values before any user input: built-ins plus each variable's default
+    pub fn default_values(&self, project_name: &str) -> Values {
+        let mut values = builtin_values(project_name);
+        for variable in &self.manifest.variables {
+            let default = substitute(&variable.default, &values);
+            values.insert(variable.name.clone(), default);
+        }
+        values
+    }
+
+    /// Final values: `provided` where given, defaults elsewhere, checked
+    /// against each variable's kind
+    pub fn resolve_values(&self, project_name: &str, provided: &Values) -> Result<Values> {
+        let mut values = builtin_values(project_name);
+        for variable in &self.manifest.variables {
+            let value = match provided.get(&variable.name) {
+                Some(value) => value.trim().to_string(),
+                None => substitute(&variable.default, &values),
+            };
+            let value = match variable.kind {
+                VariableKind::Text => value,
+                VariableKind::Bool => match value.to_lowercase().as_str() {
+                    "true" | "yes" | "y" | "1" => "true".to_string(),
+                    "false" | "no" | "n" | "0" | "" => "false".to_string(),
+                    _ => bail!("'{}' must be true or false, got '{}'", variable.name, value),
+                },
+                VariableKind::Choice => {
+                    if !value.is_empty() && !variable.choices.contains(&value) {
+                        bail!("'{}' must be one of {}, got '{}'", variable.name, variable.choices.join(", "), value);
+                    }
+                    value
+                }
+            };
+            if variable.required && value.is_empty() {
+                bail!("'{}' is required", variable.name);
+            }
+            values.insert(variable.name.clone(), value);
+        }
+        Ok(values)
+    }
+
+    /// Remove the temporary clone of a git template
+    pub fn cleanup(&self) {
+        if matches!(self.source, TemplateSource::Git(_)) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+}
+
+/// Variables every template can use
+fn builtin_values(project_name: &str) -> Values {
+    let mut values = Values::new();
+    values.insert("name".to_string(), project_name.to_string());
+    values.insert("year".to_string(), chrono::Local::now().format("%Y").to_string());
+    values
+}
+
+/// Directories searched for templates, most specific first
+pub fn template_dirs(global: &GlobalConfig) -> Vec<(PathBuf, TemplateSource)> {
+    let mut dirs = Vec::new();
+    for workspace in std::iter::once(&global.workspace.path).chain(&global.workspace.registered) {
+        if let Ok(path) = crate::path::SafePath::from_user_input(workspace) {
+            let workspace = path.expanded().to_path_buf();
+            dirs.push((workspace.join(".byte/templates"), TemplateSource::Workspace(workspace)));
+        }
+    }
+    if let Some(home) = dirs::home_dir() {
+        dirs.push((home.join(".config/byte/templates"), TemplateSource::Global));
+    }
+    dirs
+}
+
+/// Every template in the global and workspace template directories
+///
+/// Templates that fail to load are logged and skipped.
+pub fn discover_templates(global: &GlobalConfig) -> Vec<Template> {
+    let mut templates: Vec<Template> = Vec::new();
+    for (dir, source) in template_dirs(global) {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        let mut roots: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.join(MANIFEST).is_file())
+            .collect();
+        roots.sort();
+
+        for root in roots {
+            match Template::load(&root, source.clone()) {
+                Ok(template) if templates.iter().any(|t| t.name == template.name) => {}
+                Ok(template) => templates.push(template),
+                Err(e) => crate::log::error("TEMPLATES", &format!("{:#}", e)),
+            }
+        }
+    }
+    templates
+}
+
+/// Find a template by name, directory path or git URL
+pub fn resolve_template(global: &GlobalConfig, spec: &str) -> Result<Template> {
+    if is_git_url(spec) {
+        return clone_template(spec);
+    }
+
+    let path = crate::path::SafePath::from_user_input(spec)?;
+    if path.expanded().join(MANIFEST).is_file() {
+        return Template::load(path.expanded(), TemplateSource::Path);
+    }
+
+    let mut templates = discover_templates(global);
+    if let Some(index) = templates.iter().position(|t| t.name == spec) {
+        return Ok(templates.swap_remove(index));
+    }
+    if templates.is_empty() {
+        bail!("No template named '{}' (no templates in ~/.config/byte/templates or <workspace>/.byte/templates)", spec);
+    }
+    let available: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+    bail!("No template named '{}'. Available: {}", spec, available.join(", "))
+}
+
+fn is_git_url(spec: &str) -> bool {
+    ["https://", "http://", "git@", "ssh://", "git://", "file://"]
+        .iter()
+        .any(|prefix| spec.starts_with(prefix))
+        || spec.ends_with(".git")
+}
+
+/// Shallow-clone a template repository into a temporary directory
+fn clone_template(url: &str) -> Result<Template> {
+    let dir = std::env::temp_dir().join(format!(
+        "byte-template-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let result = CommandBuilder::git("clone")
+        .arg("--depth")
+        .arg("1")
+        .arg("--quiet")
+        .arg(url)
+        .arg(dir.to_string_lossy())
+        .execute()?;
+    if !result.success {
+        bail!("Failed to clone {}: {}", url, result.stderr.trim());
+    }
+
+    let template = Template::load(&dir, TemplateSource::Git(url.to_string()));
+    if template.is_err() {
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    template
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    pub(crate) fn write_template(root: &Path) {
+        let files: &[(&str, &str)] = &[
+            (
+                MANIFEST,
+                "[template]\nname = \"service\"\necosystem = \"go\"\ntype = \"api\"\n\n\
+                 [[variables]]\nname = \"module\"\ndefault = \"example.com/{{name}}\"\n\n\
+                 [[variables]]\nname = \"docker\"\nkind = \"bool\"\ndefault = \"false\"\n\n\
+                 [[variables]]\nname = \"db\"\nkind = \"choice\"\nchoices = [\"postgres\", \"sqlite\"]\ndefault = \"sqlite\"\n\n\
+                 [[files]]\npath = \"Dockerfile\"\nwhen = \"docker\"\n\n\
+                 [[files]]\npath = \"migrations\"\nwhen = \"db == postgres\"\n\n\
+                 [hooks]\npost_create = [\"echo {{module}} > hook.txt\"]\n",
+            ),
+            ("go.mod", "module {{module}}\n\ngo 1.22\n"),
+            ("cmd/{{name}}/main.go", "package main // {{ name }} uses {{db}}\n"),
+            ("Dockerfile", "FROM golang\n"),
+            ("migrations/001.sql", "create table t ();\n"),
+        ];
+        for (path, content) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resolve_values() {
+        let temp = TempDir::new().unwrap();
+        write_template(temp.path());
+        let template = Template::load(temp.path(), TemplateSource::Path).unwrap();
+        assert_eq!(template.name, "service");
+
+        let values = template.default_values("api");
+        assert_eq!(values["module"], "example.com/api");
+        assert_eq!(values["docker"], "false");
+
+        let mut provided = Values::new();
+        provided.insert("docker".to_string(), "yes".to_string());
+        assert_eq!(template.resolve_values("api", &provided).unwrap()["docker"], "true");
+
+        provided.insert("db".to_string(), "mysql".to_string());
+        assert!(template.resolve_values("api", &provided).is_err());
+    }
+
+    #[test]
+    fn test_discover_and_resolve_by_name() {
+        let temp = TempDir::new().unwrap();
+        let workspace = temp.path().join("ws");
+        write_template(&workspace.join(".byte/templates/service"));
+        std::fs::create_dir_all(workspace.join(".byte/templates/not-a-template")).unwrap();
+
+        let mut global = GlobalConfig::default();
+        global.workspace.path = workspace.to_string_lossy().to_string();
+
+        let templates = discover_templates(&global);
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert!(names.contains(&"service"));
+        assert!(!names.contains(&"not-a-template"));
+        assert_eq!(resolve_template(&global, "service").unwrap().source, TemplateSource::Workspace(workspace.clone()));
+        assert!(resolve_template(&global, "missing").is_err());
+
+        let by_path = workspace.join(".byte/templates/service");
+        assert_eq!(resolve_template(&global, &by_path.to_string_lossy()).unwrap().source, TemplateSource::Path);
+    }
+
+    #[test]
+    fn test_resolve_git_url() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("template-repo");
+        write_template(&repo);
+        let git = |args: &[&str]| {
+            let mut builder = CommandBuilder::new("git");
+            for arg in args {
+                builder = builder.arg(*arg);
+            }
+            assert!(builder.working_dir(&repo).execute().unwrap().success);
+        };
+        git(&["init", "-q"]);
+        git(&["add", "."]);
+        git(&["-c", "user.name=Byte", "-c", "user.email=byte@example.com", "commit", "-q", "-m", "Template"]);
+
+        let url = format!("file://{}", repo.display());
+        let template = resolve_template(&GlobalConfig::default(), &url).unwrap();
+        assert_eq!(template.source, TemplateSource::Git(url));
+        assert!(template.root.join("go.mod").is_file());
+        template.cleanup();
+        assert!(!template.root.exists());
+    }
+}
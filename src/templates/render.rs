@@ -0,0 +1,240 @@
+//! Variable substitution, file conditions and copying a template into place
+
+use anyhow::{bail, Context, Result};
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{Template, Values, MANIFEST};
+
+/// Replace `{{name}}` (spaces inside the braces allowed) with its value
+///
+/// Unknown names are left as they are, so files that use `{{ }}` for their
+/// own templating survive.
+pub fn substitute(text: &str, values: &Values) -> String {
+    substitute_with(text, values, |value| value.to_string())
+}
+
+/// A post-create hook with its values substituted shell-quoted, so a value
+/// (a project name, a module path) is one word and can't add commands of its own
+pub fn hook_command(hook: &str, values: &Values) -> String {
+    substitute_with(hook, values, |value| shell_words::quote(value).into_owned())
+}
+
+fn substitute_with(text: &str, values: &Values, value_text: impl Fn(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => match values.get(after[..end].trim()) {
+                Some(value) => {
+                    out.push_str(&value_text(value));
+                    rest = &after[end + 2..];
+                }
+                None => {
+                    out.push_str("{{");
+                    rest = after;
+                }
+            },
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Evaluate a `when` condition: "var", "!var", "var == value" or "var != value"
+///
+/// A variable is true when it is non-empty and not "false".
+pub fn condition_holds(condition: &str, values: &Values) -> bool {
+    let value = |name: &str| values.get(name.trim()).map(String::as_str).unwrap_or("");
+    let condition = condition.trim();
+
+    if let Some((name, expected)) = condition.split_once("==") {
+        value(name) == expected.trim().trim_matches(['"', '\''])
+    } else if let Some((name, expected)) = condition.split_once("!=") {
+        value(name) != expected.trim().trim_matches(['"', '\''])
+    } else if let Some(name) = condition.strip_prefix('!') {
+        !truthy(value(name))
+    } else {
+        truthy(value(condition))
+    }
+}
+
+fn truthy(value: &str) -> bool {
+    !value.is_empty() && value != "false"
+}
+
+/// Copy a template into `target`, which must not exist or be empty
+///
+/// Returns the created files, relative to `target`.
+pub fn render(template: &Template, target: &Path, values: &Values) -> Result<Vec<PathBuf>> {
+    if target.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+        bail!("{} already exists and is not empty", target.display());
+    }
+
+    let excluded: Vec<&str> = template
+        .manifest
+        .files
+        .iter()
+        .filter(|file| !condition_holds(&file.when, values))
+        .map(|file| file.path.trim_matches('/'))
+        .collect();
+
+    let root = std::fs::canonicalize(&template.root)
+        .with_context(|| format!("Failed to read {}", template.root.display()))?;
+    let mut created = Vec::new();
+    let walker = WalkDir::new(&template.root).min_depth(1).sort_by_file_name().into_iter().filter_entry(|entry| {
+        let relative = entry.path().strip_prefix(&template.root).unwrap_or(entry.path());
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        relative != ".git" && relative != MANIFEST && !excluded.iter().any(|path| relative == *path)
+    });
+
+    for entry in walker {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(&template.root)?;
+        let rendered: PathBuf = relative
+            .components()
+            .map(|c| substitute(&c.as_os_str().to_string_lossy(), values))
+            .collect();
+        // A value can hold `/` or `..`: the file must still land inside `target`
+        if rendered
+            .components()
+            .any(|c| matches!(c, Component::ParentDir | Component::RootDir | Component::Prefix(_)))
+        {
+            bail!("{} renders to {}, outside the project", relative.display(), rendered.display());
+        }
+        // Links are followed to copy what they point at, so one leading out of
+        // the template (`id -> ~/.ssh/id_ed25519`) would copy that file in
+        if entry.path_is_symlink() && !std::fs::canonicalize(entry.path()).is_ok_and(|real| real.starts_with(&root)) {
+            bail!("{} links outside the template", relative.display());
+        }
+        let destination = target.join(&rendered);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&destination)?;
+            continue;
+        }
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Text files get substitution; anything else is copied byte for byte
+        match std::fs::read_to_string(entry.path()) {
+            Ok(content) => std::fs::write(&destination, substitute(&content, values)),
+            Err(_) => std::fs::copy(entry.path(), &destination).map(|_| ()),
+        }
+        .with_context(|| format!("Failed to write {}", destination.display()))?;
+        created.push(rendered);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::templates::{Template, TemplateSource};
+    use tempfile::TempDir;
+
+    fn values(pairs: &[(&str, &str)]) -> Values {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let values = values(&[("name", "api"), ("db", "sqlite")]);
+        assert_eq!(substitute("{{name}}-{{ db }}", &values), "api-sqlite");
+        assert_eq!(substitute("{{ .Title }} {{name}}", &values), "{{ .Title }} api");
+        assert_eq!(substitute("open {{name", &values), "open {{name");
+    }
+
+    #[test]
+    fn test_hook_values_are_quoted() {
+        let values = values(&[("name", "api"), ("module", "x; touch pwned"), ("title", "$(id)")]);
+        assert_eq!(hook_command("go mod init {{ name }}", &values), "go mod init api");
+        assert_eq!(hook_command("echo {{module}} > out.txt", &values), "echo 'x; touch pwned' > out.txt");
+        assert_eq!(hook_command("echo {{title}}", &values), "echo '$(id)'");
+    }
+
+    #[test]
+    fn test_condition_holds() {
+        let values = values(&[("docker", "true"), ("ci", "false"), ("db", "postgres")]);
+        assert!(condition_holds("docker", &values));
+        assert!(!condition_holds("ci", &values));
+        assert!(condition_holds("!ci", &values));
+        assert!(!condition_holds("missing", &values));
+        assert!(condition_holds("db == postgres", &values));
+        assert!(condition_holds("db != 'sqlite'", &values));
+    }
+
+    #[test]
+    fn test_render_skips_conditional_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("template");
+        crate::templates::tests::write_template(&root);
+        let template = Template::load(&root, TemplateSource::Path).unwrap();
+
+        let mut provided = Values::new();
+        provided.insert("db".to_string(), "postgres".to_string());
+        let values = template.resolve_values("billing", &provided).unwrap();
+
+        let target = temp.path().join("billing");
+        let mut created = render(&template, &target, &values).unwrap();
+        created.sort();
+        assert_eq!(
+            created,
+            vec![PathBuf::from("cmd/billing/main.go"), PathBuf::from("go.mod"), PathBuf::from("migrations/001.sql")]
+        );
+        assert_eq!(std::fs::read_to_string(target.join("go.mod")).unwrap(), "module example.com/billing\n\ngo 1.22\n");
+        assert_eq!(
+            std::fs::read_to_string(target.join("cmd/billing/main.go")).unwrap(),
+            "package main // billing uses postgres\n"
+        );
+
+        // Refuses to write into a non-empty directory
+        assert!(render(&template, &target, &values).is_err());
+    }
+
+    #[test]
+    fn test_render_keeps_values_inside_the_project() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("template");
+        crate::templates::tests::write_template(&root);
+        let template = Template::load(&root, TemplateSource::Path).unwrap();
+
+        for name in ["../../escaped", "/tmp/escaped"] {
+            let mut values = template.resolve_values("billing", &Values::new()).unwrap();
+            values.insert("name".to_string(), name.to_string());
+            let target = temp.path().join(format!("billing-{}", name.len()));
+            assert!(render(&template, &target, &values).is_err(), "{}", name);
+        }
+        assert!(!temp.path().join("escaped").exists());
+    }
+
+    #[test]
+    fn test_render_refuses_links_out_of_the_template() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("template");
+        crate::templates::tests::write_template(&root);
+        std::fs::write(temp.path().join("id_ed25519"), "secret\n").unwrap();
+        let template = Template::load(&root, TemplateSource::Path).unwrap();
+        let values = template.resolve_values("billing", &Values::new()).unwrap();
+
+        // A link to a file of the template is copied like the file
+        std::os::unix::fs::symlink("go.mod", root.join("go.mod.link")).unwrap();
+        let target = temp.path().join("billing");
+        render(&template, &target, &values).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.join("go.mod.link")).unwrap(),
+            std::fs::read_to_string(target.join("go.mod")).unwrap()
+        );
+
+        std::os::unix::fs::symlink(temp.path().join("id_ed25519"), root.join("id")).unwrap();
+        let target = temp.path().join("billing-2");
+        assert!(render(&template, &target, &values).is_err());
+        assert!(!target.join("id").exists());
+    }
+}
@@ -0,0 +1,98 @@
+//! JUnit XML reports
+//!
+//! Accepts both a bare `<testsuite>` and `<testsuites>` wrapping several.
+//! `<failure>` and `<error>` mark a failed test, `<skipped>` an ignored one.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::{TestCase, TestOutcome};
+
+/// Parse a JUnit XML report file
+pub fn parse_file(path: &Path) -> Result<Vec<TestCase>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read test report: {}", path.display()))?;
+    parse(&content).with_context(|| format!("Invalid JUnit report: {}", path.display()))
+}
+
+/// Parse JUnit XML
+pub fn parse(xml: &str) -> Result<Vec<TestCase>> {
+    let document = roxmltree::Document::parse(xml)?;
+    let mut cases = Vec::new();
+
+    for testcase in document.descendants().filter(|n| n.has_tag_name("testcase")) {
+        let Some(name) = testcase.attribute("name") else {
+            continue;
+        };
+        // Prefer the enclosing suite's name; fall back to the class name
+        let suite = testcase
+            .ancestors()
+            .find(|n| n.has_tag_name("testsuite"))
+            .and_then(|n| n.attribute("name"))
+            .or_else(|| testcase.attribute("classname"))
+            .map(String::from);
+
+        let children: Vec<_> = testcase.children().filter(|n| n.is_element()).collect();
+        let failure = children
+            .iter()
+            .find(|n| n.has_tag_name("failure") || n.has_tag_name("error"));
+        let outcome = if failure.is_some() {
+            TestOutcome::Failed
+        } else if children.iter().any(|n| n.has_tag_name("skipped")) {
+            TestOutcome::Ignored
+        } else {
+            TestOutcome::Passed
+        };
+
+        let mut case = TestCase::new(name, suite, outcome);
+        case.duration_ms = testcase
+            .attribute("time")
+            .and_then(|t| t.parse::<f64>().ok())
+            .map(|secs| (secs * 1000.0) as u64);
+
+        if let Some(failure) = failure {
+            if let Some(message) = failure.attribute("message") {
+                case.output.push(message.to_string());
+            }
+            let details = children
+                .iter()
+                .filter(|n| n.has_tag_name("system-out") || n.has_tag_name("system-err"))
+                .chain(std::iter::once(failure))
+                .filter_map(|n| n.text());
+            for text in details {
+                case.output.extend(text.trim().lines().map(String::from));
+            }
+        }
+
+        cases.push(case);
+    }
+
+    Ok(cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_junit() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="bun test">
+  <testsuite name="src/math.test.ts" tests="3">
+    <testcase name="adds" classname="math" time="0.002" />
+    <testcase name="subtracts" classname="math" time="0.5">
+      <failure message="expected 4, received 3" type="AssertionError">at src/math.test.ts:9:5</failure>
+    </testcase>
+    <testcase name="divides" classname="math"><skipped /></testcase>
+  </testsuite>
+</testsuites>"#;
+        let cases = parse(xml).unwrap();
+
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].id(), "src/math.test.ts::adds");
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(cases[1].duration_ms, Some(500));
+        assert_eq!(cases[1].output, vec!["expected 4, received 3", "at src/math.test.ts:9:5"]);
+        assert_eq!(cases[2].outcome, TestOutcome::Ignored);
+    }
+}
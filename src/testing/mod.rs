@@ -0,0 +1,72 @@
+//! Per-test results parsed from test runner output
+//!
+//! Recognizes `cargo test`, `go test -json` and `bun test` output, plus JUnit
+//! XML reports for tasks that declare `report = "junit.xml"` in byte.toml.
+//! Results are persisted by [`crate::state::test_results`].
+
+pub mod junit;
+pub mod parsers;
+
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// One test from one run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestCase {
+    pub name: String,
+    /// Test binary, Go package, bun test file or JUnit suite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<String>,
+    pub outcome: TestOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Captured output (kept for failures only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output: Vec<String>,
+}
+
+impl TestCase {
+    pub fn new(name: impl Into<String>, suite: Option<String>, outcome: TestOutcome) -> Self {
+        Self {
+            name: name.into(),
+            suite,
+            outcome,
+            duration_ms: None,
+            output: Vec::new(),
+        }
+    }
+
+    /// Stable identifier across runs (`suite::name`)
+    pub fn id(&self) -> String {
+        match &self.suite {
+            Some(suite) => format!("{}::{}", suite, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Parse every recognized test format in a run's output
+pub fn parse_output(lines: &[&str]) -> Vec<TestCase> {
+    let mut cases = parsers::parse_cargo(lines);
+    cases.extend(parsers::parse_go_json(lines));
+    cases.extend(parsers::parse_bun(lines));
+    cases
+}
+
+/// (passed, failed, ignored)
+pub fn counts(cases: &[TestCase]) -> (usize, usize, usize) {
+    let count = |outcome| cases.iter().filter(|c| c.outcome == outcome).count();
+    (
+        count(TestOutcome::Passed),
+        count(TestOutcome::Failed),
+        count(TestOutcome::Ignored),
+    )
+}
@@ -0,0 +1,279 @@
+//! Test runner output parsers
+
+use std::collections::HashMap;
+
+use super::{TestCase, TestOutcome};
+use crate::diagnostics::matchers::strip_ansi;
+
+/// `cargo test` (libtest) output
+///
+/// ```text
+///      Running unittests src/lib.rs (target/debug/deps/demo-1a2b3c)
+/// test parser::tests::empty ... FAILED
+/// test parser::tests::ok ... ok
+///
+/// ---- parser::tests::empty stdout ----
+/// thread 'parser::tests::empty' panicked at src/parser.rs:88:9
+/// ```
+pub fn parse_cargo(lines: &[&str]) -> Vec<TestCase> {
+    let mut cases: Vec<TestCase> = Vec::new();
+    let mut suite: Option<String> = None;
+    // (suite, test name) whose failure output is being captured
+    let mut capturing: Option<(Option<String>, String)> = None;
+    let mut outputs: HashMap<(Option<String>, String), Vec<String>> = HashMap::new();
+
+    for line in lines {
+        // Colored when cargo runs in a terminal (`pty = true` tasks)
+        let line = strip_ansi(line);
+        let trimmed = line.trim();
+
+        if let Some(binary) = trimmed.strip_prefix("Running ") {
+            // Drop the hashed artifact path so the suite is stable across builds
+            suite = Some(binary.split(" (").next().unwrap_or(binary).to_string());
+            capturing = None;
+            continue;
+        }
+        if trimmed.starts_with("Doc-tests ") {
+            suite = Some(trimmed.to_string());
+            capturing = None;
+            continue;
+        }
+
+        if let Some(name) = trimmed.strip_prefix("---- ").and_then(|rest| rest.strip_suffix(" stdout ----")) {
+            capturing = Some((suite.clone(), name.to_string()));
+            continue;
+        }
+        if trimmed == "failures:" || trimmed == "successes:" || trimmed.starts_with("test result:") {
+            capturing = None;
+            continue;
+        }
+        if let Some(key) = &capturing {
+            outputs.entry(key.clone()).or_default().push(line.to_string());
+            continue;
+        }
+
+        let Some((name, result)) = trimmed.strip_prefix("test ").and_then(|rest| rest.split_once(" ... ")) else {
+            continue;
+        };
+        let outcome = match result {
+            "ok" => TestOutcome::Passed,
+            "FAILED" => TestOutcome::Failed,
+            r if r.starts_with("ignored") => TestOutcome::Ignored,
+            _ => continue,
+        };
+        cases.push(TestCase::new(name, suite.clone(), outcome));
+    }
+
+    for case in cases.iter_mut().filter(|c| c.outcome == TestOutcome::Failed) {
+        if let Some(mut output) = outputs.remove(&(case.suite.clone(), case.name.clone())) {
+            while output.last().is_some_and(|l| l.trim().is_empty()) {
+                output.pop();
+            }
+            case.output = output;
+        }
+    }
+
+    cases
+}
+
+/// `go test -json` event stream
+pub fn parse_go_json(lines: &[&str]) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut outputs: HashMap<(String, String), Vec<String>> = HashMap::new();
+
+    for line in lines.iter().filter(|l| l.starts_with('{') && l.contains("\"Action\"")) {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let field = |name: &str| event.get(name).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let (action, package, test) = (field("Action"), field("Package"), field("Test"));
+        if test.is_empty() {
+            continue;
+        }
+
+        let outcome = match action.as_str() {
+            "output" => {
+                let text = field("Output");
+                outputs
+                    .entry((package, test))
+                    .or_default()
+                    .push(text.trim_end_matches('\n').to_string());
+                continue;
+            }
+            "pass" => TestOutcome::Passed,
+            "fail" => TestOutcome::Failed,
+            "skip" => TestOutcome::Ignored,
+            _ => continue,
+        };
+
+        let output = outputs.remove(&(package.clone(), test.clone())).unwrap_or_default();
+        let mut case = TestCase::new(test, Some(package).filter(|p| !p.is_empty()), outcome);
+        case.duration_ms = event.get("Elapsed").and_then(|e| e.as_f64()).map(|secs| (secs * 1000.0) as u64);
+        if outcome == TestOutcome::Failed {
+            case.output = output;
+        }
+        cases.push(case);
+    }
+
+    cases
+}
+
+/// `bun test` output
+///
+/// Bun prints `(pass) name [1.2ms]` when not attached to a terminal and
+/// `✓ name [1.2ms]` when it is; failure details come before the `(fail)` line.
+pub fn parse_bun(lines: &[&str]) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut suite: Option<String> = None;
+    let mut pending_output: Vec<String> = Vec::new();
+
+    for line in lines {
+        let line = strip_ansi(line);
+        let trimmed = line.trim();
+
+        if !line.starts_with(' ')
+            && let Some(file) = trimmed.strip_suffix(':')
+            && (file.contains(".test.") || file.contains("_test.") || file.contains(".spec."))
+        {
+            suite = Some(file.to_string());
+            pending_output.clear();
+            continue;
+        }
+
+        let result = [
+            ("(pass) ", TestOutcome::Passed),
+            ("✓ ", TestOutcome::Passed),
+            ("(fail) ", TestOutcome::Failed),
+            ("✗ ", TestOutcome::Failed),
+            ("(skip) ", TestOutcome::Ignored),
+            ("» ", TestOutcome::Ignored),
+            ("(todo) ", TestOutcome::Ignored),
+            ("✎ ", TestOutcome::Ignored),
+        ]
+        .into_iter()
+        .find_map(|(prefix, outcome)| trimmed.strip_prefix(prefix).map(|rest| (rest, outcome)));
+
+        let Some((rest, outcome)) = result else {
+            if !trimmed.is_empty() {
+                pending_output.push(line.to_string());
+            }
+            continue;
+        };
+
+        // "name [1.23ms]"
+        let (name, duration_ms) = match rest.rsplit_once(" [") {
+            Some((name, timing)) => (name, parse_bun_duration(timing.trim_end_matches(']'))),
+            None => (rest, None),
+        };
+        let mut case = TestCase::new(name.trim(), suite.clone(), outcome);
+        case.duration_ms = duration_ms;
+        if outcome == TestOutcome::Failed {
+            case.output = std::mem::take(&mut pending_output);
+        }
+        pending_output.clear();
+        cases.push(case);
+    }
+
+    cases
+}
+
+fn parse_bun_duration(timing: &str) -> Option<u64> {
+    if let Some(ms) = timing.strip_suffix("ms") {
+        return ms.parse::<f64>().ok().map(|ms| ms as u64);
+    }
+    timing.strip_suffix('s')?.parse::<f64>().ok().map(|secs| (secs * 1000.0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_output() {
+        let output = [
+            "     Running unittests src/lib.rs (target/debug/deps/demo-1a2b3c)",
+            "running 3 tests",
+            "test parser::tests::ok ... ok",
+            "test parser::tests::slow ... ignored, needs network",
+            "test parser::tests::empty ... FAILED",
+            "",
+            "failures:",
+            "",
+            "---- parser::tests::empty stdout ----",
+            "thread 'parser::tests::empty' panicked at src/parser.rs:88:9:",
+            "assertion failed: tokens.is_empty()",
+            "",
+            "failures:",
+            "    parser::tests::empty",
+            "",
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out",
+        ];
+        let cases = parse_cargo(&output);
+
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].id(), "unittests src/lib.rs::parser::tests::ok");
+        assert_eq!(cases[1].outcome, TestOutcome::Ignored);
+        assert_eq!(cases[2].outcome, TestOutcome::Failed);
+        assert_eq!(cases[2].output.len(), 2);
+        assert!(cases[2].output[1].contains("assertion failed"));
+    }
+
+    #[test]
+    fn test_colored_cargo_output() {
+        let output = [
+            "\x1b[1m\x1b[92m     Running\x1b[0m unittests src/lib.rs (target/debug/deps/demo-1a2b3c)",
+            "test parser::tests::ok ... \x1b[32mok\x1b[0m",
+            "test parser::tests::empty ... \x1b[31mFAILED\x1b[0m",
+        ];
+        let cases = parse_cargo(&output);
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].id(), "unittests src/lib.rs::parser::tests::ok");
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+    }
+
+    #[test]
+    fn test_go_json() {
+        let output = [
+            r#"{"Action":"run","Package":"example.com/demo","Test":"TestAdd"}"#,
+            r#"{"Action":"output","Package":"example.com/demo","Test":"TestAdd","Output":"    add_test.go:9: got 3, want 4\n"}"#,
+            r#"{"Action":"fail","Package":"example.com/demo","Test":"TestAdd","Elapsed":0.01}"#,
+            r#"{"Action":"pass","Package":"example.com/demo","Test":"TestSub","Elapsed":0}"#,
+            r#"{"Action":"skip","Package":"example.com/demo","Test":"TestNet","Elapsed":0}"#,
+            r#"{"Action":"fail","Package":"example.com/demo","Elapsed":0.02}"#,
+        ];
+        let cases = parse_go_json(&output);
+
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].outcome, TestOutcome::Failed);
+        assert_eq!(cases[0].duration_ms, Some(10));
+        assert_eq!(cases[0].output, vec!["    add_test.go:9: got 3, want 4"]);
+        assert_eq!(cases[2].outcome, TestOutcome::Ignored);
+    }
+
+    #[test]
+    fn test_bun_output() {
+        let output = [
+            "bun test v1.1.0",
+            "",
+            "src/math.test.ts:",
+            "(pass) math > adds [0.12ms]",
+            "error: expect(received).toBe(expected)",
+            "Expected: 4",
+            "Received: 3",
+            "(fail) math > subtracts [1.50ms]",
+            "(skip) math > divides",
+            "",
+            " 1 pass",
+            " 1 fail",
+        ];
+        let cases = parse_bun(&output);
+
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[0].id(), "src/math.test.ts::math > adds");
+        assert_eq!(cases[1].outcome, TestOutcome::Failed);
+        assert_eq!(cases[1].duration_ms, Some(1));
+        assert_eq!(cases[1].output.len(), 3);
+        assert_eq!(cases[2].outcome, TestOutcome::Ignored);
+    }
+}
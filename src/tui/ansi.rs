@@ -0,0 +1,312 @@
+//! ANSI escape sequence handling for captured command output
+//!
+//! Cargo, rustc and most test runners emit SGR color codes. Instead of
+//! stripping them, we translate SGR sequences into ratatui [`Span`] styles
+//! and drop every other escape sequence (cursor movement, OSC titles, ...).
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+/// Parse a single line of terminal output into styled spans
+///
+/// When `colors` is false (monochrome theme / `NO_COLOR`), color codes are
+/// discarded but text modifiers such as bold and underline are kept.
+pub fn parse_line(input: &str, colors: bool) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut text = String::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            if ch >= ' ' || ch == '\t' {
+                text.push(ch);
+            }
+            continue;
+        }
+
+        match chars.peek() {
+            Some(&'[') => {
+                chars.next();
+                let mut params = String::new();
+                let mut terminator = None;
+                for next_ch in chars.by_ref() {
+                    if ('@'..='~').contains(&next_ch) {
+                        terminator = Some(next_ch);
+                        break;
+                    }
+                    params.push(next_ch);
+                }
+
+                // Only SGR ("m") sequences change the style; other CSI sequences are dropped
+                if terminator == Some('m') {
+                    if !text.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut text), style));
+                    }
+                    style = apply_sgr(style, &params, colors);
+                }
+            }
+            Some(&']') => {
+                // OSC sequence: ESC ] ... BEL or ESC \
+                chars.next();
+                while let Some(next_ch) = chars.next() {
+                    if next_ch == '\x07' {
+                        break;
+                    }
+                    if next_ch == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(&'(') | Some(&')') => {
+                chars.next();
+                chars.next();
+            }
+            _ => {
+                chars.next();
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        spans.push(Span::styled(text, style));
+    }
+
+    spans
+}
+
+/// Concatenate span contents into plain text (used for searching)
+pub fn plain_text(spans: &[Span<'_>]) -> String {
+    spans.iter().map(|s| s.content.as_ref()).collect()
+}
+
+/// Apply `highlight` on top of the given character ranges (`start..end`, in chars)
+pub fn highlight_ranges(
+    spans: Vec<Span<'static>>,
+    ranges: &[(usize, usize)],
+    highlight: Style,
+) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+
+    let in_range = |idx: usize| {
+        ranges
+            .iter()
+            .any(|(start, end)| idx >= *start && idx < *end)
+    };
+
+    let mut result: Vec<Span<'static>> = Vec::new();
+    let mut pos = 0;
+
+    for span in spans {
+        let mut current = String::new();
+        let mut current_hl = None;
+
+        for ch in span.content.chars() {
+            let hl = in_range(pos);
+            if current_hl != Some(hl) && !current.is_empty() {
+                let style = if current_hl == Some(true) {
+                    span.style.patch(highlight)
+                } else {
+                    span.style
+                };
+                result.push(Span::styled(std::mem::take(&mut current), style));
+            }
+            current_hl = Some(hl);
+            current.push(ch);
+            pos += 1;
+        }
+
+        if !current.is_empty() {
+            let style = if current_hl == Some(true) {
+                span.style.patch(highlight)
+            } else {
+                span.style
+            };
+            result.push(Span::styled(current, style));
+        }
+    }
+
+    result
+}
+
+/// Truncate spans to `width` characters, ending with "…" when cut
+pub fn truncate(spans: Vec<Span<'static>>, width: usize) -> Vec<Span<'static>> {
+    let total: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+    if total <= width {
+        return spans;
+    }
+
+    let mut remaining = width.saturating_sub(1);
+    let mut result = Vec::new();
+
+    for span in spans {
+        if remaining == 0 {
+            break;
+        }
+        let count = span.content.chars().count();
+        if count <= remaining {
+            remaining -= count;
+            result.push(span);
+        } else {
+            let cut: String = span.content.chars().take(remaining).collect();
+            result.push(Span::styled(cut, span.style));
+            remaining = 0;
+        }
+    }
+
+    result.push(Span::raw("…"));
+    result
+}
+
+/// Apply a semicolon-separated SGR parameter list to a style
+fn apply_sgr(mut style: Style, params: &str, colors: bool) -> Style {
+    let codes: Vec<u16> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        let code = codes[i];
+        match code {
+            0 => style = Style::default(),
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            5 | 6 => style = style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            9 => style = style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            25 => style = style.remove_modifier(Modifier::SLOW_BLINK),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            29 => style = style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 if colors => style = style.fg(basic_color(code - 30)),
+            90..=97 if colors => style = style.fg(bright_color(code - 90)),
+            40..=47 if colors => style = style.bg(basic_color(code - 40)),
+            100..=107 if colors => style = style.bg(bright_color(code - 100)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            38 | 48 => {
+                let (color, consumed) = extended_color(&codes[i + 1..]);
+                if colors && let Some(color) = color {
+                    style = if code == 38 {
+                        style.fg(color)
+                    } else {
+                        style.bg(color)
+                    };
+                }
+                i += consumed;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    style
+}
+
+/// Parse the tail of a `38;…`/`48;…` sequence; returns the color and how many codes it used
+fn extended_color(rest: &[u16]) -> (Option<Color>, usize) {
+    match rest.first() {
+        Some(5) => (rest.get(1).map(|n| Color::Indexed(*n as u8)), 2),
+        Some(2) if rest.len() >= 4 => (
+            Some(Color::Rgb(rest[1] as u8, rest[2] as u8, rest[3] as u8)),
+            4,
+        ),
+        _ => (None, rest.len()),
+    }
+}
+
+fn basic_color(n: u16) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+fn bright_color(n: u16) -> Color {
+    match n {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_passthrough() {
+        let spans = parse_line("hello world", true);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(plain_text(&spans), "hello world");
+    }
+
+    #[test]
+    fn test_cargo_style_colors() {
+        // "error[E0308]" in bold red, then reset
+        let spans = parse_line("\x1b[1m\x1b[31merror[E0308]\x1b[0m: mismatched types", true);
+        assert_eq!(plain_text(&spans), "error[E0308]: mismatched types");
+        assert_eq!(spans[0].style.fg, Some(Color::Red));
+        assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(spans[1].style, Style::default());
+    }
+
+    #[test]
+    fn test_extended_colors() {
+        let spans = parse_line("\x1b[38;5;208mA\x1b[48;2;1;2;3mB", true);
+        assert_eq!(spans[0].style.fg, Some(Color::Indexed(208)));
+        assert_eq!(spans[1].style.bg, Some(Color::Rgb(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_colors_dropped_in_monochrome() {
+        let spans = parse_line("\x1b[1;32mok\x1b[0m", false);
+        assert_eq!(spans[0].style.fg, None);
+        assert!(spans[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_non_sgr_sequences_stripped() {
+        let spans = parse_line("\x1b[2K\x1b]0;title\x07\rdone", true);
+        assert_eq!(plain_text(&spans), "done");
+    }
+
+    #[test]
+    fn test_highlight_splits_spans() {
+        let spans = parse_line("\x1b[31mabc\x1b[0mdef", true);
+        let hl = Style::default().add_modifier(Modifier::REVERSED);
+        let result = highlight_ranges(spans, &[(2, 4)], hl);
+        let parts: Vec<&str> = result.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(parts, vec!["ab", "c", "d", "ef"]);
+        assert_eq!(result[1].style.fg, Some(Color::Red));
+        assert!(result[1].style.add_modifier.contains(Modifier::REVERSED));
+        assert!(result[2].style.add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_truncate_across_spans() {
+        let spans = parse_line("\x1b[31mabc\x1b[0mdef", true);
+        let result = truncate(spans, 5);
+        assert_eq!(plain_text(&result), "abcd…");
+    }
+}
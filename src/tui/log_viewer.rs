@@ -0,0 +1,276 @@
+//! Log viewer overlay state: stream filtering, search and navigation
+//!
+//! Rendering lives with the other overlay renderers in `tui/mod.rs`; this
+//! module owns the parts that are independent of the terminal.
+
+use std::path::Path;
+
+use super::{ansi, terminal};
+use crate::exec::{OutputStream, TerminalSize};
+use crate::fs::log_format;
+
+/// Which output stream(s) the viewer shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum StreamFilter {
+    #[default]
+    Both,
+    Stdout,
+    Stderr,
+}
+
+impl StreamFilter {
+    pub fn as_str(&self) -> &str {
+        match self {
+            StreamFilter::Both => "stdout+stderr",
+            StreamFilter::Stdout => "stdout",
+            StreamFilter::Stderr => "stderr",
+        }
+    }
+
+    pub fn next(&self) -> StreamFilter {
+        match self {
+            StreamFilter::Both => StreamFilter::Stdout,
+            StreamFilter::Stdout => StreamFilter::Stderr,
+            StreamFilter::Stderr => StreamFilter::Both,
+        }
+    }
+
+    fn shows(&self, stream: LineStream) -> bool {
+        match (self, stream) {
+            (StreamFilter::Both, _) => true,
+            (_, LineStream::Header) => false,
+            (StreamFilter::Stdout, LineStream::Stdout) => true,
+            (StreamFilter::Stderr, LineStream::Stderr) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Incremental search state (`/` to edit, `n`/`N` to navigate)
+#[derive(Clone, Debug, Default)]
+pub struct LogSearch {
+    pub query: String,
+    /// True while the user is typing the query
+    pub editing: bool,
+    /// Index into the match list of the current match
+    pub current: usize,
+}
+
+/// Origin of a log line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineStream {
+    Header,
+    Stdout,
+    Stderr,
+}
+
+/// A single line of a command log
+#[derive(Clone, Debug)]
+pub struct LogLine {
+    pub stream: LineStream,
+    /// Raw text, ANSI codes included
+    pub raw: String,
+    /// Text with escape sequences removed (used for search and error detection)
+    pub plain: String,
+}
+
+impl LogLine {
+    fn new(stream: LineStream, raw: String) -> Self {
+        let plain = ansi::plain_text(&ansi::parse_line(&raw, false));
+        Self { stream, raw, plain }
+    }
+}
+
+/// A command log split into header, stdout and stderr lines
+#[derive(Clone, Debug, Default)]
+pub struct LogDocument {
+    pub lines: Vec<LogLine>,
+}
+
+impl LogDocument {
+    /// Read and parse a log file (gzipped or not); read errors become a one-line document
+    pub fn load(path: &Path) -> Self {
+        match crate::fs::read_log(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) => Self::parse(&format!("Error reading log file: {:#}", e)),
+        }
+    }
+
+    /// Parse a command log (structured or legacy) into header and output lines
+    pub fn parse(content: &str) -> Self {
+        let parsed = log_format::parse_log(content);
+        let mut lines = Vec::new();
+
+        // Structured logs keep metadata as JSON; render it as a readable header
+        if content.starts_with(log_format::LOG_MAGIC)
+            && let Some(meta) = &parsed.meta
+        {
+            for raw in log_format::describe_meta(meta) {
+                lines.push(LogLine::new(LineStream::Header, raw));
+            }
+            lines.push(LogLine::new(LineStream::Header, String::new()));
+        }
+
+        // Output of a pseudo-terminal is one stream of terminal input
+        if let Some(size) = parsed.meta.as_ref().and_then(|meta| meta.pty) {
+            for raw in terminal::replay(parsed.lines.iter().map(|line| line.text.as_str()), size) {
+                lines.push(LogLine::new(LineStream::Stdout, raw));
+            }
+            return Self { lines };
+        }
+
+        for line in parsed.lines {
+            let stream = match line.stream {
+                Some(OutputStream::Stdout) => LineStream::Stdout,
+                Some(OutputStream::Stderr) => LineStream::Stderr,
+                None => LineStream::Header,
+            };
+            lines.push(LogLine::new(stream, line.text));
+        }
+
+        Self { lines }
+    }
+
+    /// Lines visible under a stream filter
+    pub fn filtered(&self, filter: StreamFilter) -> Vec<&LogLine> {
+        self.lines
+            .iter()
+            .filter(|l| filter.shows(l.stream))
+            .collect()
+    }
+}
+
+/// Indices (into the filtered lines) of lines containing `query`, case-insensitive
+pub fn find_matches(lines: &[&LogLine], query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let needle = query.to_lowercase();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.plain.to_lowercase().contains(&needle))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Character ranges of every occurrence of `query` in `text`, case-insensitive
+pub fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let hay: Vec<char> = text.to_lowercase().chars().collect();
+    let needle: Vec<char> = query.to_lowercase().chars().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i + needle.len() <= hay.len() {
+        if hay[i..i + needle.len()] == needle[..] {
+            ranges.push((i, i + needle.len()));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+
+    ranges
+}
+
+/// Index of the first line that looks like a compiler/test error
+pub fn first_error(lines: &[&LogLine]) -> Option<usize> {
+    lines.iter().position(|l| is_error_line(&l.plain))
+}
+
+/// Heuristic error detection shared by cargo, go, tsc/bun and test runners
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.trim_start().to_lowercase();
+    lower.starts_with("error")
+        || lower.starts_with("fatal")
+        || lower.starts_with("fail")
+        || lower.starts_with("--- fail")
+        || lower.contains("panicked at")
+        || lower.contains(": error")
+        || lower.contains(" failed")
+}
+
+/// Number of log lines that fit on screen in the log viewer overlay
+///
+/// Mirrors the layout in `ui()`: header (3) + tab bar (1) + footer (3), then
+/// the viewer's borders and 2-line header.
+pub fn page_height() -> usize {
+    let rows = crossterm::terminal::size().map(|(_, h)| h).unwrap_or(30) as usize;
+    rows.saturating_sub(7).saturating_sub(4).max(1)
+}
+
+/// Size of the log viewer's text area, which tasks run in a pseudo-terminal
+/// get as their terminal size
+pub fn page_size() -> TerminalSize {
+    let cols = crossterm::terminal::size().map(|(w, _)| w).unwrap_or(100);
+    TerminalSize {
+        rows: page_height() as u16,
+        // Overlay margin and borders
+        cols: cols.saturating_sub(4).max(20),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Command: cargo test\nExit Code: 101\n\n--- STDOUT ---\nrunning 2 tests\ntest a ... ok\n--- STDERR ---\n\x1b[1;31merror\x1b[0m: test failed\n";
+
+    #[test]
+    fn test_parse_sections() {
+        let doc = LogDocument::parse(SAMPLE);
+        assert_eq!(doc.filtered(StreamFilter::Both).len(), 6);
+        assert_eq!(doc.filtered(StreamFilter::Stdout).len(), 2);
+
+        let stderr = doc.filtered(StreamFilter::Stderr);
+        assert_eq!(stderr.len(), 1);
+        assert_eq!(stderr[0].plain, "error: test failed");
+    }
+
+    #[test]
+    fn test_parse_structured_log() {
+        let content = "BYTE-LOG/2 {\"command\":\"cargo test\",\"category\":\"test\",\"started_at\":\"\",\"exit_code\":1}\n       5 out running\n       9 err boom\n      12 out done\n";
+        let doc = LogDocument::parse(content);
+
+        let stdout = doc.filtered(StreamFilter::Stdout);
+        assert_eq!(
+            stdout.iter().map(|l| l.plain.as_str()).collect::<Vec<_>>(),
+            vec!["running", "done"]
+        );
+        assert_eq!(doc.filtered(StreamFilter::Stderr)[0].plain, "boom");
+        assert_eq!(doc.lines[0].plain, "Command: cargo test");
+    }
+
+    #[test]
+    fn test_pty_log_is_replayed() {
+        let content = "BYTE-LOG/2 {\"command\":\"bun test\",\"category\":\"test\",\"started_at\":\"\",\"exit_code\":0,\"pty\":{\"rows\":10,\"cols\":40}}\n       5 out 1/2\r\x1b[K\x1b[32m2/2 pass\x1b[0m\n";
+        let doc = LogDocument::parse(content);
+
+        let stdout = doc.filtered(StreamFilter::Stdout);
+        assert_eq!(
+            stdout.iter().map(|l| l.plain.as_str()).collect::<Vec<_>>(),
+            vec!["2/2 pass"]
+        );
+        assert!(doc.lines.iter().any(|l| l.plain == "Terminal: 40x10 (pty)"));
+    }
+
+    #[test]
+    fn test_search_is_case_insensitive() {
+        let doc = LogDocument::parse(SAMPLE);
+        let lines = doc.filtered(StreamFilter::Both);
+        assert_eq!(find_matches(&lines, "TEST"), vec![0, 3, 4, 5]);
+        assert_eq!(match_ranges("a Test test", "test"), vec![(2, 6), (7, 11)]);
+    }
+
+    #[test]
+    fn test_first_error() {
+        let doc = LogDocument::parse(SAMPLE);
+        let lines = doc.filtered(StreamFilter::Both);
+        assert_eq!(first_error(&lines), Some(5));
+        assert!(!is_error_line("test a ... ok"));
+    }
+}
@@ -7,36 +7,31 @@ use ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Margin},
-    style::{Modifier, Style, Color},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::state::{self, BuildState, BuildStatus, GitStatus, ProjectState, get_project_state};
 
-// Theme colors optimized for OLED black backgrounds
-mod theme {
-    use ratatui::style::Color;
+pub mod ansi;
+pub mod log_viewer;
+pub mod terminal;
+pub mod theme;
 
-    // Primary colors
-    pub const ACCENT: Color = Color::Cyan; // Brand color, primary actions
-    pub const SUCCESS: Color = Color::Green; // Success states, active items
-    pub const ERROR: Color = Color::Red; // Error states, warnings
+pub use log_viewer::{LogSearch, StreamFilter};
+pub use theme::Theme;
 
-    // Text hierarchy (simplified for OLED black - high contrast)
-    pub const TEXT_PRIMARY: Color = Color::White; // Primary content, main text
-    pub const TEXT_SECONDARY: Color = Color::Rgb(180, 180, 180); // Secondary content, metadata, paths
-
-    // UI elements
-    pub const SEPARATOR: Color = Color::Rgb(60, 60, 60); // Lines, dividers
-    // pub const HIGHLIGHT_BG: Color = Color::Rgb(40, 40, 40); // Selected item background (reserved for future use)
-    pub const BADGE_BG: Color = Color::Cyan; // Badge backgrounds
-    pub const BADGE_TEXT: Color = Color::Black; // Badge text
-}
+/// "Template" option in the new project form for Byte's own scaffolding
+const BUILTIN_TEMPLATE: &str = "(built-in)";
+/// Template form field approving the post-create hooks; not a valid variable name
+const HOOKS_FIELD: &str = "(hooks)";
 
 #[derive(Clone, Debug)]
 pub struct Project {
@@ -44,6 +39,8 @@ pub struct Project {
     pub description: String,
     pub drivers: Vec<String>,
     pub path: String,
+    /// Recognized from its manifest; has no byte.toml yet
+    pub implicit: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -51,6 +48,20 @@ pub struct Command {
     pub name: String,
     pub description: String,
     pub command: String,
+    /// JUnit XML report the task writes (`report = "..."` in byte.toml)
+    pub report: Option<String>,
+    /// Run in a pseudo-terminal (`pty = true` in byte.toml)
+    pub pty: bool,
+    /// Provenance badge: "byte" for byte.toml tasks, "npm", "make", ... for
+    /// imported ones
+    pub source: Option<String>,
+    /// How a byte.toml task runs, or why it can't; None runs `command` with `sh`
+    pub invocation: Option<Result<crate::exec::Invocation, String>>,
+    /// Commands a polyglot aggregate starts, each as its own job
+    pub members: Vec<String>,
+    /// Palette entry that scaffolds a project with `byte init`, in-process
+    /// rather than through a shell
+    pub scaffold: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -79,11 +90,62 @@ pub enum OverlayPosition {
 /// Content types that can be displayed in an overlay
 #[derive(Clone, Debug)]
 pub enum OverlayContent {
-    /// Log file viewer with scroll support
+    /// Log file viewer with scroll, search and stream filtering
     LogViewer {
         log_path: PathBuf,
         scroll_offset: usize,
         project_path: String,
+        search: LogSearch,
+        /// Keep the view pinned to the end of a log that is still being written
+        follow: bool,
+        stream: StreamFilter,
+    },
+    /// Normalized diff between two runs of the same task
+    LogDiff {
+        diff: Box<crate::fs::log_diff::LogDiff>,
+        old_name: String,
+        new_name: String,
+        scroll_offset: usize,
+        side_by_side: bool,
+    },
+    /// Changed files of a project, with an inline diff of the selected one
+    GitChanges {
+        project_path: String,
+        changes: Vec<crate::state::git_diff::FileChange>,
+        /// Index into the files shown for the current `staged` mode
+        selected: usize,
+        /// Show staged (index) changes instead of unstaged (worktree) ones
+        staged: bool,
+        /// Diff of the selected file while it is open
+        diff: Option<Box<crate::state::git_diff::FileDiff>>,
+        scroll_offset: usize,
+    },
+    /// Per-project results of a multi-repo git sync
+    SyncSummary {
+        results: Vec<crate::state::git_sync::SyncResult>,
+        selected: usize,
+        /// What the sync did besides fetching
+        mode: crate::state::git_sync::SyncMode,
+    },
+    /// Local branches: switch, create and delete
+    Branches {
+        project_path: String,
+        branches: Vec<crate::state::git_ops::Branch>,
+        selected: usize,
+        /// Name being typed for a new branch
+        new_branch: Option<String>,
+    },
+    /// Failing tests with their output, and flaky tests
+    TestResults {
+        state: Box<crate::state::TestState>,
+        scroll_offset: usize,
+    },
+    /// Diagnostics from the latest run of each task category
+    Problems {
+        project_path: String,
+        /// (log category, diagnostic), errors first
+        problems: Vec<(String, crate::diagnostics::Diagnostic)>,
+        selected: usize,
     },
     /// Form input (project creation, git tag, etc.)
     Form {
@@ -101,7 +163,13 @@ impl OverlayContent {
     #[allow(dead_code)]
     pub fn position(&self) -> OverlayPosition {
         match self {
-            OverlayContent::LogViewer { .. } => OverlayPosition::FullScreen,
+            OverlayContent::LogViewer { .. }
+            | OverlayContent::LogDiff { .. }
+            | OverlayContent::GitChanges { .. }
+            | OverlayContent::Branches { .. }
+            | OverlayContent::SyncSummary { .. }
+            | OverlayContent::TestResults { .. }
+            | OverlayContent::Problems { .. } => OverlayPosition::FullScreen,
             OverlayContent::Form { position, .. } => position.clone(),
         }
     }
@@ -213,12 +281,51 @@ pub struct App {
     pub command_tx: Option<std::sync::mpsc::Sender<CommandResult>>,
     pub pending_result: Option<CommandResult>,
     pub command_result_display: Option<(bool, Instant)>, // (success, timestamp) for showing result
+    // Stop token of each running task, by run id; Ctrl+C sets them all
+    pub command_cancels: HashMap<u64, Arc<AtomicBool>>,
+    // Id of the next task started
+    pub next_run: u64,
+    // Tasks started and not yet reported back (aggregates run several at once)
+    pub running_commands: usize,
+    // Global log retention, for the logs of tasks run from here
+    pub log_config: crate::config::types::LogsConfig,
     // Interactive editor request
-    pub pending_editor: Option<(String, String)>, // (editor, file_path)
+    pub pending_editor: Option<EditorRequest>,
+    // Results of a background git sync, while one is running
+    // (pulled, per-project results)
+    pub sync_rx: Option<std::sync::mpsc::Receiver<(crate::state::git_sync::SyncMode, Vec<crate::state::git_sync::SyncResult>)>>,
+    // Result of a release running in the background (project path, summary)
+    pub release_rx: Option<std::sync::mpsc::Receiver<(String, anyhow::Result<String>)>>,
+    // Finished sync waiting for the open form or overlay to close before its
+    // summary is shown (pulled, per-project results)
+    pub pending_sync_summary: Option<(crate::state::git_sync::SyncMode, Vec<crate::state::git_sync::SyncResult>)>,
+    // Template chosen in the new project form, while its variables are asked for
+    // (template, workspace, project name)
+    pub pending_template: Option<(crate::templates::Template, String, String)>,
+    // Command waiting for the user to trust its project's tasks
+    // (project path, command)
+    pub pending_trust: Option<(String, String)>,
+    // The command being edited came from a scaffold entry, so Enter creates
+    // a project instead of running it
+    pub editing_scaffold: bool,
     // Log navigation in Details view
     pub selected_log: usize,
+    // Log marked with `d` as the first side of a diff
+    pub diff_mark: Option<PathBuf>,
     // Flag to trigger terminal clear on next draw
     pub needs_clear: bool,
+    // Active color theme (from [tui] theme, or monochrome under NO_COLOR)
+    pub theme: Theme,
+}
+
+/// A file to open in the user's editor once the TUI is suspended
+#[derive(Clone, Debug)]
+pub struct EditorRequest {
+    pub editor: String,
+    pub file_path: String,
+    /// Jump target (problem locations); None opens at the top
+    pub line: Option<u32>,
+    pub column: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -232,6 +339,8 @@ pub enum View {
 
 #[derive(Clone)]
 pub struct CommandResult {
+    /// Run id the task was started with
+    pub run: u64,
     pub success: bool,
     pub command: String,
     pub working_dir: String,
@@ -254,16 +363,34 @@ impl Default for App {
                     name: "init go cli <name>".to_string(),
                     description: "Initialize Go CLI project".to_string(),
                     command: "byte init go cli my-project".to_string(),
+                    report: None,
+                    pty: false,
+                    source: None,
+                    invocation: None,
+                    members: Vec::new(),
+                    scaffold: true,
                 },
                 Command {
                     name: "init bun web <name>".to_string(),
                     description: "Initialize Bun web application".to_string(),
                     command: "byte init bun web my-app".to_string(),
+                    report: None,
+                    pty: false,
+                    source: None,
+                    invocation: None,
+                    members: Vec::new(),
+                    scaffold: true,
                 },
                 Command {
                     name: "init rust cli <name>".to_string(),
                     description: "Initialize Rust CLI project".to_string(),
                     command: "byte init rust cli my-tool".to_string(),
+                    report: None,
+                    pty: false,
+                    source: None,
+                    invocation: None,
+                    members: Vec::new(),
+                    scaffold: true,
                 },
             ],
             command_filter: CommandFilter::All,
@@ -293,9 +420,21 @@ impl Default for App {
             command_tx: None,
             pending_result: None,
             command_result_display: None,
+            command_cancels: HashMap::new(),
+            next_run: 0,
+            running_commands: 0,
+            log_config: Default::default(),
             pending_editor: None,
+            sync_rx: None,
+            release_rx: None,
+            pending_sync_summary: None,
+            pending_template: None,
+            pending_trust: None,
+            editing_scaffold: false,
             selected_log: 0,
+            diff_mark: None,
             needs_clear: false,
+            theme: Theme::from_config(&crate::config::GlobalConfig::default().tui),
         };
 
         if !app.projects.is_empty() {
@@ -312,6 +451,10 @@ impl App {
 
         // Load config and discover projects
         if let Ok(config) = crate::config::Config::load() {
+            app.theme = Theme::from_config(&config.global.tui);
+            app.log_config = config.global.logs.clone();
+            crate::policy::install(&config.global);
+
             // Load workspace directories
             let workspace_path = &config.global.workspace.path;
 
@@ -342,8 +485,12 @@ impl App {
                         description: p.config.project.description.clone().unwrap_or_else(|| {
                             format!("{} project", p.config.project.project_type)
                         }),
-                        drivers: vec![p.config.project.ecosystem],
+                        drivers: match &p.config.drivers {
+                            Some(drivers) => drivers.uses.clone(),
+                            None => vec![p.config.project.ecosystem.clone()],
+                        },
                         path: p.path.to_string_lossy().to_string(),
+                        implicit: p.implicit,
                     })
                     .collect();
 
@@ -514,6 +661,40 @@ impl App {
         None
     }
 
+    /// JUnit report path declared by the task behind a command, if any
+    fn task_report(&self, command_str: &str) -> Option<String> {
+        self.commands
+            .iter()
+            .find(|cmd| cmd.command == command_str)
+            .and_then(|cmd| cmd.report.clone())
+    }
+
+    /// Terminal for a task with `pty = true`, the size of the log viewer so
+    /// following the run shows it as the task drew it
+    fn task_pty(&self, command_str: &str) -> Option<crate::exec::TerminalSize> {
+        self.commands
+            .iter()
+            .any(|cmd| cmd.command == command_str && cmd.pty)
+            .then(log_viewer::page_size)
+    }
+
+    /// Reload the cached state of one project (no-op for paths that aren't projects)
+    fn refresh_project_state(&mut self, project_path: &str) {
+        if self.project_states.contains_key(project_path) {
+            self.project_states.insert(project_path.to_string(), get_project_state(project_path));
+        }
+    }
+
+    /// Report a task that finished; the animation stops with the last one
+    fn finish_command(&mut self, result: CommandResult) {
+        self.running_commands = self.running_commands.saturating_sub(1);
+        if self.running_commands == 0 {
+            self.executing_command = None;
+        }
+        self.command_cancels.remove(&result.run);
+        self.handle_command_result(result);
+    }
+
     fn handle_command_result(&mut self, result: CommandResult) {
         // Update build state after execution (for build commands)
         if result.is_build_cmd {
@@ -533,6 +714,9 @@ impl App {
             }
         }
 
+        // Pick up the new run's problem counts
+        self.refresh_project_state(&result.working_dir);
+
         // Show result in progress bar for 3 seconds
         self.command_result_display = Some((result.success, Instant::now()));
 
@@ -552,9 +736,89 @@ impl App {
         }
     }
 
-    fn execute_command(&mut self, command_str: &str) {
-        // Determine working directory based on context
-        let working_dir = if let Some(project) = self.get_selected_project() {
+    /// Whether a task may run now: the policy allows it and its project's
+    /// task files are trusted. New or changed task files open the trust form
+    /// instead, which runs the command once the user trusts it.
+    fn check_policy(&mut self, command_str: &str, invocation: &crate::exec::Invocation, working_dir: &str) -> bool {
+        let policy = crate::policy::current();
+        let dir = std::path::Path::new(working_dir);
+        let argv = invocation.argv();
+        if let Err(e) = policy.check(&argv[0], &argv[1..], Some(dir)) {
+            policy.audit(&crate::policy::Decision::Deny(e.to_string()), Some(dir), command_str);
+            self.status_message = format!("✗ Blocked by policy: {}", e);
+            return false;
+        }
+
+        let Some(project) = self.get_selected_project() else {
+            return true;
+        };
+        if policy.trusts(dir) {
+            return true;
+        }
+        let status = match crate::policy::trust::TrustStore::open() {
+            Ok(store) => store.status(dir),
+            Err(e) => {
+                self.status_message = format!("✗ {}", e);
+                return false;
+            }
+        };
+        if status == crate::policy::trust::TrustStatus::Trusted {
+            return true;
+        }
+
+        let form = crate::forms::Form::new(format!("Trust {}?", project.name), crate::forms::FormType::TrustProject)
+            .description(format!(
+                "{}. Its tasks run as shell commands on this machine; review byte.toml and the Makefile, package.json scripts, ... in {} before trusting it.\n\nAbout to run: {}",
+                status.label(),
+                working_dir,
+                command_str
+            ))
+            .checkbox("trust", "Trust this project's tasks and run the task");
+        self.pending_trust = Some((working_dir.to_string(), command_str.to_string()));
+        self.open_overlay(OverlayContent::Form {
+            form,
+            position: OverlayPosition::Centered { width: 70, height: 35 },
+        });
+        self.status_message = "Untrusted project - tick Trust (Space) and press Enter, or Esc to cancel".to_string();
+        false
+    }
+
+    /// Handle the trust form: remember the task files and run the waiting command
+    fn submit_trust_project(&mut self, values: std::collections::HashMap<String, crate::forms::FormValue>) {
+        let Some((project_path, command)) = self.pending_trust.take() else {
+            return;
+        };
+        if !matches!(values.get("trust"), Some(crate::forms::FormValue::Bool(true))) {
+            self.status_message = "Not trusted - nothing was run".to_string();
+            return;
+        }
+        match crate::policy::trust(std::path::Path::new(&project_path), "trusted from the TUI") {
+            Ok(()) => self.execute_command(&command),
+            Err(e) => self.status_message = format!("✗ Could not trust project: {}", e),
+        }
+    }
+
+    /// How a command runs: byte.toml tasks as defined, anything else (edited
+    /// commands, imported tasks) through `sh`; None for an invalid task
+    fn command_invocation(&mut self, command_str: &str) -> Option<crate::exec::Invocation> {
+        match self
+            .commands
+            .iter()
+            .find(|cmd| cmd.command == command_str)
+            .and_then(|cmd| cmd.invocation.clone())
+        {
+            Some(Ok(invocation)) => Some(invocation),
+            Some(Err(e)) => {
+                self.status_message = format!("✗ Invalid task: {}", e);
+                None
+            }
+            None => Some(crate::exec::Invocation::sh(command_str)),
+        }
+    }
+
+    /// Directory a palette command runs in
+    fn command_dir(&self) -> String {
+        if let Some(project) = self.get_selected_project() {
             // Project selected: run commands in project directory
             project.path.clone()
         } else {
@@ -564,8 +828,99 @@ impl App {
                 Ok(safe_path) => safe_path.to_string(),
                 Err(_) => target_workspace, // Fallback to original if expansion fails
             }
+        }
+    }
+
+    /// Create a project from an edited scaffold entry (`byte init <ecosystem>
+    /// <type> [name]`) in-process; anything else in the buffer is refused
+    /// rather than handed to a shell
+    fn execute_scaffold(&mut self, command_str: &str) {
+        let parts: Vec<&str> = command_str.split_whitespace().collect();
+        let (ecosystem, project_type, name) = match parts.as_slice() {
+            ["byte", "init", ecosystem, project_type] => (*ecosystem, *project_type, "my-project"),
+            ["byte", "init", ecosystem, project_type, name] => (*ecosystem, *project_type, *name),
+            _ => {
+                self.status_message = "✗ Expected: byte init <ecosystem> <type> [name]".to_string();
+                return;
+            }
+        };
+        let (ecosystem, project_type, name) = (ecosystem.to_string(), project_type.to_string(), name.to_string());
+        let working_dir = self.command_dir();
+
+        crate::log::info("EXEC", &format!("Scaffolding: {} in {}", command_str, working_dir));
+
+        self.executing_command = Some(command_str.to_string());
+        self.build_animation_frame = 0;
+        self.build_animation_start = Some(Instant::now());
+
+        if let Some(tx) = self.command_tx.clone() {
+            self.running_commands += 1;
+            let run = self.next_run;
+            self.next_run += 1;
+            let command = command_str.to_string();
+
+            std::thread::spawn(move || {
+                // Validate project name before attempting to create
+                let (success, stdout, stderr) = if let Err(e) = crate::projects::validate_project_name(&name) {
+                    (false, String::new(), format!("Invalid project name: {}", e))
+                } else {
+                    match crate::projects::init_project(&working_dir, &ecosystem, &project_type, &name) {
+                        Ok(project_path) => {
+                            crate::policy::trust_created(&project_path);
+                            (true, format!("Created project at {}", project_path.display()), String::new())
+                        }
+                        Err(e) => (false, String::new(), format!("Failed to create project: {}", e)),
+                    }
+                };
+
+                let _ = tx.send(CommandResult {
+                    run,
+                    success,
+                    command,
+                    working_dir,
+                    is_build_cmd: false,
+                    task_name: None,
+                    stdout,
+                    stderr,
+                });
+            });
+        }
+    }
+
+    fn execute_command(&mut self, command_str: &str) {
+        let working_dir = self.command_dir();
+
+        // A polyglot aggregate starts each member as its own job, once the
+        // policy and trust allow all of them
+        let members = self
+            .commands
+            .iter()
+            .find(|cmd| cmd.command == command_str)
+            .map(|cmd| cmd.members.clone())
+            .unwrap_or_default();
+        if !members.is_empty() {
+            for member in &members {
+                let Some(invocation) = self.command_invocation(member) else {
+                    return;
+                };
+                if !self.check_policy(command_str, &invocation, &working_dir) {
+                    return;
+                }
+            }
+            for member in &members {
+                self.execute_command(member);
+            }
+            return;
+        }
+
+        let Some(invocation) = self.command_invocation(command_str) else {
+            return;
         };
 
+        if !self.check_policy(command_str, &invocation, &working_dir) {
+            return;
+        }
+
         crate::log::info("EXEC", &format!("Executing: {} in {}", command_str, working_dir));
 
         // Check if this is a build command
@@ -586,6 +941,15 @@ impl App {
             let _ = state::build::save_build_state(&working_dir, state);
         }
 
+        let report = self.task_report(command_str);
+        let pty = self.task_pty(command_str);
+
+        // Ctrl+C stops every task running at the time, and none started later
+        let run = self.next_run;
+        self.next_run += 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.command_cancels.insert(run, cancel.clone());
+
         // Start animation for ALL commands
         self.executing_command = Some(command_str.to_string());
         self.build_animation_frame = 0;
@@ -593,71 +957,64 @@ impl App {
 
         // Spawn background thread to execute command
         if let Some(tx) = self.command_tx.clone() {
+            self.running_commands += 1;
             let command = command_str.to_string();
             let working_dir_clone = working_dir.clone();
+            let log_config = self.log_config.clone();
 
             std::thread::spawn(move || {
-                use crate::exec::CommandBuilder;
-
-                // Parse command to check if it's a byte init command
-                let parts: Vec<&str> = command.split_whitespace().collect();
-                let is_byte_init = parts.len() >= 4 && parts[0] == "byte" && parts[1] == "init";
-
-                let (success, _stdout, _stderr) = if is_byte_init {
-                    // Handle byte init commands specially
-                    let ecosystem = parts[2];
-                    let project_type = parts[3];
-                    let name = if parts.len() > 4 {
-                        parts[4]
-                    } else {
-                        "my-project"
-                    };
+                let (success, _stdout, _stderr) = {
+                    // Start a live log so the run can be followed while it executes
+                    let fs_api = crate::fs::ProjectFileSystem::new(&working_dir_clone).ok();
+                    let category = Self::categorize_command(&command).as_str().to_lowercase();
+                    let live_log = fs_api
+                        .as_ref()
+                        .and_then(|fs| fs.start_command_log(&category, &command, task_name.as_deref(), pty).ok());
 
-                    // Validate project name before attempting to create
-                    if let Err(e) = crate::projects::validate_project_name(name) {
-                        let msg = format!("Invalid project name: {}", e);
-                        (false, String::new(), msg)
-                    } else {
-                        match crate::projects::init_project(
-                        &working_dir_clone,
-                        ecosystem,
-                        project_type,
-                        name,
-                    ) {
-                        Ok(project_path) => {
-                            let msg = format!("Created project at {}", project_path.display());
-                            (true, msg, String::new())
-                        }
-                        Err(e) => {
-                            let msg = format!("Failed to create project: {}", e);
-                            (false, String::new(), msg)
-                        }
+                    // Execute regular shell command using exec API (with validation)
+                    let mut builder = invocation.builder(&working_dir_clone).cancel_on(cancel);
+                    if let Some(size) = pty {
+                        builder = builder.pty(size);
                     }
+                    let mut log_writer = None;
+                    if let Some(mut live) = live_log {
+                        let (line_tx, line_rx) = std::sync::mpsc::channel();
+                        builder = builder.stream_to(line_tx);
+                        log_writer = Some(std::thread::spawn(move || {
+                            for line in line_rx {
+                                let _ = live.append(&line);
+                            }
+                            live
+                        }));
                     }
-                } else {
-                    // Execute regular shell command using exec API (with validation)
-                    let result = CommandBuilder::shell(&command)
-                        .working_dir(&working_dir_clone)
-                        .execute();
+                    let result = builder.execute();
+                    // Dropping the builder closes the line channel so the writer finishes
+                    drop(builder);
+                    let live_log = log_writer.and_then(|writer| writer.join().ok());
 
                     match result {
                         Ok(cmd_result) => {
                             let success = cmd_result.success;
                             let stdout = cmd_result.stdout.clone();
                             let stderr = cmd_result.stderr.clone();
-                            let exit_code = cmd_result.exit_code;
-
-                            // Log command output using FS API
-                            if let Ok(fs_api) = crate::fs::ProjectFileSystem::new(&working_dir_clone) {
-                                // Categorize command to determine log directory
-                                let category = Self::categorize_command(&command).as_str().to_lowercase();
-                                let _ = fs_api.write_command_log(
-                                    &category,
-                                    &command,
-                                    &stdout,
-                                    &stderr,
-                                    exit_code,
-                                );
+
+                            // Per-test results for test tasks and tasks with a JUnit report
+                            if category == "test" || report.is_some() {
+                                record_test_results(&working_dir_clone, &command, task_name.as_deref(), report.as_deref(), &cmd_result);
+                            }
+
+                            // Finalize the log with exit code and duration using FS API
+                            if let Some(fs_api) = &fs_api {
+                                let _ = match live_log {
+                                    Some(live) => fs_api.finish_command_log(live, &cmd_result, &log_config),
+                                    None => fs_api.write_command_log(
+                                        &category,
+                                        &command,
+                                        task_name.as_deref(),
+                                        &cmd_result,
+                                        &log_config,
+                                    ),
+                                };
                             }
 
                             (success, stdout, stderr)
@@ -671,6 +1028,7 @@ impl App {
 
                 // Send result back
                 let _ = tx.send(CommandResult {
+                    run,
                     success,
                     command,
                     working_dir: working_dir_clone,
@@ -861,7 +1219,11 @@ impl App {
 
         match form_type {
             FormType::NewProject => self.submit_new_project(values),
+            FormType::TemplateInit => self.submit_template_init(values),
             FormType::GitTag => self.submit_git_tag(values),
+            FormType::GitCommit => self.submit_git_commit(values),
+            FormType::GitWorktree => self.submit_git_worktree(values),
+            FormType::TrustProject => self.submit_trust_project(values),
             FormType::EditCommand => {
                 // Future: handle command editing
                 self.status_message = "Command editing not yet implemented".to_string();
@@ -912,6 +1274,14 @@ impl App {
             return;
         }
 
+        // A user template asks for its own variables next
+        if let Some(FormValue::Text(template)) = values.get("template")
+            && template != BUILTIN_TEMPLATE
+        {
+            self.open_template_form(template, workspace, name);
+            return;
+        }
+
         // Create the project
         match crate::projects::init_project(&workspace, &ecosystem, &project_type, &name) {
             Ok(project_path) => {
@@ -935,325 +1305,1616 @@ impl App {
         }
     }
 
-    /// Handle git tag creation form submission
-    fn submit_git_tag(&mut self, _values: std::collections::HashMap<String, crate::forms::FormValue>) {
-        // Future: implement git tag creation
-        self.status_message = "Git tag creation not yet implemented".to_string();
-    }
+    /// Resolve a template and ask for its variables, prefilled with defaults
+    fn open_template_form(&mut self, spec: &str, workspace: String, name: String) {
+        use crate::forms::FormField;
+        use crate::templates::VariableKind;
 
-    pub fn handle_key(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char('q') | KeyCode::Char('Q') => self.quit(),
-            KeyCode::Char('r') | KeyCode::Char('R')
-                if !matches!(
-                    self.input_mode,
-                    InputMode::AddingDirectory | InputMode::EditingCommand
-                ) =>
-            {
-                self.hotload();
-                self.status_message = "✓ Reloaded all state from disk".to_string();
+        let template = match crate::config::Config::load()
+            .and_then(|config| crate::templates::resolve_template(&config.global, spec))
+        {
+            Ok(template) => template,
+            Err(e) => {
+                self.status_message = format!("✗ {}", e);
+                return;
             }
-            KeyCode::Char('n')
-                if matches!(self.current_view, View::ProjectBrowser)
-                    && matches!(self.input_mode, InputMode::Normal) =>
-            {
-                // New project creation form (from ProjectBrowser view)
-                // Load config to get workspace options
-                let workspace_options: Vec<String> = if let Ok(config) = crate::config::Config::load() {
-                    std::iter::once(config.global.workspace.path.clone())
-                        .chain(config.global.workspace.registered.clone())
-                        .collect()
-                } else {
-                    vec!["~/projects".to_string()] // Fallback
-                };
+        };
 
-                let form = crate::forms::Form::new("Create New Project", crate::forms::FormType::NewProject)
-                    .description("Initialize a new project with byte scaffolding")
-                    .select("workspace", "Target Workspace", workspace_options)
-                    .select("ecosystem", "Ecosystem", vec![
-                        "rust".to_string(),
-                        "go".to_string(),
-                        "bun".to_string(),
-                    ])
-                    .select("project_type", "Project Type", vec![
-                        "cli".to_string(),
-                        "web".to_string(),
-                        "lib".to_string(),
-                    ])
-                    .text_input("name", "Project Name", "my-project")
-                    .text_area("description", "Description (optional)", "A brief description...", 3);
+        let hooks = template.manifest.hooks.post_create.clone();
+        if template.manifest.variables.is_empty() && hooks.is_empty() {
+            self.pending_template = Some((template, workspace, name));
+            self.submit_template_init(HashMap::new());
+            return;
+        }
 
-                // Use new overlay API for forms
-                self.open_overlay(OverlayContent::Form {
-                    form,
-                    position: OverlayPosition::Centered { width: 80, height: 40 },
-                });
-                self.status_message = "Creating new project - press Enter to submit, Esc to cancel".to_string();
-            }
-            KeyCode::Char('t')
-                if matches!(self.current_view, View::Detail)
-                    && matches!(self.input_mode, InputMode::Normal) =>
-            {
-                // Git tag creation form (from Details view with git status)
-                let form = crate::forms::Form::new("Create Git Tag", crate::forms::FormType::GitTag)
-                    .description("Create a new Git tag for this project")
-                    .text_input("tag_name", "Tag Name", "v1.0.0")
-                    .text_area("message", "Tag Message", "Release notes...", 4)
-                    .checkbox("annotated", "Create annotated tag")
-                    .checkbox("push", "Push tag to remote");
+        let defaults = template.default_values(&name);
+        let mut form = crate::forms::Form::new(format!("New {} project", template.name), crate::forms::FormType::TemplateInit)
+            .description(if template.description().is_empty() {
+                format!("Variables for '{}'", name)
+            } else {
+                template.description().to_string()
+            });
+        for variable in &template.manifest.variables {
+            let id = variable.name.clone();
+            let label = variable.prompt().to_string();
+            let default = defaults.get(&variable.name).cloned().unwrap_or_default();
+            form = form.field(match variable.kind {
+                VariableKind::Text => FormField::TextInput {
+                    id,
+                    label,
+                    placeholder: variable.name.clone(),
+                    value: default,
+                    validator: None,
+                },
+                VariableKind::Bool => FormField::Checkbox {
+                    id,
+                    label,
+                    checked: matches!(default.as_str(), "true" | "yes" | "y" | "1"),
+                },
+                VariableKind::Choice => FormField::Select {
+                    id,
+                    label,
+                    selected: variable.choices.iter().position(|c| *c == default).unwrap_or(0),
+                    options: variable.choices.clone(),
+                },
+            });
+        }
+        // Templates can come from anywhere: hooks only run when ticked
+        if !hooks.is_empty() {
+            form = form.checkbox(HOOKS_FIELD, format!("Run post-create hooks: {}", hooks.join("; ")));
+        }
 
-                // Use new overlay API for forms
-                self.open_overlay(OverlayContent::Form {
-                    form,
-                    position: OverlayPosition::Centered { width: 80, height: 35 },
-                });
-                self.status_message = "Creating git tag - press Enter to submit, Esc to cancel".to_string();
+        self.pending_template = Some((template, workspace, name));
+        self.open_overlay(OverlayContent::Form {
+            form,
+            position: OverlayPosition::Centered { width: 80, height: 40 },
+        });
+        self.status_message = "Template variables - press Enter to create, Esc to cancel".to_string();
+    }
+
+    /// Handle template variables form submission: create the project
+    fn submit_template_init(&mut self, values: std::collections::HashMap<String, crate::forms::FormValue>) {
+        use crate::forms::FormValue;
+
+        let Some((template, workspace, name)) = self.pending_template.take() else {
+            self.status_message = "✗ No template selected".to_string();
+            return;
+        };
+
+        let run_hooks = matches!(values.get(HOOKS_FIELD), Some(FormValue::Bool(true)));
+        let provided: crate::templates::Values = values
+            .into_iter()
+            .filter(|(id, _)| id != HOOKS_FIELD)
+            .filter_map(|(id, value)| match value {
+                FormValue::Text(text) => Some((id, text)),
+                FormValue::Bool(checked) => Some((id, checked.to_string())),
+                _ => None,
+            })
+            .collect();
+        let result = template
+            .resolve_values(&name, &provided)
+            .and_then(|values| crate::projects::init_from_template(&workspace, &template, &name, &values, run_hooks));
+        template.cleanup();
+
+        match result {
+            Ok(project) => {
+                for hook in &project.skipped_hooks {
+                    crate::log::info("TEMPLATES", &format!("Skipped hook '{}'", hook));
+                }
+                self.status_message = match project.failed_hooks.first() {
+                    Some((hook, _)) => format!(
+                        "✓ Created project: {} ({} hook(s) failed, first: {})",
+                        name,
+                        project.failed_hooks.len(),
+                        hook
+                    ),
+                    None => format!("✓ Created project: {} at {}", name, project.path.display()),
+                };
+                for (hook, error) in &project.failed_hooks {
+                    crate::log::error("TEMPLATES", &format!("Hook '{}' failed: {}", hook, error));
+                }
+
+                self.hotload();
+                let path = project.path.to_string_lossy();
+                if let Some(pos) = self.projects.iter().position(|p| p.path == path) {
+                    self.selected_project = pos;
+                    self.project_list_state.select(Some(pos));
+                    self.current_view = View::Detail;
+                }
             }
-            // View switching keys - only when NOT in input mode or overlay view
-            KeyCode::Char('1')
-                if !matches!(self.input_mode, InputMode::AddingDirectory)
-                && !matches!(self.current_view, View::Overlay) =>
-            {
-                self.current_view = View::ProjectBrowser;
-                self.command_result_display = None; // Clear command result on view switch
-                self.status_message = "Viewing projects".to_string();
+            Err(e) => {
+                self.status_message = format!("Failed to create project: {}", e);
             }
-            KeyCode::Char('2')
-                if !matches!(self.input_mode, InputMode::AddingDirectory)
-                && !matches!(self.current_view, View::Overlay) =>
-            {
-                self.current_view = View::CommandPalette;
-                self.update_commands();
-                self.command_result_display = None; // Clear command result on view switch
-                self.status_message = "Viewing commands".to_string();
+        }
+    }
+
+    /// Handle git tag creation form submission
+    fn submit_git_tag(&mut self, values: std::collections::HashMap<String, crate::forms::FormValue>) {
+        use crate::forms::FormValue;
+
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+        if self.release_rx.is_some() {
+            self.status_message = "Release already running".to_string();
+            return;
+        }
+
+        let text = |id: &str| match values.get(id) {
+            Some(FormValue::Text(value)) => value.trim().to_string(),
+            _ => String::new(),
+        };
+        let checked = |id: &str| matches!(values.get(id), Some(FormValue::Bool(true)));
+
+        let remote = text("remote");
+        let options = crate::release::ReleaseOptions {
+            tag: text("tag_name"),
+            message: text("message"),
+            annotated: checked("annotated"),
+            bump: checked("bump"),
+            remote: checked("push").then(|| if remote.is_empty() { "origin".to_string() } else { remote }),
+            prompts: false,
+        };
+
+        // Pushing can take a while; the result comes back through poll_release
+        self.executing_command = Some(format!("release {}", options.tag));
+        self.build_animation_frame = 0;
+        self.build_animation_start = Some(Instant::now());
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.release_rx = Some(rx);
+        std::thread::spawn(move || {
+            let outcome = crate::release::release(std::path::Path::new(&project_path), &options)
+                .map(|outcome| outcome.summary(&options.tag));
+            let _ = tx.send((project_path, outcome));
+        });
+    }
+
+    /// Pick up a finished release (called every frame)
+    pub fn poll_release(&mut self) {
+        let Some(rx) = &self.release_rx else {
+            return;
+        };
+        let (project_path, outcome) = match rx.try_recv() {
+            Ok(received) => received,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.release_rx = None;
+                self.executing_command = None;
+                return;
             }
-            KeyCode::Char('3')
-                if !matches!(self.input_mode, InputMode::AddingDirectory)
-                && !matches!(self.current_view, View::Overlay) =>
-            {
-                self.current_view = View::Detail;
-                self.selected_log = 0; // Reset log selection
-                self.command_result_display = None; // Clear command result on view switch
-                self.status_message = format!(
-                    "Viewing details for: {}",
-                    self.projects
-                        .get(self.selected_project)
-                        .map(|p| p.name.as_str())
-                        .unwrap_or("unknown")
-                );
-            }
-            KeyCode::Char('4')
-                if !matches!(self.input_mode, InputMode::AddingDirectory)
-                && !matches!(self.current_view, View::Overlay) =>
-            {
-                self.current_view = View::WorkspaceManager;
-                self.command_result_display = None; // Clear command result on view switch
-                self.status_message = "Managing workspace directories".to_string();
-            }
-            // Workspace Manager specific keys
-            KeyCode::Char('a') if matches!(self.current_view, View::WorkspaceManager) => {
-                if matches!(self.input_mode, InputMode::Normal) {
-                    self.input_mode = InputMode::AddingDirectory;
-                    self.input_buffer.clear();
-                    self.fuzzy_browsing = false;
-                    self.editing_workspace_index = None;
-                    self.update_fuzzy_matches();
-                    self.status_message =
-                        "Type path, use ↑↓ to browse matches, Tab/Enter to select".to_string();
-                }
-            }
-            KeyCode::Char('e') if matches!(self.current_view, View::WorkspaceManager) => {
-                if matches!(self.input_mode, InputMode::Normal) {
-                    if let Some(workspace) = self.workspace_directories.get(self.selected_workspace)
-                    {
-                        if workspace.is_primary {
-                            self.status_message =
-                                "✗ Cannot edit primary workspace (use config file)".to_string();
-                        } else {
-                            // Enter edit mode with current path pre-filled
-                            self.input_mode = InputMode::AddingDirectory;
-                            self.input_buffer = workspace.path.clone();
-                            self.fuzzy_browsing = false;
-                            self.editing_workspace_index = Some(self.selected_workspace);
-                            self.update_fuzzy_matches();
-                            self.status_message =
-                                "Editing path - use ↑↓ to browse matches, Tab/Enter to save"
-                                    .to_string();
-                        }
+        };
+        self.release_rx = None;
+        self.executing_command = None;
+
+        self.command_result_display = Some((outcome.is_ok(), Instant::now()));
+        match outcome {
+            Ok(summary) => self.status_message = format!("✓ {}", summary),
+            Err(e) => self.status_message = format!("✗ {:#}", e),
+        }
+        self.refresh_project_state(&project_path);
+    }
+
+    /// Handle git commit form submission
+    fn submit_git_commit(&mut self, values: std::collections::HashMap<String, crate::forms::FormValue>) {
+        use crate::forms::FormValue;
+
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+
+        let text = |id: &str| match values.get(id) {
+            Some(FormValue::Text(value)) => value.clone(),
+            _ => String::new(),
+        };
+        let amend = matches!(values.get("amend"), Some(FormValue::Bool(true)));
+
+        match crate::state::git_ops::commit(&project_path, &text("subject"), &text("body"), amend) {
+            Ok(summary) => self.status_message = format!("✓ {}", summary),
+            Err(e) => self.status_message = format!("✗ Commit failed: {}", e),
+        }
+        self.refresh_project_state(&project_path);
+    }
+
+    /// Handle worktree creation form submission
+    fn submit_git_worktree(&mut self, values: std::collections::HashMap<String, crate::forms::FormValue>) {
+        use crate::forms::FormValue;
+
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+
+        let text = |id: &str| match values.get(id) {
+            Some(FormValue::Text(value)) => value.trim().to_string(),
+            _ => String::new(),
+        };
+        let branch = text("branch");
+        if branch.is_empty() {
+            self.status_message = "✗ Branch name is required".to_string();
+            return;
+        }
+
+        match crate::state::git_ops::add_worktree(&project_path, &branch, &text("directory")) {
+            Ok(path) => {
+                let path = path.to_string_lossy().to_string();
+                self.hotload();
+                // Select the new sibling project if discovery picked it up
+                match self.projects.iter().position(|p| p.path == path) {
+                    Some(index) => {
+                        self.selected_project = index;
+                        self.project_list_state.select(Some(index));
+                        self.status_message = format!("✓ Created worktree {} on {}", path, branch);
                     }
-                }
-            }
-            KeyCode::Char('d') if matches!(self.current_view, View::WorkspaceManager) => {
-                if matches!(self.input_mode, InputMode::Normal) {
-                    if let Some(workspace) = self.workspace_directories.get(self.selected_workspace)
-                    {
-                        if workspace.is_primary {
-                            self.status_message = "✗ Cannot remove primary workspace".to_string();
-                        } else {
-                            let path = workspace.path.clone();
-                            match self.remove_workspace(&path) {
-                                Ok(_) => {
-                                    self.status_message = format!("✓ Removed {}", path);
-                                }
-                                Err(e) => {
-                                    self.status_message = format!("✗ Error: {}", e);
-                                }
-                            }
-                        }
+                    None => {
+                        self.status_message = format!("✓ Created worktree {} on {} (outside the scanned workspaces)", path, branch);
                     }
                 }
             }
-            KeyCode::Char('t')
-                if matches!(self.current_view, View::CommandPalette)
-                    && matches!(self.input_mode, InputMode::Normal) =>
-            {
-                self.cycle_target_workspace();
+            Err(e) => self.status_message = format!("✗ {:#}", e),
+        }
+    }
+
+    /// Handle keys for the log viewer overlay
+    ///
+    /// Returns true if the key was consumed. Runs before the global key map so
+    /// that search input can contain characters like `q` and `r`.
+    fn handle_log_viewer_key(&mut self, key: KeyCode) -> bool {
+        use log_viewer::LogDocument;
+
+        let Some(OverlayContent::LogViewer {
+            log_path,
+            scroll_offset,
+            search,
+            follow,
+            stream,
+            ..
+        }) = &mut self.active_overlay
+        else {
+            return false;
+        };
+
+        // Search input mode: capture everything until Enter/Esc
+        if search.editing {
+            match key {
+                KeyCode::Enter => {
+                    search.editing = false;
+                    search.current = 0;
+                }
+                KeyCode::Esc => {
+                    search.editing = false;
+                    search.query.clear();
+                }
+                KeyCode::Backspace => {
+                    search.query.pop();
+                }
+                KeyCode::Char(c) => search.query.push(c),
+                _ => return true,
             }
-            KeyCode::Char('l')
-                if matches!(self.current_view, View::Detail)
-                    && matches!(self.input_mode, InputMode::Normal) =>
-            {
-                // Open log viewer overlay
-                if let Some(project) = self.get_selected_project() {
-                    let logs = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
-                    if let Some(log) = logs.get(self.selected_log) {
-                        // Use new overlay API
-                        self.open_overlay(OverlayContent::LogViewer {
-                            log_path: log.path.clone(),
-                            scroll_offset: 0,
-                            project_path: project.path.clone(),
-                        });
-                        self.status_message = format!("Viewing: {}", log.filename);
-                    } else {
-                        self.status_message = "✗ No logs available".to_string();
-                    }
+
+            if !search.editing && !search.query.is_empty() {
+                let doc = LogDocument::load(log_path);
+                let lines = doc.filtered(*stream);
+                let matches = log_viewer::find_matches(&lines, &search.query);
+                if let Some(first) = matches.first() {
+                    *scroll_offset = *first;
+                    *follow = false;
+                    self.status_message =
+                        format!("{} matches for '{}'", matches.len(), search.query);
                 } else {
-                    self.status_message = "✗ No project selected".to_string();
+                    self.status_message = format!("No matches for '{}'", search.query);
                 }
             }
-            KeyCode::Char('o')
-                if matches!(self.current_view, View::Detail)
-                    && matches!(self.input_mode, InputMode::Normal) =>
-            {
-                // Open selected log file in external editor
-                if let Some(project) = self.get_selected_project() {
-                    let logs = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
-                    if let Some(log) = logs.get(self.selected_log) {
-                        let editor = crate::exec::get_default_editor();
-                        let log_path = log.path.to_string_lossy().to_string();
+            return true;
+        }
 
-                        // Set pending editor request (will be handled in main loop)
-                        self.pending_editor = Some((editor, log_path));
-                    } else {
-                        self.status_message = "✗ No logs available".to_string();
-                    }
+        let page = log_viewer::page_height();
+        let doc = LogDocument::load(log_path);
+        let lines = doc.filtered(*stream);
+        let max_offset = lines.len().saturating_sub(page);
+
+        match key {
+            KeyCode::Char('/') => {
+                search.editing = true;
+                search.query.clear();
+                self.status_message =
+                    "Search: type a pattern, Enter to confirm, Esc to cancel".to_string();
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                let matches = log_viewer::find_matches(&lines, &search.query);
+                if matches.is_empty() {
+                    self.status_message = "No search matches".to_string();
                 } else {
-                    self.status_message = "✗ No project selected".to_string();
+                    search.current = if key == KeyCode::Char('n') {
+                        (search.current + 1) % matches.len()
+                    } else {
+                        (search.current + matches.len() - 1) % matches.len()
+                    };
+                    *scroll_offset = matches[search.current];
+                    *follow = false;
+                    self.status_message = format!("Match {}/{}", search.current + 1, matches.len());
                 }
             }
-            KeyCode::Esc if matches!(self.current_view, View::Overlay) => {
-                // Close any overlay and return to previous view
-                let overlay_type = self.active_overlay.as_ref().map(|o| match o {
-                    OverlayContent::LogViewer { .. } => "log viewer",
-                    OverlayContent::Form { .. } => "form",
-                });
-                self.close_overlay();
-                if let Some(name) = overlay_type {
-                    self.status_message = format!("Closed {}", name);
-                }
+            KeyCode::Char('g') | KeyCode::Home => {
+                *scroll_offset = 0;
+                *follow = false;
             }
-            KeyCode::Esc
-                if matches!(
-                    self.input_mode,
-                    InputMode::AddingDirectory | InputMode::EditingCommand
-                ) =>
-            {
-                self.input_mode = InputMode::Normal;
-                self.input_buffer.clear();
-                self.editing_workspace_index = None;
-                self.status_message = "Cancelled".to_string();
+            KeyCode::Char('G') | KeyCode::End => {
+                *scroll_offset = max_offset;
             }
-            KeyCode::Backspace
-                if matches!(
-                    self.input_mode,
-                    InputMode::AddingDirectory | InputMode::EditingCommand
-                ) =>
-            {
-                self.input_buffer.pop();
-                if matches!(self.input_mode, InputMode::AddingDirectory) {
-                    self.fuzzy_browsing = false;
-                    self.update_fuzzy_matches();
+            KeyCode::Char('e') => match log_viewer::first_error(&lines) {
+                Some(line) => {
+                    *scroll_offset = line;
+                    *follow = false;
+                    self.status_message = format!("First error at line {}", line + 1);
                 }
+                None => self.status_message = "No errors found".to_string(),
+            },
+            KeyCode::Char('f') => {
+                *follow = !*follow;
+                self.status_message = if *follow {
+                    "Follow mode on".to_string()
+                } else {
+                    "Follow mode off".to_string()
+                };
             }
-            KeyCode::Backspace if matches!(self.current_view, View::Overlay) => {
-                // Handle backspace in overlay form fields
-                if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay {
-                    if let Some(field) = form.current_field_mut() {
-                        field.handle_backspace();
-                    }
-                }
+            KeyCode::Char('s') => {
+                *stream = stream.next();
+                *scroll_offset = 0;
+                search.current = 0;
+                self.status_message = format!("Showing {}", stream.as_str());
             }
-            KeyCode::Tab if matches!(self.input_mode, InputMode::AddingDirectory) => {
-                // If browsing matches, select current match
-                if self.fuzzy_browsing && !self.fuzzy_matches.is_empty() {
-                    let selected = self.fuzzy_matches[self.fuzzy_selected].clone();
-                    // Add trailing slash to match tab completion behavior
-                    self.input_buffer = if selected.ends_with('/') {
-                        selected
-                    } else {
-                        selected + "/"
-                    };
-                    self.fuzzy_browsing = false;
-                    self.status_message = format!("Selected: {}", self.input_buffer);
-                    self.update_fuzzy_matches();
-                } else if let Some(completed) = self.complete_path(&self.input_buffer.clone()) {
-                    self.input_buffer = completed;
-                    self.update_fuzzy_matches();
-                }
+            KeyCode::Up => {
+                *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(1);
+                *follow = false;
             }
-            KeyCode::Char(c)
-                if matches!(
-                    self.input_mode,
-                    InputMode::AddingDirectory | InputMode::EditingCommand
-                ) =>
-            {
-                self.input_buffer.push(c);
-                if matches!(self.input_mode, InputMode::AddingDirectory) {
-                    self.fuzzy_browsing = false;
-                    self.update_fuzzy_matches();
-                }
+            KeyCode::Down => *scroll_offset = (*scroll_offset + 1).min(max_offset),
+            KeyCode::PageUp => {
+                *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(page);
+                *follow = false;
             }
-            KeyCode::Char(c) if matches!(self.current_view, View::Overlay) && c != ' ' => {
-                // Handle character input in overlay form fields (space is handled separately)
-                if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay {
-                    if let Some(field) = form.current_field_mut() {
-                        field.handle_char(c);
+            KeyCode::PageDown => *scroll_offset = (*scroll_offset + page).min(max_offset),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Handle keys for the log diff overlay; returns true if the key was consumed
+    fn handle_log_diff_key(&mut self, key: KeyCode) -> bool {
+        let Some(OverlayContent::LogDiff {
+            diff,
+            scroll_offset,
+            side_by_side,
+            ..
+        }) = &mut self.active_overlay
+        else {
+            return false;
+        };
+
+        let page = log_viewer::page_height();
+        let rows = if *side_by_side { diff.side_by_side().len() } else { diff.lines.len() };
+        let max_offset = rows.saturating_sub(page);
+
+        match key {
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                let hunks = diff_hunk_rows(diff, *side_by_side);
+                let target = if key == KeyCode::Char('n') {
+                    hunks.iter().find(|&&row| row > *scroll_offset).or(hunks.first())
+                } else {
+                    hunks.iter().rev().find(|&&row| row < *scroll_offset).or(hunks.last())
+                };
+                match target {
+                    Some(&row) => {
+                        *scroll_offset = row;
+                        let index = hunks.iter().position(|&r| r == row).unwrap_or(0);
+                        self.status_message = format!("Change {}/{}", index + 1, hunks.len());
                     }
+                    None => self.status_message = "No differences".to_string(),
                 }
             }
-            KeyCode::Up => {
-                // Handle fuzzy match navigation when in input mode
-                if matches!(self.input_mode, InputMode::AddingDirectory)
-                    && !self.fuzzy_matches.is_empty()
-                {
-                    self.fuzzy_browsing = true;
-                    if self.fuzzy_selected > 0 {
-                        self.fuzzy_selected -= 1;
-                    }
+            KeyCode::Char('s') => {
+                // Keep roughly the same place when switching layouts
+                let line = if *side_by_side {
+                    diff.side_by_side().get(*scroll_offset).and_then(|(old, new)| old.or(*new)).unwrap_or(0)
                 } else {
-                    match self.current_view {
-                        View::ProjectBrowser => {
-                            if self.selected_project > 0 {
-                                self.selected_project -= 1;
-                                self.project_list_state.select(Some(self.selected_project));
-                                self.status_message = "Selected previous project".to_string();
-                            }
-                        }
-                        View::CommandPalette => {
-                            if self.selected_command > 0 {
-                                self.selected_command -= 1;
-                                self.command_list_state.select(Some(self.selected_command));
-                                self.status_message = "Selected previous command".to_string();
+                    *scroll_offset
+                };
+                *side_by_side = !*side_by_side;
+                *scroll_offset = if *side_by_side {
+                    diff.side_by_side()
+                        .iter()
+                        .position(|(old, new)| old.or(*new).is_some_and(|i| i >= line))
+                        .unwrap_or(0)
+                } else {
+                    line
+                };
+                self.status_message = if *side_by_side {
+                    "Side-by-side diff".to_string()
+                } else {
+                    "Unified diff".to_string()
+                };
+            }
+            KeyCode::Char('g') | KeyCode::Home => *scroll_offset = 0,
+            KeyCode::Char('G') | KeyCode::End => *scroll_offset = max_offset,
+            KeyCode::Up => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(1),
+            KeyCode::Down => *scroll_offset = (*scroll_offset + 1).min(max_offset),
+            KeyCode::PageUp => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(page),
+            KeyCode::PageDown => *scroll_offset = (*scroll_offset + page).min(max_offset),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Mark the selected log for diffing, or diff it against the marked one
+    fn mark_log_for_diff(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let logs = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
+        let Some(log) = logs.get(self.selected_log) else {
+            self.status_message = "✗ No logs available".to_string();
+            return;
+        };
+
+        let Some(marked) = self.diff_mark.take() else {
+            self.diff_mark = Some(log.path.clone());
+            self.status_message = format!("Marked {} - select another log and press d to diff", log.filename);
+            return;
+        };
+        if marked == log.path {
+            self.status_message = "Diff mark cleared".to_string();
+            return;
+        }
+
+        // Older run on the left, newer on the right
+        let (old_path, new_path) = match logs.iter().find(|l| l.path == marked) {
+            Some(other) if other.timestamp > log.timestamp => (log.path.clone(), marked),
+            _ => (marked, log.path.clone()),
+        };
+        let old_content = crate::fs::read_log(&old_path).unwrap_or_default();
+        let new_content = crate::fs::read_log(&new_path).unwrap_or_default();
+        let diff = crate::fs::log_diff::LogDiff::between(&old_content, &new_content);
+
+        let file_name = |p: &PathBuf| p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        self.status_message = if diff.is_identical() {
+            "Runs are identical after normalization".to_string()
+        } else {
+            format!("+{} -{} lines", diff.added(), diff.removed())
+        };
+        self.open_overlay(OverlayContent::LogDiff {
+            diff: Box::new(diff),
+            old_name: file_name(&old_path),
+            new_name: file_name(&new_path),
+            scroll_offset: 0,
+            side_by_side: false,
+        });
+    }
+
+    /// Open the Changes overlay for the selected project
+    fn open_git_changes(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+
+        match crate::state::git_diff::list_changes(&project_path) {
+            Ok(changes) => {
+                // Start on whichever side has something to show
+                let staged = changes.iter().any(|c| c.has_staged()) && !changes.iter().any(|c| c.has_unstaged());
+                self.status_message = if changes.is_empty() {
+                    "✓ Working tree clean".to_string()
+                } else {
+                    format!("{} changed files", changes.len())
+                };
+                self.open_overlay(OverlayContent::GitChanges {
+                    project_path,
+                    changes,
+                    selected: 0,
+                    staged,
+                    diff: None,
+                    scroll_offset: 0,
+                });
+            }
+            Err(e) => self.status_message = format!("✗ {}", e),
+        }
+    }
+
+    /// Handle keys for the Changes overlay; returns true if the key was consumed
+    fn handle_git_changes_key(&mut self, key: KeyCode) -> bool {
+        use crate::state::git_diff;
+
+        if !matches!(self.active_overlay, Some(OverlayContent::GitChanges { .. })) {
+            return false;
+        }
+        match key {
+            KeyCode::Char(' ') => {
+                self.toggle_git_staged();
+                return true;
+            }
+            KeyCode::Char('C') => {
+                self.close_overlay();
+                self.open_commit_form();
+                return true;
+            }
+            KeyCode::Char('b') => {
+                self.close_overlay();
+                self.open_branches();
+                return true;
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                self.git_stash(key == KeyCode::Char('Z'));
+                return true;
+            }
+            _ => {}
+        }
+
+        let Some(OverlayContent::GitChanges {
+            project_path,
+            changes,
+            selected,
+            staged,
+            diff,
+            scroll_offset,
+        }) = &mut self.active_overlay
+        else {
+            return false;
+        };
+
+        let visible = visible_changes(changes, *staged);
+        let page = log_viewer::page_height();
+
+        match key {
+            KeyCode::Char('s') => {
+                *staged = !*staged;
+                let current = visible.get(*selected).map(|c| c.path.clone());
+                let now_visible = visible_changes(changes, *staged);
+                *selected = current
+                    .and_then(|path| now_visible.iter().position(|c| c.path == path))
+                    .unwrap_or(0);
+                *scroll_offset = 0;
+                if diff.is_some() {
+                    *diff = now_visible
+                        .get(*selected)
+                        .and_then(|change| git_diff::file_diff(project_path, change, *staged).ok())
+                        .map(Box::new);
+                }
+                self.status_message = if *staged {
+                    format!("Staged changes ({} files)", now_visible.len())
+                } else {
+                    format!("Unstaged changes ({} files)", now_visible.len())
+                };
+            }
+            KeyCode::Char('o') => {
+                let Some(change) = visible.get(*selected) else {
+                    return true;
+                };
+                // Status paths are relative to the repository root
+                let path = std::path::Path::new(&git_diff::repo_root(project_path)).join(&change.path);
+                if !path.exists() {
+                    self.status_message = format!("✗ File not found: {}", change.path);
+                    return true;
+                }
+                let line = diff.as_ref().and_then(|d| d.new_line_at(*scroll_offset));
+                self.pending_editor = Some(EditorRequest {
+                    editor: crate::exec::get_default_editor(),
+                    file_path: path.to_string_lossy().to_string(),
+                    line,
+                    column: None,
+                });
+            }
+            _ if diff.is_some() => {
+                let Some(file_diff) = diff.as_ref() else {
+                    return false;
+                };
+                let rows = file_diff.lines().len();
+                let max_offset = rows.saturating_sub(page);
+                match key {
+                    KeyCode::Esc | KeyCode::Backspace | KeyCode::Left => *diff = None,
+                    KeyCode::Char('n') | KeyCode::Char('N') => {
+                        let hunks = file_diff.hunk_rows();
+                        let target = if key == KeyCode::Char('n') {
+                            hunks.iter().find(|&&row| row > *scroll_offset).or(hunks.first())
+                        } else {
+                            hunks.iter().rev().find(|&&row| row < *scroll_offset).or(hunks.last())
+                        };
+                        if let Some(&row) = target {
+                            *scroll_offset = row;
+                            let index = hunks.iter().position(|&r| r == row).unwrap_or(0);
+                            self.status_message = format!("Hunk {}/{}", index + 1, hunks.len());
+                        }
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => *scroll_offset = 0,
+                    KeyCode::Char('G') | KeyCode::End => *scroll_offset = max_offset,
+                    KeyCode::Up => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(1),
+                    KeyCode::Down => *scroll_offset = (*scroll_offset + 1).min(max_offset),
+                    KeyCode::PageUp => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(page),
+                    KeyCode::PageDown => *scroll_offset = (*scroll_offset + page).min(max_offset),
+                    _ => return false,
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *selected = (*selected + 1).min(visible.len().saturating_sub(1)),
+            KeyCode::Char('g') | KeyCode::Home => *selected = 0,
+            KeyCode::Char('G') | KeyCode::End => *selected = visible.len().saturating_sub(1),
+            KeyCode::Enter | KeyCode::Right => {
+                let Some(change) = visible.get(*selected) else {
+                    return true;
+                };
+                match git_diff::file_diff(project_path, change, *staged) {
+                    Ok(file_diff) if file_diff.is_empty() => {
+                        self.status_message = format!("No textual changes in {}", change.path);
+                    }
+                    Ok(file_diff) => {
+                        // Start at the first hunk
+                        *scroll_offset = file_diff.hunk_rows().first().copied().unwrap_or(0);
+                        *diff = Some(Box::new(file_diff));
+                    }
+                    Err(e) => self.status_message = format!("✗ {}", e),
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Stage or unstage the selected file in the Changes overlay, or the hunk
+    /// at the top of the screen while a diff is open
+    fn toggle_git_staged(&mut self) {
+        use crate::state::git_ops;
+
+        let Some(OverlayContent::GitChanges {
+            project_path,
+            changes,
+            selected,
+            staged,
+            diff,
+            scroll_offset,
+        }) = &self.active_overlay
+        else {
+            return;
+        };
+        let Some(change) = visible_changes(changes, *staged).get(*selected).copied() else {
+            return;
+        };
+        let project_path = project_path.clone();
+
+        let result = match diff {
+            Some(file_diff) if !change.is_untracked() => {
+                let hunk = file_diff.hunk_at(*scroll_offset).unwrap_or(0);
+                let action = if *staged { "Unstaged" } else { "Staged" };
+                git_ops::apply_hunk(&project_path, file_diff, hunk, *staged)
+                    .map(|_| format!("{} hunk {}/{} of {}", action, hunk + 1, file_diff.hunks.len(), change.path))
+            }
+            _ if *staged => git_ops::unstage_file(&project_path, change).map(|_| format!("Unstaged {}", change.path)),
+            _ => git_ops::stage_file(&project_path, change).map(|_| format!("Staged {}", change.path)),
+        };
+
+        self.status_message = match result {
+            Ok(message) => format!("✓ {}", message),
+            Err(e) => format!("✗ {}", e),
+        };
+        self.reload_git_changes();
+        self.refresh_project_state(&project_path);
+    }
+
+    /// Re-read the file list (and open diff) of the Changes overlay, keeping the selection
+    fn reload_git_changes(&mut self) {
+        use crate::state::git_diff;
+
+        let Some(OverlayContent::GitChanges {
+            project_path,
+            changes,
+            selected,
+            staged,
+            diff,
+            scroll_offset,
+        }) = &mut self.active_overlay
+        else {
+            return;
+        };
+
+        let current = visible_changes(changes, *staged).get(*selected).map(|c| c.path.clone());
+        match git_diff::list_changes(project_path) {
+            Ok(latest) => *changes = latest,
+            Err(e) => {
+                self.status_message = format!("✗ {}", e);
+                return;
+            }
+        }
+
+        let visible = visible_changes(changes, *staged);
+        *selected = current
+            .as_ref()
+            .and_then(|path| visible.iter().position(|c| &c.path == path))
+            .unwrap_or(*selected)
+            .min(visible.len().saturating_sub(1));
+
+        if diff.is_some() {
+            let reloaded = visible
+                .get(*selected)
+                .filter(|c| Some(&c.path) == current.as_ref())
+                .and_then(|c| git_diff::file_diff(project_path, c, *staged).ok())
+                .filter(|d| !d.is_empty());
+            match reloaded {
+                Some(file_diff) => {
+                    *scroll_offset = (*scroll_offset).min(file_diff.lines().len().saturating_sub(1));
+                    *diff = Some(Box::new(file_diff));
+                }
+                None => {
+                    *diff = None;
+                    *scroll_offset = 0;
+                }
+            }
+        }
+    }
+
+    /// Stash all changes of the Changes overlay's project, or pop the latest stash
+    fn git_stash(&mut self, pop: bool) {
+        use crate::state::git_ops;
+
+        let Some(OverlayContent::GitChanges { project_path, .. }) = &self.active_overlay else {
+            return;
+        };
+        let project_path = project_path.clone();
+
+        let result = if pop {
+            git_ops::stash_pop(&project_path).map(|_| "Popped stash")
+        } else {
+            git_ops::stash_push(&project_path).map(|_| "Stashed changes")
+        };
+        self.status_message = match result {
+            Ok(message) => format!("✓ {}", message),
+            Err(e) => format!("✗ {}", e),
+        };
+        self.reload_git_changes();
+        self.refresh_project_state(&project_path);
+    }
+
+    /// Open the commit form for the selected project
+    fn open_commit_form(&mut self) {
+        let form = crate::forms::Form::new("Commit", crate::forms::FormType::GitCommit)
+            .description("Commit staged changes (leave the subject empty to amend without rewording)")
+            .text_input("subject", "Subject", "Short summary of the change")
+            .text_area("body", "Body", "Why the change was made...", 6)
+            .checkbox("amend", "Amend previous commit");
+
+        self.open_overlay(OverlayContent::Form {
+            form,
+            position: OverlayPosition::Centered { width: 80, height: 35 },
+        });
+        self.status_message = "Committing - press Enter to submit, Esc to cancel".to_string();
+    }
+
+    /// Write a byte.toml for the selected implicit project
+    fn adopt_selected_project(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        if !project.implicit {
+            self.status_message = format!("{} already has a byte.toml", project.name);
+            return;
+        }
+        let (name, path) = (project.name.clone(), project.path.clone());
+
+        match crate::projects::adopt_project(std::path::Path::new(&path)) {
+            Ok(_) => {
+                self.hotload();
+                // The project keeps its place in the list only by path
+                if let Some(index) = self.projects.iter().position(|p| p.path == path) {
+                    self.selected_project = index;
+                    self.project_list_state.select(Some(index));
+                }
+                self.status_message = format!("✓ Adopted {}: wrote byte.toml", name);
+            }
+            Err(e) => self.status_message = format!("✗ Adopt failed: {}", e),
+        }
+    }
+
+    /// Open the branch picker for the selected project
+    fn open_branches(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+
+        match crate::state::git_ops::list_branches(&project_path) {
+            Ok(branches) => {
+                self.status_message = format!("{} branches", branches.len());
+                self.open_overlay(OverlayContent::Branches {
+                    project_path,
+                    branches,
+                    selected: 0,
+                    new_branch: None,
+                });
+            }
+            Err(e) => self.status_message = format!("✗ {}", e),
+        }
+    }
+
+    /// Handle keys for the branch picker; returns true if the key was consumed
+    fn handle_branches_key(&mut self, key: KeyCode) -> bool {
+        use crate::state::git_ops;
+
+        let Some(OverlayContent::Branches {
+            project_path,
+            branches,
+            selected,
+            new_branch,
+        }) = &mut self.active_overlay
+        else {
+            return false;
+        };
+        let project_path = project_path.clone();
+
+        // Typing a new branch name
+        if let Some(name) = new_branch {
+            match key {
+                KeyCode::Char(c) if !c.is_whitespace() => name.push(c),
+                KeyCode::Backspace => {
+                    name.pop();
+                }
+                KeyCode::Esc => {
+                    *new_branch = None;
+                    self.status_message = "Cancelled".to_string();
+                }
+                KeyCode::Enter if !name.is_empty() => {
+                    let name = name.clone();
+                    match git_ops::create_branch(&project_path, &name) {
+                        Ok(()) => {
+                            self.close_overlay();
+                            self.status_message = format!("✓ Created and switched to {}", name);
+                        }
+                        Err(e) => self.status_message = format!("✗ {}", e),
+                    }
+                    self.refresh_project_state(&project_path);
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *selected = (*selected + 1).min(branches.len().saturating_sub(1)),
+            KeyCode::Char('n') => {
+                *new_branch = Some(String::new());
+                self.status_message = "New branch name - Enter to create, Esc to cancel".to_string();
+            }
+            KeyCode::Enter => {
+                let Some(branch) = branches.get(*selected) else {
+                    return true;
+                };
+                if branch.current {
+                    self.status_message = format!("Already on {}", branch.name);
+                    return true;
+                }
+                let name = branch.name.clone();
+                match git_ops::switch_branch(&project_path, &name) {
+                    Ok(()) => {
+                        self.close_overlay();
+                        self.status_message = format!("✓ Switched to {}", name);
+                    }
+                    Err(e) => self.status_message = format!("✗ {}", e),
+                }
+                self.refresh_project_state(&project_path);
+            }
+            KeyCode::Char('D') => {
+                let Some(branch) = branches.get(*selected) else {
+                    return true;
+                };
+                if branch.current {
+                    self.status_message = "✗ Can't delete the current branch".to_string();
+                    return true;
+                }
+                let name = branch.name.clone();
+                match git_ops::delete_branch(&project_path, &name) {
+                    Ok(()) => {
+                        branches.remove(*selected);
+                        *selected = (*selected).min(branches.len().saturating_sub(1));
+                        self.status_message = format!("✓ Deleted {}", name);
+                    }
+                    Err(e) => self.status_message = format!("✗ {}", e),
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Fetch (and fast-forward or push) projects in the background; results
+    /// open the sync summary
+    fn start_sync(&mut self, projects: Vec<(String, String)>, mode: crate::state::git_sync::SyncMode) {
+        if self.sync_rx.is_some() {
+            self.status_message = "Sync already running".to_string();
+            return;
+        }
+        if projects.is_empty() {
+            self.status_message = "✗ No projects to sync".to_string();
+            return;
+        }
+
+        let action = match (mode.pull, mode.push) {
+            (true, _) => "pull --ff-only",
+            (false, true) => "push",
+            (false, false) => "fetch",
+        };
+        let label = format!("git {} ({} projects)", action, projects.len());
+        self.executing_command = Some(label);
+        self.build_animation_frame = 0;
+        self.build_animation_start = Some(Instant::now());
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.sync_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send((mode, crate::state::git_sync::sync_all(&projects, mode)));
+        });
+    }
+
+    /// Pick up finished sync results, showing their summary once no form or
+    /// overlay is open (called every frame)
+    pub fn poll_sync(&mut self) {
+        if let Some(rx) = &self.sync_rx {
+            let (mode, results) = match rx.try_recv() {
+                Ok(received) => received,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => Default::default(),
+            };
+            self.sync_rx = None;
+            self.executing_command = None;
+
+            // Ahead/behind changed with the fetch
+            for result in &results {
+                self.refresh_project_state(&result.path);
+            }
+            self.status_message = crate::state::git_sync::summarize(&results);
+            self.command_result_display = Some((!results.iter().any(|r| r.outcome.is_problem()), Instant::now()));
+
+            // A summary from an earlier sync gives way; anything else (a form
+            // being filled in) is left alone until it closes
+            if matches!(self.active_overlay, Some(OverlayContent::SyncSummary { .. })) {
+                self.close_overlay();
+            } else if self.active_overlay.is_some() {
+                self.status_message.push_str(" - summary opens when this closes");
+            }
+            self.pending_sync_summary = Some((mode, results));
+        }
+
+        if self.active_overlay.is_none()
+            && let Some((mode, results)) = self.pending_sync_summary.take()
+        {
+            self.open_overlay(OverlayContent::SyncSummary {
+                results,
+                selected: 0,
+                mode,
+            });
+        }
+    }
+
+    /// Handle keys for the sync summary; returns true if the key was consumed
+    fn handle_sync_summary_key(&mut self, key: KeyCode) -> bool {
+        let Some(OverlayContent::SyncSummary { results, selected, .. }) = &mut self.active_overlay else {
+            return false;
+        };
+
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *selected = (*selected + 1).min(results.len().saturating_sub(1)),
+            KeyCode::Char('P') => {
+                // Fast-forward the projects that are behind
+                let behind: Vec<(String, String)> = results
+                    .iter()
+                    .filter(|r| matches!(r.outcome, crate::state::git_sync::SyncOutcome::Behind(_)))
+                    .map(|r| (r.name.clone(), r.path.clone()))
+                    .collect();
+                if behind.is_empty() {
+                    self.status_message = "Nothing to fast-forward".to_string();
+                } else {
+                    self.start_sync(behind, crate::state::git_sync::SyncMode { pull: true, push: false });
+                }
+            }
+            KeyCode::Char('U') => {
+                // Push the projects that are ahead
+                let ahead: Vec<(String, String)> = results
+                    .iter()
+                    .filter(|r| matches!(r.outcome, crate::state::git_sync::SyncOutcome::Ahead(_)))
+                    .map(|r| (r.name.clone(), r.path.clone()))
+                    .collect();
+                if ahead.is_empty() {
+                    self.status_message = "Nothing to push".to_string();
+                } else {
+                    self.start_sync(ahead, crate::state::git_sync::SyncMode { pull: false, push: true });
+                }
+            }
+            KeyCode::Enter => {
+                // Jump to the project's details
+                let Some(path) = results.get(*selected).map(|r| r.path.clone()) else {
+                    return true;
+                };
+                if let Some(index) = self.projects.iter().position(|p| p.path == path) {
+                    self.close_overlay();
+                    self.selected_project = index;
+                    self.project_list_state.select(Some(index));
+                    self.current_view = View::Detail;
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Open the failing/flaky test report for the selected project
+    fn open_test_results(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let Some(state) = state::test_results::load_test_state(&project.path) else {
+            self.status_message = "✗ No test results yet - run a test task first".to_string();
+            return;
+        };
+
+        let run = &state.last_run;
+        self.status_message = format!("{} passed, {} failed, {} ignored", run.passed, run.failed, run.ignored);
+        self.open_overlay(OverlayContent::TestResults {
+            state: Box::new(state),
+            scroll_offset: 0,
+        });
+    }
+
+    /// Handle keys for the test results overlay; returns true if the key was consumed
+    fn handle_test_results_key(&mut self, key: KeyCode) -> bool {
+        let Some(OverlayContent::TestResults { state, scroll_offset }) = &mut self.active_overlay else {
+            return false;
+        };
+
+        let rows = test_report_rows(state);
+        let page = log_viewer::page_height();
+        let max_offset = rows.len().saturating_sub(page);
+
+        match key {
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                let failures: Vec<usize> = rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| matches!(row, TestReportRow::Failure(_)))
+                    .map(|(i, _)| i)
+                    .collect();
+                let target = if key == KeyCode::Char('n') {
+                    failures.iter().find(|&&row| row > *scroll_offset).or(failures.first())
+                } else {
+                    failures.iter().rev().find(|&&row| row < *scroll_offset).or(failures.last())
+                };
+                match target {
+                    Some(&row) => *scroll_offset = row,
+                    None => self.status_message = "No failing tests".to_string(),
+                }
+            }
+            KeyCode::Char('g') | KeyCode::Home => *scroll_offset = 0,
+            KeyCode::Char('G') | KeyCode::End => *scroll_offset = max_offset,
+            KeyCode::Up => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(1),
+            KeyCode::Down => *scroll_offset = (*scroll_offset + 1).min(max_offset),
+            KeyCode::PageUp => *scroll_offset = (*scroll_offset).min(max_offset).saturating_sub(page),
+            KeyCode::PageDown => *scroll_offset = (*scroll_offset + page).min(max_offset),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Open the Problems panel for the selected project
+    fn open_problems(&mut self) {
+        let Some(project) = self.get_selected_project() else {
+            self.status_message = "✗ No project selected".to_string();
+            return;
+        };
+        let project_path = project.path.clone();
+        let problems = crate::fs::ProjectFileSystem::new(&project_path)
+            .ok()
+            .and_then(|fs| fs.latest_problems().ok())
+            .unwrap_or_default();
+
+        if problems.is_empty() {
+            self.status_message = "✓ No problems in the latest runs".to_string();
+            return;
+        }
+
+        let (errors, warnings) = crate::diagnostics::counts(
+            &problems.iter().map(|(_, d)| d.clone()).collect::<Vec<_>>(),
+        );
+        self.status_message = format!("{} errors, {} warnings", errors, warnings);
+        self.open_overlay(OverlayContent::Problems {
+            project_path,
+            problems,
+            selected: 0,
+        });
+    }
+
+    /// Handle keys for the Problems panel; returns true if the key was consumed
+    fn handle_problems_key(&mut self, key: KeyCode) -> bool {
+        let Some(OverlayContent::Problems {
+            project_path,
+            problems,
+            selected,
+        }) = &mut self.active_overlay
+        else {
+            return false;
+        };
+
+        let last = problems.len().saturating_sub(1);
+        let page = log_viewer::page_height();
+
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => *selected = (*selected + 1).min(last),
+            KeyCode::PageUp => *selected = selected.saturating_sub(page),
+            KeyCode::PageDown => *selected = (*selected + page).min(last),
+            KeyCode::Char('g') | KeyCode::Home => *selected = 0,
+            KeyCode::Char('G') | KeyCode::End => *selected = last,
+            KeyCode::Enter => {
+                let Some((_, problem)) = problems.get(*selected) else {
+                    return true;
+                };
+                let path = problem.resolve(std::path::Path::new(project_path.as_str()));
+                if !path.exists() {
+                    self.status_message = format!("✗ File not found: {}", path.display());
+                    return true;
+                }
+                self.pending_editor = Some(EditorRequest {
+                    editor: crate::exec::get_default_editor(),
+                    file_path: path.to_string_lossy().to_string(),
+                    line: Some(problem.line),
+                    column: problem.column,
+                });
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if matches!(self.current_view, View::Overlay)
+            && (self.handle_log_viewer_key(key)
+                || self.handle_log_diff_key(key)
+                || self.handle_problems_key(key)
+                || self.handle_test_results_key(key)
+                || self.handle_git_changes_key(key)
+                || self.handle_branches_key(key)
+                || self.handle_sync_summary_key(key))
+        {
+            return;
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.quit(),
+            KeyCode::Char('r') | KeyCode::Char('R')
+                if !matches!(
+                    self.input_mode,
+                    InputMode::AddingDirectory | InputMode::EditingCommand
+                ) =>
+            {
+                self.hotload();
+                self.status_message = "✓ Reloaded all state from disk".to_string();
+            }
+            KeyCode::Char('n')
+                if matches!(self.current_view, View::ProjectBrowser)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                // New project creation form (from ProjectBrowser view)
+                // Load config to get workspace and template options
+                let (workspace_options, templates): (Vec<String>, Vec<String>) =
+                    if let Ok(config) = crate::config::Config::load() {
+                        (
+                            std::iter::once(config.global.workspace.path.clone())
+                                .chain(config.global.workspace.registered.clone())
+                                .collect(),
+                            crate::templates::discover_templates(&config.global)
+                                .into_iter()
+                                .map(|t| t.name)
+                                .collect(),
+                        )
+                    } else {
+                        (vec!["~/projects".to_string()], Vec::new()) // Fallback
+                    };
+
+                let mut form = crate::forms::Form::new("Create New Project", crate::forms::FormType::NewProject)
+                    .description("Initialize a new project with byte scaffolding")
+                    .select("workspace", "Target Workspace", workspace_options);
+                if !templates.is_empty() {
+                    // Ecosystem and type below only apply to the built-in scaffolding
+                    let options = std::iter::once(BUILTIN_TEMPLATE.to_string()).chain(templates).collect();
+                    form = form.select("template", "Template", options);
+                }
+                let form = form
+                    .select("ecosystem", "Ecosystem", vec![
+                        "rust".to_string(),
+                        "go".to_string(),
+                        "bun".to_string(),
+                        "python".to_string(),
+                        "node".to_string(),
+                        "deno".to_string(),
+                        "zig".to_string(),
+                        "go+bun".to_string(),
+                    ])
+                    .select("project_type", "Project Type", vec![
+                        "cli".to_string(),
+                        "web".to_string(),
+                        "lib".to_string(),
+                        "api".to_string(),
+                    ])
+                    .text_input("name", "Project Name", "my-project")
+                    .text_area("description", "Description (optional)", "A brief description...", 3);
+
+                // Use new overlay API for forms
+                self.open_overlay(OverlayContent::Form {
+                    form,
+                    position: OverlayPosition::Centered { width: 80, height: 40 },
+                });
+                self.status_message = "Creating new project - press Enter to submit, Esc to cancel".to_string();
+            }
+            KeyCode::Char('t')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                // Git tag creation form (from Details view with git status)
+                let form = crate::forms::Form::new("Create Git Tag", crate::forms::FormType::GitTag)
+                    .description("Tag a release (version tags like v1.2.0 must be valid semver)")
+                    .text_input("tag_name", "Tag Name", "v1.0.0")
+                    .text_area("message", "Tag Message", "Release notes...", 4)
+                    .checkbox("annotated", "Create annotated tag")
+                    .checkbox("bump", "Bump version in manifest and commit")
+                    .checkbox("push", "Push tag to remote")
+                    .text_input("remote", "Remote", "origin");
+
+                // Use new overlay API for forms
+                self.open_overlay(OverlayContent::Form {
+                    form,
+                    position: OverlayPosition::Centered { width: 80, height: 35 },
+                });
+                self.status_message = "Creating git tag - press Enter to submit, Esc to cancel".to_string();
+            }
+            // View switching keys - only when NOT in input mode or overlay view
+            KeyCode::Char('1')
+                if !matches!(self.input_mode, InputMode::AddingDirectory)
+                && !matches!(self.current_view, View::Overlay) =>
+            {
+                self.current_view = View::ProjectBrowser;
+                self.command_result_display = None; // Clear command result on view switch
+                self.status_message = "Viewing projects".to_string();
+            }
+            KeyCode::Char('2')
+                if !matches!(self.input_mode, InputMode::AddingDirectory)
+                && !matches!(self.current_view, View::Overlay) =>
+            {
+                self.current_view = View::CommandPalette;
+                self.update_commands();
+                self.command_result_display = None; // Clear command result on view switch
+                self.status_message = "Viewing commands".to_string();
+            }
+            KeyCode::Char('3')
+                if !matches!(self.input_mode, InputMode::AddingDirectory)
+                && !matches!(self.current_view, View::Overlay) =>
+            {
+                self.current_view = View::Detail;
+                self.selected_log = 0; // Reset log selection
+                self.diff_mark = None;
+                self.command_result_display = None; // Clear command result on view switch
+                self.status_message = format!(
+                    "Viewing details for: {}",
+                    self.projects
+                        .get(self.selected_project)
+                        .map(|p| p.name.as_str())
+                        .unwrap_or("unknown")
+                );
+            }
+            KeyCode::Char('4')
+                if !matches!(self.input_mode, InputMode::AddingDirectory)
+                && !matches!(self.current_view, View::Overlay) =>
+            {
+                self.current_view = View::WorkspaceManager;
+                self.command_result_display = None; // Clear command result on view switch
+                self.status_message = "Managing workspace directories".to_string();
+            }
+            // Workspace Manager specific keys
+            KeyCode::Char('a') if matches!(self.current_view, View::WorkspaceManager) => {
+                if matches!(self.input_mode, InputMode::Normal) {
+                    self.input_mode = InputMode::AddingDirectory;
+                    self.input_buffer.clear();
+                    self.fuzzy_browsing = false;
+                    self.editing_workspace_index = None;
+                    self.update_fuzzy_matches();
+                    self.status_message =
+                        "Type path, use ↑↓ to browse matches, Tab/Enter to select".to_string();
+                }
+            }
+            KeyCode::Char('e') if matches!(self.current_view, View::WorkspaceManager) => {
+                if matches!(self.input_mode, InputMode::Normal) {
+                    if let Some(workspace) = self.workspace_directories.get(self.selected_workspace)
+                    {
+                        if workspace.is_primary {
+                            self.status_message =
+                                "✗ Cannot edit primary workspace (use config file)".to_string();
+                        } else {
+                            // Enter edit mode with current path pre-filled
+                            self.input_mode = InputMode::AddingDirectory;
+                            self.input_buffer = workspace.path.clone();
+                            self.fuzzy_browsing = false;
+                            self.editing_workspace_index = Some(self.selected_workspace);
+                            self.update_fuzzy_matches();
+                            self.status_message =
+                                "Editing path - use ↑↓ to browse matches, Tab/Enter to save"
+                                    .to_string();
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('d') if matches!(self.current_view, View::WorkspaceManager) => {
+                if matches!(self.input_mode, InputMode::Normal) {
+                    if let Some(workspace) = self.workspace_directories.get(self.selected_workspace)
+                    {
+                        if workspace.is_primary {
+                            self.status_message = "✗ Cannot remove primary workspace".to_string();
+                        } else {
+                            let path = workspace.path.clone();
+                            match self.remove_workspace(&path) {
+                                Ok(_) => {
+                                    self.status_message = format!("✓ Removed {}", path);
+                                }
+                                Err(e) => {
+                                    self.status_message = format!("✗ Error: {}", e);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('t')
+                if matches!(self.current_view, View::CommandPalette)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.cycle_target_workspace();
+            }
+            KeyCode::Char('l')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                // Open log viewer overlay
+                if let Some(project) = self.get_selected_project() {
+                    let logs = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
+                    if let Some(log) = logs.get(self.selected_log) {
+                        // Use new overlay API
+                        self.open_overlay(OverlayContent::LogViewer {
+                            log_path: log.path.clone(),
+                            scroll_offset: 0,
+                            project_path: project.path.clone(),
+                            search: LogSearch::default(),
+                            follow: false,
+                            stream: StreamFilter::Both,
+                        });
+                        self.status_message = format!("Viewing: {}", log.filename);
+                    } else {
+                        self.status_message = "✗ No logs available".to_string();
+                    }
+                } else {
+                    self.status_message = "✗ No project selected".to_string();
+                }
+            }
+            KeyCode::Char('d')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.mark_log_for_diff();
+            }
+            KeyCode::Char('c')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.open_git_changes();
+            }
+            KeyCode::Char('b')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.open_branches();
+            }
+            KeyCode::Char('W')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                let form = crate::forms::Form::new("New Worktree", crate::forms::FormType::GitWorktree)
+                    .description("Check out a branch in a new worktree next to this repository (a sibling project)")
+                    .text_input("branch", "Branch", "feature/my-change (created if it doesn't exist)")
+                    .text_input("directory", "Directory", "<repo>-<branch> (optional)");
+
+                self.open_overlay(OverlayContent::Form {
+                    form,
+                    position: OverlayPosition::Centered { width: 80, height: 20 },
+                });
+                self.status_message = "Creating worktree - press Enter to submit, Esc to cancel".to_string();
+            }
+            KeyCode::Char('A')
+                if matches!(self.current_view, View::Detail | View::ProjectBrowser)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.adopt_selected_project();
+            }
+            KeyCode::Char('S')
+                if matches!(self.current_view, View::Detail | View::ProjectBrowser)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                // Browser: every project; Details: the selected one
+                let projects: Vec<(String, String)> = if matches!(self.current_view, View::Detail) {
+                    self.get_selected_project().into_iter().map(|p| (p.name.clone(), p.path.clone())).collect()
+                } else {
+                    self.projects.iter().map(|p| (p.name.clone(), p.path.clone())).collect()
+                };
+                self.start_sync(projects, crate::state::git_sync::SyncMode::default());
+            }
+            KeyCode::Char('T')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.open_test_results();
+            }
+            KeyCode::Char('p')
+                if matches!(self.current_view, View::Detail | View::ProjectBrowser)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                self.open_problems();
+            }
+            KeyCode::Char('o')
+                if matches!(self.current_view, View::Detail)
+                    && matches!(self.input_mode, InputMode::Normal) =>
+            {
+                // Open selected log file in external editor
+                if let Some(project) = self.get_selected_project() {
+                    let logs = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
+                    if let Some(log) = logs.get(self.selected_log) {
+                        let editor = crate::exec::get_default_editor();
+                        let log_path = log.path.to_string_lossy().to_string();
+
+                        // Set pending editor request (will be handled in main loop)
+                        self.pending_editor = Some(EditorRequest {
+                            editor,
+                            file_path: log_path,
+                            line: None,
+                            column: None,
+                        });
+                    } else {
+                        self.status_message = "✗ No logs available".to_string();
+                    }
+                } else {
+                    self.status_message = "✗ No project selected".to_string();
+                }
+            }
+            KeyCode::Esc if matches!(self.current_view, View::Overlay) => {
+                // Close any overlay and return to previous view
+                let overlay_type = self.active_overlay.as_ref().map(|o| match o {
+                    OverlayContent::LogViewer { .. } => "log viewer",
+                    OverlayContent::LogDiff { .. } => "log diff",
+                    OverlayContent::GitChanges { .. } => "changes",
+                    OverlayContent::Branches { .. } => "branches",
+                    OverlayContent::SyncSummary { .. } => "sync summary",
+                    OverlayContent::TestResults { .. } => "test results",
+                    OverlayContent::Problems { .. } => "problems",
+                    OverlayContent::Form { .. } => "form",
+                });
+                self.close_overlay();
+                // Abandoned template form: drop the template (and its clone)
+                if let Some((template, _, _)) = self.pending_template.take() {
+                    template.cleanup();
+                }
+                self.pending_trust = None;
+                if let Some(name) = overlay_type {
+                    self.status_message = format!("Closed {}", name);
+                }
+            }
+            KeyCode::Esc
+                if matches!(
+                    self.input_mode,
+                    InputMode::AddingDirectory | InputMode::EditingCommand
+                ) =>
+            {
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                self.editing_workspace_index = None;
+                self.status_message = "Cancelled".to_string();
+            }
+            KeyCode::Backspace
+                if matches!(
+                    self.input_mode,
+                    InputMode::AddingDirectory | InputMode::EditingCommand
+                ) =>
+            {
+                self.input_buffer.pop();
+                if matches!(self.input_mode, InputMode::AddingDirectory) {
+                    self.fuzzy_browsing = false;
+                    self.update_fuzzy_matches();
+                }
+            }
+            KeyCode::Backspace if matches!(self.current_view, View::Overlay) => {
+                // Handle backspace in overlay form fields
+                if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay {
+                    if let Some(field) = form.current_field_mut() {
+                        field.handle_backspace();
+                    }
+                }
+            }
+            KeyCode::Tab if matches!(self.input_mode, InputMode::AddingDirectory) => {
+                // If browsing matches, select current match
+                if self.fuzzy_browsing && !self.fuzzy_matches.is_empty() {
+                    let selected = self.fuzzy_matches[self.fuzzy_selected].clone();
+                    // Add trailing slash to match tab completion behavior
+                    self.input_buffer = if selected.ends_with('/') {
+                        selected
+                    } else {
+                        selected + "/"
+                    };
+                    self.fuzzy_browsing = false;
+                    self.status_message = format!("Selected: {}", self.input_buffer);
+                    self.update_fuzzy_matches();
+                } else if let Some(completed) = self.complete_path(&self.input_buffer.clone()) {
+                    self.input_buffer = completed;
+                    self.update_fuzzy_matches();
+                }
+            }
+            KeyCode::Char(c)
+                if matches!(
+                    self.input_mode,
+                    InputMode::AddingDirectory | InputMode::EditingCommand
+                ) =>
+            {
+                self.input_buffer.push(c);
+                if matches!(self.input_mode, InputMode::AddingDirectory) {
+                    self.fuzzy_browsing = false;
+                    self.update_fuzzy_matches();
+                }
+            }
+            KeyCode::Char(c) if matches!(self.current_view, View::Overlay) && c != ' ' => {
+                // Handle character input in overlay form fields (space is handled separately)
+                if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay {
+                    if let Some(field) = form.current_field_mut() {
+                        field.handle_char(c);
+                    }
+                }
+            }
+            KeyCode::Up => {
+                // Handle fuzzy match navigation when in input mode
+                if matches!(self.input_mode, InputMode::AddingDirectory)
+                    && !self.fuzzy_matches.is_empty()
+                {
+                    self.fuzzy_browsing = true;
+                    if self.fuzzy_selected > 0 {
+                        self.fuzzy_selected -= 1;
+                    }
+                } else {
+                    match self.current_view {
+                        View::ProjectBrowser => {
+                            if self.selected_project > 0 {
+                                self.selected_project -= 1;
+                                self.project_list_state.select(Some(self.selected_project));
+                                self.status_message = "Selected previous project".to_string();
+                            }
+                        }
+                        View::CommandPalette => {
+                            if self.selected_command > 0 {
+                                self.selected_command -= 1;
+                                self.command_list_state.select(Some(self.selected_command));
+                                self.status_message = "Selected previous command".to_string();
                             }
                         }
                         View::WorkspaceManager => {
@@ -1270,18 +2931,11 @@ impl App {
                             }
                         }
                         View::Overlay => {
-                            // Handle overlay-specific up navigation
-                            if let Some(overlay) = &mut self.active_overlay {
-                                match overlay {
-                                    OverlayContent::LogViewer { scroll_offset, .. } => {
-                                        *scroll_offset = scroll_offset.saturating_sub(1);
-                                    }
-                                    OverlayContent::Form { form, .. } => {
-                                        if let Some(field) = form.current_field_mut() {
-                                            field.handle_up();
-                                        }
-                                    }
-                                }
+                            // Handle overlay-specific up navigation (log viewer keys are handled earlier)
+                            if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay
+                                && let Some(field) = form.current_field_mut()
+                            {
+                                field.handle_up();
                             }
                         }
                     }
@@ -1330,46 +2984,27 @@ impl App {
                         View::Detail => {
                             // Navigate log list in detail view
                             if let Some(project) = self.get_selected_project() {
-                                let log_count = crate::fs::ProjectFileSystem::new(&project.path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default().len();
+                                let log_count = crate::fs::ProjectFileSystem::new(&project.path)
+                                    .ok()
+                                    .and_then(|fs| fs.recent_logs_all(5).ok())
+                                    .unwrap_or_default()
+                                    .len();
                                 if self.selected_log < log_count.saturating_sub(1) {
                                     self.selected_log += 1;
                                 }
                             }
                         }
                         View::Overlay => {
-                            // Handle overlay-specific down navigation
-                            if let Some(overlay) = &mut self.active_overlay {
-                                match overlay {
-                                    OverlayContent::LogViewer { scroll_offset, .. } => {
-                                        *scroll_offset = scroll_offset.saturating_add(1);
-                                    }
-                                    OverlayContent::Form { form, .. } => {
-                                        if let Some(field) = form.current_field_mut() {
-                                            field.handle_down();
-                                        }
-                                    }
-                                }
+                            // Handle overlay-specific down navigation (log viewer keys are handled earlier)
+                            if let Some(OverlayContent::Form { form, .. }) = &mut self.active_overlay
+                                && let Some(field) = form.current_field_mut()
+                            {
+                                field.handle_down();
                             }
                         }
                     }
                 }
             }
-            KeyCode::PageUp => {
-                // Scroll overlay log viewer up (page)
-                if matches!(self.current_view, View::Overlay) {
-                    if let Some(OverlayContent::LogViewer { scroll_offset, .. }) = &mut self.active_overlay {
-                        *scroll_offset = scroll_offset.saturating_sub(10);
-                    }
-                }
-            }
-            KeyCode::PageDown => {
-                // Scroll overlay log viewer down (page)
-                if matches!(self.current_view, View::Overlay) {
-                    if let Some(OverlayContent::LogViewer { scroll_offset, .. }) = &mut self.active_overlay {
-                        *scroll_offset = scroll_offset.saturating_add(10);
-                    }
-                }
-            }
             KeyCode::Left => {
                 // Navigate command filter tabs (only in CommandPalette view)
                 if matches!(self.current_view, View::CommandPalette)
@@ -1402,7 +3037,11 @@ impl App {
                         // Execute the edited command
                         let command_str = self.input_buffer.trim().to_string();
                         if !command_str.is_empty() {
-                            self.execute_command(&command_str);
+                            if self.editing_scaffold {
+                                self.execute_scaffold(&command_str);
+                            } else {
+                                self.execute_command(&command_str);
+                            }
                             self.input_mode = InputMode::Normal;
                             self.input_buffer.clear();
                         }
@@ -1410,6 +3049,7 @@ impl App {
                         // Enter edit mode with command pre-filled
                         self.input_mode = InputMode::EditingCommand;
                         self.input_buffer = cmd.command.clone();
+                        self.editing_scaffold = cmd.scaffold;
                         self.status_message = format!(
                             "Edit command (working dir: {}) then press Enter to execute",
                             self.get_target_workspace()
@@ -1545,6 +3185,10 @@ impl App {
 
         // Reload config and rediscover projects
         if let Ok(config) = crate::config::Config::load() {
+            self.theme = Theme::from_config(&config.global.tui);
+            self.log_config = config.global.logs.clone();
+            crate::policy::install(&config.global);
+
             // Clear and reload workspace directories
             self.workspace_directories.clear();
 
@@ -1572,8 +3216,12 @@ impl App {
                         description: p.config.project.description.clone().unwrap_or_else(|| {
                             format!("{} project", p.config.project.project_type)
                         }),
-                        drivers: vec![p.config.project.ecosystem],
+                        drivers: match &p.config.drivers {
+                            Some(drivers) => drivers.uses.clone(),
+                            None => vec![p.config.project.ecosystem.clone()],
+                        },
                         path: p.path.to_string_lossy().to_string(),
+                        implicit: p.implicit,
                     })
                     .collect();
 
@@ -1708,16 +3356,34 @@ impl App {
                 name: "init go cli <name>".to_string(),
                 description: "Initialize Go CLI project".to_string(),
                 command: "byte init go cli my-project".to_string(),
+                report: None,
+                pty: false,
+                source: None,
+                invocation: None,
+                members: Vec::new(),
+                scaffold: true,
             },
             Command {
                 name: "init bun web <name>".to_string(),
                 description: "Initialize Bun web application".to_string(),
                 command: "byte init bun web my-app".to_string(),
+                report: None,
+                pty: false,
+                source: None,
+                invocation: None,
+                members: Vec::new(),
+                scaffold: true,
             },
             Command {
                 name: "init rust cli <name>".to_string(),
                 description: "Initialize Rust CLI project".to_string(),
                 command: "byte init rust cli my-tool".to_string(),
+                report: None,
+                pty: false,
+                source: None,
+                invocation: None,
+                members: Vec::new(),
+                scaffold: true,
             },
         ];
     }
@@ -1727,46 +3393,135 @@ impl App {
 
         let config_path = PathBuf::from(project_path).join("byte.toml");
 
-        if let Ok(content) = std::fs::read_to_string(&config_path) {
-            if let Ok(config) = toml::from_str::<crate::config::ProjectConfig>(&content) {
-                // Add build commands
-                if let Some(build_cmds) = config.build {
-                    for (name, cmd) in build_cmds.iter() {
-                        self.commands.push(Command {
-                            name: format!("build: {}", name),
-                            description: format!("Run build task: {}", name),
-                            command: cmd.clone(),
-                        });
-                    }
+        // Implicit projects get the tasks a generated byte.toml would have
+        let config = match std::fs::read_to_string(&config_path) {
+            Ok(content) => toml::from_str::<crate::config::ProjectConfig>(&content).ok(),
+            Err(_) => crate::projects::detect::infer_config(&PathBuf::from(project_path)),
+        };
+
+        if let Some(config) = &config {
+            // Add build commands; `backend:build` runs in the backend directory
+            if let Some(build_cmds) = &config.build {
+                for (name, cmd) in build_cmds.iter() {
+                    let (command, invocation) = byte_task(config, name, cmd);
+                    self.commands.push(Command {
+                        name: format!("build: {}", name),
+                        description: match &invocation {
+                            Ok(_) => format!("Run build task: {}", name),
+                            Err(e) => format!("Invalid task: {}", e),
+                        },
+                        command,
+                        report: cmd.report().map(String::from),
+                        pty: cmd.pty(),
+                        source: Some("byte".to_string()),
+                        invocation: Some(invocation),
+                        members: Vec::new(),
+                        scaffold: false,
+                    });
                 }
+            }
 
-                // Add custom commands
-                if let Some(custom_cmds) = config.commands {
-                    for (name, cmd) in custom_cmds.iter() {
-                        self.commands.push(Command {
-                            name: name.clone(),
-                            description: format!("Run: {}", name),
-                            command: cmd.clone(),
-                        });
-                    }
+            // Polyglot projects: `build` runs every driver's build
+            for (name, members) in crate::projects::drivers::aggregate_tasks(config) {
+                let roles: Vec<&str> = members.iter().filter_map(|key| key.split_once(':')).map(|(role, _)| role).collect();
+                let members = members
+                    .iter()
+                    .filter_map(|key| Some(byte_task(config, key, config.build.as_ref()?.get(key)?).0))
+                    .collect();
+                self.commands.push(Command {
+                    name: format!("build: {}", name),
+                    description: format!("Run {} in {}, side by side", name, roles.join(", ")),
+                    command: format!("{} ({})", name, roles.join(" + ")),
+                    report: None,
+                    pty: false,
+                    source: Some("byte".to_string()),
+                    invocation: None,
+                    members,
+                    scaffold: false,
+                });
+            }
+
+            // Add custom commands
+            if let Some(custom_cmds) = &config.commands {
+                for (name, cmd) in custom_cmds.iter() {
+                    let (command, invocation) = byte_task(config, name, cmd);
+                    self.commands.push(Command {
+                        name: name.clone(),
+                        description: match &invocation {
+                            Ok(_) => format!("Run: {}", name),
+                            Err(e) => format!("Invalid task: {}", e),
+                        },
+                        command,
+                        report: cmd.report().map(String::from),
+                        pty: cmd.pty(),
+                        source: Some("byte".to_string()),
+                        invocation: Some(invocation),
+                        members: Vec::new(),
+                        scaffold: false,
+                    });
                 }
             }
         }
 
+        // package.json scripts, Cargo aliases, Makefile targets, ... that
+        // byte.toml doesn't override or hide
+        for task in crate::tasks::imported_tasks(&PathBuf::from(project_path), config.as_ref()) {
+            let badge = task.source.badge();
+            self.commands.push(Command {
+                description: task.description.unwrap_or_else(|| format!("Run {} task: {}", badge, task.name)),
+                name: task.name,
+                command: task.command,
+                report: None,
+                pty: false,
+                source: Some(badge.to_string()),
+                invocation: None,
+                members: Vec::new(),
+                scaffold: false,
+            });
+        }
+
         // Add common git commands
         self.commands.push(Command {
             name: "git status".to_string(),
             description: "Show git status".to_string(),
             command: "git status".to_string(),
+            report: None,
+            pty: false,
+            source: None,
+            invocation: None,
+            members: Vec::new(),
+            scaffold: false,
         });
         self.commands.push(Command {
             name: "git diff".to_string(),
             description: "Show uncommitted changes".to_string(),
             command: "git diff".to_string(),
+            report: None,
+            pty: false,
+            source: None,
+            invocation: None,
+            members: Vec::new(),
+            scaffold: false,
         });
     }
 }
 
+/// Command line and invocation of a byte.toml task; an invalid task keeps
+/// its command line so it still shows up in the list
+fn byte_task(
+    config: &crate::config::ProjectConfig,
+    name: &str,
+    task: &crate::config::types::TaskConfig,
+) -> (String, Result<crate::exec::Invocation, String>) {
+    match crate::projects::drivers::task_invocation(config, name, task) {
+        Ok(invocation) => (invocation.command_line(), Ok(invocation)),
+        Err(e) => (
+            crate::projects::drivers::task_command(config, name, &task.command()),
+            Err(e.to_string()),
+        ),
+    }
+}
+
 pub fn run() -> anyhow::Result<()> {
     if !is_tty() {
         anyhow::bail!("TUI requires a terminal. Please run in an interactive terminal.");
@@ -1894,20 +3649,59 @@ fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> an
     Ok(())
 }
 
+/// Parse a test run (JUnit report if the task wrote one, else its output) into .byte/state/tests.json
+fn record_test_results(
+    project_path: &str,
+    command: &str,
+    task: Option<&str>,
+    report: Option<&str>,
+    result: &crate::exec::CommandResult,
+) {
+    // Only trust a report written by this run, not one left over from an earlier one
+    let report_path = report
+        .map(|r| std::path::Path::new(project_path).join(r))
+        .filter(|path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified >= result.timestamp)
+        });
+
+    let tests = match report_path {
+        Some(path) => crate::testing::junit::parse_file(&path).unwrap_or_else(|e| {
+            crate::log::error("TEST", &format!("{}", e));
+            Vec::new()
+        }),
+        None => {
+            let lines: Vec<&str> = result.output.iter().map(|l| l.text.as_str()).collect();
+            crate::testing::parse_output(&lines)
+        }
+    };
+
+    // Nothing recognizable (e.g. the build failed before any test ran)
+    if tests.is_empty() {
+        return;
+    }
+    let run = state::test_results::TestRun::new(command, task, tests);
+    if let Err(e) = state::test_results::record_test_run(project_path, run) {
+        crate::log::error("TEST", &format!("Failed to save test results: {}", e));
+    }
+}
+
 /// Suspend TUI, run interactive command with terminal access, then resume TUI
 fn run_interactive_command(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    editor: &str,
-    file_path: &str,
+    request: &EditorRequest,
 ) -> anyhow::Result<()> {
-    use crate::exec::CommandBuilder;
+    use crate::exec::{editor_args, CommandBuilder};
 
     // Suspend TUI
     restore_terminal(terminal)?;
 
     // Run editor with inherited stdin/stdout/stderr via exec API
-    let result = CommandBuilder::new(editor)
-        .arg(file_path)
+    let args = editor_args(&request.editor, &request.file_path, request.line, request.column);
+    let result = args
+        .into_iter()
+        .fold(CommandBuilder::new(request.editor.as_str()), |builder, arg| builder.arg(arg))
         .execute_interactive();
 
     // Resume TUI
@@ -1952,977 +3746,1631 @@ fn truncate_path(path: &str, max_width: usize) -> String {
     format!("{}...{}", start, end)
 }
 
-/// Strip ANSI escape codes from a string (aggressive full coverage)
-fn strip_ansi_codes(input: &str) -> String {
-    let mut result = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // ESC sequence detected
-            match chars.peek() {
-                Some(&'[') => {
-                    // CSI sequence: ESC [ ... letter
-                    chars.next(); // consume '['
-                    while let Some(&next_ch) = chars.peek() {
-                        chars.next();
-                        // CSI terminators are ASCII letters (A-Z, a-z) or @ through ~
-                        if next_ch.is_ascii_alphabetic() || ('@'..='~').contains(&next_ch) {
-                            break;
+/// Launch fuzzy finder for directory selection
+fn run_fuzzy_picker(current_input: &str) -> Option<String> {
+    use skim::prelude::*;
+    use std::io::Cursor;
+
+    // Build list of candidate directories
+    let mut candidates = Vec::new();
+
+    // Add common directories
+    if let Some(home) = dirs::home_dir() {
+        candidates.push("~".to_string());
+
+        // Common subdirectories
+        for subdir in &[
+            "Desktop",
+            "Documents",
+            "Downloads",
+            "Music",
+            "Pictures",
+            "Videos",
+            "projects",
+        ] {
+            let path = home.join(subdir);
+            if path.exists() {
+                candidates.push(format!("~/{}", subdir));
+            }
+        }
+
+        // Add registered workspaces from config
+        if let Ok(config) = crate::config::Config::load() {
+            candidates.push(config.global.workspace.path.clone());
+            for registered in &config.global.workspace.registered {
+                candidates.push(registered.clone());
+            }
+        }
+
+        // If there's a partial path typed, add subdirectories
+        if !current_input.is_empty() {
+            let expanded = match crate::path::SafePath::from_user_input(current_input) {
+                Ok(safe_path) => safe_path.to_string(),
+                Err(_) => current_input.to_string(), // Use original if expansion fails
+            };
+            if let Ok(entries) = std::fs::read_dir(&expanded) {
+                for entry in entries.filter_map(|e| e.ok()) {
+                    if entry
+                        .file_type()
+                        .ok()
+                        .map(|ft| ft.is_dir())
+                        .unwrap_or(false)
+                    {
+                        if let Ok(name) = entry.file_name().into_string() {
+                            let full_path =
+                                format!("{}/{}", current_input.trim_end_matches('/'), name);
+                            candidates.push(full_path);
                         }
                     }
                 }
-                Some(&']') => {
-                    // OSC sequence: ESC ] ... BEL or ESC \
-                    chars.next(); // consume ']'
-                    while let Some(&next_ch) = chars.peek() {
-                        if next_ch == '\x07' {
-                            // BEL terminator
-                            chars.next();
-                            break;
-                        }
-                        if next_ch == '\x1b' {
-                            chars.next(); // consume ESC
-                            if chars.peek() == Some(&'\\') {
-                                // ESC \ terminator
-                                chars.next();
-                                break;
-                            }
-                        } else {
-                            chars.next();
-                        }
+            }
+        }
+    }
+
+    // Remove duplicates
+    candidates.sort();
+    candidates.dedup();
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    // Prepare skim input
+    let input = candidates.join("\n");
+
+    // Configure skim options
+    let options = SkimOptionsBuilder::default()
+        .height("50%".to_string())
+        .reverse(true)
+        .prompt("Select directory> ".to_string())
+        .build()
+        .ok()?;
+
+    // Run skim
+    let item_reader = SkimItemReader::default();
+    let items = item_reader.of_bufread(Cursor::new(input));
+
+    let output = Skim::run_with(&options, Some(items))?;
+
+    // Handle selection
+    if output.is_abort {
+        return None;
+    }
+
+    output
+        .selected_items
+        .first()
+        .map(|item| item.output().to_string())
+}
+
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    file_rx: std::sync::mpsc::Receiver<()>,
+    cmd_rx: std::sync::mpsc::Receiver<CommandResult>,
+) -> anyhow::Result<()> {
+    // Clear any initialization logs before first draw
+    terminal.clear()?;
+
+    while !app.should_quit {
+        // Clear terminal if needed (e.g., after closing log preview)
+        if app.needs_clear {
+            terminal.clear()?;
+            app.needs_clear = false;
+        }
+
+        terminal.draw(|f| ui(f, app))?;
+
+        // Update animation frame if command is executing
+        if app.executing_command.is_some() {
+            app.build_animation_frame += 1;
+        }
+
+        // Auto-dismiss command result display after 3 seconds
+        if let Some((_, timestamp)) = app.command_result_display {
+            if timestamp.elapsed() >= Duration::from_secs(3) {
+                app.command_result_display = None;
+            }
+        }
+
+        app.poll_sync();
+        app.poll_release();
+
+        // Check for command completion (non-blocking); one at a time, as
+        // several tasks can be running
+        if app.pending_result.is_none() && let Ok(result) = cmd_rx.try_recv() {
+            // Store the result but keep animating for minimum duration
+            app.pending_result = Some(result);
+        }
+
+        // Process pending result after minimum animation duration (500ms)
+        if let Some(_) = &app.pending_result {
+            if let Some(start_time) = app.build_animation_start {
+                let elapsed = start_time.elapsed();
+                if elapsed >= Duration::from_millis(500) {
+                    // Minimum animation time has passed, process result
+                    let result = app.pending_result.take().unwrap();
+                    app.finish_command(result);
+                }
+            } else {
+                // No start time? Process immediately
+                let result = app.pending_result.take().unwrap();
+                app.finish_command(result);
+            }
+        }
+
+        // Check for file system events (non-blocking)
+        if let Ok(_) = file_rx.try_recv() {
+            // Throttle: only hotload if it's been at least 1 second since last hotload
+            if app.last_hotload.elapsed() >= Duration::from_secs(1) {
+                app.hotload();
+                app.last_hotload = Instant::now();
+                // Don't override status message - visual updates are enough feedback
+            }
+        }
+
+        if event::poll(Duration::from_millis(50))? {
+            let event = event::read()?;
+            if let Event::Key(key) = event {
+                // Handle Ctrl+D specially for fuzzy picker
+                if matches!(app.input_mode, InputMode::AddingDirectory)
+                    && key.code == KeyCode::Char('d')
+                    && key
+                        .modifiers
+                        .contains(crossterm::event::KeyModifiers::CONTROL)
+                {
+                    app.launch_fuzzy_picker = true;
+                } else if key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL)
+                    && !app.command_cancels.is_empty()
+                {
+                    // A task waiting for input nobody can type would otherwise run forever
+                    for cancel in app.command_cancels.values() {
+                        cancel.store(true, Ordering::Relaxed);
                     }
+                    app.status_message = "Stopping task...".to_string();
+                } else {
+                    app.handle_key(key.code);
                 }
-                Some(&'(') | Some(&')') => {
-                    // Character set selection: ESC ( X or ESC ) X
-                    chars.next(); // consume '(' or ')'
-                    chars.next(); // skip the next character
+            }
+        }
+
+        // Handle fuzzy picker launch (suspend TUI)
+        if app.launch_fuzzy_picker {
+            app.launch_fuzzy_picker = false;
+
+            // Restore terminal
+            restore_terminal(terminal)?;
+
+            // Run fuzzy picker
+            if let Some(selected) = run_fuzzy_picker(&app.input_buffer) {
+                app.input_buffer = selected;
+                app.status_message = format!("Selected: {}", app.input_buffer);
+            } else {
+                app.status_message = "Cancelled".to_string();
+            }
+
+            // Re-setup terminal
+            *terminal = setup_terminal()?;
+        }
+
+        // Handle pending editor request (suspend TUI and run editor)
+        if let Some(request) = app.pending_editor.take() {
+            match run_interactive_command(terminal, &request) {
+                Ok(_) => {
+                    app.status_message = format!("✓ Closed {}", request.editor);
                 }
-                _ => {
-                    // Other ESC sequences, skip one more character
-                    chars.next();
+                Err(e) => {
+                    app.status_message = format!("✗ Editor error: {}", e);
                 }
             }
-        } else if ch >= ' ' || ch == '\t' || ch == '\n' || ch == '\r' {
-            // Printable character or whitespace
-            result.push(ch);
         }
-        // Skip control characters
     }
 
-    result
+    Ok(())
 }
 
-/// Launch fuzzy finder for directory selection
-fn run_fuzzy_picker(current_input: &str) -> Option<String> {
-    use skim::prelude::*;
-    use std::io::Cursor;
+fn ui(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3), // Header
+                Constraint::Length(1), // Tab bar
+                Constraint::Min(10),   // Main content
+                Constraint::Length(3), // Footer/status
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
 
-    // Build list of candidate directories
-    let mut candidates = Vec::new();
+    // Header - clean, centered branding
+    let header = Paragraph::new(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("●", Style::default().fg(theme.accent)),
+        Span::raw("  "),
+        Span::styled(
+            "B Y T E",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+        Span::styled("│", Style::default().fg(theme.separator)),
+        Span::raw("  "),
+        Span::styled(
+            "Project Orchestration",
+            Style::default().fg(theme.text_secondary),
+        ),
+    ]))
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::NONE)
+            .style(Style::default()),
+    );
+    f.render_widget(header, chunks[0]);
 
-    // Add common directories
-    if let Some(home) = dirs::home_dir() {
-        candidates.push("~".to_string());
+    // Tab bar
+    render_tab_bar(f, chunks[1], app);
 
-        // Common subdirectories
-        for subdir in &[
-            "Desktop",
-            "Documents",
-            "Downloads",
-            "Music",
-            "Pictures",
-            "Videos",
-            "projects",
-        ] {
-            let path = home.join(subdir);
-            if path.exists() {
-                candidates.push(format!("~/{}", subdir));
-            }
-        }
+    // Main content
+    match app.current_view {
+        View::ProjectBrowser => render_project_browser(f, chunks[2], app),
+        View::CommandPalette => render_command_palette(f, chunks[2], app),
+        View::Detail => render_detail(f, chunks[2], app),
+        View::WorkspaceManager => render_workspace_manager(f, chunks[2], app),
+        View::Overlay => render_overlay(f, chunks[2], app),
+    }
 
-        // Add registered workspaces from config
-        if let Ok(config) = crate::config::Config::load() {
-            candidates.push(config.global.workspace.path.clone());
-            for registered in &config.global.workspace.registered {
-                candidates.push(registered.clone());
-            }
-        }
+    // Footer
+    render_footer(f, chunks[3], app);
 
-        // If there's a partial path typed, add subdirectories
-        if !current_input.is_empty() {
-            let expanded = match crate::path::SafePath::from_user_input(current_input) {
-                Ok(safe_path) => safe_path.to_string(),
-                Err(_) => current_input.to_string(), // Use original if expansion fails
-            };
-            if let Ok(entries) = std::fs::read_dir(&expanded) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if entry
-                        .file_type()
-                        .ok()
-                        .map(|ft| ft.is_dir())
-                        .unwrap_or(false)
-                    {
-                        if let Ok(name) = entry.file_name().into_string() {
-                            let full_path =
-                                format!("{}/{}", current_input.trim_end_matches('/'), name);
-                            candidates.push(full_path);
-                        }
-                    }
-                }
-            }
-        }
+    // Horizontal progress bar on right side (if command is executing or showing result)
+    if app.executing_command.is_some() || app.command_result_display.is_some() {
+        render_progress_bar(f, f.area(), app);
     }
+}
 
-    // Remove duplicates
-    candidates.sort();
-    candidates.dedup();
+fn render_tab_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let current_view = match app.current_view {
+        View::ProjectBrowser => 0,
+        View::CommandPalette => 1,
+        View::Detail => 2,
+        View::WorkspaceManager => 3,
+        View::Overlay => 99, // Overlay is modal, not a tab
+    };
 
-    if candidates.is_empty() {
-        return None;
-    }
+    let tabs = vec![
+        ("1", "Projects", 0),
+        ("2", "Commands", 1),
+        ("3", "Details", 2),
+        ("4", "Workspace", 3),
+    ];
 
-    // Prepare skim input
-    let input = candidates.join("\n");
+    let mut spans = vec![Span::raw("  ")];
 
-    // Configure skim options
-    let options = SkimOptionsBuilder::default()
-        .height("50%".to_string())
-        .reverse(true)
-        .prompt("Select directory> ".to_string())
-        .build()
-        .ok()?;
+    for (i, (key, label, index)) in tabs.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("  ", Style::default()));
+        }
 
-    // Run skim
-    let item_reader = SkimItemReader::default();
-    let items = item_reader.of_bufread(Cursor::new(input));
+        let is_active = current_view == *index;
 
-    let output = Skim::run_with(&options, Some(items))?;
+        if is_active {
+            spans.push(Span::styled(
+                *key,
+                Style::default()
+                    .fg(theme.badge_text)
+                    .bg(theme.badge_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                *label,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.push(Span::styled(
+                *key,
+                Style::default().fg(theme.text_secondary),
+            ));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                *label,
+                Style::default().fg(theme.text_secondary),
+            ));
+        }
+    }
 
-    // Handle selection
-    if output.is_abort {
-        return None;
+    // Add selected project on the right side
+    if let Some(project) = app.get_selected_project() {
+        // Calculate padding to right-align the project name
+        let tabs_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        let tabs_len = tabs_text.len();
+        let project_text = format!("Project: {}", project.name);
+        let available_width = area.width as usize;
+
+        if tabs_len + project_text.len() + 4 < available_width {
+            let padding_len = available_width - tabs_len - project_text.len() - 2;
+            spans.push(Span::raw(" ".repeat(padding_len)));
+            spans.push(Span::styled(
+                project_text,
+                Style::default().fg(theme.text_secondary),
+            ));
+            spans.push(Span::raw("  "));
+        }
     }
 
-    output
-        .selected_items
-        .first()
-        .map(|item| item.output().to_string())
+    let tabs_line = Line::from(spans);
+    let paragraph = Paragraph::new(tabs_line).alignment(Alignment::Left);
+    f.render_widget(paragraph, area);
 }
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    app: &mut App,
-    file_rx: std::sync::mpsc::Receiver<()>,
-    cmd_rx: std::sync::mpsc::Receiver<CommandResult>,
-) -> anyhow::Result<()> {
-    // Clear any initialization logs before first draw
-    terminal.clear()?;
+fn render_footer(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let status_style = if app.status_message.contains("Error") {
+        Style::default().fg(theme.error)
+    } else {
+        Style::default().fg(theme.text_secondary)
+    };
 
-    while !app.should_quit {
-        // Clear terminal if needed (e.g., after closing log preview)
-        if app.needs_clear {
-            terminal.clear()?;
-            app.needs_clear = false;
-        }
+    let footer = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(app.status_message.clone(), status_style),
+            Span::raw("  "),
+            Span::styled("│", Style::default().fg(theme.separator)),
+            Span::raw("  "),
+            Span::styled("?", Style::default().fg(theme.text_secondary)),
+            Span::styled(" help", Style::default().fg(theme.text_secondary)),
+            Span::raw("  "),
+            Span::styled("r", Style::default().fg(theme.text_secondary)),
+            Span::styled(" reload", Style::default().fg(theme.text_secondary)),
+            Span::raw("  "),
+            Span::styled("q", Style::default().fg(theme.text_secondary)),
+            Span::styled(" quit", Style::default().fg(theme.text_secondary)),
+        ]),
+    ])
+    .alignment(Alignment::Left);
+    f.render_widget(footer, area);
+}
 
-        terminal.draw(|f| ui(f, app))?;
+fn render_project_browser(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let inner_area = area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    // Split into title and content
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Title
+            Constraint::Min(0),    // Project list
+        ])
+        .split(inner_area);
+
+    // Title
+    let title = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(
+                "Projects",
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{}", app.projects.len()),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]),
+        Line::from(""),
+    ]);
+    f.render_widget(title, chunks[0]);
 
-        // Update animation frame if command is executing
-        if app.executing_command.is_some() {
-            app.build_animation_frame += 1;
-        }
+    // Project list with table layout: Project (left) | Location (right)
+    let items: Vec<ListItem> = app
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(i, project)| {
+            let is_selected = i == app.selected_project;
 
-        // Auto-dismiss command result display after 3 seconds
-        if let Some((_, timestamp)) = app.command_result_display {
-            if timestamp.elapsed() >= Duration::from_secs(3) {
-                app.command_result_display = None;
-            }
-        }
+            let drivers_display = project
+                .drivers
+                .iter()
+                .map(|d| format!("#{}", d))
+                .collect::<Vec<_>>()
+                .join(" ");
 
-        // Check for command completion (non-blocking)
-        if let Ok(result) = cmd_rx.try_recv() {
-            // Store the result but keep animating for minimum duration
-            app.pending_result = Some(result);
-        }
+            // Calculate column widths (60% project, 40% location)
+            let total_width = inner_area.width.saturating_sub(4) as usize; // Account for padding
+            let project_width = (total_width * 60) / 100;
+            let location_width = total_width.saturating_sub(project_width);
 
-        // Process pending result after minimum animation duration (500ms)
-        if let Some(_) = &app.pending_result {
-            if let Some(start_time) = app.build_animation_start {
-                let elapsed = start_time.elapsed();
-                if elapsed >= Duration::from_millis(500) {
-                    // Minimum animation time has passed, process result
-                    let result = app.pending_result.take().unwrap();
-                    app.executing_command = None;
-                    app.handle_command_result(result);
-                }
+            // Show workspace instead of full path
+            let workspace_path = app.get_workspace_for_project(&project.path);
+            let display_path = if workspace_path.chars().count() > location_width {
+                let skip = workspace_path.chars().count() - location_width + 1;
+                let truncated: String = workspace_path.chars().skip(skip).collect();
+                format!("…{}", truncated)
             } else {
-                // No start time? Process immediately
-                let result = app.pending_result.take().unwrap();
-                app.executing_command = None;
-                app.handle_command_result(result);
-            }
-        }
-
-        // Check for file system events (non-blocking)
-        if let Ok(_) = file_rx.try_recv() {
-            // Throttle: only hotload if it's been at least 1 second since last hotload
-            if app.last_hotload.elapsed() >= Duration::from_secs(1) {
-                app.hotload();
-                app.last_hotload = Instant::now();
-                // Don't override status message - visual updates are enough feedback
-            }
-        }
-
-        if event::poll(Duration::from_millis(50))? {
-            let event = event::read()?;
-            if let Event::Key(key) = event {
-                // Handle Ctrl+D specially for fuzzy picker
-                if matches!(app.input_mode, InputMode::AddingDirectory)
-                    && key.code == KeyCode::Char('d')
-                    && key
-                        .modifiers
-                        .contains(crossterm::event::KeyModifiers::CONTROL)
-                {
-                    app.launch_fuzzy_picker = true;
-                } else {
-                    app.handle_key(key.code);
-                }
-            }
-        }
+                workspace_path
+            };
 
-        // Handle fuzzy picker launch (suspend TUI)
-        if app.launch_fuzzy_picker {
-            app.launch_fuzzy_picker = false;
+            // Line 1: Name (left) | Path (right)
+            let name_text = format!("{:width$}", project.name, width = project_width);
+            let line1 = vec![
+                Span::raw("  "),
+                Span::styled(
+                    name_text,
+                    Style::default()
+                        .fg(if is_selected {
+                            theme.accent
+                        } else {
+                            theme.text_primary
+                        })
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+                Span::styled(display_path, Style::default().fg(theme.text_secondary)),
+            ];
 
-            // Restore terminal
-            restore_terminal(terminal)?;
+            // Line 2: Description (left) | empty (right)
+            let max_desc_width = total_width.saturating_sub(2); // Account for "  " indent
+            let truncated_desc = truncate_path(&project.description, max_desc_width);
+            let line2 = vec![
+                Span::raw("  "),
+                Span::styled(
+                    truncated_desc,
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ];
 
-            // Run fuzzy picker
-            if let Some(selected) = run_fuzzy_picker(&app.input_buffer) {
-                app.input_buffer = selected;
-                app.status_message = format!("Selected: {}", app.input_buffer);
-            } else {
-                app.status_message = "Cancelled".to_string();
+            // Line 3: Ecosystem tags (left) | problem counts (right)
+            let mut line3 = vec![
+                Span::raw("  "),
+                Span::styled(drivers_display, Style::default().fg(theme.text_secondary)),
+            ];
+            if project.implicit {
+                line3.push(Span::raw("  "));
+                line3.push(Span::styled("implicit · A to adopt", Style::default().fg(theme.warning)));
+            }
+            if let Some(state) = app.project_states.get(&project.path)
+                && state.problems != (0, 0)
+            {
+                line3.push(Span::raw("  "));
+                line3.extend(problem_count_spans(state.problems, theme));
             }
 
-            // Re-setup terminal
-            *terminal = setup_terminal()?;
-        }
+            let content = vec![
+                Line::from(line1),
+                Line::from(line2),
+                Line::from(line3),
+                Line::from(""),
+            ];
 
-        // Handle pending editor request (suspend TUI and run editor)
-        if let Some((editor, file_path)) = app.pending_editor.take() {
-            match run_interactive_command(terminal, &editor, &file_path) {
-                Ok(_) => {
-                    app.status_message = format!("✓ Closed {}", editor);
-                }
-                Err(e) => {
-                    app.status_message = format!("✗ Editor error: {}", e);
-                }
-            }
-        }
-    }
+            ListItem::new(content)
+        })
+        .collect();
 
-    Ok(())
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::NONE))
+        .highlight_style(Style::default())
+        .highlight_symbol("▸ ");
+
+    let mut state = app.project_list_state.clone();
+    f.render_stateful_widget(list, chunks[1], &mut state);
 }
 
-fn ui(f: &mut Frame, app: &App) {
+fn render_command_palette(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let inner_area = area.inner(Margin {
+        horizontal: 2,
+        vertical: 1,
+    });
+
+    // Split into tab bar, command list, and preview
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(
-            [
-                Constraint::Length(3), // Header
-                Constraint::Length(1), // Tab bar
-                Constraint::Min(10),   // Main content
-                Constraint::Length(3), // Footer/status
-            ]
-            .as_ref(),
-        )
-        .split(f.area());
-
-    // Header - clean, centered branding
-    let header = Paragraph::new(Line::from(vec![
-        Span::raw("  "),
-        Span::styled("●", Style::default().fg(theme::ACCENT)),
-        Span::raw("  "),
-        Span::styled(
-            "B Y T E",
-            Style::default()
-                .fg(theme::ACCENT)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw("  "),
-        Span::styled("│", Style::default().fg(theme::SEPARATOR)),
-        Span::raw("  "),
-        Span::styled(
-            "Project Orchestration",
-            Style::default().fg(theme::TEXT_SECONDARY),
-        ),
-    ]))
-    .alignment(Alignment::Center)
-    .block(
-        Block::default()
-            .borders(Borders::NONE)
-            .style(Style::default()),
-    );
-    f.render_widget(header, chunks[0]);
+        .constraints([
+            Constraint::Length(1), // Filter tabs
+            Constraint::Length(2), // Title
+            Constraint::Min(8),    // Command list
+            Constraint::Length(1), // Separator
+            Constraint::Length(6), // Preview
+        ])
+        .split(inner_area);
 
-    // Tab bar
-    render_tab_bar(f, chunks[1], app);
+    // Filter tabs (horizontal bar)
+    let tab_spans: Vec<Span> = CommandFilter::all_filters()
+        .into_iter()
+        .flat_map(|filter| {
+            let is_active = filter == app.command_filter;
+            vec![
+                Span::styled(
+                    "[",
+                    Style::default().fg(if is_active {
+                        theme.accent
+                    } else {
+                        theme.text_secondary
+                    }),
+                ),
+                Span::styled(
+                    filter.as_str().to_string(),
+                    Style::default()
+                        .fg(if is_active {
+                            theme.accent
+                        } else {
+                            theme.text_secondary
+                        })
+                        .add_modifier(if is_active {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+                Span::styled(
+                    "]",
+                    Style::default().fg(if is_active {
+                        theme.accent
+                    } else {
+                        theme.text_secondary
+                    }),
+                ),
+                Span::raw(" "),
+            ]
+        })
+        .collect();
 
-    // Main content
-    match app.current_view {
-        View::ProjectBrowser => render_project_browser(f, chunks[2], app),
-        View::CommandPalette => render_command_palette(f, chunks[2], app),
-        View::Detail => render_detail(f, chunks[2], app),
-        View::WorkspaceManager => render_workspace_manager(f, chunks[2], app),
-        View::Overlay => render_overlay(f, chunks[2], app),
-    }
+    let tabs = Paragraph::new(Line::from(tab_spans));
+    f.render_widget(tabs, chunks[0]);
 
-    // Footer
-    render_footer(f, chunks[3], app);
+    // Title
+    let title = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(
+                "Commands",
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("{}", app.commands.len()),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]),
+        Line::from(""),
+    ]);
+    f.render_widget(title, chunks[1]);
 
-    // Horizontal progress bar on right side (if command is executing or showing result)
-    if app.executing_command.is_some() || app.command_result_display.is_some() {
-        render_progress_bar(f, f.area(), app);
-    }
-}
+    // Command list with table layout: Command (left) | Target (right)
+    let items: Vec<ListItem> = app
+        .commands
+        .iter()
+        .enumerate()
+        .map(|(i, cmd)| {
+            let is_selected = i == app.selected_command;
 
-fn render_tab_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    let current_view = match app.current_view {
-        View::ProjectBrowser => 0,
-        View::CommandPalette => 1,
-        View::Detail => 2,
-        View::WorkspaceManager => 3,
-        View::Overlay => 99, // Overlay is modal, not a tab
-    };
+            // Calculate column widths (65% command, 35% target)
+            let total_width = inner_area.width.saturating_sub(4) as usize;
+            let command_width = (total_width * 65) / 100;
+            let target_width = total_width.saturating_sub(command_width);
 
-    let tabs = vec![
-        ("1", "Projects", 0),
-        ("2", "Commands", 1),
-        ("3", "Details", 2),
-        ("4", "Workspace", 3),
-    ];
+            // Truncate target path to fit
+            // Show project's path when project selected, otherwise target workspace
+            let target_path = if let Some(project) = app.get_selected_project() {
+                project.path.clone()
+            } else {
+                app.get_target_workspace()
+            };
 
-    let mut spans = vec![Span::raw("  ")];
+            let display_target = if target_path.chars().count() > target_width {
+                let skip = target_path.chars().count() - target_width + 1;
+                let truncated: String = target_path.chars().skip(skip).collect();
+                format!("…{}", truncated)
+            } else {
+                target_path
+            };
 
-    for (i, (key, label, index)) in tabs.iter().enumerate() {
-        if i > 0 {
-            spans.push(Span::styled("  ", Style::default()));
-        }
+            // Line 1: Command name and provenance badge (left) | Target directory (right)
+            let badge = cmd.source.as_deref().map(|s| format!("[{}] ", s)).unwrap_or_default();
+            let name_width = command_width.saturating_sub(badge.chars().count());
+            let name_text = format!("{:width$}", cmd.name, width = name_width);
+            let line1 = vec![
+                Span::raw("  "),
+                Span::styled(
+                    name_text,
+                    Style::default()
+                        .fg(if is_selected {
+                            theme.accent
+                        } else {
+                            theme.text_primary
+                        })
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+                Span::styled(badge, Style::default().fg(theme.text_secondary)),
+                Span::styled(display_target, Style::default().fg(theme.text_secondary)),
+            ];
 
-        let is_active = current_view == *index;
+            // Line 2: Description (left) | empty (right)
+            let max_desc_width = total_width.saturating_sub(2); // Account for "  " indent
+            let truncated_desc = truncate_path(&cmd.description, max_desc_width);
+            let line2 = vec![
+                Span::raw("  "),
+                Span::styled(
+                    truncated_desc,
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ];
 
-        if is_active {
-            spans.push(Span::styled(
-                *key,
-                Style::default()
-                    .fg(theme::BADGE_TEXT)
-                    .bg(theme::BADGE_BG)
-                    .add_modifier(Modifier::BOLD),
-            ));
-            spans.push(Span::raw(" "));
-            spans.push(Span::styled(
-                *label,
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        } else {
-            spans.push(Span::styled(
-                *key,
-                Style::default().fg(theme::TEXT_SECONDARY),
-            ));
-            spans.push(Span::raw(" "));
-            spans.push(Span::styled(
-                *label,
-                Style::default().fg(theme::TEXT_SECONDARY),
-            ));
-        }
-    }
+            let content = vec![Line::from(line1), Line::from(line2), Line::from("")];
 
-    // Add selected project on the right side
-    if let Some(project) = app.get_selected_project() {
-        // Calculate padding to right-align the project name
-        let tabs_text: String = spans.iter().map(|s| s.content.as_ref()).collect();
-        let tabs_len = tabs_text.len();
-        let project_text = format!("Project: {}", project.name);
-        let available_width = area.width as usize;
+            ListItem::new(content)
+        })
+        .collect();
 
-        if tabs_len + project_text.len() + 4 < available_width {
-            let padding_len = available_width - tabs_len - project_text.len() - 2;
-            spans.push(Span::raw(" ".repeat(padding_len)));
-            spans.push(Span::styled(
-                project_text,
-                Style::default().fg(theme::TEXT_SECONDARY),
-            ));
-            spans.push(Span::raw("  "));
-        }
-    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::NONE))
+        .highlight_style(Style::default())
+        .highlight_symbol("▸ ");
 
-    let tabs_line = Line::from(spans);
-    let paragraph = Paragraph::new(tabs_line).alignment(Alignment::Left);
-    f.render_widget(paragraph, area);
-}
+    let mut state = app.command_list_state.clone();
+    f.render_stateful_widget(list, chunks[2], &mut state);
 
-fn render_footer(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    let status_style = if app.status_message.contains("Error") {
-        Style::default().fg(theme::ERROR)
-    } else {
-        Style::default().fg(theme::TEXT_SECONDARY)
-    };
+    // Separator
+    let separator = Paragraph::new(Line::from(vec![Span::styled(
+        "─".repeat(inner_area.width as usize),
+        Style::default().fg(theme.separator),
+    )]));
+    f.render_widget(separator, chunks[3]);
 
-    let footer = Paragraph::new(vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::raw("  "),
-            Span::styled(app.status_message.clone(), status_style),
-            Span::raw("  "),
-            Span::styled("│", Style::default().fg(theme::SEPARATOR)),
-            Span::raw("  "),
-            Span::styled("?", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" help", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::raw("  "),
-            Span::styled("r", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" reload", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::raw("  "),
-            Span::styled("q", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" quit", Style::default().fg(theme::TEXT_SECONDARY)),
-        ]),
-    ])
-    .alignment(Alignment::Left);
-    f.render_widget(footer, area);
+    // Preview or Edit Mode
+    if matches!(app.input_mode, InputMode::EditingCommand) {
+        // Edit mode: show working directory and editable command
+        let edit_ui = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("Working dir: {}", app.get_target_workspace()),
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("$ ", Style::default().fg(theme.text_secondary)),
+                Span::styled(
+                    &app.input_buffer,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("_", Style::default().fg(theme.accent)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("[Enter]", Style::default().fg(theme.text_secondary)),
+                Span::styled(" execute  ", Style::default().fg(theme.text_secondary)),
+                Span::styled("[Esc]", Style::default().fg(theme.text_secondary)),
+                Span::styled(" cancel", Style::default().fg(theme.text_secondary)),
+            ]),
+        ]);
+        f.render_widget(edit_ui, chunks[4]);
+    } else if let Some(cmd) = app.get_selected_command() {
+        // Preview mode
+        let preview = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "  Preview",
+                Style::default().fg(theme.text_secondary),
+            )]),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("$ ", Style::default().fg(theme.text_secondary)),
+                Span::styled(cmd.command.clone(), Style::default().fg(theme.accent)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled("[t]", Style::default().fg(theme.text_secondary)),
+                Span::styled(
+                    " change target workspace",
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ]),
+        ]);
+        f.render_widget(preview, chunks[4]);
+    }
 }
 
-fn render_project_browser(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+fn render_detail(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    // Note: Log viewing now uses the overlay API (View::Overlay with OverlayContent::LogViewer)
     let inner_area = area.inner(Margin {
         horizontal: 2,
         vertical: 1,
     });
 
-    // Split into title and content
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(2), // Title
-            Constraint::Min(0),    // Project list
-        ])
-        .split(inner_area);
+    if let Some(project) = app.get_selected_project() {
+        let mut lines = vec![];
+
+        // Full 2-column layout
+        let total_width = inner_area.width.saturating_sub(4) as usize;
+        let left_width = (total_width * 70) / 100;
+        let right_width = total_width.saturating_sub(left_width);
+
+        // Truncate path to fit in right column (accounting for "PATH: " prefix)
+        let path_prefix = "PATH: ";
+        let max_path_width = right_width.saturating_sub(path_prefix.len());
+        let truncated_path = truncate_path(&project.path, max_path_width);
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:width$}", project.name, width = left_width),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}{}", path_prefix, truncated_path),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            project.description.clone(),
+            Style::default().fg(theme.text_secondary),
+        )]));
+        lines.push(Line::from(""));
+
+        // Git Status and Build State
+        if let Some(state) = app.get_current_project_state() {
+            lines.extend(render_git_status(&state.git, theme));
+            lines.push(Line::from(""));
+
+            if !state.drivers.is_empty() {
+                lines.extend(render_driver_statuses(&state.drivers, theme));
+                lines.push(Line::from(""));
+            } else if !state.toolchains.is_empty() || state.python.is_some() {
+                for toolchain in &state.toolchains {
+                    lines.extend(render_toolchain(toolchain, theme));
+                }
+                if let Some(python) = &state.python {
+                    lines.push(render_python_env(python, theme));
+                }
+                lines.push(Line::from(""));
+            }
+
+            if let Some(build) = &state.build {
+                lines.extend(render_build_state(build, theme));
+                lines.push(Line::from(""));
+            }
+
+            if let Some(tests) = &state.tests {
+                lines.extend(render_test_summary(tests, theme));
+                lines.push(Line::from(""));
+            }
 
-    // Title
-    let title = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled(
-                "Projects",
-                Style::default()
-                    .fg(theme::TEXT_PRIMARY)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("  "),
+            if state.problems != (0, 0) {
+                lines.push(Line::from(problem_count_spans(state.problems, theme)));
+                lines.push(Line::from(vec![Span::styled(
+                    "  [p] show problems",
+                    Style::default().fg(theme.text_secondary),
+                )]));
+                lines.push(Line::from(""));
+            }
+        }
+
+        // Recent Logs
+        lines.extend(render_recent_logs(&project.path, app.selected_log, app.diff_mark.as_deref(), theme));
+        lines.push(Line::from(""));
+
+        lines.push(Line::from(vec![Span::styled(
+            "─".repeat(40),
+            Style::default().fg(theme.separator),
+        )]));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Press ", Style::default().fg(theme.text_secondary)),
+            Span::styled("1", Style::default().fg(theme.accent)),
             Span::styled(
-                format!("{}", app.projects.len()),
-                Style::default().fg(theme::TEXT_SECONDARY),
+                " to return to projects",
+                Style::default().fg(theme.text_secondary),
             ),
-        ]),
-        Line::from(""),
-    ]);
-    f.render_widget(title, chunks[0]);
+        ]));
 
-    // Project list with table layout: Project (left) | Location (right)
-    let items: Vec<ListItem> = app
-        .projects
-        .iter()
-        .enumerate()
-        .map(|(i, project)| {
-            let is_selected = i == app.selected_project;
+        let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::NONE));
+        f.render_widget(paragraph, inner_area);
+    } else {
+        let paragraph = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "No project selected",
+                Style::default().fg(theme.text_secondary),
+            )]),
+        ])
+        .block(Block::default().borders(Borders::NONE))
+        .alignment(Alignment::Center);
+        f.render_widget(paragraph, inner_area);
+    }
+}
 
-            let drivers_display = project
-                .drivers
-                .iter()
-                .map(|d| format!("#{}", d))
-                .collect::<Vec<_>>()
-                .join(" ");
+/// Render git status information
+fn render_git_status<'a>(git: &'a GitStatus, theme: &Theme) -> Vec<Line<'a>> {
+    let mut lines = vec![];
 
-            // Calculate column widths (60% project, 40% location)
-            let total_width = inner_area.width.saturating_sub(4) as usize; // Account for padding
-            let project_width = (total_width * 60) / 100;
-            let location_width = total_width.saturating_sub(project_width);
+    if !git.is_repo {
+        lines.push(Line::from(vec![Span::styled(
+            "Not a git repository",
+            Style::default().fg(theme.text_secondary),
+        )]));
+        return lines;
+    }
 
-            // Show workspace instead of full path
-            let workspace_path = app.get_workspace_for_project(&project.path);
-            let display_path = if workspace_path.chars().count() > location_width {
-                let skip = workspace_path.chars().count() - location_width + 1;
-                let truncated: String = workspace_path.chars().skip(skip).collect();
-                format!("…{}", truncated)
-            } else {
-                workspace_path
-            };
+    // Branch and status line
+    let branch_text = git
+        .branch
+        .as_ref()
+        .map(|b| format!("Branch: {}", b))
+        .unwrap_or_else(|| "Branch: (detached HEAD)".to_string());
 
-            // Line 1: Name (left) | Path (right)
-            let name_text = format!("{:width$}", project.name, width = project_width);
-            let line1 = vec![
-                Span::raw("  "),
-                Span::styled(
-                    name_text,
-                    Style::default()
-                        .fg(if is_selected {
-                            theme::ACCENT
-                        } else {
-                            theme::TEXT_PRIMARY
-                        })
-                        .add_modifier(if is_selected {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
-                ),
-                Span::styled(display_path, Style::default().fg(theme::TEXT_SECONDARY)),
-            ];
+    let status_color = if git.is_clean {
+        theme.success
+    } else {
+        theme.error
+    };
 
-            // Line 2: Description (left) | empty (right)
-            let max_desc_width = total_width.saturating_sub(2); // Account for "  " indent
-            let truncated_desc = truncate_path(&project.description, max_desc_width);
-            let line2 = vec![
-                Span::raw("  "),
-                Span::styled(
-                    truncated_desc,
-                    Style::default().fg(theme::TEXT_SECONDARY),
-                ),
-            ];
+    let status_text = if git.conflicted > 0 {
+        " ✗ Conflicts"
+    } else if git.is_clean {
+        " ✓ Clean"
+    } else {
+        " ● Modified"
+    };
 
-            // Line 3: Ecosystem tags (left) | empty (right)
-            let line3 = vec![
-                Span::raw("  "),
-                Span::styled(drivers_display, Style::default().fg(theme::TEXT_SECONDARY)),
-            ];
+    let upstream_span = match &git.upstream {
+        Some(upstream) => Span::styled(format!(" → {}", upstream), Style::default().fg(theme.text_secondary)),
+        None if git.has_no_upstream() => Span::styled(" (no upstream)", Style::default().fg(theme.warning)),
+        None => Span::raw(""),
+    };
 
-            let content = vec![
-                Line::from(line1),
-                Line::from(line2),
-                Line::from(line3),
-                Line::from(""),
-            ];
+    lines.push(Line::from(vec![
+        Span::styled(branch_text, Style::default().fg(theme.text_primary)),
+        upstream_span,
+        Span::styled(
+            status_text,
+            Style::default()
+                .fg(status_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
 
-            ListItem::new(content)
-        })
-        .collect();
+    // Where the project sits in its repository
+    if let Some(layout) = &git.layout {
+        let mut relations = vec![];
+        if let Some(main) = &layout.main_worktree {
+            relations.push(format!("⑂ worktree of {}", main.display()));
+        }
+        if let Some(superproject) = &layout.superproject {
+            relations.push(format!("⊂ submodule of {}", superproject.display()));
+        }
+        if let Some(subdir) = &layout.subdir {
+            relations.push(format!("in {} of {}", subdir, layout.toplevel.display()));
+        }
+        for relation in relations {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", relation),
+                Style::default().fg(theme.text_secondary),
+            )]));
+        }
+    }
 
-    let list = List::new(items)
-        .block(Block::default().borders(Borders::NONE))
-        .highlight_style(Style::default())
-        .highlight_symbol("▸ ");
+    // Interrupted merge/rebase/cherry-pick/revert/bisect
+    if let Some(operation) = git.operation {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  ⚠ {} in progress", operation.as_str()),
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+        )]));
+    }
 
-    let mut state = app.project_list_state.clone();
-    f.render_stateful_widget(list, chunks[1], &mut state);
-}
+    // File counts (if not clean)
+    if !git.is_clean {
+        let mut parts = vec![];
 
-fn render_command_palette(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    let inner_area = area.inner(Margin {
-        horizontal: 2,
-        vertical: 1,
-    });
+        if git.conflicted > 0 {
+            parts.push(format!("{} conflicted", git.conflicted));
+        }
+        if git.staged > 0 {
+            parts.push(format!("{} staged", git.staged));
+        }
+        if git.modified > 0 {
+            parts.push(format!("{} modified", git.modified));
+        }
+        if git.untracked > 0 {
+            parts.push(format!("{} untracked", git.untracked));
+        }
+        if git.renamed > 0 {
+            parts.push(format!("{} renamed", git.renamed));
+        }
 
-    // Split into tab bar, command list, and preview
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // Filter tabs
-            Constraint::Length(2), // Title
-            Constraint::Min(8),    // Command list
-            Constraint::Length(1), // Separator
-            Constraint::Length(6), // Preview
-        ])
-        .split(inner_area);
+        if !parts.is_empty() {
+            lines.push(Line::from(vec![Span::styled(
+                format!("  {}", parts.join(", ")),
+                Style::default().fg(theme.text_secondary),
+            )]));
+        }
+    }
 
-    // Filter tabs (horizontal bar)
-    let tab_spans: Vec<Span> = CommandFilter::all_filters()
-        .into_iter()
-        .flat_map(|filter| {
-            let is_active = filter == app.command_filter;
-            vec![
-                Span::styled(
-                    "[",
-                    Style::default().fg(if is_active {
-                        theme::ACCENT
-                    } else {
-                        theme::TEXT_SECONDARY
-                    }),
-                ),
-                Span::styled(
-                    filter.as_str().to_string(),
-                    Style::default()
-                        .fg(if is_active {
-                            theme::ACCENT
-                        } else {
-                            theme::TEXT_SECONDARY
-                        })
-                        .add_modifier(if is_active {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
-                ),
-                Span::styled(
-                    "]",
-                    Style::default().fg(if is_active {
-                        theme::ACCENT
-                    } else {
-                        theme::TEXT_SECONDARY
-                    }),
-                ),
-                Span::raw(" "),
-            ]
-        })
-        .collect();
+    // Tracking info (ahead/behind, as of the last fetch) and stash
+    let fetched = git.upstream.as_ref().map(|_| match git.last_fetch {
+        Some(timestamp) => format!("fetched {}", crate::state::git::format_age(timestamp)),
+        None => "never fetched".to_string(),
+    });
+    if git.ahead > 0 || git.behind > 0 || git.stash > 0 || fetched.is_some() {
+        let mut tracking = vec![];
+        if git.ahead > 0 {
+            tracking.push(format!("↑{}", git.ahead));
+        }
+        if git.behind > 0 {
+            tracking.push(format!("↓{}", git.behind));
+        }
+        if git.stash > 0 {
+            tracking.push(format!("≡{} stashed", git.stash));
+        }
 
-    let tabs = Paragraph::new(Line::from(tab_spans));
-    f.render_widget(tabs, chunks[0]);
+        let mut spans = vec![Span::styled(
+            format!("  {}", tracking.join(" ")),
+            Style::default().fg(theme.accent),
+        )];
+        if let Some(fetched) = fetched {
+            let separator = if tracking.is_empty() { "" } else { "  " };
+            spans.push(Span::styled(format!("{}({})", separator, fetched), Style::default().fg(theme.text_secondary)));
+        }
+        lines.push(Line::from(spans));
+    }
 
-    // Title
-    let title = Paragraph::new(vec![
-        Line::from(vec![
-            Span::styled(
-                "Commands",
-                Style::default()
-                    .fg(theme::TEXT_PRIMARY)
-                    .add_modifier(Modifier::BOLD),
+    // Submodules: a count, plus each one that needs attention
+    if !git.submodules.is_empty() {
+        use crate::state::git::SubmoduleState;
+
+        let needs_attention: Vec<_> = git
+            .submodules
+            .iter()
+            .filter(|s| s.modified || s.state != SubmoduleState::Current)
+            .collect();
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  Submodules: {}{}",
+                git.submodules.len(),
+                if needs_attention.is_empty() { " ✓".to_string() } else { format!(" ({} need attention)", needs_attention.len()) }
             ),
-            Span::raw("  "),
+            Style::default().fg(theme.text_secondary),
+        )]));
+        for submodule in needs_attention {
+            let mut problems = vec![];
+            match submodule.state {
+                SubmoduleState::OutOfDate => problems.push("out of date"),
+                SubmoduleState::Uninitialized => problems.push("not initialized"),
+                SubmoduleState::Conflict => problems.push("conflict"),
+                SubmoduleState::Current => {}
+            }
+            if submodule.modified {
+                problems.push("modified");
+            }
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {} ", submodule.path), Style::default().fg(theme.text_primary)),
+                Span::styled(format!("@{} ", submodule.commit), Style::default().fg(theme.text_secondary)),
+                Span::styled(problems.join(", "), Style::default().fg(theme.warning)),
+            ]));
+        }
+    }
+
+    // Last commit and tag at HEAD
+    if let Some(commit) = &git.last_commit {
+        let mut spans = vec![
+            Span::styled(format!("  {} ", commit.hash), Style::default().fg(theme.accent)),
+            Span::styled(commit.subject.clone(), Style::default().fg(theme.text_primary)),
             Span::styled(
-                format!("{}", app.commands.len()),
-                Style::default().fg(theme::TEXT_SECONDARY),
+                format!(" — {}, {}", commit.author, commit.age()),
+                Style::default().fg(theme.text_secondary),
             ),
-        ]),
-        Line::from(""),
-    ]);
-    f.render_widget(title, chunks[1]);
-
-    // Command list with table layout: Command (left) | Target (right)
-    let items: Vec<ListItem> = app
-        .commands
-        .iter()
-        .enumerate()
-        .map(|(i, cmd)| {
-            let is_selected = i == app.selected_command;
+        ];
+        if let Some(tag) = &git.tag {
+            spans.push(Span::styled(format!("  ◆ {}", tag), Style::default().fg(theme.success)));
+        }
+        lines.push(Line::from(spans));
+    }
 
-            // Calculate column widths (65% command, 35% target)
-            let total_width = inner_area.width.saturating_sub(4) as usize;
-            let command_width = (total_width * 65) / 100;
-            let target_width = total_width.saturating_sub(command_width);
+    lines
+}
 
-            // Truncate target path to fit
-            // Show project's path when project selected, otherwise target workspace
-            let target_path = if let Some(project) = app.get_selected_project() {
-                project.path.clone()
-            } else {
-                app.get_target_workspace()
+/// Render the latest test run: counts, failing tests and flaky tests
+/// One line per driver of a polyglot project: directory, manifest, toolchain
+/// and the last build when it was one of the driver's tasks
+fn render_driver_statuses(drivers: &[crate::state::DriverStatus], theme: &Theme) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![Span::styled(
+        "Drivers",
+        Style::default().fg(theme.text_primary),
+    )])];
+    for driver in drivers {
+        let mut spans = vec![
+            Span::styled(format!("  {:<10}", driver.capability), Style::default().fg(theme.accent)),
+            Span::styled(format!("{:<8}", driver.driver), Style::default().fg(theme.text_primary)),
+            Span::styled(format!("{}/  ", driver.dir), Style::default().fg(theme.text_secondary)),
+        ];
+        spans.push(match driver.manifest {
+            Some(manifest) => Span::styled(format!("✓ {}", manifest), Style::default().fg(theme.success)),
+            None => Span::styled("✗ no manifest", Style::default().fg(theme.error)),
+        });
+        if let Some(toolchain) = &driver.toolchain {
+            spans.push(Span::raw("  "));
+            spans.push(match (&toolchain.version, &toolchain.required) {
+                (Some(version), Some(required)) if toolchain.mismatch() => Span::styled(
+                    format!("{} ✗ requires {}", version, required.spec),
+                    Style::default().fg(theme.error),
+                ),
+                (Some(version), _) => Span::styled(version.clone(), Style::default().fg(theme.text_secondary)),
+                (None, _) => Span::styled(format!("{} not on PATH", toolchain.name), Style::default().fg(theme.warning)),
+            });
+        }
+        if let Some(build) = &driver.last_build {
+            let (status, color) = match build.status {
+                BuildStatus::Success => ("✓", theme.success),
+                BuildStatus::Failed => ("✗", theme.error),
+                BuildStatus::Running => ("⟳", theme.accent),
             };
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(format!("{} {}", status, build.task), Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
 
-            let display_target = if target_path.chars().count() > target_width {
-                let skip = target_path.chars().count() - target_width + 1;
-                let truncated: String = target_path.chars().skip(skip).collect();
-                format!("…{}", truncated)
-            } else {
-                target_path
-            };
+/// Installed version of a toolchain against the project's requirement, with
+/// the fix when they don't match
+fn render_toolchain(toolchain: &crate::state::Toolchain, theme: &Theme) -> Vec<Line<'static>> {
+    let installed = match &toolchain.version {
+        Some(version) => Span::styled(
+            format!("{} {}", toolchain.name, version),
+            Style::default().fg(theme.text_primary),
+        ),
+        None => Span::styled(
+            format!("{} not found on PATH", toolchain.name),
+            Style::default().fg(theme.warning),
+        ),
+    };
+    let mut spans = vec![installed];
+    if let Some(required) = &toolchain.required {
+        let (icon, color) = match toolchain.satisfied() {
+            Some(true) => ("✓", theme.success),
+            Some(false) => ("✗ requires", theme.error),
+            None if toolchain.mismatch() => ("✗ requires", theme.error),
+            None => ("·", theme.text_secondary),
+        };
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{} {} ({})", icon, required.spec, required.source),
+            Style::default().fg(color),
+        ));
+    }
 
-            // Line 1: Command name (left) | Target directory (right)
-            let name_text = format!("{:width$}", cmd.name, width = command_width);
-            let line1 = vec![
-                Span::raw("  "),
-                Span::styled(
-                    name_text,
-                    Style::default()
-                        .fg(if is_selected {
-                            theme::ACCENT
-                        } else {
-                            theme::TEXT_PRIMARY
-                        })
-                        .add_modifier(if is_selected {
-                            Modifier::BOLD
-                        } else {
-                            Modifier::empty()
-                        }),
-                ),
-                Span::styled(display_target, Style::default().fg(theme::TEXT_SECONDARY)),
-            ];
+    let mut lines = vec![Line::from(spans)];
+    if toolchain.mismatch()
+        && let Some(fix) = toolchain.fix()
+    {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  → {}", fix),
+            Style::default().fg(theme.text_secondary),
+        )]));
+    }
+    lines
+}
 
-            // Line 2: Description (left) | empty (right)
-            let max_desc_width = total_width.saturating_sub(2); // Account for "  " indent
-            let truncated_desc = truncate_path(&cmd.description, max_desc_width);
-            let line2 = vec![
-                Span::raw("  "),
-                Span::styled(
-                    truncated_desc,
-                    Style::default().fg(theme::TEXT_SECONDARY),
-                ),
-            ];
+/// Virtualenv and package manager of a Python project
+fn render_python_env(python: &crate::state::PythonEnv, theme: &Theme) -> Line<'static> {
+    let (venv, venv_color) = match &python.venv {
+        Some(venv) => (format!("Virtualenv {}", venv.display()), theme.text_primary),
+        None => ("No virtualenv".to_string(), theme.warning),
+    };
+    Line::from(vec![
+        Span::styled(venv, Style::default().fg(venv_color)),
+        Span::raw("  "),
+        Span::styled(format!("({})", python.manager), Style::default().fg(theme.text_secondary)),
+    ])
+}
 
-            let content = vec![Line::from(line1), Line::from(line2), Line::from("")];
+fn render_test_summary(tests: &crate::state::TestState, theme: &Theme) -> Vec<Line<'static>> {
+    let run = &tests.last_run;
+    let label = run.task.clone().unwrap_or_else(|| run.command.clone());
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("Tests: {}", label), Style::default().fg(theme.text_primary)),
+        Span::raw("  "),
+        Span::styled(format!("✓ {} passed", run.passed), Style::default().fg(theme.success)),
+        Span::raw("  "),
+        Span::styled(
+            format!("✗ {} failed", run.failed),
+            Style::default().fg(if run.failed > 0 { theme.error } else { theme.text_secondary }),
+        ),
+        Span::raw("  "),
+        Span::styled(format!("○ {} ignored", run.ignored), Style::default().fg(theme.text_secondary)),
+    ])];
 
-            ListItem::new(content)
-        })
-        .collect();
+    const SHOWN: usize = 3;
+    for failure in run.failures().take(SHOWN) {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  ✗ {}", failure.name),
+            Style::default().fg(theme.error),
+        )]));
+    }
+    if run.failed > SHOWN {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  … {} more", run.failed - SHOWN),
+            Style::default().fg(theme.text_secondary),
+        )]));
+    }
 
-    let list = List::new(items)
-        .block(Block::default().borders(Borders::NONE))
-        .highlight_style(Style::default())
-        .highlight_symbol("▸ ");
+    let flaky = tests.flaky_tests();
+    if !flaky.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            format!("  ⚠ {} flaky", flaky.len()),
+            Style::default().fg(theme.warning),
+        )]));
+    }
+    if run.failed > 0 || !flaky.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "  [T] show test report",
+            Style::default().fg(theme.text_secondary),
+        )]));
+    }
 
-    let mut state = app.command_list_state.clone();
-    f.render_stateful_widget(list, chunks[2], &mut state);
+    lines
+}
 
-    // Separator
-    let separator = Paragraph::new(Line::from(vec![Span::styled(
-        "─".repeat(inner_area.width as usize),
-        Style::default().fg(theme::SEPARATOR),
-    )]));
-    f.render_widget(separator, chunks[3]);
+/// One row of the test report overlay
+enum TestReportRow {
+    Heading(String),
+    Failure(String),
+    Output(String),
+    Flaky(String, usize),
+    Blank,
+}
 
-    // Preview or Edit Mode
-    if matches!(app.input_mode, InputMode::EditingCommand) {
-        // Edit mode: show working directory and editable command
-        let edit_ui = Paragraph::new(vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled(
-                    format!("Working dir: {}", app.get_target_workspace()),
-                    Style::default().fg(theme::TEXT_SECONDARY),
-                ),
-            ]),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("$ ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(
-                    &app.input_buffer,
-                    Style::default()
-                        .fg(theme::ACCENT)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("_", Style::default().fg(theme::ACCENT)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("[Enter]", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(" execute  ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled("[Esc]", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(" cancel", Style::default().fg(theme::TEXT_SECONDARY)),
-            ]),
-        ]);
-        f.render_widget(edit_ui, chunks[4]);
-    } else if let Some(cmd) = app.get_selected_command() {
-        // Preview mode
-        let preview = Paragraph::new(vec![
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "  Preview",
-                Style::default().fg(theme::TEXT_SECONDARY),
-            )]),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("$ ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(cmd.command.clone(), Style::default().fg(theme::ACCENT)),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::raw("  "),
-                Span::styled("[t]", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(
-                    " change target workspace",
-                    Style::default().fg(theme::TEXT_SECONDARY),
-                ),
-            ]),
-        ]);
-        f.render_widget(preview, chunks[4]);
+fn test_report_rows(state: &crate::state::TestState) -> Vec<TestReportRow> {
+    let mut rows = Vec::new();
+    let run = &state.last_run;
+
+    rows.push(TestReportRow::Heading(format!("Failing tests ({})", run.failed)));
+    for failure in run.failures() {
+        rows.push(TestReportRow::Failure(failure.id()));
+        rows.extend(failure.output.iter().map(|line| TestReportRow::Output(line.clone())));
+        rows.push(TestReportRow::Blank);
+    }
+    if run.failed == 0 {
+        rows.push(TestReportRow::Output("All tests passed".to_string()));
+        rows.push(TestReportRow::Blank);
+    }
+
+    let flipped = state.flipped_in_last_run();
+    if !flipped.is_empty() {
+        rows.push(TestReportRow::Heading(format!("Changed since previous run ({})", flipped.len())));
+        rows.extend(flipped.into_iter().map(|id| TestReportRow::Flaky(id.clone(), state.flips(&id))));
+        rows.push(TestReportRow::Blank);
     }
+
+    let flaky = state.flaky_tests();
+    if !flaky.is_empty() {
+        rows.push(TestReportRow::Heading(format!("Flaky tests ({})", flaky.len())));
+        rows.extend(flaky.into_iter().map(|(id, flips)| TestReportRow::Flaky(id.to_string(), flips)));
+    }
+
+    rows
 }
 
-fn render_detail(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    // Note: Log viewing now uses the overlay API (View::Overlay with OverlayContent::LogViewer)
+/// Files shown in the Changes overlay for the staged or unstaged side
+fn visible_changes(
+    changes: &[crate::state::git_diff::FileChange],
+    staged: bool,
+) -> Vec<&crate::state::git_diff::FileChange> {
+    changes
+        .iter()
+        .filter(|c| if staged { c.has_staged() } else { c.has_unstaged() })
+        .collect()
+}
+
+/// Render the Changes overlay: file list, or the selected file's diff
+fn render_overlay_git_changes(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    use crate::state::git_diff::DiffLineKind;
+
+    let theme = &app.theme;
+    let Some(OverlayContent::GitChanges {
+        project_path,
+        changes,
+        selected,
+        staged,
+        diff,
+        scroll_offset,
+    }) = &app.active_overlay
+    else {
+        return;
+    };
+
     let inner_area = area.inner(Margin {
-        horizontal: 2,
-        vertical: 1,
+        horizontal: 1,
+        vertical: 0,
     });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
 
-    if let Some(project) = app.get_selected_project() {
-        let mut lines = vec![];
-
-        // Full 2-column layout
-        let total_width = inner_area.width.saturating_sub(4) as usize;
-        let left_width = (total_width * 70) / 100;
-        let right_width = total_width.saturating_sub(left_width);
+    let side = if *staged { "staged" } else { "unstaged" };
+    let visible = visible_changes(changes, *staged);
+    let mut display_lines = Vec::new();
 
-        // Truncate path to fit in right column (accounting for "PATH: " prefix)
-        let path_prefix = "PATH: ";
-        let max_path_width = right_width.saturating_sub(path_prefix.len());
-        let truncated_path = truncate_path(&project.path, max_path_width);
+    match diff {
+        Some(file_diff) => {
+            let path = visible.get(*selected).map(|c| c.path.as_str()).unwrap_or_default();
+            display_lines.push(Line::from(vec![
+                Span::styled(path.to_string(), Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("  ({}, {} hunks)", side, file_diff.hunks.len()),
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ]));
+            display_lines.push(Line::from(vec![Span::styled(
+                "[Esc] back  [n/N] next/prev hunk  [Space] stage/unstage hunk  [s] staged/unstaged  [o] open in editor",
+                Style::default().fg(theme.text_secondary),
+            )]));
 
-        lines.push(Line::from(vec![
-            Span::styled(
-                format!("{:width$}", project.name, width = left_width),
-                Style::default()
-                    .fg(theme::ACCENT)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}{}", path_prefix, truncated_path),
-                Style::default().fg(theme::TEXT_SECONDARY),
-            ),
-        ]));
-        lines.push(Line::from(""));
-        lines.push(Line::from(vec![Span::styled(
-            project.description.clone(),
-            Style::default().fg(theme::TEXT_SECONDARY),
-        )]));
-        lines.push(Line::from(""));
+            let lines = file_diff.lines();
+            let start = (*scroll_offset).min(lines.len().saturating_sub(visible_height));
+            for (kind, text) in lines.into_iter().skip(start).take(visible_height) {
+                let style = match kind {
+                    DiffLineKind::Header => Style::default().fg(theme.text_secondary),
+                    DiffLineKind::HunkHeader => Style::default().fg(theme.accent),
+                    DiffLineKind::Added => Style::default().fg(theme.success),
+                    DiffLineKind::Removed => Style::default().fg(theme.error),
+                    DiffLineKind::Context => Style::default().fg(theme.text_primary),
+                };
+                let spans = vec![Span::styled(text.replace('\t', "    "), style)];
+                display_lines.push(Line::from(ansi::truncate(spans, available_width)));
+            }
+        }
+        None => {
+            let branch = app
+                .project_states
+                .get(project_path)
+                .and_then(|state| state.git.branch.clone())
+                .unwrap_or_default();
+            display_lines.push(Line::from(vec![
+                Span::styled("Changes", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  on {}", branch), Style::default().fg(theme.accent)),
+                Span::styled(
+                    format!("  {} ({} of {} files)", side, visible.len(), changes.len()),
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ]));
+            display_lines.push(Line::from(vec![Span::styled(
+                "[Esc] close  [Enter] diff  [Space] stage/unstage  [s] staged/unstaged  [C] commit  [b] branches  [z/Z] stash/pop  [o] edit",
+                Style::default().fg(theme.text_secondary),
+            )]));
 
-        // Git Status and Build State
-        if let Some(state) = app.get_current_project_state() {
-            lines.extend(render_git_status(&state.git));
-            lines.push(Line::from(""));
+            if visible.is_empty() {
+                display_lines.push(Line::from(vec![Span::styled(
+                    format!("  No {} changes", side),
+                    Style::default().fg(theme.text_secondary),
+                )]));
+            }
 
-            if let Some(build) = &state.build {
-                lines.extend(render_build_state(build));
-                lines.push(Line::from(""));
+            let start = selected.saturating_sub(visible_height.saturating_sub(1));
+            for (i, change) in visible.iter().enumerate().skip(start).take(visible_height) {
+                let is_selected = i == *selected;
+                let code_color = if change.is_conflicted() {
+                    theme.error
+                } else if change.is_untracked() {
+                    theme.text_secondary
+                } else if *staged {
+                    theme.success
+                } else {
+                    theme.warning
+                };
+                let path = match &change.orig_path {
+                    Some(orig) => format!("{} → {}", orig, change.path),
+                    None => change.path.clone(),
+                };
+                let spans = vec![
+                    Span::styled(if is_selected { "▸ " } else { "  " }, Style::default().fg(theme.accent)),
+                    Span::styled(format!("{}  ", change.code()), Style::default().fg(code_color).add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        path,
+                        Style::default()
+                            .fg(if is_selected { theme.accent } else { theme.text_primary })
+                            .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() }),
+                    ),
+                ];
+                display_lines.push(Line::from(ansi::truncate(spans, available_width)));
             }
         }
+    }
 
-        // Recent Logs
-        lines.extend(render_recent_logs(&project.path, app.selected_log));
-        lines.push(Line::from(""));
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
 
-        lines.push(Line::from(vec![Span::styled(
-            "─".repeat(40),
-            Style::default().fg(theme::SEPARATOR),
-        )]));
-        lines.push(Line::from(""));
-        lines.push(Line::from(vec![
-            Span::styled("Press ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("1", Style::default().fg(theme::ACCENT)),
+    f.render_widget(paragraph, inner_area);
+}
+
+/// Render the multi-repo sync summary overlay
+fn render_overlay_sync_summary(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    use crate::state::git_sync::SyncOutcome;
+
+    let theme = &app.theme;
+    let Some(OverlayContent::SyncSummary { results, selected, mode }) = &app.active_overlay else {
+        return;
+    };
+
+    let inner_area = area.inner(Margin {
+        horizontal: 1,
+        vertical: 0,
+    });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
+
+    let mut display_lines = vec![
+        Line::from(vec![
             Span::styled(
-                " to return to projects",
-                Style::default().fg(theme::TEXT_SECONDARY),
+                match (mode.pull, mode.push) {
+                    (true, true) => "Sync (fetch + fast-forward + push)",
+                    (true, false) => "Sync (fetch + fast-forward)",
+                    (false, true) => "Sync (fetch + push)",
+                    (false, false) => "Sync (fetch)",
+                },
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
             ),
-        ]));
-
-        let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::NONE));
-        f.render_widget(paragraph, inner_area);
-    } else {
-        let paragraph = Paragraph::new(vec![
-            Line::from(""),
+            Span::styled(
+                format!("  {}", crate::state::git_sync::summarize(results)),
+                Style::default().fg(theme.text_secondary),
+            ),
+        ]),
+        Line::from(vec![Span::styled(
+            "[Esc] close  [↑↓] select  [Enter] project details  [P] fast-forward projects that are behind  [U] push projects that are ahead",
+            Style::default().fg(theme.text_secondary),
+        )]),
+    ];
+    if mode.push {
+        display_lines.insert(
+            1,
             Line::from(vec![Span::styled(
-                "No project selected",
-                Style::default().fg(theme::TEXT_SECONDARY),
+                format!("Push: {}", crate::state::git_sync::push_report(results)),
+                Style::default().fg(theme.text_secondary),
             )]),
-        ])
-        .block(Block::default().borders(Borders::NONE))
-        .alignment(Alignment::Center);
-        f.render_widget(paragraph, inner_area);
+        );
+    }
+
+    let name_width = results.iter().map(|r| r.name.chars().count()).max().unwrap_or(0);
+    let start = selected.saturating_sub(visible_height.saturating_sub(1));
+    for (i, result) in results.iter().enumerate().skip(start).take(visible_height) {
+        let is_selected = i == *selected;
+        let (icon, color) = match &result.outcome {
+            SyncOutcome::UpToDate => ("✓", theme.success),
+            SyncOutcome::FastForwarded(_) => ("↓", theme.success),
+            SyncOutcome::Pushed(_) => ("↑", theme.success),
+            SyncOutcome::Behind(_) | SyncOutcome::Ahead(_) => ("•", theme.warning),
+            SyncOutcome::NoUpstream | SyncOutcome::NotARepo => ("-", theme.text_secondary),
+            _ => ("✗", theme.error),
+        };
+        let spans = vec![
+            Span::styled(if is_selected { "▸ " } else { "  " }, Style::default().fg(theme.accent)),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(
+                format!("{:width$}  ", result.name, width = name_width),
+                Style::default()
+                    .fg(if is_selected { theme.accent } else { theme.text_primary })
+                    .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() }),
+            ),
+            Span::styled(result.outcome.label(), Style::default().fg(color)),
+        ];
+        display_lines.push(Line::from(ansi::truncate(spans, available_width)));
     }
+
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
+
+    f.render_widget(paragraph, inner_area);
 }
 
-/// Render git status information
-fn render_git_status(git: &GitStatus) -> Vec<Line<'_>> {
-    let mut lines = vec![];
+/// Render the branch picker overlay
+fn render_overlay_branches(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(OverlayContent::Branches {
+        branches,
+        selected,
+        new_branch,
+        ..
+    }) = &app.active_overlay
+    else {
+        return;
+    };
 
-    if !git.is_repo {
-        lines.push(Line::from(vec![Span::styled(
-            "Not a git repository",
-            Style::default().fg(theme::TEXT_SECONDARY),
-        )]));
-        return lines;
+    let inner_area = area.inner(Margin {
+        horizontal: 1,
+        vertical: 0,
+    });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
+
+    let mut display_lines = vec![
+        Line::from(vec![
+            Span::styled("Branches", Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  ({})", branches.len()), Style::default().fg(theme.text_secondary)),
+        ]),
+        match new_branch {
+            Some(name) => Line::from(vec![
+                Span::styled("New branch ===> ", Style::default().fg(theme.accent)),
+                Span::styled(format!("{}█", name), Style::default().fg(theme.text_primary)),
+            ]),
+            None => Line::from(vec![Span::styled(
+                "[Esc] close  [↑↓] select  [Enter] switch  [n] new branch  [D] delete",
+                Style::default().fg(theme.text_secondary),
+            )]),
+        },
+    ];
+
+    let start = selected.saturating_sub(visible_height.saturating_sub(1));
+    for (i, branch) in branches.iter().enumerate().skip(start).take(visible_height) {
+        let is_selected = i == *selected;
+        let mut spans = vec![
+            Span::styled(if is_selected { "▸ " } else { "  " }, Style::default().fg(theme.accent)),
+            Span::styled(if branch.current { "* " } else { "  " }, Style::default().fg(theme.success)),
+            Span::styled(
+                branch.name.clone(),
+                Style::default()
+                    .fg(if branch.current { theme.success } else if is_selected { theme.accent } else { theme.text_primary })
+                    .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() }),
+            ),
+        ];
+        if let Some(upstream) = &branch.upstream {
+            spans.push(Span::styled(format!("  → {}", upstream), Style::default().fg(theme.text_secondary)));
+        }
+        display_lines.push(Line::from(ansi::truncate(spans, available_width)));
     }
 
-    // Branch and status line
-    let branch_text = git
-        .branch
-        .as_ref()
-        .map(|b| format!("Branch: {}", b))
-        .unwrap_or_else(|| "Branch: (detached HEAD)".to_string());
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
 
-    let status_color = if git.is_clean {
-        theme::SUCCESS
-    } else {
-        theme::ERROR
-    };
+    f.render_widget(paragraph, inner_area);
+}
 
-    let status_text = if git.is_clean {
-        " ✓ Clean"
-    } else {
-        " ● Modified"
+/// Render the test report overlay
+fn render_overlay_test_results(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(OverlayContent::TestResults { state, scroll_offset }) = &app.active_overlay else {
+        return;
     };
 
-    lines.push(Line::from(vec![
-        Span::styled(branch_text, Style::default().fg(theme::TEXT_PRIMARY)),
-        Span::styled(
-            status_text,
-            Style::default()
-                .fg(status_color)
-                .add_modifier(Modifier::BOLD),
-        ),
-    ]));
+    let inner_area = area.inner(Margin {
+        horizontal: 1,
+        vertical: 0,
+    });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
 
-    // File counts (if not clean)
-    if !git.is_clean {
-        let mut parts = vec![];
+    let run = &state.last_run;
+    let rows = test_report_rows(state);
+    let start = (*scroll_offset).min(rows.len().saturating_sub(visible_height));
 
-        if git.staged > 0 {
-            parts.push(format!("{} staged", git.staged));
-        }
-        if git.modified > 0 {
-            parts.push(format!("{} modified", git.modified));
-        }
-        if git.untracked > 0 {
-            parts.push(format!("{} untracked", git.untracked));
-        }
+    let mut display_lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("Tests: {}  ", run.task.as_deref().unwrap_or(&run.command)),
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("✓ {} passed  ", run.passed), Style::default().fg(theme.success)),
+            Span::styled(format!("✗ {} failed  ", run.failed), Style::default().fg(theme.error)),
+            Span::styled(format!("○ {} ignored", run.ignored), Style::default().fg(theme.text_secondary)),
+        ]),
+        Line::from(vec![Span::styled(
+            "[Esc] close  [n/N] next/prev failure  [g/G] top/bottom",
+            Style::default().fg(theme.text_secondary),
+        )]),
+    ];
 
-        if !parts.is_empty() {
-            lines.push(Line::from(vec![Span::styled(
-                format!("  {}", parts.join(", ")),
-                Style::default().fg(theme::TEXT_SECONDARY),
-            )]));
-        }
+    for row in rows.iter().skip(start).take(visible_height) {
+        let spans = match row {
+            TestReportRow::Heading(text) => vec![Span::styled(
+                text.clone(),
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+            )],
+            TestReportRow::Failure(id) => vec![Span::styled(
+                format!("✗ {}", id),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            )],
+            TestReportRow::Output(text) => {
+                let mut spans = vec![Span::raw("    ")];
+                spans.extend(ansi::parse_line(text, true));
+                spans
+            }
+            TestReportRow::Flaky(id, flips) => vec![
+                Span::styled(format!("⚠ {}", id), Style::default().fg(theme.warning)),
+                Span::styled(
+                    format!("  {} flips in recent runs", flips),
+                    Style::default().fg(theme.text_secondary),
+                ),
+            ],
+            TestReportRow::Blank => vec![],
+        };
+        display_lines.push(Line::from(ansi::truncate(spans, available_width)));
     }
 
-    // Tracking info (ahead/behind)
-    if git.ahead > 0 || git.behind > 0 {
-        let mut tracking = vec![];
-        if git.ahead > 0 {
-            tracking.push(format!("↑{}", git.ahead));
-        }
-        if git.behind > 0 {
-            tracking.push(format!("↓{}", git.behind));
-        }
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
 
-        lines.push(Line::from(vec![Span::styled(
-            format!("  {}", tracking.join(" ")),
-            Style::default().fg(theme::ACCENT),
-        )]));
-    }
+    f.render_widget(paragraph, inner_area);
+}
 
-    lines
+/// "✗ 2 errors  ⚠ 1 warning" (zero counts omitted)
+fn problem_count_spans(problems: (usize, usize), theme: &Theme) -> Vec<Span<'static>> {
+    let plural = |n: usize, word: &str| if n == 1 { format!("{} {}", n, word) } else { format!("{} {}s", n, word) };
+    let (errors, warnings) = problems;
+    let mut spans = vec![];
+    if errors > 0 {
+        spans.push(Span::styled(format!("✗ {}", plural(errors, "error")), Style::default().fg(theme.error)));
+    }
+    if warnings > 0 {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(format!("⚠ {}", plural(warnings, "warning")), Style::default().fg(theme.warning)));
+    }
+    spans
 }
 
 /// Render build state information
-fn render_build_state(build: &BuildState) -> Vec<Line<'_>> {
+fn render_build_state<'a>(build: &'a BuildState, theme: &Theme) -> Vec<Line<'a>> {
     use chrono::{DateTime, Utc};
 
     let mut lines = vec![];
 
     // Status line
     let (status_text, status_color) = match build.status {
-        BuildStatus::Success => ("✓ Success", theme::SUCCESS),
-        BuildStatus::Failed => ("✗ Failed", theme::ERROR),
-        BuildStatus::Running => ("⟳ Running", theme::ACCENT),
+        BuildStatus::Success => ("✓ Success", theme.success),
+        BuildStatus::Failed => ("✗ Failed", theme.error),
+        BuildStatus::Running => ("⟳ Running", theme.accent),
     };
 
     lines.push(Line::from(vec![
         Span::styled(
             format!("Build: {}", build.task),
-            Style::default().fg(theme::TEXT_PRIMARY),
+            Style::default().fg(theme.text_primary),
         ),
         Span::raw("  "),
         Span::styled(
@@ -2950,7 +5398,7 @@ fn render_build_state(build: &BuildState) -> Vec<Line<'_>> {
 
         lines.push(Line::from(vec![Span::styled(
             format!("  Last build: {}", time_ago),
-            Style::default().fg(theme::TEXT_SECONDARY),
+            Style::default().fg(theme.text_secondary),
         )]));
     }
 
@@ -2958,12 +5406,17 @@ fn render_build_state(build: &BuildState) -> Vec<Line<'_>> {
 }
 
 /// Render recent command logs
-fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>> {
+fn render_recent_logs(
+    project_path: &str,
+    selected_log: usize,
+    diff_mark: Option<&std::path::Path>,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
     let mut lines = vec![];
 
     lines.push(Line::from(vec![Span::styled(
         "Recent Logs",
-        Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::BOLD),
+        Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
     )]));
 
     let logs = crate::fs::ProjectFileSystem::new(project_path).ok().and_then(|fs| fs.recent_logs_all(5).ok()).unwrap_or_default();
@@ -2971,7 +5424,7 @@ fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>>
     if logs.is_empty() {
         lines.push(Line::from(vec![Span::styled(
             "  No logs available",
-            Style::default().fg(theme::TEXT_SECONDARY),
+            Style::default().fg(theme.text_secondary),
         )]));
     } else {
         use chrono::{DateTime, Utc};
@@ -3002,10 +5455,10 @@ fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>>
 
             // Category badge color
             let category_color = match log.category.as_str() {
-                "build" => theme::ACCENT,
-                "git" => ratatui::style::Color::Rgb(255, 165, 0), // Orange
-                "lint" => ratatui::style::Color::Rgb(147, 112, 219), // Purple
-                _ => theme::TEXT_SECONDARY,
+                "build" => theme.accent,
+                "git" => theme.category_git,
+                "lint" => theme.category_lint,
+                _ => theme.text_secondary,
             };
 
             // Selection indicator and styling
@@ -3015,9 +5468,10 @@ fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>>
                 (" ", Style::default())
             };
 
-            lines.push(Line::from(vec![
-                Span::styled(indicator, Style::default().fg(theme::ACCENT)),
-                Span::raw(" "),
+            let marked = diff_mark == Some(log.path.as_path());
+            let mut spans = vec![
+                Span::styled(indicator, Style::default().fg(theme.accent)),
+                Span::styled(if marked { "◆" } else { " " }, Style::default().fg(theme.warning)),
                 Span::styled(
                     format!("[{}]", log.category),
                     Style::default().fg(category_color).add_modifier(Modifier::BOLD),
@@ -3025,25 +5479,46 @@ fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>>
                 Span::raw(" "),
                 Span::styled(
                     log.filename.clone(),
-                    text_style.fg(theme::TEXT_PRIMARY),
+                    text_style.fg(theme.text_primary),
                 ),
                 Span::raw("  "),
                 Span::styled(
                     time_str,
-                    text_style.fg(theme::TEXT_SECONDARY),
+                    text_style.fg(theme.text_secondary),
                 ),
-            ]));
+            ];
+
+            // Exit status and duration from the log index
+            if let Some(meta) = &log.meta {
+                let (status, color) = if meta.success() {
+                    ("✓".to_string(), theme.success)
+                } else {
+                    (format!("✗ {}", meta.exit_code), theme.error)
+                };
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(status, Style::default().fg(color)));
+                if meta.duration_ms > 0 {
+                    spans.push(Span::styled(
+                        format!(" {:.1}s", meta.duration_ms as f64 / 1000.0),
+                        text_style.fg(theme.text_secondary),
+                    ));
+                }
+            }
+
+            lines.push(Line::from(spans));
         }
 
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
-            Span::styled("  Use ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("↑↓", Style::default().fg(theme::ACCENT)),
-            Span::styled(" to navigate, ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("l", Style::default().fg(theme::ACCENT)),
-            Span::styled(" to preview, ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("o", Style::default().fg(theme::ACCENT)),
-            Span::styled(" to open in editor", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("  Use ", Style::default().fg(theme.text_secondary)),
+            Span::styled("↑↓", Style::default().fg(theme.accent)),
+            Span::styled(" to navigate, ", Style::default().fg(theme.text_secondary)),
+            Span::styled("l", Style::default().fg(theme.accent)),
+            Span::styled(" to preview, ", Style::default().fg(theme.text_secondary)),
+            Span::styled("o", Style::default().fg(theme.accent)),
+            Span::styled(" to open in editor, ", Style::default().fg(theme.text_secondary)),
+            Span::styled("d", Style::default().fg(theme.accent)),
+            Span::styled(" on two logs to diff", Style::default().fg(theme.text_secondary)),
         ]));
     }
 
@@ -3052,6 +5527,7 @@ fn render_recent_logs(project_path: &str, selected_log: usize) -> Vec<Line<'_>>
 
 
 fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
     let inner_area = area.inner(Margin {
         horizontal: 2,
         vertical: 1,
@@ -3082,13 +5558,13 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
             Span::styled(
                 "Workspace Manager",
                 Style::default()
-                    .fg(theme::TEXT_PRIMARY)
+                    .fg(theme.text_primary)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("  "),
             Span::styled(
                 format!("{}", app.workspace_directories.len()),
-                Style::default().fg(theme::TEXT_SECONDARY),
+                Style::default().fg(theme.text_secondary),
             ),
         ]),
         Line::from(""),
@@ -3122,9 +5598,9 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                 display_path,
                 Style::default()
                     .fg(if is_selected {
-                        theme::ACCENT
+                        theme.accent
                     } else {
-                        theme::TEXT_PRIMARY
+                        theme.text_primary
                     })
                     .add_modifier(if is_selected {
                         Modifier::BOLD
@@ -3144,7 +5620,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
             };
             line1.push(Span::styled(
                 count_text,
-                Style::default().fg(theme::TEXT_SECONDARY),
+                Style::default().fg(theme.text_secondary),
             ));
 
             // Primary indicator
@@ -3152,7 +5628,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                 line1.push(Span::raw("  "));
                 line1.push(Span::styled(
                     "[primary]",
-                    Style::default().fg(theme::TEXT_SECONDARY),
+                    Style::default().fg(theme.text_secondary),
                 ));
             }
 
@@ -3173,7 +5649,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
     // Separator
     let separator = Paragraph::new(Line::from(vec![Span::styled(
         "─".repeat(inner_area.width as usize),
-        Style::default().fg(theme::SEPARATOR),
+        Style::default().fg(theme.separator),
     )]));
     f.render_widget(separator, chunks[2]);
 
@@ -3191,26 +5667,26 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
             let truncated: String = app.input_buffer.chars().skip(skip).collect();
             Line::from(vec![
                 Span::raw("  "),
-                Span::styled(label, Style::default().fg(theme::TEXT_SECONDARY)),
+                Span::styled(label, Style::default().fg(theme.text_secondary)),
                 Span::styled(
                     format!("…{}", truncated),
                     Style::default()
-                        .fg(theme::ACCENT)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("_", Style::default().fg(theme::ACCENT)),
+                Span::styled("_", Style::default().fg(theme.accent)),
             ])
         } else {
             Line::from(vec![
                 Span::raw("  "),
-                Span::styled(label, Style::default().fg(theme::TEXT_SECONDARY)),
+                Span::styled(label, Style::default().fg(theme.text_secondary)),
                 Span::styled(
                     &app.input_buffer,
                     Style::default()
-                        .fg(theme::ACCENT)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled("_", Style::default().fg(theme::ACCENT)),
+                Span::styled("_", Style::default().fg(theme.accent)),
             ])
         };
 
@@ -3223,7 +5699,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                 Span::raw("  "),
                 Span::styled(
                     "───────────────────────────────────────────────",
-                    Style::default().fg(theme::ACCENT),
+                    Style::default().fg(theme.accent),
                 ),
             ]));
 
@@ -3265,8 +5741,8 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                         Span::styled(
                             format!("▸ {}", display_path),
                             Style::default()
-                                .fg(theme::BADGE_TEXT)
-                                .bg(theme::ACCENT)
+                                .fg(theme.badge_text)
+                                .bg(theme.accent)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ]));
@@ -3277,7 +5753,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                         Span::styled(
                             display_path,
                             Style::default()
-                                .fg(theme::TEXT_PRIMARY)
+                                .fg(theme.text_primary)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ]));
@@ -3302,7 +5778,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                         Span::raw("  "),
                         Span::styled(
                             format!("  {}", indicator_parts.join("  •  ")),
-                            Style::default().fg(theme::TEXT_SECONDARY),
+                            Style::default().fg(theme.text_secondary),
                         ),
                     ]));
                 }
@@ -3312,7 +5788,7 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
                 Span::raw("  "),
                 Span::styled(
                     "───────────────────────────────────────────────",
-                    Style::default().fg(theme::ACCENT),
+                    Style::default().fg(theme.accent),
                 ),
             ]));
         }
@@ -3320,14 +5796,14 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
         // Add keyboard help
         lines.push(Line::from(vec![
             Span::raw("  "),
-            Span::styled("[Tab]", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" complete  ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("[Ctrl+D]", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" fuzzy find  ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("[Enter]", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" add  ", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled("[Esc]", Style::default().fg(theme::TEXT_SECONDARY)),
-            Span::styled(" cancel", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("[Tab]", Style::default().fg(theme.text_secondary)),
+            Span::styled(" complete  ", Style::default().fg(theme.text_secondary)),
+            Span::styled("[Ctrl+D]", Style::default().fg(theme.text_secondary)),
+            Span::styled(" fuzzy find  ", Style::default().fg(theme.text_secondary)),
+            Span::styled("[Enter]", Style::default().fg(theme.text_secondary)),
+            Span::styled(" add  ", Style::default().fg(theme.text_secondary)),
+            Span::styled("[Esc]", Style::default().fg(theme.text_secondary)),
+            Span::styled(" cancel", Style::default().fg(theme.text_secondary)),
         ]));
 
         Paragraph::new(lines)
@@ -3337,16 +5813,16 @@ fn render_workspace_manager(f: &mut Frame, area: ratatui::layout::Rect, app: &Ap
             Line::from(""),
             Line::from(vec![
                 Span::raw("  "),
-                Span::styled("a", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(" add  ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled("e", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(" edit  ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled("d", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled(" remove  ", Style::default().fg(theme::TEXT_SECONDARY)),
-                Span::styled("1", Style::default().fg(theme::TEXT_SECONDARY)),
+                Span::styled("a", Style::default().fg(theme.text_secondary)),
+                Span::styled(" add  ", Style::default().fg(theme.text_secondary)),
+                Span::styled("e", Style::default().fg(theme.text_secondary)),
+                Span::styled(" edit  ", Style::default().fg(theme.text_secondary)),
+                Span::styled("d", Style::default().fg(theme.text_secondary)),
+                Span::styled(" remove  ", Style::default().fg(theme.text_secondary)),
+                Span::styled("1", Style::default().fg(theme.text_secondary)),
                 Span::styled(
                     " back to projects",
-                    Style::default().fg(theme::TEXT_SECONDARY),
+                    Style::default().fg(theme.text_secondary),
                 ),
             ]),
         ])
@@ -3379,17 +5855,36 @@ fn create_centered_modal(
 }
 
 fn render_overlay(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
     let Some(overlay) = &app.active_overlay else {
         // No overlay active - this shouldn't happen if View::Overlay is set
         return;
     };
 
     match overlay {
-        OverlayContent::LogViewer { log_path, scroll_offset, project_path } => {
-            render_overlay_log_viewer(f, area, log_path, *scroll_offset, project_path);
+        OverlayContent::LogViewer { .. } => {
+            render_overlay_log_viewer(f, area, app);
+        }
+        OverlayContent::LogDiff { .. } => {
+            render_overlay_log_diff(f, area, app);
+        }
+        OverlayContent::Problems { .. } => {
+            render_overlay_problems(f, area, app);
+        }
+        OverlayContent::TestResults { .. } => {
+            render_overlay_test_results(f, area, app);
+        }
+        OverlayContent::GitChanges { .. } => {
+            render_overlay_git_changes(f, area, app);
+        }
+        OverlayContent::Branches { .. } => {
+            render_overlay_branches(f, area, app);
+        }
+        OverlayContent::SyncSummary { .. } => {
+            render_overlay_sync_summary(f, area, app);
         }
         OverlayContent::Form { form, position } => {
-            render_overlay_form(f, area, form, position);
+            render_overlay_form(f, area, form, position, theme);
         }
     }
 }
@@ -3398,12 +5893,20 @@ fn render_overlay(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 fn render_overlay_log_viewer(
     f: &mut Frame,
     area: ratatui::layout::Rect,
-    log_path: &PathBuf,
-    scroll_offset: usize,
-    project_path: &str,
+    app: &App,
 ) {
-    use std::fs;
-    use std::io::{BufRead, BufReader};
+    let theme = &app.theme;
+    let Some(OverlayContent::LogViewer {
+        log_path,
+        scroll_offset,
+        project_path,
+        search,
+        follow,
+        stream,
+    }) = &app.active_overlay
+    else {
+        return;
+    };
 
     // Use minimal margin for maximum content visibility
     let inner_area = area.inner(Margin {
@@ -3416,24 +5919,21 @@ fn render_overlay_log_viewer(
         .and_then(|n| n.to_str())
         .unwrap_or("unknown");
 
-    // Read log file
-    let content = match fs::File::open(log_path) {
-        Ok(file) => {
-            let reader = BufReader::new(file);
-            let lines: Vec<String> = reader.lines()
-                .filter_map(|line| line.ok())
-                .collect();
-            lines
-        }
-        Err(e) => {
-            vec![format!("Error reading log file: {}", e)]
-        }
-    };
+    // Read and split the log into header/stdout/stderr lines
+    let document = log_viewer::LogDocument::load(log_path);
+    let content = document.filtered(*stream);
+    let matches = log_viewer::find_matches(&content, &search.query);
+    let current_match = matches.get(search.current).copied();
 
     // Calculate visible range - maximize visible content
     let total_lines = content.len();
     let visible_height = inner_area.height.saturating_sub(4) as usize; // borders + 2-line header
-    let start_line = scroll_offset.min(total_lines.saturating_sub(1));
+    let max_start = total_lines.saturating_sub(visible_height);
+    let start_line = if *follow {
+        max_start
+    } else {
+        (*scroll_offset).min(max_start)
+    };
     let end_line = (start_line + visible_height).min(total_lines);
 
     // Create lines to display
@@ -3446,43 +5946,281 @@ fn render_overlay_log_viewer(
     let truncated_project_path = truncate_path(project_path, max_path_width);
 
     // Ultra-compact 2-line header
-    display_lines.push(Line::from(vec![
-        Span::styled(filename, Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD)),
+    let mut header = vec![
+        Span::styled(filename, Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
         Span::raw("  "),
         Span::styled(
-            format!("({}/{})", start_line + 1, total_lines),
-            Style::default().fg(theme::TEXT_SECONDARY),
+            format!("({}/{})", (start_line + 1).min(total_lines), total_lines),
+            Style::default().fg(theme.text_secondary),
         ),
         Span::raw("  "),
-        Span::styled("[Esc] close  [↑↓] scroll  [PgUp/PgDn] page", Style::default().fg(theme::TEXT_SECONDARY)),
-    ]));
+        Span::styled(
+            format!("[{}]", stream.as_str()),
+            Style::default().fg(theme.text_secondary),
+        ),
+    ];
+    if *follow {
+        header.push(Span::raw("  "));
+        header.push(Span::styled(
+            "FOLLOW",
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if search.editing {
+        header.push(Span::raw("  "));
+        header.push(Span::styled(
+            format!("/{}", search.query),
+            Style::default().fg(theme.accent),
+        ));
+        header.push(Span::styled("█", Style::default().fg(theme.accent)));
+    } else if !search.query.is_empty() {
+        header.push(Span::raw("  "));
+        header.push(Span::styled(
+            format!(
+                "/{} ({}/{})",
+                search.query,
+                current_match.map(|_| search.current + 1).unwrap_or(0),
+                matches.len()
+            ),
+            Style::default().fg(theme.text_secondary),
+        ));
+    }
+    display_lines.push(Line::from(header));
     display_lines.push(Line::from(vec![
         Span::styled(
             format!("{}{}", path_prefix, truncated_project_path),
-            Style::default().fg(theme::TEXT_SECONDARY),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            "[Esc] close  [/] search  [n/N] next/prev  [e] error  [g/G] top/bottom  [f] follow  [s] streams",
+            Style::default().fg(theme.text_secondary),
         ),
     ]));
 
-    // Add visible lines with character-based truncation (prevents text bleed)
+    // Add visible lines: ANSI colors become span styles, search hits get highlighted
+    let colors = !theme.is_monochrome();
+    let mut match_style = Style::default().fg(theme.badge_text).bg(theme.warning);
+    if !colors {
+        match_style = match_style.add_modifier(Modifier::REVERSED);
+    }
+    let current_style = match_style.bg(theme.accent).add_modifier(Modifier::BOLD);
     let content_width = available_width;
-    for line in content.iter().skip(start_line).take(end_line - start_line) {
-        // Strip ANSI codes and truncate to fit width (character-based, not byte-based)
-        let stripped = strip_ansi_codes(line);
-        let char_count = stripped.chars().count();
-        let truncated = if char_count > content_width {
-            let truncated_chars: String = stripped.chars().take(content_width.saturating_sub(1)).collect();
-            format!("{}…", truncated_chars)
-        } else {
-            stripped
+    for (idx, line) in content
+        .iter()
+        .enumerate()
+        .skip(start_line)
+        .take(end_line - start_line)
+    {
+        let mut spans = ansi::parse_line(&line.raw, colors);
+        if matches.binary_search(&idx).is_ok() {
+            let style = if Some(idx) == current_match {
+                current_style
+            } else {
+                match_style
+            };
+            let ranges = log_viewer::match_ranges(&line.plain, &search.query);
+            spans = ansi::highlight_ranges(spans, &ranges, style);
+        }
+        display_lines.push(Line::from(ansi::truncate(spans, content_width)));
+    }
+
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
+
+    f.render_widget(paragraph, inner_area);
+}
+
+/// Rows (in the current layout) where each block of changes starts
+fn diff_hunk_rows(diff: &crate::fs::log_diff::LogDiff, side_by_side: bool) -> Vec<usize> {
+    use crate::fs::log_diff::DiffTag;
+
+    if !side_by_side {
+        return diff.hunk_starts();
+    }
+    let changed = |row: &(Option<usize>, Option<usize>)| {
+        row.0.or(row.1).is_some_and(|i| diff.lines[i].tag != DiffTag::Equal)
+    };
+    let rows = diff.side_by_side();
+    (0..rows.len())
+        .filter(|&i| changed(&rows[i]) && (i == 0 || !changed(&rows[i - 1])))
+        .collect()
+}
+
+/// Render the log diff overlay (unified or side-by-side)
+fn render_overlay_log_diff(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    use crate::fs::log_diff::{DiffLine, DiffTag};
+
+    let theme = &app.theme;
+    let Some(OverlayContent::LogDiff {
+        diff,
+        old_name,
+        new_name,
+        scroll_offset,
+        side_by_side,
+    }) = &app.active_overlay
+    else {
+        return;
+    };
+
+    let inner_area = area.inner(Margin {
+        horizontal: 1,
+        vertical: 0,
+    });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
+
+    let rows = if *side_by_side { diff.side_by_side().len() } else { diff.lines.len() };
+    let start = (*scroll_offset).min(rows.saturating_sub(visible_height));
+    let end = (start + visible_height).min(rows);
+
+    let run_summary = |meta: &Option<crate::fs::log_format::LogMeta>| match meta {
+        Some(m) => format!("exit {}, {:.1}s", m.exit_code, m.duration_ms as f64 / 1000.0),
+        None => "no metadata".to_string(),
+    };
+
+    let mut display_lines = vec![
+        Line::from(vec![
+            Span::styled(old_name.clone(), Style::default().fg(theme.error).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" ({})", run_summary(&diff.old_meta)), Style::default().fg(theme.text_secondary)),
+            Span::raw("  →  "),
+            Span::styled(new_name.clone(), Style::default().fg(theme.success).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" ({})", run_summary(&diff.new_meta)), Style::default().fg(theme.text_secondary)),
+            Span::raw("  "),
+            Span::styled(
+                format!("+{} -{}", diff.added(), diff.removed()),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![Span::styled(
+            "[Esc] close  [n/N] next/prev change  [s] side-by-side  [g/G] top/bottom  (timestamps, durations and temp paths ignored)",
+            Style::default().fg(theme.text_secondary),
+        )]),
+    ];
+
+    let style_for = |tag: DiffTag| match tag {
+        DiffTag::Equal => Style::default().fg(theme.text_primary),
+        DiffTag::Removed => Style::default().fg(theme.error),
+        DiffTag::Added => Style::default().fg(theme.success),
+    };
+    let cell = |line: Option<&DiffLine>, width: usize| -> Vec<Span<'static>> {
+        let Some(line) = line else {
+            return vec![Span::raw(" ".repeat(width))];
+        };
+        let number = line.new_line.or(line.old_line).map(|n| n.to_string()).unwrap_or_default();
+        let text_width = width.saturating_sub(6);
+        let text: String = line.text.chars().take(text_width).collect();
+        let padding = text_width.saturating_sub(text.chars().count());
+        vec![
+            Span::styled(format!("{:>5} ", number), Style::default().fg(theme.text_secondary)),
+            Span::styled(format!("{}{}", text, " ".repeat(padding)), style_for(line.tag)),
+        ]
+    };
+
+    if *side_by_side {
+        let half = available_width.saturating_sub(3) / 2;
+        for (old, new) in diff.side_by_side().into_iter().skip(start).take(end - start) {
+            let old_line = old.map(|i| &diff.lines[i]).filter(|l| l.tag != DiffTag::Added);
+            let new_line = new.map(|i| &diff.lines[i]).filter(|l| l.tag != DiffTag::Removed);
+            let mut spans = cell(old_line, half);
+            spans.push(Span::styled(" │ ", Style::default().fg(theme.separator)));
+            spans.extend(cell(new_line, half));
+            display_lines.push(Line::from(spans));
+        }
+    } else {
+        for line in diff.lines.iter().skip(start).take(end - start) {
+            let prefix = match line.tag {
+                DiffTag::Equal => "  ",
+                DiffTag::Removed => "- ",
+                DiffTag::Added => "+ ",
+            };
+            let old = line.old_line.map(|n| n.to_string()).unwrap_or_default();
+            let new = line.new_line.map(|n| n.to_string()).unwrap_or_default();
+            let spans = vec![
+                Span::styled(format!("{:>5} {:>5} ", old, new), Style::default().fg(theme.text_secondary)),
+                Span::styled(format!("{}{}", prefix, line.text), style_for(line.tag)),
+            ];
+            display_lines.push(Line::from(ansi::truncate(spans, available_width)));
+        }
+    }
+
+    let paragraph = Paragraph::new(display_lines)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent)));
+
+    f.render_widget(paragraph, inner_area);
+}
+
+/// Render the Problems panel (diagnostics from the latest runs)
+fn render_overlay_problems(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    use crate::diagnostics::Severity;
+
+    let theme = &app.theme;
+    let Some(OverlayContent::Problems { problems, selected, .. }) = &app.active_overlay else {
+        return;
+    };
+
+    let inner_area = area.inner(Margin {
+        horizontal: 1,
+        vertical: 0,
+    });
+    let available_width = inner_area.width.saturating_sub(2) as usize;
+    let visible_height = inner_area.height.saturating_sub(4) as usize;
+
+    // Keep the selection on screen
+    let start = selected.saturating_sub(visible_height.saturating_sub(1));
+    let end = (start + visible_height).min(problems.len());
+
+    let diagnostics: Vec<_> = problems.iter().map(|(_, d)| d.clone()).collect();
+    let mut header = vec![Span::styled(
+        "Problems  ",
+        Style::default().fg(theme.text_primary).add_modifier(Modifier::BOLD),
+    )];
+    header.extend(problem_count_spans(crate::diagnostics::counts(&diagnostics), theme));
+
+    let mut display_lines = vec![
+        Line::from(header),
+        Line::from(vec![Span::styled(
+            "[Esc] close  [↑↓] select  [Enter] open in editor  [g/G] first/last",
+            Style::default().fg(theme.text_secondary),
+        )]),
+    ];
+
+    for (i, (category, problem)) in problems.iter().enumerate().skip(start).take(end - start) {
+        let is_selected = i == *selected;
+        let (icon, color) = match problem.severity {
+            Severity::Error => ("✗", theme.error),
+            Severity::Warning => ("⚠", theme.warning),
+            Severity::Note => ("·", theme.text_secondary),
         };
-        display_lines.push(Line::from(truncated));
+        let code = problem.code.as_ref().map(|c| format!(" [{}]", c)).unwrap_or_default();
+        let spans = vec![
+            Span::styled(if is_selected { "▸ " } else { "  " }, Style::default().fg(theme.accent)),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(format!("{:<8}", category), Style::default().fg(theme.text_secondary)),
+            Span::styled(
+                format!("{}  ", problem.location()),
+                Style::default()
+                    .fg(if is_selected { theme.accent } else { theme.text_primary })
+                    .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() }),
+            ),
+            Span::styled(format!("{}{}", problem.message, code), Style::default().fg(theme.text_primary)),
+        ];
+        display_lines.push(Line::from(ansi::truncate(spans, available_width)));
     }
 
     let paragraph = Paragraph::new(display_lines)
         .block(Block::default()
             .borders(Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Rounded)
-            .border_style(Style::default().fg(theme::ACCENT)));
+            .border_style(Style::default().fg(theme.accent)));
 
     f.render_widget(paragraph, inner_area);
 }
@@ -3493,6 +6231,7 @@ fn render_overlay_form(
     area: ratatui::layout::Rect,
     form: &crate::forms::Form,
     position: &OverlayPosition,
+    theme: &Theme,
 ) {
     // Determine modal dimensions based on position
     let modal_area = match position {
@@ -3537,16 +6276,16 @@ fn render_overlay_form(
     let title = Paragraph::new(vec![Line::from(vec![Span::styled(
         &form.title,
         Style::default()
-            .fg(theme::ACCENT)
+            .fg(theme.accent)
             .add_modifier(Modifier::BOLD),
     )])])
-    .style(Style::default().bg(Color::Black))
+    .style(Style::default().bg(theme.background))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::ACCENT))
+            .border_style(Style::default().fg(theme.accent))
             .border_type(BorderType::Rounded)
-            .style(Style::default().bg(Color::Black)),
+            .style(Style::default().bg(theme.background)),
     );
     f.render_widget(title, modal_area);
     chunk_idx += 1;
@@ -3555,9 +6294,9 @@ fn render_overlay_form(
     if let Some(desc) = &form.description {
         let description = Paragraph::new(vec![Line::from(vec![Span::styled(
             desc,
-            Style::default().fg(theme::TEXT_SECONDARY),
+            Style::default().fg(theme.text_secondary),
         )])])
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
         f.render_widget(description, chunks[chunk_idx]);
         chunk_idx += 1;
     }
@@ -3578,10 +6317,10 @@ fn render_overlay_form(
         // Render field label
         let label_style = if is_current {
             Style::default()
-                .fg(theme::ACCENT)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(theme::TEXT_PRIMARY)
+            Style::default().fg(theme.text_primary)
         };
 
         field_lines.push(Line::from(vec![
@@ -3591,12 +6330,12 @@ fn render_overlay_form(
         ]));
 
         // Render field value based on type
-        render_form_field(&mut field_lines, field, is_current);
+        render_form_field(&mut field_lines, field, is_current, theme);
     }
 
     let fields_widget = Paragraph::new(field_lines)
         .wrap(ratatui::widgets::Wrap { trim: false })
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     f.render_widget(fields_widget, fields_area);
 
     // Render help text
@@ -3605,26 +6344,26 @@ fn render_overlay_form(
         Line::from(""),
         Line::from(vec![
             Span::raw("  "),
-            Span::styled("Tab/Shift+Tab", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("Tab/Shift+Tab", Style::default().fg(theme.text_secondary)),
             Span::raw(" navigate  "),
-            Span::styled("↑↓", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("↑↓", Style::default().fg(theme.text_secondary)),
             Span::raw(" select  "),
-            Span::styled("Space", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("Space", Style::default().fg(theme.text_secondary)),
             Span::raw(" toggle  "),
-            Span::styled("Enter", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("Enter", Style::default().fg(theme.text_secondary)),
             Span::raw(" submit  "),
-            Span::styled("Esc", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled("Esc", Style::default().fg(theme.text_secondary)),
             Span::raw(" cancel"),
         ]),
     ];
     let help = Paragraph::new(help_text)
         .alignment(Alignment::Left)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     f.render_widget(help, chunks[chunk_idx]);
 }
 
 /// Helper function to render form field values
-fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::forms::FormField, is_current: bool) {
+fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::forms::FormField, is_current: bool, theme: &Theme) {
     match field {
         crate::forms::FormField::TextInput { value, placeholder, .. }
         | crate::forms::FormField::Email { value, placeholder, .. } => {
@@ -3634,17 +6373,17 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
                 value.as_str()
             };
             let value_style = if value.is_empty() {
-                Style::default().fg(theme::TEXT_SECONDARY)
+                Style::default().fg(theme.text_secondary)
             } else if is_current {
-                Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::UNDERLINED)
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::UNDERLINED)
             } else {
-                Style::default().fg(theme::TEXT_PRIMARY)
+                Style::default().fg(theme.text_primary)
             };
             field_lines.push(Line::from(vec![
                 Span::raw("     "),
                 Span::styled(display, value_style),
                 if is_current {
-                    Span::styled("█", Style::default().fg(theme::ACCENT))
+                    Span::styled("█", Style::default().fg(theme.accent))
                 } else {
                     Span::raw("")
                 },
@@ -3657,9 +6396,9 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
                 value.as_str()
             };
             let value_style = if value.is_empty() {
-                Style::default().fg(theme::TEXT_SECONDARY)
+                Style::default().fg(theme.text_secondary)
             } else {
-                Style::default().fg(theme::TEXT_PRIMARY)
+                Style::default().fg(theme.text_primary)
             };
             for line in display.lines().take(*height) {
                 field_lines.push(Line::from(vec![
@@ -3670,7 +6409,7 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
             if is_current {
                 field_lines.push(Line::from(vec![
                     Span::raw("     "),
-                    Span::styled("█", Style::default().fg(theme::ACCENT)),
+                    Span::styled("█", Style::default().fg(theme.accent)),
                 ]));
             }
         }
@@ -3687,15 +6426,15 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
             }
             let display = display_parts.join("");
             let value_style = if is_current {
-                Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::UNDERLINED)
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::UNDERLINED)
             } else {
-                Style::default().fg(theme::TEXT_PRIMARY)
+                Style::default().fg(theme.text_primary)
             };
             field_lines.push(Line::from(vec![
                 Span::raw("     "),
                 Span::styled(display, value_style),
                 if is_current {
-                    Span::styled("█", Style::default().fg(theme::ACCENT))
+                    Span::styled("█", Style::default().fg(theme.accent))
                 } else {
                     Span::raw("")
                 },
@@ -3706,11 +6445,11 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
                 let is_selected = idx == *selected;
                 let marker = if is_selected { "●" } else { "○" };
                 let style = if is_current && is_selected {
-                    Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD)
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                 } else if is_selected {
-                    Style::default().fg(theme::SUCCESS)
+                    Style::default().fg(theme.success)
                 } else {
-                    Style::default().fg(theme::TEXT_SECONDARY)
+                    Style::default().fg(theme.text_secondary)
                 };
                 field_lines.push(Line::from(vec![
                     Span::raw("     "),
@@ -3726,14 +6465,14 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
                 let marker = if is_selected { "☑" } else { "☐" };
                 let style = if is_current {
                     if is_selected {
-                        Style::default().fg(theme::ACCENT).add_modifier(Modifier::BOLD)
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
                     } else {
-                        Style::default().fg(theme::ACCENT)
+                        Style::default().fg(theme.accent)
                     }
                 } else if is_selected {
-                    Style::default().fg(theme::SUCCESS)
+                    Style::default().fg(theme.success)
                 } else {
-                    Style::default().fg(theme::TEXT_SECONDARY)
+                    Style::default().fg(theme.text_secondary)
                 };
                 field_lines.push(Line::from(vec![
                     Span::raw("     "),
@@ -3754,17 +6493,17 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
                 value.as_str()
             };
             let value_style = if value.is_empty() {
-                Style::default().fg(theme::TEXT_SECONDARY)
+                Style::default().fg(theme.text_secondary)
             } else if is_current {
-                Style::default().fg(theme::TEXT_PRIMARY).add_modifier(Modifier::UNDERLINED)
+                Style::default().fg(theme.text_primary).add_modifier(Modifier::UNDERLINED)
             } else {
-                Style::default().fg(theme::TEXT_PRIMARY)
+                Style::default().fg(theme.text_primary)
             };
             field_lines.push(Line::from(vec![
                 Span::raw("     "),
                 Span::styled(display, value_style),
                 if is_current {
-                    Span::styled("█", Style::default().fg(theme::ACCENT))
+                    Span::styled("█", Style::default().fg(theme.accent))
                 } else {
                     Span::raw("")
                 },
@@ -3773,11 +6512,11 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
         crate::forms::FormField::Checkbox { checked, .. } => {
             let marker = if *checked { "☑" } else { "☐" };
             let style = if is_current {
-                Style::default().fg(theme::ACCENT)
+                Style::default().fg(theme.accent)
             } else if *checked {
-                Style::default().fg(theme::SUCCESS)
+                Style::default().fg(theme.success)
             } else {
-                Style::default().fg(theme::TEXT_SECONDARY)
+                Style::default().fg(theme.text_secondary)
             };
             field_lines.push(Line::from(vec![
                 Span::raw("     "),
@@ -3791,9 +6530,10 @@ fn render_form_field<'a>(field_lines: &mut Vec<Line<'a>>, field: &'a crate::form
 
 /// Render horizontal progress bar on the right side
 fn render_progress_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let theme = &app.theme;
     // Check if showing result or running animation
     if let Some((success, _)) = app.command_result_display {
-        render_command_result(f, area, success);
+        render_command_result(f, area, success, theme);
         return;
     }
 
@@ -3848,28 +6588,28 @@ fn render_progress_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         Span::raw(" "),
         Span::styled(
             left_str,
-            Style::default().fg(ratatui::style::Color::Rgb(100, 100, 100)),
+            Style::default().fg(theme.progress_dim),
         ),
         Span::styled(
             bright_str,
             Style::default()
-                .fg(ratatui::style::Color::Rgb(255, 255, 255))
+                .fg(theme.progress_bright)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             right_str,
-            Style::default().fg(ratatui::style::Color::Rgb(100, 100, 100)),
+            Style::default().fg(theme.progress_dim),
         ),
         Span::raw(" "),
     ];
 
     let progress_widget = Paragraph::new(vec![
         Line::from(vec![
-            Span::styled(" Running ", Style::default().fg(theme::TEXT_SECONDARY)),
+            Span::styled(" Running ", Style::default().fg(theme.text_secondary)),
             Span::styled(
                 elapsed_text,
                 Style::default()
-                    .fg(theme::ACCENT)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -3878,7 +6618,7 @@ fn render_progress_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(theme::ACCENT))
+            .border_style(Style::default().fg(theme.accent))
             .border_type(ratatui::widgets::BorderType::Rounded),
     );
 
@@ -3886,7 +6626,7 @@ fn render_progress_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 }
 
 /// Render command result (success or failure) in attention-grabbing style
-fn render_command_result(f: &mut Frame, area: ratatui::layout::Rect, success: bool) {
+fn render_command_result(f: &mut Frame, area: ratatui::layout::Rect, success: bool, theme: &Theme) {
     let bar_width = 32;
     let bar_height = 4;
 
@@ -3902,9 +6642,9 @@ fn render_command_result(f: &mut Frame, area: ratatui::layout::Rect, success: bo
 
     // Choose color and message based on success/failure
     let (color, icon, message) = if success {
-        (ratatui::style::Color::Green, "✓", "SUCCESS")
+        (theme.success, "✓", "SUCCESS")
     } else {
-        (ratatui::style::Color::Red, "✗", "FAILED")
+        (theme.error, "✗", "FAILED")
     };
 
     // Create a solid bar in the result color
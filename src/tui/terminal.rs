@@ -0,0 +1,184 @@
+//! Replay of output captured from a pseudo-terminal
+//!
+//! A task run with `pty = true` writes what a terminal understands: `\r` to
+//! redraw a progress bar, cursor movement, line erases. Printed line by line
+//! that leaves every frame of a progress bar in the viewer, so PTY logs are
+//! fed through a [`vt100::Parser`] of the size the task ran at instead, and
+//! the viewer shows the rows it ends up with. Rows come back as text with
+//! only SGR codes, so they render like any other log line (see [`super::ansi`]).
+
+use vt100::{Cell, Color, Parser, Screen};
+
+use crate::exec::TerminalSize;
+
+/// Rows of the terminal after replaying `lines` (one log line each)
+///
+/// Rows that scrolled off the top come first; trailing blank rows are dropped.
+pub fn replay<'a>(lines: impl IntoIterator<Item = &'a str>, size: TerminalSize) -> Vec<String> {
+    let rows = size.rows.max(4);
+    let cols = size.cols.max(1);
+    // Unlimited scrollback: rows are taken out of it as soon as they arrive
+    let mut parser = Parser::new(rows, cols, usize::MAX);
+    let mut replayed = Vec::new();
+
+    for line in lines {
+        // The terminal turned `\n` into `\r\n`; the log keeps neither
+        let mut bytes = line.as_bytes().to_vec();
+        bytes.extend_from_slice(b"\r\n");
+        // At most a row's worth of bytes at a time: it can wrap once, and
+        // with the line ending scroll two rows, so nothing is lost before
+        // the scrollback is collected
+        for chunk in bytes.chunks(cols as usize) {
+            parser.process(chunk);
+            if scrolled_off(&mut parser) >= (rows - 2) as usize {
+                take_scrollback(&mut parser, &mut replayed);
+            }
+        }
+    }
+
+    take_scrollback(&mut parser, &mut replayed);
+    let screen = parser.screen();
+    replayed.extend((0..rows).map(|row| formatted_row(screen, row)));
+
+    while replayed.last().is_some_and(|row| row.is_empty()) {
+        replayed.pop();
+    }
+    replayed
+}
+
+/// Number of rows in the scrollback
+fn scrolled_off(parser: &mut Parser) -> usize {
+    parser.set_scrollback(usize::MAX);
+    let count = parser.screen().scrollback();
+    parser.set_scrollback(0);
+    count
+}
+
+/// Move the scrollback into `replayed`, starting over with an empty one
+///
+/// vt100 can only show one screen of scrollback at a time, so this runs
+/// before more than a screen has scrolled off.
+fn take_scrollback(parser: &mut Parser, replayed: &mut Vec<String>) {
+    let count = scrolled_off(parser);
+    if count == 0 {
+        return;
+    }
+
+    let (rows, cols) = parser.screen().size();
+    parser.set_scrollback(count);
+    let screen = parser.screen();
+    replayed.extend((0..count as u16).map(|row| formatted_row(screen, row)));
+
+    parser.set_scrollback(0);
+    let state = parser.screen().state_formatted();
+    *parser = Parser::new(rows, cols, usize::MAX);
+    parser.process(&state);
+}
+
+/// One visible row as text with SGR codes, trailing blanks trimmed
+fn formatted_row(screen: &Screen, row: u16) -> String {
+    let (_, cols) = screen.size();
+    let cells: Vec<&Cell> = (0..cols).filter_map(|col| screen.cell(row, col)).collect();
+    let Some(last) = cells.iter().rposition(|cell| cell.has_contents() || cell.bgcolor() != Color::Default) else {
+        return String::new();
+    };
+
+    let mut text = String::new();
+    let mut current = String::new();
+    for cell in &cells[..=last] {
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        let sgr = sgr(cell);
+        if sgr != current {
+            text.push_str(&format!("\x1b[{}m", sgr));
+            current = sgr;
+        }
+        if cell.has_contents() {
+            text.push_str(&cell.contents());
+        } else {
+            text.push(' ');
+        }
+    }
+    if current != "0" && !current.is_empty() {
+        text.push_str("\x1b[0m");
+    }
+    text
+}
+
+/// SGR parameters for a cell's attributes, starting from a reset
+fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    if cell.bold() {
+        codes.push("1".to_string());
+    }
+    if cell.italic() {
+        codes.push("3".to_string());
+    }
+    if cell.underline() {
+        codes.push("4".to_string());
+    }
+    if cell.inverse() {
+        codes.push("7".to_string());
+    }
+    codes.extend(color_code(cell.fgcolor(), 30));
+    codes.extend(color_code(cell.bgcolor(), 40));
+    codes.join(";")
+}
+
+/// `base` is 30 for the foreground and 40 for the background
+fn color_code(color: Color, base: u16) -> Option<String> {
+    match color {
+        Color::Default => None,
+        Color::Idx(n) if n < 8 => Some((base + n as u16).to_string()),
+        Color::Idx(n) if n < 16 => Some((base + 60 + (n - 8) as u16).to_string()),
+        Color::Idx(n) => Some(format!("{};5;{}", base + 8, n)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::ansi;
+
+    fn plain(rows: &[String]) -> Vec<String> {
+        rows.iter().map(|row| ansi::plain_text(&ansi::parse_line(row, false))).collect()
+    }
+
+    #[test]
+    fn test_progress_redraws_collapse() {
+        let size = TerminalSize { rows: 10, cols: 40 };
+        let lines = [
+            "   Compiling demo v0.1.0",
+            "    Building [==>   ] 1/3\r    Building [====> ] 2/3\r\x1b[K    Finished dev",
+        ];
+        assert_eq!(plain(&replay(lines, size)), vec!["   Compiling demo v0.1.0", "    Finished dev"]);
+    }
+
+    #[test]
+    fn test_colors_survive_as_sgr() {
+        let size = TerminalSize { rows: 10, cols: 40 };
+        let rows = replay(["\x1b[1;31merror\x1b[0m: boom"], size);
+        let spans = ansi::parse_line(&rows[0], true);
+        assert_eq!(ansi::plain_text(&spans), "error: boom");
+        assert_eq!(spans[0].style.fg, Some(ratatui::style::Color::Red));
+        assert!(spans[0].style.add_modifier.contains(ratatui::style::Modifier::BOLD));
+        assert_eq!(spans[1].style.fg, None);
+    }
+
+    #[test]
+    fn test_scrollback_is_kept_in_order() {
+        let size = TerminalSize { rows: 5, cols: 20 };
+        let lines: Vec<String> = (0..50).map(|i| format!("line {}", i)).collect();
+        let rows = plain(&replay(lines.iter().map(String::as_str), size));
+        assert_eq!(rows, lines);
+    }
+
+    #[test]
+    fn test_long_lines_wrap_at_the_terminal_width() {
+        let size = TerminalSize { rows: 5, cols: 10 };
+        let rows = plain(&replay(["abcdefghijklmnopqrstuvwxyz"], size));
+        assert_eq!(rows, vec!["abcdefghij", "klmnopqrst", "uvwxyz"]);
+    }
+}
@@ -0,0 +1,368 @@
+//! Runtime color themes for the TUI
+//!
+//! Every render function pulls its colors from a [`Theme`] instead of
+//! hard-coded `Color` values. Themes are selected with `[tui] theme = "..."`
+//! in the global config, either by built-in name or by path to a custom
+//! theme TOML file:
+//!
+//! ```toml
+//! # ~/.config/byte/themes/mine.toml
+//! name = "mine"
+//! base = "gruvbox"          # optional, defaults to "oled"
+//!
+//! [colors]
+//! accent = "#fabd2f"
+//! error = "lightred"
+//! ```
+//!
+//! Setting `NO_COLOR` (https://no-color.org) forces the monochrome theme.
+
+use anyhow::{Context, Result};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Name of the theme used when nothing is configured
+pub const DEFAULT_THEME: &str = "oled";
+
+/// Built-in theme names, in the order they are listed to users
+pub const BUILTIN_THEMES: &[&str] = &[
+    "oled",
+    "gruvbox",
+    "solarized",
+    "high-contrast",
+    "ibm3270",
+    "monochrome",
+];
+
+/// Color palette used by every render function
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+
+    // Primary colors
+    pub accent: Color,  // Brand color, primary actions
+    pub success: Color, // Success states, active items
+    pub error: Color,   // Error states, failures
+    pub warning: Color, // Attention without failure
+
+    // Text hierarchy
+    pub text_primary: Color,   // Primary content, main text
+    pub text_secondary: Color, // Secondary content, metadata, paths
+
+    // UI elements
+    pub separator: Color,  // Lines, dividers
+    pub badge_bg: Color,   // Badge backgrounds
+    pub badge_text: Color, // Badge text
+    pub background: Color, // Modal backgrounds (forms)
+
+    // Log category badges
+    pub category_git: Color,
+    pub category_lint: Color,
+
+    // Progress bar scanner effect
+    pub progress_dim: Color,
+    pub progress_bright: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::oled()
+    }
+}
+
+impl Theme {
+    /// Default theme optimized for OLED black backgrounds
+    pub fn oled() -> Self {
+        Self {
+            name: "oled".to_string(),
+            accent: Color::Cyan,
+            success: Color::Green,
+            error: Color::Red,
+            warning: Color::Rgb(255, 165, 0),
+            text_primary: Color::White,
+            text_secondary: Color::Rgb(180, 180, 180),
+            separator: Color::Rgb(60, 60, 60),
+            badge_bg: Color::Cyan,
+            badge_text: Color::Black,
+            background: Color::Black,
+            category_git: Color::Rgb(255, 165, 0),
+            category_lint: Color::Rgb(147, 112, 219),
+            progress_dim: Color::Rgb(100, 100, 100),
+            progress_bright: Color::Rgb(255, 255, 255),
+        }
+    }
+
+    /// Gruvbox dark palette
+    pub fn gruvbox() -> Self {
+        Self {
+            name: "gruvbox".to_string(),
+            accent: Color::Rgb(142, 192, 124),
+            success: Color::Rgb(184, 187, 38),
+            error: Color::Rgb(251, 73, 52),
+            warning: Color::Rgb(250, 189, 47),
+            text_primary: Color::Rgb(235, 219, 178),
+            text_secondary: Color::Rgb(168, 153, 132),
+            separator: Color::Rgb(80, 73, 69),
+            badge_bg: Color::Rgb(142, 192, 124),
+            badge_text: Color::Rgb(40, 40, 40),
+            background: Color::Rgb(40, 40, 40),
+            category_git: Color::Rgb(254, 128, 25),
+            category_lint: Color::Rgb(211, 134, 155),
+            progress_dim: Color::Rgb(102, 92, 84),
+            progress_bright: Color::Rgb(251, 241, 199),
+        }
+    }
+
+    /// Solarized dark palette
+    pub fn solarized() -> Self {
+        Self {
+            name: "solarized".to_string(),
+            accent: Color::Rgb(42, 161, 152),
+            success: Color::Rgb(133, 153, 0),
+            error: Color::Rgb(220, 50, 47),
+            warning: Color::Rgb(181, 137, 0),
+            text_primary: Color::Rgb(147, 161, 161),
+            text_secondary: Color::Rgb(101, 123, 131),
+            separator: Color::Rgb(7, 54, 66),
+            badge_bg: Color::Rgb(42, 161, 152),
+            badge_text: Color::Rgb(0, 43, 54),
+            background: Color::Rgb(0, 43, 54),
+            category_git: Color::Rgb(203, 75, 22),
+            category_lint: Color::Rgb(108, 113, 196),
+            progress_dim: Color::Rgb(88, 110, 117),
+            progress_bright: Color::Rgb(253, 246, 227),
+        }
+    }
+
+    /// Accessibility theme: maximum contrast, no mid-gray text
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            accent: Color::Rgb(255, 255, 0),
+            success: Color::Rgb(0, 255, 0),
+            error: Color::Rgb(255, 85, 85),
+            warning: Color::Rgb(255, 170, 0),
+            text_primary: Color::White,
+            text_secondary: Color::Rgb(230, 230, 230),
+            separator: Color::White,
+            badge_bg: Color::Rgb(255, 255, 0),
+            badge_text: Color::Black,
+            background: Color::Black,
+            category_git: Color::Rgb(255, 170, 0),
+            category_lint: Color::Rgb(255, 128, 255),
+            progress_dim: Color::Rgb(160, 160, 160),
+            progress_bright: Color::White,
+        }
+    }
+
+    /// IBM 3270 green-screen terminal: green phosphor, intensified fields for emphasis
+    pub fn ibm3270() -> Self {
+        let phosphor = Color::Rgb(51, 255, 51);
+        let normal = Color::Rgb(0, 190, 0);
+        let intensified = Color::Rgb(200, 255, 200);
+
+        Self {
+            name: "ibm3270".to_string(),
+            accent: phosphor,
+            success: phosphor,
+            error: intensified,
+            warning: intensified,
+            text_primary: phosphor,
+            text_secondary: normal,
+            separator: Color::Rgb(0, 90, 0),
+            badge_bg: phosphor,
+            badge_text: Color::Black,
+            background: Color::Black,
+            category_git: intensified,
+            category_lint: normal,
+            progress_dim: Color::Rgb(0, 90, 0),
+            progress_bright: intensified,
+        }
+    }
+
+    /// No colors at all: terminal defaults plus bold/underline modifiers
+    pub fn monochrome() -> Self {
+        Self {
+            name: "monochrome".to_string(),
+            accent: Color::Reset,
+            success: Color::Reset,
+            error: Color::Reset,
+            warning: Color::Reset,
+            text_primary: Color::Reset,
+            text_secondary: Color::Reset,
+            separator: Color::Reset,
+            badge_bg: Color::Reset,
+            badge_text: Color::Reset,
+            background: Color::Reset,
+            category_git: Color::Reset,
+            category_lint: Color::Reset,
+            progress_dim: Color::Reset,
+            progress_bright: Color::Reset,
+        }
+    }
+
+    /// Look up a built-in theme by name
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "oled" | "default" => Some(Self::oled()),
+            "gruvbox" => Some(Self::gruvbox()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" | "high_contrast" | "accessible" => Some(Self::high_contrast()),
+            "ibm3270" | "3270" | "green-screen" => Some(Self::ibm3270()),
+            "monochrome" | "mono" | "none" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Resolve a `[tui] theme` value: a built-in name or a path to a theme TOML file
+    pub fn resolve(spec: &str) -> Result<Self> {
+        if let Some(theme) = Self::builtin(spec) {
+            return Ok(theme);
+        }
+
+        if spec.ends_with(".toml") || spec.contains('/') {
+            return Self::load_file(spec);
+        }
+
+        anyhow::bail!(
+            "Unknown theme '{}'. Built-in themes: {}",
+            spec,
+            BUILTIN_THEMES.join(", ")
+        )
+    }
+
+    /// Load a custom theme from a TOML file
+    pub fn load_file(path: &str) -> Result<Self> {
+        let safe_path = crate::path::SafePath::from_user_input(path)?;
+        let content = std::fs::read_to_string(safe_path.expanded())
+            .with_context(|| format!("Failed to read theme file: {}", safe_path))?;
+
+        Self::from_toml(&content)
+            .with_context(|| format!("Invalid theme file: {}", safe_path))
+    }
+
+    /// Parse a custom theme from TOML content
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(content)?;
+
+        let base = file.base.as_deref().unwrap_or(DEFAULT_THEME);
+        let mut theme = Self::builtin(base)
+            .ok_or_else(|| anyhow::anyhow!("Unknown base theme '{}'", base))?;
+
+        if let Some(name) = file.name {
+            theme.name = name;
+        }
+
+        for (key, value) in &file.colors {
+            let color = parse_color(value)?;
+            theme.set_color(key, color)?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Load the theme for the current environment
+    ///
+    /// `NO_COLOR` always wins. Invalid theme settings fall back to the default
+    /// theme and are reported in byte.log rather than aborting the TUI.
+    pub fn from_config(tui: &crate::config::types::TuiConfig) -> Self {
+        if no_color_requested() {
+            return Self::monochrome();
+        }
+
+        match Self::resolve(&tui.theme) {
+            Ok(theme) => theme,
+            Err(e) => {
+                crate::log::warn("THEME", &format!("{:#}; using '{}'", e, DEFAULT_THEME));
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether this theme renders without any color (monochrome / `NO_COLOR`)
+    pub fn is_monochrome(&self) -> bool {
+        self.accent == Color::Reset && self.text_primary == Color::Reset
+    }
+
+    /// Override a single color slot by its TOML key
+    fn set_color(&mut self, key: &str, color: Color) -> Result<()> {
+        let slot = match key {
+            "accent" => &mut self.accent,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "warning" => &mut self.warning,
+            "text_primary" => &mut self.text_primary,
+            "text_secondary" => &mut self.text_secondary,
+            "separator" => &mut self.separator,
+            "badge_bg" => &mut self.badge_bg,
+            "badge_text" => &mut self.badge_text,
+            "background" => &mut self.background,
+            "category_git" => &mut self.category_git,
+            "category_lint" => &mut self.category_lint,
+            "progress_dim" => &mut self.progress_dim,
+            "progress_bright" => &mut self.progress_bright,
+            _ => anyhow::bail!("Unknown theme color '{}'", key),
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+/// On-disk format of a custom theme file
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    base: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+}
+
+/// Parse a color name ("cyan", "lightred") or hex value ("#rrggbb")
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value.trim()).map_err(|_| anyhow::anyhow!("Invalid color '{}'", value))
+}
+
+/// Whether the user asked for no color output (https://no-color.org)
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_builtins_resolve() {
+        for name in BUILTIN_THEMES {
+            let theme = Theme::resolve(name).unwrap();
+            assert_eq!(theme.name, *name);
+        }
+    }
+
+    #[test]
+    fn test_unknown_theme_rejected() {
+        assert!(Theme::resolve("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn test_custom_theme_overrides_base() {
+        let theme = Theme::from_toml(
+            "name = \"mine\"\nbase = \"gruvbox\"\n\n[colors]\naccent = \"#ff0000\"\nerror = \"lightred\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(theme.name, "mine");
+        assert_eq!(theme.accent, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.success, Theme::gruvbox().success);
+    }
+
+    #[test]
+    fn test_custom_theme_rejects_unknown_keys_and_colors() {
+        assert!(Theme::from_toml("[colors]\nnot_a_slot = \"red\"\n").is_err());
+        assert!(Theme::from_toml("[colors]\naccent = \"not-a-color\"\n").is_err());
+        assert!(Theme::from_toml("base = \"nope\"\n").is_err());
+    }
+}